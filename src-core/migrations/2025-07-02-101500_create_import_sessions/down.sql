@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_activities_import_session_id;
+
+ALTER TABLE activities DROP COLUMN import_session_id;
+
+DROP INDEX IF EXISTS idx_import_sessions_account_id;
+
+DROP TABLE import_sessions;
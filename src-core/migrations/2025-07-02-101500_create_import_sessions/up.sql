@@ -0,0 +1,18 @@
+CREATE TABLE import_sessions (
+    id TEXT PRIMARY KEY NOT NULL,
+    account_id TEXT NOT NULL,
+    source TEXT NOT NULL,
+    file_name TEXT,
+    file_hash TEXT,
+    row_count INTEGER NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'COMPLETED',
+    created_at TEXT NOT NULL,
+    rolled_back_at TEXT,
+    FOREIGN KEY (account_id) REFERENCES accounts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_import_sessions_account_id ON import_sessions(account_id);
+
+ALTER TABLE activities ADD COLUMN import_session_id TEXT;
+
+CREATE INDEX idx_activities_import_session_id ON activities(import_session_id);
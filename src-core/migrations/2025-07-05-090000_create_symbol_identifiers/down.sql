@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_symbol_identifiers_symbol;
+
+DROP TABLE symbol_identifiers;
@@ -0,0 +1,13 @@
+CREATE TABLE symbol_identifiers (
+    identifier TEXT PRIMARY KEY NOT NULL,
+    identifier_type TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    isin TEXT,
+    name TEXT,
+    exchange TEXT,
+    source TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_symbol_identifiers_symbol ON symbol_identifiers(symbol);
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_change_log_created_at;
+DROP INDEX IF EXISTS idx_change_log_entity;
+
+DROP TABLE change_log;
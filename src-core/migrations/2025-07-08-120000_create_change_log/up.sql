@@ -0,0 +1,13 @@
+CREATE TABLE change_log (
+    id TEXT PRIMARY KEY NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    before_json TEXT,
+    after_json TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_change_log_entity ON change_log(entity_type, entity_id, created_at);
+CREATE INDEX idx_change_log_created_at ON change_log(created_at);
@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS activity_saved_views;
+DROP INDEX IF EXISTS idx_activities_currency;
+DROP TRIGGER IF EXISTS activities_fts_after_update;
+DROP TRIGGER IF EXISTS activities_fts_after_delete;
+DROP TRIGGER IF EXISTS activities_fts_after_insert;
+DROP TABLE IF EXISTS activities_fts;
@@ -0,0 +1,41 @@
+-- Full-text index over activity comments. Keyed by activity id rather than
+-- rowid, which VACUUM may renumber on a table with a TEXT primary key.
+-- Only activities with a comment are indexed.
+CREATE VIRTUAL TABLE activities_fts USING fts5(
+    activity_id UNINDEXED,
+    comment,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO activities_fts (activity_id, comment)
+SELECT id, comment FROM activities WHERE comment IS NOT NULL AND comment != '';
+
+CREATE TRIGGER activities_fts_after_insert AFTER INSERT ON activities
+WHEN new.comment IS NOT NULL AND new.comment != ''
+BEGIN
+    INSERT INTO activities_fts (activity_id, comment) VALUES (new.id, new.comment);
+END;
+
+CREATE TRIGGER activities_fts_after_delete AFTER DELETE ON activities
+WHEN old.comment IS NOT NULL AND old.comment != ''
+BEGIN
+    DELETE FROM activities_fts WHERE activity_id = old.id;
+END;
+
+CREATE TRIGGER activities_fts_after_update AFTER UPDATE OF id, comment ON activities
+BEGIN
+    DELETE FROM activities_fts WHERE activity_id = old.id;
+    INSERT INTO activities_fts (activity_id, comment)
+    SELECT new.id, new.comment WHERE new.comment IS NOT NULL AND new.comment != '';
+END;
+
+CREATE INDEX idx_activities_currency ON activities(currency);
+
+CREATE TABLE activity_saved_views (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    filters TEXT NOT NULL,
+    sort TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE activities DROP COLUMN source_asset_id;
@@ -0,0 +1,2 @@
+-- Asset whose lots a corporate action (ticker change, merger, spin-off) moves into asset_id
+ALTER TABLE activities ADD COLUMN source_asset_id TEXT;
@@ -0,0 +1,12 @@
+-- Target allocation models. Targets are stored as a JSON array of
+-- {key, targetWeight, tolerance, assetIds}; whatever weight they leave is cash.
+CREATE TABLE allocation_models (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    target_kind TEXT NOT NULL,
+    scope_type TEXT NOT NULL,
+    scope_id TEXT,
+    targets TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
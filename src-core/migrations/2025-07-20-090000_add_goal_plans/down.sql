@@ -0,0 +1,4 @@
+ALTER TABLE goals DROP COLUMN contribution_frequency;
+ALTER TABLE goals DROP COLUMN contribution_amount;
+ALTER TABLE goals DROP COLUMN starting_amount;
+ALTER TABLE goals DROP COLUMN target_date;
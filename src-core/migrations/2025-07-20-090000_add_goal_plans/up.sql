@@ -0,0 +1,7 @@
+-- Dated targets and contribution plans for goals. is_achieved is now derived
+-- from the goal's funded value and only cached here.
+ALTER TABLE goals ADD COLUMN target_date DATE;
+ALTER TABLE goals ADD COLUMN starting_amount REAL NOT NULL DEFAULT 0;
+ALTER TABLE goals ADD COLUMN contribution_amount REAL NOT NULL DEFAULT 0;
+ALTER TABLE goals ADD COLUMN contribution_frequency TEXT NOT NULL DEFAULT 'MONTHLY';
+UPDATE goals SET is_achieved = FALSE WHERE is_achieved IS NULL;
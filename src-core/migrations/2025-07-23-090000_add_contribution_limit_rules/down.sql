@@ -0,0 +1,4 @@
+ALTER TABLE contribution_limits DROP COLUMN rules;
+ALTER TABLE contribution_limits DROP COLUMN room_start_year;
+ALTER TABLE contribution_limits DROP COLUMN birth_year;
+ALTER TABLE contribution_limits DROP COLUMN preset;
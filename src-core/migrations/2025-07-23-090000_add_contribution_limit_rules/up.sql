@@ -0,0 +1,6 @@
+-- Registered-account rules for contribution limits. preset names a built-in rule set
+-- (TFSA, RRSP, ISA, IRA, 401K); rules holds custom or overriding rules as JSON.
+ALTER TABLE contribution_limits ADD COLUMN preset TEXT;
+ALTER TABLE contribution_limits ADD COLUMN birth_year INTEGER;
+ALTER TABLE contribution_limits ADD COLUMN room_start_year INTEGER;
+ALTER TABLE contribution_limits ADD COLUMN rules TEXT;
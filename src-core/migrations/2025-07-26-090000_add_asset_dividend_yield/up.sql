@@ -0,0 +1,2 @@
+-- Forward dividend yield reported by the market data provider, as a fraction of price.
+ALTER TABLE assets ADD COLUMN dividend_yield REAL;
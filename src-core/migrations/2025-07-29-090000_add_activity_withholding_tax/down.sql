@@ -0,0 +1,2 @@
+ALTER TABLE activities DROP COLUMN withholding_country;
+ALTER TABLE activities DROP COLUMN withholding_tax;
@@ -0,0 +1,4 @@
+-- Tax withheld at source on income activities. amount stays the net cash received;
+-- gross income is amount + withholding_tax.
+ALTER TABLE activities ADD COLUMN withholding_tax TEXT;
+ALTER TABLE activities ADD COLUMN withholding_country TEXT;
@@ -0,0 +1,4 @@
+DELETE FROM holdings_snapshots WHERE account_id IN (SELECT id FROM account_groups);
+DELETE FROM daily_account_valuation WHERE account_id IN (SELECT id FROM account_groups);
+ALTER TABLE accounts DROP COLUMN group_id;
+DROP TABLE IF EXISTS account_groups;
@@ -0,0 +1,21 @@
+-- Account groups as entities. Groups nest through parent_id; an account belongs
+-- to at most one group through accounts.group_id. accounts."group" is kept as the
+-- group's display path ("Retirement > Spouse") for existing readers.
+CREATE TABLE account_groups (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    parent_id TEXT REFERENCES account_groups(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE accounts ADD COLUMN group_id TEXT;
+
+-- Existing free-text groups become top-level groups.
+INSERT INTO account_groups (id, name)
+SELECT lower(hex(randomblob(16))), name
+FROM (SELECT DISTINCT trim("group") AS name FROM accounts WHERE trim(COALESCE("group", '')) <> '');
+
+UPDATE accounts
+SET group_id = (SELECT g.id FROM account_groups g WHERE g.name = trim(accounts."group"))
+WHERE trim(COALESCE("group", '')) <> '';
@@ -0,0 +1,10 @@
+DELETE FROM holdings_snapshots WHERE account_id IN (SELECT id FROM profiles);
+DELETE FROM daily_account_valuation WHERE account_id IN (SELECT id FROM profiles);
+DELETE FROM app_settings WHERE setting_key = 'active_profile_id';
+
+DROP INDEX IF EXISTS idx_accounts_profile_id;
+ALTER TABLE contribution_limits DROP COLUMN profile_id;
+ALTER TABLE goals DROP COLUMN profile_id;
+ALTER TABLE accounts DROP COLUMN profile_id;
+
+DROP TABLE profiles;
@@ -0,0 +1,26 @@
+-- Profiles split one database into separate portfolios, e.g. one per household
+-- member. Accounts, goals and contribution limits belong to exactly one profile;
+-- everything already recorded starts out in the default profile.
+CREATE TABLE profiles (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    base_currency TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO profiles (id, name, base_currency)
+VALUES (
+    'default',
+    'Default',
+    COALESCE(
+        (SELECT NULLIF(setting_value, '') FROM app_settings WHERE setting_key = 'base_currency'),
+        'USD'
+    )
+);
+
+ALTER TABLE accounts ADD COLUMN profile_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE goals ADD COLUMN profile_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE contribution_limits ADD COLUMN profile_id TEXT NOT NULL DEFAULT 'default';
+
+CREATE INDEX idx_accounts_profile_id ON accounts(profile_id);
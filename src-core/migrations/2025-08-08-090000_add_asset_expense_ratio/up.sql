@@ -0,0 +1,2 @@
+-- Total expense ratio charged by a fund, as a yearly fraction of the amount held.
+ALTER TABLE assets ADD COLUMN expense_ratio REAL;
@@ -0,0 +1,4 @@
+ALTER TABLE daily_account_valuation DROP COLUMN accrued_interest;
+
+DROP INDEX IF EXISTS idx_cash_interest_rates_account_currency_date;
+DROP TABLE cash_interest_rates;
@@ -0,0 +1,19 @@
+-- Interest paid on an account's cash in one currency. A row sets the annual rate
+-- from its effective date until the next row for the same account and currency;
+-- a zero rate stops accrual. Interest is paid out at the end of each month.
+CREATE TABLE cash_interest_rates (
+    id TEXT PRIMARY KEY NOT NULL,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    currency TEXT NOT NULL,
+    annual_rate TEXT NOT NULL,
+    effective_date DATE NOT NULL,
+    compounding TEXT NOT NULL DEFAULT 'DAILY',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_cash_interest_rates_account_currency_date
+    ON cash_interest_rates(account_id, currency, effective_date);
+
+-- Interest earned on cash since the last payout, in the account currency.
+ALTER TABLE daily_account_valuation ADD COLUMN accrued_interest TEXT NOT NULL DEFAULT '0';
@@ -0,0 +1,9 @@
+-- Existing activities an import merged duplicate rows into, with the row as it was
+-- before the merge so rolling the session back can restore it.
+CREATE TABLE import_session_merges (
+    session_id TEXT NOT NULL,
+    activity_id TEXT NOT NULL,
+    before_json TEXT NOT NULL,
+    PRIMARY KEY (session_id, activity_id),
+    FOREIGN KEY (session_id) REFERENCES import_sessions(id) ON DELETE CASCADE
+);
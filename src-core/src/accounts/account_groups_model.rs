@@ -0,0 +1,312 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::accounts_model::Account;
+use crate::{errors::ValidationError, Error, Result};
+
+/// Separator between group names in an account group path.
+pub const ACCOUNT_GROUP_PATH_SEPARATOR: &str = " > ";
+
+/// A named group of accounts, optionally nested under a parent group.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountGroup {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Input model for creating a new account group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAccountGroup {
+    pub name: String,
+    pub parent_id: Option<String>,
+}
+
+impl NewAccountGroup {
+    pub fn validate(&self) -> Result<()> {
+        validate_group_name(&self.name)
+    }
+}
+
+/// Input model for renaming or moving an account group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountGroupUpdate {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+}
+
+impl AccountGroupUpdate {
+    pub fn validate(&self) -> Result<()> {
+        validate_group_name(&self.name)
+    }
+}
+
+fn validate_group_name(name: &str) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(Error::Validation(ValidationError::InvalidInput(
+            "Group name cannot be empty".to_string(),
+        )));
+    }
+    if name.contains(ACCOUNT_GROUP_PATH_SEPARATOR.trim()) {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Group name cannot contain '{}'; nest groups with a parent instead",
+            ACCOUNT_GROUP_PATH_SEPARATOR.trim()
+        ))));
+    }
+    Ok(())
+}
+
+/// Database model for account groups
+#[derive(
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Selectable,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+)]
+#[diesel(table_name = crate::schema::account_groups)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[diesel(treat_none_as_null = true)]
+pub struct AccountGroupDB {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<AccountGroupDB> for AccountGroup {
+    fn from(db: AccountGroupDB) -> Self {
+        Self {
+            id: db.id,
+            name: db.name,
+            parent_id: db.parent_id,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        }
+    }
+}
+
+/// A group with its place in the hierarchy and the accounts it aggregates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountGroupMembership {
+    pub group: AccountGroup,
+    /// Names from the top-level group down, e.g. "Retirement > Spouse".
+    pub path: String,
+    /// 0 for top-level groups.
+    pub depth: usize,
+    /// Active accounts in the group or any of its subgroups.
+    pub account_ids: Vec<String>,
+}
+
+/// Path of every group, from its top-level ancestor down. A group whose parent
+/// is missing is treated as top-level.
+pub fn group_paths(groups: &[AccountGroup]) -> HashMap<String, String> {
+    let by_id: HashMap<&str, &AccountGroup> = groups.iter().map(|g| (g.id.as_str(), g)).collect();
+    groups
+        .iter()
+        .map(|group| {
+            let names: Vec<&str> = ancestors(&by_id, group)
+                .iter()
+                .rev()
+                .map(|g| g.name.as_str())
+                .collect();
+            (group.id.clone(), names.join(ACCOUNT_GROUP_PATH_SEPARATOR))
+        })
+        .collect()
+}
+
+// The group followed by its parent, grandparent and so on. Stops at a repeated
+// group so a corrupt cycle cannot loop forever.
+fn ancestors<'a>(
+    by_id: &HashMap<&str, &'a AccountGroup>,
+    group: &'a AccountGroup,
+) -> Vec<&'a AccountGroup> {
+    let mut chain = vec![group];
+    let mut seen: HashSet<&str> = HashSet::from([group.id.as_str()]);
+    let mut current = group;
+    while let Some(parent) = current
+        .parent_id
+        .as_deref()
+        .and_then(|id| by_id.get(id).copied())
+    {
+        if !seen.insert(parent.id.as_str()) {
+            break;
+        }
+        chain.push(parent);
+        current = parent;
+    }
+    chain
+}
+
+/// Ids of the group and every group nested below it.
+pub fn descendant_group_ids(groups: &[AccountGroup], group_id: &str) -> HashSet<String> {
+    let mut found: HashSet<String> = HashSet::from([group_id.to_string()]);
+    let mut frontier = vec![group_id.to_string()];
+    while let Some(parent) = frontier.pop() {
+        for child in groups
+            .iter()
+            .filter(|g| g.parent_id.as_deref() == Some(parent.as_str()))
+        {
+            if found.insert(child.id.clone()) {
+                frontier.push(child.id.clone());
+            }
+        }
+    }
+    found
+}
+
+/// Checks that `parent_id` exists and that moving `group_id` under it does not
+/// make the group its own ancestor.
+pub fn validate_group_parent(
+    groups: &[AccountGroup],
+    group_id: Option<&str>,
+    parent_id: Option<&str>,
+) -> Result<()> {
+    let Some(parent_id) = parent_id else {
+        return Ok(());
+    };
+    if !groups.iter().any(|g| g.id == parent_id) {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Parent group {} does not exist",
+            parent_id
+        ))));
+    }
+    if let Some(group_id) = group_id {
+        if descendant_group_ids(groups, group_id).contains(parent_id) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A group cannot be nested under itself or one of its subgroups".to_string(),
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Every group with its path and the active accounts it aggregates, parents
+/// before their children.
+pub fn group_memberships(
+    groups: &[AccountGroup],
+    accounts: &[Account],
+) -> Vec<AccountGroupMembership> {
+    let paths = group_paths(groups);
+    let mut memberships: Vec<AccountGroupMembership> = groups
+        .iter()
+        .map(|group| {
+            let members = descendant_group_ids(groups, &group.id);
+            let account_ids = accounts
+                .iter()
+                .filter(|a| a.is_active)
+                .filter(|a| a.group_id.as_ref().is_some_and(|id| members.contains(id)))
+                .map(|a| a.id.clone())
+                .collect();
+            let path = paths[&group.id].clone();
+            AccountGroupMembership {
+                group: group.clone(),
+                depth: path.matches(ACCOUNT_GROUP_PATH_SEPARATOR).count(),
+                path,
+                account_ids,
+            }
+        })
+        .collect();
+    memberships.sort_by(|a, b| a.path.cmp(&b.path));
+    memberships
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(id: &str, name: &str, parent_id: Option<&str>) -> AccountGroup {
+        AccountGroup {
+            id: id.to_string(),
+            name: name.to_string(),
+            parent_id: parent_id.map(str::to_string),
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    fn account(id: &str, group_id: Option<&str>, is_active: bool) -> Account {
+        Account {
+            id: id.to_string(),
+            is_active,
+            group_id: group_id.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn household() -> Vec<AccountGroup> {
+        vec![
+            group("ret", "Retirement", None),
+            group("spouse", "Spouse", Some("ret")),
+            group("ira", "IRA", Some("spouse")),
+            group("taxable", "Taxable", None),
+        ]
+    }
+
+    #[test]
+    fn test_memberships_roll_subgroup_accounts_up_to_ancestors() {
+        let accounts = vec![
+            account("a1", Some("ret"), true),
+            account("a2", Some("spouse"), true),
+            account("a3", Some("ira"), true),
+            account("a4", Some("ira"), false),
+            account("a5", Some("taxable"), true),
+            account("a6", None, true),
+        ];
+        let memberships = group_memberships(&household(), &accounts);
+
+        let paths: Vec<&str> = memberships.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "Retirement",
+                "Retirement > Spouse",
+                "Retirement > Spouse > IRA",
+                "Taxable"
+            ]
+        );
+        assert_eq!(memberships[0].account_ids, vec!["a1", "a2", "a3"]);
+        assert_eq!(memberships[1].account_ids, vec!["a2", "a3"]);
+        assert_eq!(memberships[2].depth, 2);
+        assert_eq!(memberships[2].account_ids, vec!["a3"]);
+        assert_eq!(memberships[3].account_ids, vec!["a5"]);
+    }
+
+    #[test]
+    fn test_parent_must_exist_and_not_be_a_descendant() {
+        let groups = household();
+        assert!(validate_group_parent(&groups, None, Some("ret")).is_ok());
+        assert!(validate_group_parent(&groups, Some("taxable"), Some("ira")).is_ok());
+        assert!(validate_group_parent(&groups, Some("ret"), None).is_ok());
+        assert!(validate_group_parent(&groups, None, Some("missing")).is_err());
+        assert!(validate_group_parent(&groups, Some("ret"), Some("ret")).is_err());
+        assert!(validate_group_parent(&groups, Some("ret"), Some("ira")).is_err());
+    }
+
+    #[test]
+    fn test_group_names_cannot_contain_the_path_separator() {
+        let new_group = |name: &str| NewAccountGroup {
+            name: name.to_string(),
+            parent_id: None,
+        };
+        assert!(new_group("Retirement").validate().is_ok());
+        assert!(new_group("  ").validate().is_err());
+        assert!(new_group("Retirement > Spouse").validate().is_err());
+    }
+}
@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{self, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::{Result, ValidationError};
+use crate::schema::{account_groups, accounts, daily_account_valuation, holdings_snapshots};
+use crate::Error;
+
+use super::account_groups_model::{
+    group_paths, validate_group_parent, AccountGroup, AccountGroupDB, AccountGroupUpdate,
+    NewAccountGroup, ACCOUNT_GROUP_PATH_SEPARATOR,
+};
+use super::accounts_traits::AccountGroupRepositoryTrait;
+
+/// Repository for managing account groups in the database
+pub struct AccountGroupRepository {
+    pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl AccountGroupRepository {
+    pub fn new(
+        pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+        writer: WriteHandle,
+    ) -> Self {
+        Self { pool, writer }
+    }
+}
+
+fn load_groups(conn: &mut SqliteConnection) -> Result<Vec<AccountGroup>> {
+    Ok(account_groups::table
+        .select(AccountGroupDB::as_select())
+        .load::<AccountGroupDB>(conn)?
+        .into_iter()
+        .map(AccountGroup::from)
+        .collect())
+}
+
+/// Group id and path for an account being saved. An explicit `group_id` must
+/// exist; otherwise a free-text `group` such as "Retirement > Spouse" is matched
+/// against the group paths, creating whichever groups along it are missing.
+pub(crate) fn resolve_account_group(
+    conn: &mut SqliteConnection,
+    group_id: Option<&str>,
+    group: Option<&str>,
+) -> Result<(Option<String>, Option<String>)> {
+    let mut groups = load_groups(conn)?;
+    if let Some(group_id) = group_id {
+        return match group_paths(&groups).remove(group_id) {
+            Some(path) => Ok((Some(group_id.to_string()), Some(path))),
+            None => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Account group {} does not exist",
+                group_id
+            )))),
+        };
+    }
+
+    let names: Vec<&str> = group
+        .unwrap_or_default()
+        .split(ACCOUNT_GROUP_PATH_SEPARATOR.trim())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect();
+    let mut parent_id: Option<String> = None;
+    for name in &names {
+        let existing = groups
+            .iter()
+            .find(|g| g.parent_id == parent_id && g.name.eq_ignore_ascii_case(name))
+            .map(|g| g.id.clone());
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                let now = chrono::Utc::now().naive_utc();
+                let group_db = AccountGroupDB {
+                    id: Uuid::new_v4().to_string(),
+                    name: name.to_string(),
+                    parent_id: parent_id.clone(),
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(account_groups::table)
+                    .values(&group_db)
+                    .execute(conn)?;
+                let id = group_db.id.clone();
+                groups.push(group_db.into());
+                id
+            }
+        };
+        parent_id = Some(id);
+    }
+
+    let path = parent_id
+        .as_ref()
+        .and_then(|id| group_paths(&groups).remove(id));
+    Ok((parent_id, path))
+}
+
+// Rewrites accounts.group for every grouped account after groups were renamed or moved.
+fn refresh_account_group_labels(conn: &mut SqliteConnection) -> Result<()> {
+    let groups = load_groups(conn)?;
+    for (group_id, path) in group_paths(&groups) {
+        diesel::update(accounts::table.filter(accounts::group_id.eq(&group_id)))
+            .set(accounts::group.eq(path))
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl AccountGroupRepositoryTrait for AccountGroupRepository {
+    fn list(&self) -> Result<Vec<AccountGroup>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut groups = load_groups(&mut conn)?;
+        groups.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(groups)
+    }
+
+    fn get_by_id(&self, group_id: &str) -> Result<AccountGroup> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(account_groups::table
+            .find(group_id)
+            .select(AccountGroupDB::as_select())
+            .first::<AccountGroupDB>(&mut conn)?
+            .into())
+    }
+
+    async fn create(&self, new_group: NewAccountGroup) -> Result<AccountGroup> {
+        new_group.validate()?;
+        self.writer
+            .exec(move |conn| {
+                let groups = load_groups(conn)?;
+                validate_group_parent(&groups, None, new_group.parent_id.as_deref())?;
+
+                let now = chrono::Utc::now().naive_utc();
+                let group_db = AccountGroupDB {
+                    id: Uuid::new_v4().to_string(),
+                    name: new_group.name.trim().to_string(),
+                    parent_id: new_group.parent_id,
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(account_groups::table)
+                    .values(&group_db)
+                    .execute(conn)?;
+                Ok(group_db.into())
+            })
+            .await
+    }
+
+    async fn update(&self, group_update: AccountGroupUpdate) -> Result<AccountGroup> {
+        group_update.validate()?;
+        self.writer
+            .exec(move |conn| {
+                let existing = account_groups::table
+                    .find(&group_update.id)
+                    .select(AccountGroupDB::as_select())
+                    .first::<AccountGroupDB>(conn)?;
+                let groups = load_groups(conn)?;
+                validate_group_parent(
+                    &groups,
+                    Some(&group_update.id),
+                    group_update.parent_id.as_deref(),
+                )?;
+
+                let group_db = AccountGroupDB {
+                    id: existing.id,
+                    name: group_update.name.trim().to_string(),
+                    parent_id: group_update.parent_id,
+                    created_at: existing.created_at,
+                    updated_at: chrono::Utc::now().naive_utc(),
+                };
+                diesel::update(account_groups::table.find(&group_db.id))
+                    .set(&group_db)
+                    .execute(conn)?;
+                refresh_account_group_labels(conn)?;
+                Ok(group_db.into())
+            })
+            .await
+    }
+
+    async fn delete(&self, group_id: &str) -> Result<usize> {
+        let group_id = group_id.to_string();
+        self.writer
+            .exec(move |conn| {
+                let Some(existing) = account_groups::table
+                    .find(&group_id)
+                    .select(AccountGroupDB::as_select())
+                    .first::<AccountGroupDB>(conn)
+                    .optional()?
+                else {
+                    return Ok(0);
+                };
+
+                // Subgroups and accounts move up to the deleted group's parent.
+                diesel::update(
+                    account_groups::table.filter(account_groups::parent_id.eq(&group_id)),
+                )
+                .set(account_groups::parent_id.eq(&existing.parent_id))
+                .execute(conn)?;
+                diesel::update(accounts::table.filter(accounts::group_id.eq(&group_id)))
+                    .set((
+                        accounts::group_id.eq(&existing.parent_id),
+                        accounts::group.eq(None::<String>),
+                    ))
+                    .execute(conn)?;
+                diesel::delete(
+                    holdings_snapshots::table.filter(holdings_snapshots::account_id.eq(&group_id)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    daily_account_valuation::table
+                        .filter(daily_account_valuation::account_id.eq(&group_id)),
+                )
+                .execute(conn)?;
+                let deleted =
+                    diesel::delete(account_groups::table.find(&group_id)).execute(conn)?;
+                refresh_account_group_labels(conn)?;
+                Ok(deleted)
+            })
+            .await
+    }
+}
@@ -0,0 +1,60 @@
+use log::debug;
+use std::sync::Arc;
+
+use super::account_groups_model::{
+    group_memberships, AccountGroup, AccountGroupMembership, AccountGroupUpdate, NewAccountGroup,
+};
+use super::accounts_traits::{
+    AccountGroupRepositoryTrait, AccountGroupServiceTrait, AccountRepositoryTrait,
+};
+use crate::errors::Result;
+
+/// Service for managing account groups and resolving which accounts each one covers
+pub struct AccountGroupService {
+    repository: Arc<dyn AccountGroupRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+}
+
+impl AccountGroupService {
+    pub fn new(
+        repository: Arc<dyn AccountGroupRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AccountGroupServiceTrait for AccountGroupService {
+    fn get_account_groups(&self) -> Result<Vec<AccountGroup>> {
+        self.repository.list()
+    }
+
+    async fn create_account_group(&self, new_group: NewAccountGroup) -> Result<AccountGroup> {
+        debug!("Creating account group {}", new_group.name);
+        self.repository.create(new_group).await
+    }
+
+    async fn update_account_group(&self, group_update: AccountGroupUpdate) -> Result<AccountGroup> {
+        debug!("Updating account group {}", group_update.id);
+        self.repository.update(group_update).await
+    }
+
+    async fn delete_account_group(&self, group_id: &str) -> Result<()> {
+        debug!("Deleting account group {}", group_id);
+        self.repository.delete(group_id).await?;
+        Ok(())
+    }
+
+    fn get_group_memberships(&self) -> Result<Vec<AccountGroupMembership>> {
+        let groups = self.repository.list()?;
+        if groups.is_empty() {
+            return Ok(Vec::new());
+        }
+        let accounts = self.account_repository.list(Some(true), None)?;
+        Ok(group_memberships(&groups, &accounts))
+    }
+}
@@ -2,6 +2,7 @@ use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::constants::DEFAULT_PROFILE_ID;
 use crate::{errors::ValidationError, Error, Result};
 
 /// Domain model representing an account in the system
@@ -18,6 +19,12 @@ pub struct Account {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub platform_id: Option<String>,
+    /// Account group the account belongs to; `group` then holds that group's path.
+    #[serde(default)]
+    pub group_id: Option<String>,
+    /// Profile (portfolio) the account belongs to.
+    #[serde(default)]
+    pub profile_id: String,
 }
 
 /// Input model for creating a new account
@@ -33,6 +40,11 @@ pub struct NewAccount {
     pub is_default: bool,
     pub is_active: bool,
     pub platform_id: Option<String>,
+    #[serde(default)]
+    pub group_id: Option<String>,
+    /// Defaults to the active profile.
+    #[serde(default)]
+    pub profile_id: Option<String>,
 }
 
 impl NewAccount {
@@ -63,6 +75,11 @@ pub struct AccountUpdate {
     pub is_default: bool,
     pub is_active: bool,
     pub platform_id: Option<String>,
+    #[serde(default)]
+    pub group_id: Option<String>,
+    /// Moves the account to another profile; `None` keeps its current one.
+    #[serde(default)]
+    pub profile_id: Option<String>,
 }
 
 impl AccountUpdate {
@@ -111,6 +128,15 @@ pub struct AccountDB {
     #[diesel(skip_insertion)]
     pub updated_at: NaiveDateTime,
     pub platform_id: Option<String>,
+    #[diesel(treat_none_as_null = true)]
+    pub group_id: Option<String>,
+    /// Change log rows written before profiles existed have no profile id.
+    #[serde(default = "default_profile_id")]
+    pub profile_id: String,
+}
+
+fn default_profile_id() -> String {
+    DEFAULT_PROFILE_ID.to_string()
 }
 
 // Conversion implementations
@@ -127,6 +153,8 @@ impl From<AccountDB> for Account {
             created_at: db.created_at,
             updated_at: db.updated_at,
             platform_id: db.platform_id,
+            group_id: db.group_id,
+            profile_id: db.profile_id,
         }
     }
 }
@@ -145,6 +173,10 @@ impl From<NewAccount> for AccountDB {
             created_at: now,
             updated_at: now,
             platform_id: domain.platform_id,
+            group_id: domain.group_id,
+            profile_id: domain
+                .profile_id
+                .unwrap_or_else(|| DEFAULT_PROFILE_ID.to_string()),
         }
     }
 }
@@ -162,6 +194,8 @@ impl From<AccountUpdate> for AccountDB {
             created_at: NaiveDateTime::default(), // This will be filled from existing record
             updated_at: chrono::Utc::now().naive_utc(),
             platform_id: domain.platform_id,
+            group_id: domain.group_id,
+            profile_id: domain.profile_id.unwrap_or_default(), // Empty keeps the existing profile
         }
     }
 } 
\ No newline at end of file
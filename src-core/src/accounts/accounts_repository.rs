@@ -4,11 +4,18 @@ use diesel::sqlite::SqliteConnection;
 use std::sync::Arc;
 use async_trait::async_trait;
 
+use crate::audit::audit_repository::record_change;
+use crate::audit::{
+    CHANGE_ACTOR_USER, CHANGE_OPERATION_CREATE, CHANGE_OPERATION_DELETE, CHANGE_OPERATION_UPDATE,
+    ENTITY_TYPE_ACCOUNT,
+};
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::Result;
 use crate::schema::accounts;
 use crate::schema::accounts::dsl::*;
 
+use super::account_groups_repository::resolve_account_group;
+use crate::profiles::profiles_repository::ensure_profile_exists;
 use super::accounts_model::{Account, AccountDB, AccountUpdate, NewAccount};
 use super::accounts_traits::AccountRepositoryTrait;
 
@@ -41,12 +48,30 @@ impl AccountRepositoryTrait for AccountRepository {
 
         let mut account_db: AccountDB = new_account.into();
         account_db.id = uuid::Uuid::new_v4().to_string();
+        ensure_profile_exists(conn, &account_db.profile_id)?;
+        (account_db.group_id, account_db.group) = resolve_account_group(
+            conn,
+            account_db.group_id.as_deref(),
+            account_db.group.as_deref(),
+        )?;
 
         diesel::insert_into(accounts::table)
             .values(&account_db)
             .execute(conn)?;
 
-        Ok(account_db.into())
+        // Re-read so the logged row carries the database-assigned timestamps
+        let inserted = accounts.find(&account_db.id).first::<AccountDB>(conn)?;
+        record_change(
+            conn,
+            ENTITY_TYPE_ACCOUNT,
+            &inserted.id,
+            CHANGE_OPERATION_CREATE,
+            CHANGE_ACTOR_USER,
+            None,
+            Some(&inserted),
+        )?;
+
+        Ok(inserted.into())
     }
 
     async fn update(&self, account_update: AccountUpdate) -> Result<Account> {
@@ -60,13 +85,32 @@ impl AccountRepositoryTrait for AccountRepository {
                     .find(&account_db.id)
                     .first::<AccountDB>(conn)?;
 
-                account_db.currency = existing.currency;
+                account_db.currency = existing.currency.clone();
                 account_db.created_at = existing.created_at;
+                if account_db.profile_id.is_empty() {
+                    account_db.profile_id = existing.profile_id.clone();
+                } else {
+                    ensure_profile_exists(conn, &account_db.profile_id)?;
+                }
                 account_db.updated_at = chrono::Utc::now().naive_utc();
+                (account_db.group_id, account_db.group) = resolve_account_group(
+                    conn,
+                    account_db.group_id.as_deref(),
+                    account_db.group.as_deref(),
+                )?;
 
                 diesel::update(accounts.find(&account_db.id))
                     .set(&account_db)
                     .execute(conn)?;
+                record_change(
+                    conn,
+                    ENTITY_TYPE_ACCOUNT,
+                    &account_db.id,
+                    CHANGE_OPERATION_UPDATE,
+                    CHANGE_ACTOR_USER,
+                    Some(&existing),
+                    Some(&account_db),
+                )?;
 
                 Ok(account_db.into())
             })
@@ -113,8 +157,24 @@ impl AccountRepositoryTrait for AccountRepository {
         let id_to_delete_owned = account_id_param.to_string();
         self.writer
             .exec(move |conn| {
+                let Some(account) = accounts
+                    .find(&id_to_delete_owned)
+                    .first::<AccountDB>(conn)
+                    .optional()?
+                else {
+                    return Ok(0);
+                };
                 let affected_rows =
-                    diesel::delete(accounts.find(id_to_delete_owned)).execute(conn)?;
+                    diesel::delete(accounts.find(&id_to_delete_owned)).execute(conn)?;
+                record_change(
+                    conn,
+                    ENTITY_TYPE_ACCOUNT,
+                    &account.id,
+                    CHANGE_OPERATION_DELETE,
+                    CHANGE_ACTOR_USER,
+                    Some(&account),
+                    None,
+                )?;
                 Ok(affected_rows)
             })
             .await
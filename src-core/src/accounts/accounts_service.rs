@@ -6,12 +6,14 @@ use super::accounts_traits::{AccountRepositoryTrait, AccountServiceTrait};
 use crate::errors::Result;
 use crate::db::DbTransactionExecutor;
 use crate::fx::fx_traits::FxServiceTrait;
+use crate::profiles::profile_in_scope;
 
 /// Service for managing accounts (Generic over Executor)
 pub struct AccountService<E: DbTransactionExecutor + Send + Sync + Clone> {
     repository: Arc<dyn AccountRepositoryTrait>,
     fx_service: Arc<dyn FxServiceTrait>,
     base_currency: Arc<RwLock<String>>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
     transaction_executor: E,
 }
 
@@ -22,20 +24,34 @@ impl<E: DbTransactionExecutor + Send + Sync + Clone> AccountService<E> {
         fx_service: Arc<dyn FxServiceTrait>,
         transaction_executor: E,
         base_currency: Arc<RwLock<String>>,
+        active_profile_id: Arc<RwLock<Option<String>>>,
     ) -> Self {
         Self {
             repository,
             fx_service,
             transaction_executor,
             base_currency,
+            active_profile_id,
         }
     }
+
+    // Drops accounts outside the active profile.
+    fn in_active_profile(&self, accounts: Vec<Account>) -> Vec<Account> {
+        accounts
+            .into_iter()
+            .filter(|a| profile_in_scope(&self.active_profile_id, &a.profile_id))
+            .collect()
+    }
 }
 
 #[async_trait::async_trait]
 impl<E: DbTransactionExecutor + Send + Sync + Clone> AccountServiceTrait for AccountService<E> {
     /// Creates a new account with currency exchange support
     async fn create_account(&self, new_account: NewAccount) -> Result<Account> {
+        let mut new_account = new_account;
+        if new_account.profile_id.is_none() {
+            new_account.profile_id = self.active_profile_id.read().unwrap().clone();
+        }
         let base_currency = self.base_currency.read().unwrap().clone();
         debug!(
             "Creating account..., base_currency: {}, new_account.currency: {}",
@@ -71,21 +87,30 @@ impl<E: DbTransactionExecutor + Send + Sync + Clone> AccountServiceTrait for Acc
         (*self.repository).get_by_id(account_id)
     }
 
-    /// Lists all accounts with optional filtering by active status and account IDs
+    /// Lists accounts in the active profile with optional filtering by active status and account IDs
     fn list_accounts(
         &self,
         is_active_filter: Option<bool>,
         account_ids: Option<&[String]>,
+    ) -> Result<Vec<Account>> {
+        Ok(self.in_active_profile(self.list_household_accounts(is_active_filter, account_ids)?))
+    }
+
+    /// Lists accounts across all profiles with optional filtering by active status and account IDs
+    fn list_household_accounts(
+        &self,
+        is_active_filter: Option<bool>,
+        account_ids: Option<&[String]>,
     ) -> Result<Vec<Account>> {
         (*self.repository).list(is_active_filter, account_ids)
     }
 
-    /// Lists all accounts
+    /// Lists all accounts in the active profile
     fn get_all_accounts(&self) -> Result<Vec<Account>> {
-        (*self.repository).list(None, None)
+        Ok(self.in_active_profile((*self.repository).list(None, None)?))
     }
 
-    /// Lists only active accounts
+    /// Lists only active accounts in the active profile
     fn get_active_accounts(&self) -> Result<Vec<Account>> {
         self.list_accounts(Some(true), None)
     }
@@ -1,6 +1,9 @@
 use diesel::sqlite::SqliteConnection;
 use async_trait::async_trait;
 
+use super::account_groups_model::{
+    AccountGroup, AccountGroupMembership, AccountGroupUpdate, NewAccountGroup,
+};
 use super::accounts_model::{Account, AccountUpdate, NewAccount};
 use crate::errors::Result;
 
@@ -29,12 +32,44 @@ pub trait AccountServiceTrait: Send + Sync {
     async fn update_account(&self, account_update: AccountUpdate) -> Result<Account>;
     async fn delete_account(&self, account_id: &str) -> Result<()>;
     fn get_account(&self, account_id: &str) -> Result<Account>;
+    /// Accounts of the active profile, or of every profile in the household view.
     fn list_accounts(
         &self,
         is_active_filter: Option<bool>,
         account_ids: Option<&[String]>,
     ) -> Result<Vec<Account>>;
+    /// Accounts of every profile; calculations that keep all profiles current use this.
+    fn list_household_accounts(
+        &self,
+        is_active_filter: Option<bool>,
+        account_ids: Option<&[String]>,
+    ) -> Result<Vec<Account>>;
+    /// Accounts of the active profile, or of every profile in the household view.
     fn get_all_accounts(&self) -> Result<Vec<Account>>;
+    /// Active accounts of the active profile, or of every profile in the household view.
     fn get_active_accounts(&self) -> Result<Vec<Account>>;
     fn get_accounts_by_ids(&self, account_ids: &[String]) -> Result<Vec<Account>>;
-} 
\ No newline at end of file
+} 
+/// Trait defining the contract for Account group repository operations.
+#[async_trait]
+pub trait AccountGroupRepositoryTrait: Send + Sync {
+    fn list(&self) -> Result<Vec<AccountGroup>>;
+    fn get_by_id(&self, group_id: &str) -> Result<AccountGroup>;
+    async fn create(&self, new_group: NewAccountGroup) -> Result<AccountGroup>;
+    /// Renames or moves the group and refreshes the `group` path of the accounts below it.
+    async fn update(&self, group_update: AccountGroupUpdate) -> Result<AccountGroup>;
+    /// Deletes the group, moving its subgroups and accounts up to its parent, and drops
+    /// its aggregated snapshots and valuations.
+    async fn delete(&self, group_id: &str) -> Result<usize>;
+}
+
+/// Trait defining the contract for Account group service operations.
+#[async_trait]
+pub trait AccountGroupServiceTrait: Send + Sync {
+    fn get_account_groups(&self) -> Result<Vec<AccountGroup>>;
+    async fn create_account_group(&self, new_group: NewAccountGroup) -> Result<AccountGroup>;
+    async fn update_account_group(&self, group_update: AccountGroupUpdate) -> Result<AccountGroup>;
+    async fn delete_account_group(&self, group_id: &str) -> Result<()>;
+    /// Every group with its path and the active accounts it aggregates, subgroups included.
+    fn get_group_memberships(&self) -> Result<Vec<AccountGroupMembership>>;
+}
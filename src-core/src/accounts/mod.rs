@@ -1,4 +1,7 @@
 // Module declarations
+pub(crate) mod account_groups_model;
+pub(crate) mod account_groups_repository;
+pub(crate) mod account_groups_service;
 pub(crate) mod accounts_constants;
 pub(crate) mod accounts_model;
 pub(crate) mod accounts_repository;
@@ -6,10 +9,19 @@ pub(crate) mod accounts_service;
 pub(crate) mod accounts_traits;
 
 // Re-export the public interface
+pub use account_groups_model::{
+    AccountGroup, AccountGroupDB, AccountGroupMembership, AccountGroupUpdate, NewAccountGroup,
+    ACCOUNT_GROUP_PATH_SEPARATOR,
+};
+pub use account_groups_repository::AccountGroupRepository;
+pub use account_groups_service::AccountGroupService;
 pub use accounts_constants::*;
 // pub use accounts_errors::*;
 pub use accounts_model::{Account, AccountDB, AccountUpdate, NewAccount};
 pub use accounts_repository::AccountRepository;
 pub use accounts_service::AccountService;
-pub use accounts_traits::{AccountRepositoryTrait, AccountServiceTrait};
+pub use accounts_traits::{
+    AccountGroupRepositoryTrait, AccountGroupServiceTrait, AccountRepositoryTrait,
+    AccountServiceTrait,
+};
 
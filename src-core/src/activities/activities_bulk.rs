@@ -0,0 +1,200 @@
+use super::activities_errors::ActivityError;
+use super::activities_model::{ActivityBulkAction, ActivityBulkChanges, ActivityBulkFilter, ActivityDB};
+use super::activities_search::activity_date_bounds;
+
+impl ActivityBulkFilter {
+    pub fn is_empty(&self) -> bool {
+        let unset = |list: &Option<Vec<String>>| list.as_ref().is_none_or(|l| l.is_empty());
+        unset(&self.account_ids)
+            && unset(&self.activity_types)
+            && unset(&self.asset_ids)
+            && self.asset_id_keyword.as_deref().is_none_or(|k| k.trim().is_empty())
+            && self.start_date.is_none()
+            && self.end_date.is_none()
+    }
+
+    /// Rejects filters that would select every activity, and inverted date ranges.
+    pub fn validate(&self) -> Result<(), ActivityError> {
+        if self.is_empty() {
+            return Err(ActivityError::InvalidData(
+                "A bulk edit needs at least one filter".to_string(),
+            ));
+        }
+        if let (Some(start), Some(end)) = (self.start_date, self.end_date) {
+            if start > end {
+                return Err(ActivityError::InvalidData(format!(
+                    "Start date {} is after end date {}",
+                    start, end
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// String bounds on `activity_date`, as for search filters.
+    pub(crate) fn date_bounds(&self) -> (Option<String>, Option<String>) {
+        activity_date_bounds(self.start_date, self.end_date)
+    }
+}
+
+impl ActivityBulkChanges {
+    pub fn is_empty(&self) -> bool {
+        self.account_id.is_none()
+            && self.asset_id.is_none()
+            && self.currency.is_none()
+            && self.is_draft.is_none()
+    }
+
+    /// Uppercases the symbol and currency and rejects blank or malformed values.
+    pub fn normalized(&self) -> Result<Self, ActivityError> {
+        if self.is_empty() {
+            return Err(ActivityError::InvalidData(
+                "A bulk update needs at least one field to change".to_string(),
+            ));
+        }
+        let account_id = self.account_id.as_deref().map(str::trim);
+        if account_id.is_some_and(str::is_empty) {
+            return Err(ActivityError::InvalidData("Account cannot be empty".to_string()));
+        }
+        let asset_id = self.asset_id.as_deref().map(|s| s.trim().to_uppercase());
+        if asset_id.as_deref().is_some_and(str::is_empty) {
+            return Err(ActivityError::InvalidData("Symbol cannot be empty".to_string()));
+        }
+        let currency = self.currency.as_deref().map(|s| s.trim().to_uppercase());
+        if let Some(ref currency) = currency {
+            if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(ActivityError::InvalidData(format!(
+                    "'{}' is not a valid currency code",
+                    currency
+                )));
+            }
+        }
+        Ok(Self {
+            account_id: account_id.map(str::to_string),
+            asset_id,
+            currency,
+            is_draft: self.is_draft,
+        })
+    }
+
+    pub(crate) fn apply(&self, row: &mut ActivityDB) {
+        if let Some(ref account_id) = self.account_id {
+            row.account_id = account_id.clone();
+        }
+        if let Some(ref asset_id) = self.asset_id {
+            row.asset_id = asset_id.clone();
+        }
+        if let Some(ref currency) = self.currency {
+            row.currency = currency.clone();
+        }
+        if let Some(is_draft) = self.is_draft {
+            row.is_draft = is_draft;
+        }
+    }
+}
+
+/// Rows as they will be after `action`, in the order of `rows`; empty for deletes.
+pub(crate) fn plan_bulk_edit(
+    rows: &[ActivityDB],
+    action: &ActivityBulkAction,
+    updated_at: &str,
+) -> Vec<ActivityDB> {
+    match action {
+        ActivityBulkAction::Delete => Vec::new(),
+        ActivityBulkAction::Update(changes) => rows
+            .iter()
+            .map(|row| {
+                let mut updated = row.clone();
+                changes.apply(&mut updated);
+                updated.updated_at = updated_at.to_string();
+                updated
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn row(id: &str, asset_id: &str) -> ActivityDB {
+        ActivityDB {
+            id: id.to_string(),
+            account_id: "acc-1".to_string(),
+            asset_id: asset_id.to_string(),
+            activity_type: "BUY".to_string(),
+            activity_date: "2024-03-01T00:00:00+00:00".to_string(),
+            quantity: "10".to_string(),
+            unit_price: "100".to_string(),
+            currency: "USD".to_string(),
+            fee: "0".to_string(),
+            amount: None,
+            is_draft: false,
+            comment: None,
+            created_at: "2024-03-01T00:00:00+00:00".to_string(),
+            updated_at: "2024-03-01T00:00:00+00:00".to_string(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
+        }
+    }
+
+    #[test]
+    fn test_filter_validation_and_date_bounds() {
+        assert!(ActivityBulkFilter::default().validate().is_err());
+        assert!(ActivityBulkFilter {
+            asset_id_keyword: Some("  ".to_string()),
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+
+        let filter = ActivityBulkFilter {
+            start_date: NaiveDate::from_ymd_opt(2024, 1, 1),
+            end_date: NaiveDate::from_ymd_opt(2024, 12, 31),
+            ..Default::default()
+        };
+        assert!(filter.validate().is_ok());
+        assert_eq!(
+            filter.date_bounds(),
+            (Some("2024-01-01".to_string()), Some("2025-01-01".to_string()))
+        );
+
+        let inverted = ActivityBulkFilter {
+            start_date: filter.end_date,
+            end_date: filter.start_date,
+            ..Default::default()
+        };
+        assert!(inverted.validate().is_err());
+    }
+
+    #[test]
+    fn test_plan_bulk_update_only_touches_set_fields() {
+        let changes = ActivityBulkChanges {
+            asset_id: Some(" fb ".to_string()),
+            currency: Some("cad".to_string()),
+            ..Default::default()
+        }
+        .normalized()
+        .unwrap();
+        let rows = vec![row("a", "META"), row("b", "META")];
+
+        let after = plan_bulk_edit(&rows, &ActivityBulkAction::Update(changes), "2024-06-01T00:00:00+00:00");
+        assert_eq!(after.len(), 2);
+        assert!(after.iter().all(|r| r.asset_id == "FB" && r.currency == "CAD"));
+        assert!(after.iter().all(|r| r.account_id == "acc-1" && r.quantity == "10"));
+        assert_eq!(after[1].id, "b");
+        assert_eq!(after[0].updated_at, "2024-06-01T00:00:00+00:00");
+
+        assert!(plan_bulk_edit(&rows, &ActivityBulkAction::Delete, "").is_empty());
+        assert!(ActivityBulkChanges::default().normalized().is_err());
+        assert!(ActivityBulkChanges {
+            currency: Some("CA".to_string()),
+            ..Default::default()
+        }
+        .normalized()
+        .is_err());
+    }
+}
@@ -12,6 +12,11 @@ pub const ACTIVITY_TYPE_SELL: &str = "SELL";
 /// Cash dividend paid into the account. Increases cash.
 pub const ACTIVITY_TYPE_DIVIDEND: &str = "DIVIDEND";
 
+/// Dividend reinvested in the paying asset. Counts as dividend income (`amount`, defaulting
+/// to quantity × unit price) and adds a lot at the reinvestment price, so cash only moves
+/// by any residual and the fee.
+pub const ACTIVITY_TYPE_DIVIDEND_REINVESTMENT: &str = "DIVIDEND_REINVESTMENT";
+
 /// Interest earned on cash or fixed-income positions. Increases cash.
 pub const ACTIVITY_TYPE_INTEREST: &str = "INTEREST";
 
@@ -44,6 +49,19 @@ pub const ACTIVITY_TYPE_ADD_HOLDING: &str = "ADD_HOLDING";
 /// Write-off, gift, or expire a position without recording a sale. Fee only, decreases quantity.
 pub const ACTIVITY_TYPE_REMOVE_HOLDING: &str = "REMOVE_HOLDING";
 
+/// Symbol change with no economic effect. Moves every lot of `source_asset_id` into
+/// the activity's asset, keeping acquisition dates and the full cost basis.
+pub const ACTIVITY_TYPE_TICKER_CHANGE: &str = "TICKER_CHANGE";
+
+/// Acquisition of `source_asset_id` for stock, optionally plus cash. Moves its lots into
+/// the activity's asset with `amount` of the cost basis; any cash per old share
+/// (`unit_price`) is paid out and the rest of the cost basis is released.
+pub const ACTIVITY_TYPE_MERGER: &str = "MERGER";
+
+/// New shares distributed to holders of `source_asset_id`, which is kept. Copies its lots
+/// into the activity's asset and moves `amount` of their cost basis with them.
+pub const ACTIVITY_TYPE_SPIN_OFF: &str = "SPIN_OFF";
+
 /// Trading activity types
 pub const TRADING_ACTIVITY_TYPES: [&str; 5] = [
     ACTIVITY_TYPE_BUY,
@@ -53,8 +71,32 @@ pub const TRADING_ACTIVITY_TYPES: [&str; 5] = [
     ACTIVITY_TYPE_REMOVE_HOLDING,
 ];
 
+/// Corporate actions that move lots from `source_asset_id` into the activity's asset
+pub const CORPORATE_ACTION_ACTIVITY_TYPES: [&str; 3] = [
+    ACTIVITY_TYPE_TICKER_CHANGE,
+    ACTIVITY_TYPE_MERGER,
+    ACTIVITY_TYPE_SPIN_OFF,
+];
+
 /// Income activity types
-pub const INCOME_ACTIVITY_TYPES: [&str; 2] = [
+pub const INCOME_ACTIVITY_TYPES: [&str; 3] = [
     ACTIVITY_TYPE_DIVIDEND,
+    ACTIVITY_TYPE_DIVIDEND_REINVESTMENT,
     ACTIVITY_TYPE_INTEREST,
 ];
+
+/// Built-in broker statement parsers
+pub const STATEMENT_PARSER_IBKR_FLEX: &str = "IBKR_FLEX";
+pub const STATEMENT_PARSER_SCHWAB: &str = "SCHWAB";
+pub const STATEMENT_PARSER_FIDELITY: &str = "FIDELITY";
+pub const STATEMENT_PARSER_QUESTRADE: &str = "QUESTRADE";
+pub const STATEMENT_PARSER_DEGIRO: &str = "DEGIRO";
+pub const STATEMENT_PARSER_OFX: &str = "OFX";
+pub const STATEMENT_PARSER_QIF: &str = "QIF";
+
+/// Import source recorded for rows mapped from a generic CSV file
+pub const IMPORT_SOURCE_CSV: &str = "CSV";
+
+/// Import session statuses
+pub const IMPORT_SESSION_STATUS_COMPLETED: &str = "COMPLETED";
+pub const IMPORT_SESSION_STATUS_ROLLED_BACK: &str = "ROLLED_BACK";
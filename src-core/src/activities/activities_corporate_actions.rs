@@ -0,0 +1,120 @@
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+use super::activities_errors::ActivityError;
+use super::activities_model::ActivityType;
+
+impl ActivityType {
+    /// Ticker changes, mergers and spin-offs, which move lots between two assets.
+    pub fn is_corporate_action(&self) -> bool {
+        matches!(
+            self,
+            ActivityType::TickerChange | ActivityType::Merger | ActivityType::SpinOff
+        )
+    }
+}
+
+/// Share of the source asset's cost basis a corporate action moves to the new asset,
+/// read from the activity `amount`. Ticker changes always move all of it, mergers
+/// default to all of it, and spin-offs must state the allocation.
+pub fn corporate_action_cost_ratio(
+    activity_type: &ActivityType,
+    amount: Option<Decimal>,
+) -> Result<Decimal, ActivityError> {
+    let ratio = match (activity_type, amount) {
+        (ActivityType::TickerChange, None) | (ActivityType::Merger, None) => Decimal::ONE,
+        (ActivityType::TickerChange, Some(ratio)) if ratio == Decimal::ONE => ratio,
+        (ActivityType::TickerChange, Some(ratio)) => {
+            return Err(ActivityError::InvalidData(format!(
+                "A ticker change moves the whole cost basis, got a ratio of {}",
+                ratio
+            )))
+        }
+        (ActivityType::Merger, Some(ratio)) if ratio > Decimal::ZERO && ratio <= Decimal::ONE => {
+            ratio
+        }
+        (ActivityType::SpinOff, Some(ratio)) if ratio > Decimal::ZERO && ratio < Decimal::ONE => {
+            ratio
+        }
+        (ActivityType::SpinOff, None) => {
+            return Err(ActivityError::InvalidData(
+                "A spin-off needs the share of cost basis allocated to the new asset".to_string(),
+            ))
+        }
+        (ActivityType::Merger, Some(ratio)) | (ActivityType::SpinOff, Some(ratio)) => {
+            return Err(ActivityError::InvalidData(format!(
+                "Cost basis ratio {} is out of range for a {}",
+                ratio,
+                activity_type.as_str()
+            )))
+        }
+        _ => {
+            return Err(ActivityError::InvalidData(format!(
+                "{} is not a corporate action",
+                activity_type.as_str()
+            )))
+        }
+    };
+    Ok(ratio)
+}
+
+/// Checks the source asset and cost basis ratio of a corporate action and returns the
+/// trimmed source asset id. Other activity types never carry a source asset.
+pub(crate) fn validate_corporate_action(
+    activity_type: &str,
+    asset_id: &str,
+    source_asset_id: Option<&str>,
+    amount: Option<Decimal>,
+) -> Result<Option<String>, ActivityError> {
+    let activity_type = match ActivityType::from_str(activity_type) {
+        Ok(activity_type) if activity_type.is_corporate_action() => activity_type,
+        _ => return Ok(None),
+    };
+    let source_asset_id = source_asset_id.map(str::trim).unwrap_or_default();
+    if source_asset_id.is_empty() {
+        return Err(ActivityError::InvalidData(format!(
+            "A {} needs the asset it replaces",
+            activity_type.as_str()
+        )));
+    }
+    if source_asset_id.eq_ignore_ascii_case(asset_id.trim()) {
+        return Err(ActivityError::InvalidData(format!(
+            "A {} must move lots into a different asset than {}",
+            activity_type.as_str(),
+            source_asset_id
+        )));
+    }
+    corporate_action_cost_ratio(&activity_type, amount)?;
+    Ok(Some(source_asset_id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_cost_ratio_defaults_and_bounds() {
+        assert_eq!(corporate_action_cost_ratio(&ActivityType::TickerChange, None).unwrap(), dec!(1));
+        assert!(corporate_action_cost_ratio(&ActivityType::TickerChange, Some(dec!(0.5))).is_err());
+        assert_eq!(corporate_action_cost_ratio(&ActivityType::Merger, None).unwrap(), dec!(1));
+        assert_eq!(corporate_action_cost_ratio(&ActivityType::Merger, Some(dec!(0.8))).unwrap(), dec!(0.8));
+        assert!(corporate_action_cost_ratio(&ActivityType::Merger, Some(dec!(0))).is_err());
+        assert!(corporate_action_cost_ratio(&ActivityType::SpinOff, None).is_err());
+        assert!(corporate_action_cost_ratio(&ActivityType::SpinOff, Some(dec!(1))).is_err());
+        assert_eq!(corporate_action_cost_ratio(&ActivityType::SpinOff, Some(dec!(0.1))).unwrap(), dec!(0.1));
+        assert!(corporate_action_cost_ratio(&ActivityType::Buy, None).is_err());
+    }
+
+    #[test]
+    fn test_validate_corporate_action_source() {
+        assert_eq!(validate_corporate_action("BUY", "AAPL", Some("MSFT"), None).unwrap(), None);
+        assert_eq!(
+            validate_corporate_action("TICKER_CHANGE", "META", Some(" FB "), None).unwrap(),
+            Some("FB".to_string())
+        );
+        assert!(validate_corporate_action("MERGER", "AVGO", None, None).is_err());
+        assert!(validate_corporate_action("MERGER", "AVGO", Some("avgo"), None).is_err());
+        assert!(validate_corporate_action("SPIN_OFF", "GEHC", Some("GE"), Some(dec!(1.2))).is_err());
+    }
+}
@@ -14,6 +14,8 @@ pub enum ActivityError {
     AssetError(String),
     #[error("Currency exchange error: {0}")]
     CurrencyExchangeError(String),
+    #[error("Statement parse error: {0}")]
+    ParseError(String),
 }
 
 impl From<DieselError> for ActivityError {
@@ -0,0 +1,204 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::activities_model::{Activity, ActivityImport};
+
+/// Key under which a possible duplicate is reported in `ActivityImport::errors`.
+pub const DUPLICATE_ERROR_KEY: &str = "duplicate";
+
+/// Tolerances used when comparing activity fingerprints.
+#[derive(Debug, Clone, Copy)]
+pub struct DuplicateTolerance {
+    /// Dates may differ by this many days, which absorbs trade vs settlement dates
+    /// and timezone shifts between broker exports.
+    pub date_days: i64,
+    /// Quantities are compared after rounding to this many decimal places.
+    pub quantity_decimal_places: u32,
+    /// Prices and amounts are compared after rounding to this many decimal places.
+    pub price_decimal_places: u32,
+}
+
+impl Default for DuplicateTolerance {
+    fn default() -> Self {
+        Self {
+            date_days: 1,
+            quantity_decimal_places: 4,
+            price_decimal_places: 2,
+        }
+    }
+}
+
+/// The fields that identify an activity when looking for duplicates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityFingerprint {
+    pub account_id: String,
+    pub date: NaiveDate,
+    pub activity_type: String,
+    pub symbol: String,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+    pub amount: Decimal,
+}
+
+impl ActivityFingerprint {
+    pub fn from_activity(activity: &Activity) -> Self {
+        Self {
+            account_id: activity.account_id.clone(),
+            date: activity.activity_date.naive_utc().date(),
+            activity_type: activity.activity_type.to_uppercase(),
+            symbol: activity.asset_id.trim().to_uppercase(),
+            quantity: activity.quantity,
+            unit_price: activity.unit_price,
+            amount: activity.amount.unwrap_or_default(),
+        }
+    }
+
+    /// Returns `None` when the row has no account or its date cannot be parsed.
+    pub fn from_import(activity: &ActivityImport) -> Option<Self> {
+        Some(Self {
+            account_id: activity.account_id.clone()?,
+            date: activity.parsed_date()?,
+            activity_type: activity.activity_type.to_uppercase(),
+            symbol: activity.symbol.trim().to_uppercase(),
+            quantity: activity.quantity,
+            unit_price: activity.unit_price,
+            amount: activity.amount.unwrap_or_default(),
+        })
+    }
+
+    pub fn matches(&self, other: &ActivityFingerprint, tolerance: &DuplicateTolerance) -> bool {
+        let same_quantity = self.quantity.round_dp(tolerance.quantity_decimal_places)
+            == other.quantity.round_dp(tolerance.quantity_decimal_places);
+        let same_price = self.unit_price.round_dp(tolerance.price_decimal_places)
+            == other.unit_price.round_dp(tolerance.price_decimal_places);
+        let same_amount = self.amount.round_dp(tolerance.price_decimal_places)
+            == other.amount.round_dp(tolerance.price_decimal_places);
+
+        self.account_id == other.account_id
+            && self.activity_type == other.activity_type
+            && self.symbol == other.symbol
+            && (self.date - other.date).num_days().abs() <= tolerance.date_days
+            && same_quantity
+            && same_price
+            && same_amount
+    }
+}
+
+/// Matches imported rows against existing activities, one to one.
+///
+/// Each existing activity can absorb at most one imported row, so a statement that
+/// legitimately contains two identical trades only flags as many of them as already
+/// exist. When several candidates match, the one with the closest date wins.
+pub struct DuplicateMatcher {
+    candidates: Vec<(String, ActivityFingerprint)>,
+    tolerance: DuplicateTolerance,
+}
+
+impl DuplicateMatcher {
+    pub fn new(existing: &[Activity], tolerance: DuplicateTolerance) -> Self {
+        Self {
+            candidates: existing
+                .iter()
+                .map(|activity| (activity.id.clone(), ActivityFingerprint::from_activity(activity)))
+                .collect(),
+            tolerance,
+        }
+    }
+
+    /// Returns the id of the existing activity duplicated by `fingerprint` and removes it
+    /// from the candidates.
+    pub fn take_match(&mut self, fingerprint: &ActivityFingerprint) -> Option<String> {
+        let index = self
+            .candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, candidate))| candidate.matches(fingerprint, &self.tolerance))
+            .min_by_key(|(_, (_, candidate))| (candidate.date - fingerprint.date).num_days().abs())
+            .map(|(index, _)| index)?;
+        Some(self.candidates.swap_remove(index).0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use rust_decimal_macros::dec;
+
+    fn existing(id: &str, date: NaiveDate, quantity: Decimal, unit_price: Decimal) -> Activity {
+        Activity {
+            id: id.to_string(),
+            account_id: "acc1".to_string(),
+            asset_id: "AAPL".to_string(),
+            activity_type: "BUY".to_string(),
+            activity_date: Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()),
+            quantity,
+            unit_price,
+            currency: "USD".to_string(),
+            fee: Decimal::ZERO,
+            amount: None,
+            is_draft: false,
+            comment: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
+        }
+    }
+
+    fn fingerprint(date: NaiveDate, quantity: Decimal, unit_price: Decimal) -> ActivityFingerprint {
+        ActivityFingerprint {
+            account_id: "acc1".to_string(),
+            date,
+            activity_type: "BUY".to_string(),
+            symbol: "AAPL".to_string(),
+            quantity,
+            unit_price,
+            amount: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_matches_with_date_shift_and_rounding() {
+        let d = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let mut matcher = DuplicateMatcher::new(
+            &[existing("a1", d, dec!(10), dec!(170.123))],
+            DuplicateTolerance::default(),
+        );
+
+        let shifted = fingerprint(d.succ_opt().unwrap(), dec!(10.00001), dec!(170.12));
+        assert_eq!(matcher.take_match(&shifted), Some("a1".to_string()));
+        // The existing activity has been consumed by the first match.
+        assert_eq!(matcher.take_match(&shifted), None);
+    }
+
+    #[test]
+    fn test_does_not_match_outside_tolerance() {
+        let d = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let mut matcher = DuplicateMatcher::new(
+            &[existing("a1", d, dec!(10), dec!(170.12))],
+            DuplicateTolerance::default(),
+        );
+
+        let late = fingerprint(NaiveDate::from_ymd_opt(2024, 3, 7).unwrap(), dec!(10), dec!(170.12));
+        let other_price = fingerprint(d, dec!(10), dec!(171.12));
+        assert_eq!(matcher.take_match(&late), None);
+        assert_eq!(matcher.take_match(&other_price), None);
+    }
+
+    #[test]
+    fn test_prefers_closest_date() {
+        let d = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let mut matcher = DuplicateMatcher::new(
+            &[
+                existing("a1", d.pred_opt().unwrap(), dec!(5), dec!(100)),
+                existing("a2", d, dec!(5), dec!(100)),
+            ],
+            DuplicateTolerance::default(),
+        );
+
+        assert_eq!(matcher.take_match(&fingerprint(d, dec!(5), dec!(100))), Some("a2".to_string()));
+    }
+}
@@ -7,6 +7,7 @@ use rust_decimal::prelude::FromPrimitive;
 use crate::accounts::Account;
 use crate::Result;
 use crate::activities::activities_errors::ActivityError;
+use crate::activities::activities_constants::ACTIVITY_TYPE_DIVIDEND_REINVESTMENT;
 
 /// Helper function to parse a string into a Decimal,
 /// with a fallback for scientific notation by parsing as f64 first.
@@ -64,6 +65,18 @@ pub struct Activity {
     pub created_at: DateTime<Utc>,
     #[serde(with = "timestamp_format")]
     pub updated_at: DateTime<Utc>,
+    /// Import session that created the activity, if it was imported
+    #[serde(default)]
+    pub import_session_id: Option<String>,
+    /// Asset whose lots a corporate action moves into `asset_id`
+    #[serde(default)]
+    pub source_asset_id: Option<String>,
+    /// Tax withheld at source on income; `amount` is what was received after it
+    #[serde(default)]
+    pub withholding_tax: Option<Decimal>,
+    /// ISO country code of the withholding jurisdiction
+    #[serde(default)]
+    pub withholding_country: Option<String>,
 }
 
 /// Database model for activities
@@ -74,6 +87,8 @@ pub struct Activity {
     Associations,
     Insertable,
     AsChangeset,
+    Serialize,
+    Deserialize,
     PartialEq,
     Debug,
     Clone,
@@ -97,6 +112,10 @@ pub struct ActivityDB {
     pub comment: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub import_session_id: Option<String>,
+    pub source_asset_id: Option<String>,
+    pub withholding_tax: Option<String>,
+    pub withholding_country: Option<String>,
 }
 
 /// Input model for creating a new activity
@@ -115,6 +134,14 @@ pub struct NewActivity {
     pub amount: Option<Decimal>,
     pub is_draft: bool,
     pub comment: Option<String>,
+    #[serde(default)]
+    pub import_session_id: Option<String>,
+    #[serde(default)]
+    pub source_asset_id: Option<String>,
+    #[serde(default)]
+    pub withholding_tax: Option<Decimal>,
+    #[serde(default)]
+    pub withholding_country: Option<String>,
 }
 
 impl NewActivity {
@@ -164,6 +191,12 @@ pub struct ActivityUpdate {
     pub amount: Option<Decimal>,
     pub is_draft: bool,
     pub comment: Option<String>,
+    #[serde(default)]
+    pub source_asset_id: Option<String>,
+    #[serde(default)]
+    pub withholding_tax: Option<Decimal>,
+    #[serde(default)]
+    pub withholding_country: Option<String>,
 }
 
 impl ActivityUpdate {
@@ -236,6 +269,12 @@ pub struct ActivityDetails {
     pub asset_name: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub asset_data_source: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub source_asset_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub withholding_tax: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub withholding_country: Option<String>,
 }
 
 impl ActivityDetails {
@@ -255,6 +294,12 @@ impl ActivityDetails {
         self.amount.as_ref().map(|s| parse_decimal_string_tolerant(s, "amount"))
     }
 
+    pub fn get_withholding_tax(&self) -> Option<Decimal> {
+        self.withholding_tax
+            .as_ref()
+            .map(|s| parse_decimal_string_tolerant(s, "withholding_tax"))
+    }
+
     // Helper to parse the date string
     pub fn get_date(&self) -> std::result::Result<DateTime<Utc>, chrono::ParseError> {
         DateTime::parse_from_rfc3339(&self.date).map(|dt| dt.with_timezone(&Utc))
@@ -307,16 +352,219 @@ pub struct ActivityImport {
     pub is_draft: bool,
     pub is_valid: bool,
     pub line_number: Option<i32>,
+    /// Id of the existing activity this row duplicates, set by the import check.
+    #[serde(default)]
+    pub duplicate_of: Option<String>,
+    /// ISIN reported by the source statement, recorded on the asset if it has none.
+    #[serde(default)]
+    pub isin: Option<String>,
+    /// Import session the row was written under, set once the import completes.
+    #[serde(default)]
+    pub import_session_id: Option<String>,
+    #[serde(default)]
+    pub withholding_tax: Option<Decimal>,
+    #[serde(default)]
+    pub withholding_country: Option<String>,
+    /// Asset a corporate action moves lots out of
+    #[serde(default)]
+    pub source_asset_id: Option<String>,
+}
+
+impl ActivityImport {
+    /// Parses the row date, accepting RFC3339 timestamps or `YYYY-MM-DD`.
+    pub fn parsed_date(&self) -> Option<NaiveDate> {
+        DateTime::parse_from_rfc3339(&self.date)
+            .map(|dt| dt.naive_utc().date())
+            .or_else(|_| NaiveDate::parse_from_str(&self.date, "%Y-%m-%d"))
+            .ok()
+    }
+}
+
+/// How `import_activities` handles rows flagged as duplicates of existing activities
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DuplicateStrategy {
+    /// Leave the existing activity untouched and drop the imported row
+    #[default]
+    Skip,
+    /// Overwrite the existing activity with the imported values
+    Merge,
+}
+
+/// Record of a completed import: where the rows came from and how many there were
+#[derive(
+    Debug, Clone, Serialize, Deserialize, Queryable, Selectable, Identifiable, Insertable,
+)]
+#[diesel(table_name = crate::schema::import_sessions)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSession {
+    pub id: String,
+    pub account_id: String,
+    /// Statement parser id, or `CSV` for a column-mapped file
+    pub source: String,
+    pub file_name: Option<String>,
+    /// SHA-256 of the source file, hex encoded
+    pub file_hash: Option<String>,
+    pub row_count: i32,
+    pub status: String,
+    pub created_at: String,
+    pub rolled_back_at: Option<String>,
+}
+
+/// Existing activity an import merged a duplicate row into, with its database row
+/// from before the merge, serialized as JSON
+#[derive(Debug, Clone, Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::import_session_merges)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ImportSessionMerge {
+    pub session_id: String,
+    pub activity_id: String,
+    pub before_json: String,
+}
+
+/// Provenance supplied with an import request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewImportSession {
+    /// Statement parser id; defaults to `CSV` when empty
+    #[serde(default)]
+    pub source: String,
+    pub file_name: Option<String>,
+    pub file_hash: Option<String>,
+}
+
+/// Filters for `search_activities`. Unset filters match everything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ActivitySearchFilters {
+    pub account_ids: Option<Vec<String>>,
+    pub activity_types: Option<Vec<String>>,
+    /// Substring of the symbol
+    pub asset_id_keyword: Option<String>,
+    /// Inclusive date range
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// Inclusive range on the activity value: its amount, or quantity times unit
+    /// price for activities without one
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    pub currencies: Option<Vec<String>>,
+    pub is_draft: Option<bool>,
+    /// Words to find in the comment; each must match the start of a word
+    pub comment_query: Option<String>,
+}
+
+/// Selects the activities a bulk edit applies to: the `search_activities` filters
+/// plus an exact symbol list and an inclusive date range. Unset filters match everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBulkFilter {
+    pub account_ids: Option<Vec<String>>,
+    pub activity_types: Option<Vec<String>>,
+    /// Substring of the symbol, as in the activity search box
+    pub asset_id_keyword: Option<String>,
+    /// Exact symbols, for edits that must not touch similarly named ones
+    pub asset_ids: Option<Vec<String>>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Fields a bulk update overwrites on every selected activity; None leaves a field as is
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBulkChanges {
+    pub account_id: Option<String>,
+    pub asset_id: Option<String>,
+    pub currency: Option<String>,
+    pub is_draft: Option<bool>,
+}
+
+/// What a bulk edit does to the selected activities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ActivityBulkAction {
+    Update(ActivityBulkChanges),
+    Delete,
+}
+
+/// Bulk edit request: which activities to select and what to do with them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBulkRequest {
+    pub filter: ActivityBulkFilter,
+    pub action: ActivityBulkAction,
+}
+
+/// Outcome, or preview when `dry_run` is set, of a bulk edit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityBulkResult {
+    pub dry_run: bool,
+    /// Selected activities as they were before the edit
+    pub before: Vec<Activity>,
+    /// The same activities after the edit, in the same order; empty for deletes
+    pub after: Vec<Activity>,
 }
 
 /// Model for sorting activities
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Sort {
     pub id: String,
     pub desc: bool,
 }
 
+/// Saved activity view as stored, with filters and sort serialized as JSON
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
+#[diesel(table_name = crate::schema::activity_saved_views)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ActivitySavedViewDB {
+    pub id: String,
+    pub name: String,
+    pub filters: String,
+    pub sort: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Named activity search the user can return to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivitySavedView {
+    pub id: String,
+    pub name: String,
+    pub filters: ActivitySearchFilters,
+    pub sort: Vec<Sort>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Input for saving a view; an existing id overwrites that view
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewActivitySavedView {
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub filters: ActivitySearchFilters,
+    #[serde(default)]
+    pub sort: Vec<Sort>,
+}
+
+impl ActivitySavedViewDB {
+    pub fn to_view(&self) -> std::result::Result<ActivitySavedView, serde_json::Error> {
+        Ok(ActivitySavedView {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            filters: serde_json::from_str(&self.filters)?,
+            sort: serde_json::from_str(&self.sort)?,
+            created_at: self.created_at.clone(),
+            updated_at: self.updated_at.clone(),
+        })
+    }
+}
+
 /// Model for activity import profile mapping
 #[derive(
     Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, AsChangeset, Insertable,
@@ -365,6 +613,10 @@ impl Default for ImportMappingData {
         activity_mappings.insert("BUY".to_string(), vec!["BUY".to_string()]);
         activity_mappings.insert("SELL".to_string(), vec!["SELL".to_string()]);
         activity_mappings.insert("DIVIDEND".to_string(), vec!["DIVIDEND".to_string()]);
+        activity_mappings.insert(
+            "DIVIDEND_REINVESTMENT".to_string(),
+            vec!["DIVIDEND_REINVESTMENT".to_string(), "DRIP".to_string()],
+        );
         activity_mappings.insert("INTEREST".to_string(), vec!["INTEREST".to_string()]);
         activity_mappings.insert("DEPOSIT".to_string(), vec!["DEPOSIT".to_string()]);
         activity_mappings.insert("WITHDRAWAL".to_string(), vec!["WITHDRAWAL".to_string()]);
@@ -416,6 +668,7 @@ pub enum ActivityType {
     Buy,
     Sell,
     Dividend,
+    DividendReinvestment,
     Interest,
     Deposit,
     Withdrawal,
@@ -426,6 +679,9 @@ pub enum ActivityType {
     Split,
     AddHolding,
     RemoveHolding,
+    TickerChange,
+    Merger,
+    SpinOff,
 }
 
 impl ActivityType {
@@ -435,6 +691,7 @@ impl ActivityType {
             ActivityType::Buy => ACTIVITY_TYPE_BUY,
             ActivityType::Sell => ACTIVITY_TYPE_SELL,
             ActivityType::Dividend => ACTIVITY_TYPE_DIVIDEND,
+            ActivityType::DividendReinvestment => ACTIVITY_TYPE_DIVIDEND_REINVESTMENT,
             ActivityType::Interest => ACTIVITY_TYPE_INTEREST,
             ActivityType::Deposit => ACTIVITY_TYPE_DEPOSIT,
             ActivityType::Withdrawal => ACTIVITY_TYPE_WITHDRAWAL,
@@ -445,6 +702,9 @@ impl ActivityType {
             ActivityType::Split => ACTIVITY_TYPE_SPLIT,
             ActivityType::AddHolding => ACTIVITY_TYPE_ADD_HOLDING,
             ActivityType::RemoveHolding => ACTIVITY_TYPE_REMOVE_HOLDING,
+            ActivityType::TickerChange => ACTIVITY_TYPE_TICKER_CHANGE,
+            ActivityType::Merger => ACTIVITY_TYPE_MERGER,
+            ActivityType::SpinOff => ACTIVITY_TYPE_SPIN_OFF,
         }
     }
 }
@@ -458,6 +718,7 @@ impl FromStr for ActivityType {
             s if s == ACTIVITY_TYPE_BUY => Ok(ActivityType::Buy),
             s if s == ACTIVITY_TYPE_SELL => Ok(ActivityType::Sell),
             s if s == ACTIVITY_TYPE_DIVIDEND => Ok(ActivityType::Dividend),
+            s if s == ACTIVITY_TYPE_DIVIDEND_REINVESTMENT => Ok(ActivityType::DividendReinvestment),
             s if s == ACTIVITY_TYPE_INTEREST => Ok(ActivityType::Interest),
             s if s == ACTIVITY_TYPE_DEPOSIT => Ok(ActivityType::Deposit),
             s if s == ACTIVITY_TYPE_WITHDRAWAL => Ok(ActivityType::Withdrawal),
@@ -468,6 +729,9 @@ impl FromStr for ActivityType {
             s if s == ACTIVITY_TYPE_SPLIT => Ok(ActivityType::Split),
             s if s == ACTIVITY_TYPE_ADD_HOLDING => Ok(ActivityType::AddHolding),
             s if s == ACTIVITY_TYPE_REMOVE_HOLDING => Ok(ActivityType::RemoveHolding),
+            s if s == ACTIVITY_TYPE_TICKER_CHANGE => Ok(ActivityType::TickerChange),
+            s if s == ACTIVITY_TYPE_MERGER => Ok(ActivityType::Merger),
+            s if s == ACTIVITY_TYPE_SPIN_OFF => Ok(ActivityType::SpinOff),
             _ => Err(format!("Unknown activity type: {}", s)),
         }
     }
@@ -543,6 +807,12 @@ impl From<ActivityDB> for Activity {
                     log::error!("Failed to parse updated_at '{}': {}", db.updated_at, e);
                     Utc::now() // Fallback to now
                 }),
+            import_session_id: db.import_session_id,
+            source_asset_id: db.source_asset_id,
+            withholding_tax: db
+                .withholding_tax
+                .map(|s| parse_decimal_string_tolerant(&s, "withholding_tax")),
+            withholding_country: db.withholding_country,
         }
     }
 }
@@ -584,6 +854,12 @@ impl From<NewActivity> for ActivityDB {
                 None => domain.quantity.unwrap_or_else(|| Decimal::ZERO).to_string()
             };
             ("0".to_string(), "0".to_string(), Some(amount_str))
+        } else if activity_type == ACTIVITY_TYPE_DIVIDEND_REINVESTMENT {
+            // The dividend defaults to the value of the shares it bought
+            let quantity = domain.quantity.unwrap_or(Decimal::ZERO);
+            let unit_price = domain.unit_price.unwrap_or(Decimal::ZERO);
+            let amount = domain.amount.unwrap_or(quantity * unit_price);
+            (quantity.to_string(), unit_price.to_string(), Some(amount.to_string()))
         } else {
             // For other activities, use the provided values
             (
@@ -608,6 +884,10 @@ impl From<NewActivity> for ActivityDB {
             comment: domain.comment,
             created_at: now.to_rfc3339(),
             updated_at: now.to_rfc3339(),
+            import_session_id: domain.import_session_id,
+            source_asset_id: domain.source_asset_id,
+            withholding_tax: domain.withholding_tax.map(|t| t.to_string()),
+            withholding_country: domain.withholding_country,
         }
     }
 }
@@ -647,6 +927,12 @@ impl From<ActivityUpdate> for ActivityDB {
                 None => domain.quantity.unwrap_or_else(|| Decimal::ZERO).to_string()
             };
             ("0".to_string(), "0".to_string(), Some(amount_str))
+        } else if activity_type == ACTIVITY_TYPE_DIVIDEND_REINVESTMENT {
+            // The dividend defaults to the value of the shares it bought
+            let quantity = domain.quantity.unwrap_or(Decimal::ZERO);
+            let unit_price = domain.unit_price.unwrap_or(Decimal::ZERO);
+            let amount = domain.amount.unwrap_or(quantity * unit_price);
+            (quantity.to_string(), unit_price.to_string(), Some(amount.to_string()))
         } else {
             // For other activities, use the provided values
             (
@@ -671,6 +957,10 @@ impl From<ActivityUpdate> for ActivityDB {
             comment: domain.comment,
             created_at: now.to_rfc3339(),
             updated_at: now.to_rfc3339(),
+            import_session_id: None,
+            source_asset_id: domain.source_asset_id,
+            withholding_tax: domain.withholding_tax.map(|t| t.to_string()),
+            withholding_country: domain.withholding_country,
         }
     }
 }#[derive(Debug, Serialize, QueryableByName)]
@@ -689,6 +979,27 @@ pub struct IncomeData {
     pub currency: String,
     #[diesel(sql_type = diesel::sql_types::Text)]
     pub amount: Decimal,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub withholding_tax: Decimal,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub withholding_country: Option<String>,
+    /// Profile of the account the income was paid into.
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub profile_id: String,
+}
+
+/// An income payment that had tax withheld at source, with the account it was paid into.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignTaxData {
+    pub account_id: String,
+    pub account_name: String,
+    pub profile_id: String,
+    pub activity_date: NaiveDate,
+    pub currency: String,
+    pub amount: Decimal,
+    pub withholding_tax: Decimal,
+    pub withholding_country: String,
 }
 
 
@@ -1,24 +1,41 @@
-use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use diesel::dsl::sql;
 use diesel::expression_methods::ExpressionMethods;
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sql_types::{Bool, Double, Text};
 use diesel::sqlite::SqliteConnection;
 use rust_decimal::Decimal;
 use std::str::FromStr;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use super::activities_bulk::plan_bulk_edit;
+use super::activities_search::activity_date_bounds;
 use super::activities_traits::ActivityRepositoryTrait;
 use crate::activities::activities_constants::*;
 use crate::activities::activities_errors::ActivityError;
 use crate::activities::activities_model::*;
+use crate::audit::audit_repository::{record_change, record_changes};
+use crate::audit::{
+    ChangeLogEntry, CHANGE_ACTOR_IMPORT, CHANGE_ACTOR_USER, CHANGE_OPERATION_CREATE,
+    CHANGE_OPERATION_DELETE, CHANGE_OPERATION_UPDATE, ENTITY_TYPE_ACTIVITY,
+};
 use crate::db::{get_connection, WriteHandle};
-use crate::schema::{accounts, activities, activity_import_profiles, assets};
+use crate::schema::{
+    accounts, activities, activity_import_profiles, activity_saved_views, assets,
+    import_session_merges, import_sessions,
+};
 use crate::{Error, Result};
-use diesel::dsl::min;
+use rust_decimal::prelude::ToPrimitive;
 use num_traits::Zero;
 use async_trait::async_trait;
 
+/// Value of an activity for amount filters and sorting: its amount, or quantity
+/// times unit price for trades, which carry no amount.
+const ACTIVITY_VALUE_SQL: &str = "COALESCE(CAST(activities.amount AS REAL), \
+     CAST(activities.quantity AS REAL) * CAST(activities.unit_price AS REAL))";
+
 /// Repository for managing activity data in the database
 pub struct ActivityRepository {
     pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
@@ -27,6 +44,61 @@ pub struct ActivityRepository {
 
 // Inherent methods for ActivityRepository
 impl ActivityRepository {
+    /// Logs a create or delete of each row in one batch, inside the caller's transaction.
+    fn log_bulk_changes(
+        conn: &mut SqliteConnection,
+        rows: &[ActivityDB],
+        operation: &str,
+        actor: &str,
+    ) -> Result<()> {
+        let entries = rows
+            .iter()
+            .map(|row| {
+                let (before, after) = if operation == CHANGE_OPERATION_DELETE {
+                    (Some(row), None)
+                } else {
+                    (None, Some(row))
+                };
+                ChangeLogEntry::from_rows(ENTITY_TYPE_ACTIVITY, &row.id, operation, actor, before, after)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        record_changes(conn, &entries)
+    }
+
+    /// Activities of active accounts matching a bulk edit filter, oldest first.
+    fn select_bulk_rows(conn: &mut SqliteConnection, filter: &ActivityBulkFilter) -> Result<Vec<ActivityDB>> {
+        let active_accounts = accounts::table
+            .filter(accounts::is_active.eq(true))
+            .select(accounts::id);
+        let mut query = activities::table
+            .filter(activities::account_id.eq_any(active_accounts))
+            .into_boxed();
+
+        if let Some(ref account_ids) = filter.account_ids {
+            query = query.filter(activities::account_id.eq_any(account_ids));
+        }
+        if let Some(ref activity_types) = filter.activity_types {
+            query = query.filter(activities::activity_type.eq_any(activity_types));
+        }
+        if let Some(ref asset_ids) = filter.asset_ids {
+            query = query.filter(activities::asset_id.eq_any(asset_ids));
+        }
+        if let Some(keyword) = filter.asset_id_keyword.as_deref().map(str::trim).filter(|k| !k.is_empty()) {
+            query = query.filter(activities::asset_id.like(format!("%{}%", keyword)));
+        }
+        let (lower, upper) = filter.date_bounds();
+        if let Some(lower) = lower {
+            query = query.filter(activities::activity_date.ge(lower));
+        }
+        if let Some(upper) = upper {
+            query = query.filter(activities::activity_date.lt(upper));
+        }
+
+        Ok(query
+            .order((activities::activity_date.asc(), activities::created_at.asc()))
+            .load::<ActivityDB>(conn)?)
+    }
+
     /// Creates a new ActivityRepository instance
     pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
         Self { pool, writer }
@@ -88,16 +160,16 @@ impl ActivityRepositoryTrait for ActivityRepository {
 
     fn search_activities(
         &self,
-        page: i64,                                 // Page number, 1-based
-        page_size: i64,                            // Number of items per page
-        account_id_filter: Option<Vec<String>>,    // Optional account_id filter
-        activity_type_filter: Option<Vec<String>>, // Optional activity_type filter
-        asset_id_keyword: Option<String>,          // Optional asset_id keyword for search
-        sort: Option<Sort>,                        // Optional sort
+        page: i64,      // Page number, 1-based
+        page_size: i64, // Number of items per page
+        filters: ActivitySearchFilters,
+        sort: Vec<Sort>,
     ) -> Result<ActivitySearchResponse> {
         let mut conn = get_connection(&self.pool)?;
 
         let offset = (page - 1) * page_size;
+        let comment_match = filters.comment_match_query();
+        let (date_lower, date_upper) = activity_date_bounds(filters.start_date, filters.end_date);
 
         // Function to create base query
         let create_base_query = |_conn: &SqliteConnection| {
@@ -107,54 +179,72 @@ impl ActivityRepositoryTrait for ActivityRepository {
                 .filter(accounts::is_active.eq(true))
                 .into_boxed();
 
-            if let Some(ref account_ids) = account_id_filter {
+            if let Some(ref account_ids) = filters.account_ids {
                 query = query.filter(activities::account_id.eq_any(account_ids));
             }
-            if let Some(ref activity_types) = activity_type_filter {
+            if let Some(ref activity_types) = filters.activity_types {
                 query = query.filter(activities::activity_type.eq_any(activity_types));
             }
-            if let Some(ref keyword) = asset_id_keyword {
+            if let Some(ref keyword) = filters.asset_id_keyword {
                 query = query.filter(assets::id.like(format!("%{}%", keyword)));
             }
-
-            // Apply sorting
-            if let Some(ref sort) = sort {
-                match sort.id.as_str() {
-                    "date" => {
-                        if sort.desc {
-                            query = query.order((activities::activity_date.desc(), activities::created_at.asc()));
-                        } else {
-                            query = query.order((activities::activity_date.asc(), activities::created_at.asc()));
-                        }
-                    }
-                    "activityType" => {
-                        if sort.desc {
-                            query = query.order(activities::activity_type.desc());
-                        } else {
-                            query = query.order(activities::activity_type.asc());
-                        }
-                    }
-                    "assetSymbol" => {
-                        if sort.desc {
-                            query = query.order(activities::asset_id.desc());
-                        } else {
-                            query = query.order(activities::asset_id.asc());
-                        }
-                    }
-                    "accountName" => {
-                        if sort.desc {
-                            query = query.order(accounts::name.desc());
-                        } else {
-                            query = query.order(accounts::name.asc());
-                        }
-                    }
-                    _ => query = query.order((activities::activity_date.desc(), activities::created_at.asc())), // Default order
-                }
-            } else {
-                query = query.order((activities::activity_date.desc(), activities::created_at.asc())); // Default order
+            if let Some(ref lower) = date_lower {
+                query = query.filter(activities::activity_date.ge(lower.clone()));
+            }
+            if let Some(ref upper) = date_upper {
+                query = query.filter(activities::activity_date.lt(upper.clone()));
+            }
+            if let Some(min_amount) = filters.min_amount.and_then(|d| d.to_f64()) {
+                query = query.filter(
+                    sql::<Bool>(&format!("{} >= ", ACTIVITY_VALUE_SQL)).bind::<Double, _>(min_amount),
+                );
+            }
+            if let Some(max_amount) = filters.max_amount.and_then(|d| d.to_f64()) {
+                query = query.filter(
+                    sql::<Bool>(&format!("{} <= ", ACTIVITY_VALUE_SQL)).bind::<Double, _>(max_amount),
+                );
+            }
+            if let Some(ref currencies) = filters.currencies {
+                query = query.filter(activities::currency.eq_any(currencies));
+            }
+            if let Some(is_draft) = filters.is_draft {
+                query = query.filter(activities::is_draft.eq(is_draft));
+            }
+            if let Some(ref match_query) = comment_match {
+                query = query.filter(
+                    sql::<Bool>(
+                        "activities.id IN (SELECT activity_id FROM activities_fts WHERE activities_fts MATCH ",
+                    )
+                    .bind::<Text, _>(match_query.clone())
+                    .sql(")"),
+                );
             }
 
-            query
+            // Apply sorting, column by column, then the default order as a tie-breaker
+            for s in &sort {
+                query = match (s.id.as_str(), s.desc) {
+                    ("date", false) => query.then_order_by(activities::activity_date.asc()),
+                    ("date", true) => query.then_order_by(activities::activity_date.desc()),
+                    ("activityType", false) => query.then_order_by(activities::activity_type.asc()),
+                    ("activityType", true) => query.then_order_by(activities::activity_type.desc()),
+                    ("assetSymbol", false) => query.then_order_by(activities::asset_id.asc()),
+                    ("assetSymbol", true) => query.then_order_by(activities::asset_id.desc()),
+                    ("accountName", false) => query.then_order_by(accounts::name.asc()),
+                    ("accountName", true) => query.then_order_by(accounts::name.desc()),
+                    ("quantity", false) => query.then_order_by(sql::<Double>("CAST(activities.quantity AS REAL)").asc()),
+                    ("quantity", true) => query.then_order_by(sql::<Double>("CAST(activities.quantity AS REAL)").desc()),
+                    ("unitPrice", false) => query.then_order_by(sql::<Double>("CAST(activities.unit_price AS REAL)").asc()),
+                    ("unitPrice", true) => query.then_order_by(sql::<Double>("CAST(activities.unit_price AS REAL)").desc()),
+                    ("amount", false) => query.then_order_by(sql::<Double>(ACTIVITY_VALUE_SQL).asc()),
+                    ("amount", true) => query.then_order_by(sql::<Double>(ACTIVITY_VALUE_SQL).desc()),
+                    ("currency", false) => query.then_order_by(activities::currency.asc()),
+                    ("currency", true) => query.then_order_by(activities::currency.desc()),
+                    ("isDraft", false) => query.then_order_by(activities::is_draft.asc()),
+                    ("isDraft", true) => query.then_order_by(activities::is_draft.desc()),
+                    _ => query,
+                };
+            }
+            query.then_order_by((activities::activity_date.desc(), activities::created_at.asc()))
         };
 
         // Count query
@@ -184,6 +274,9 @@ impl ActivityRepositoryTrait for ActivityRepository {
                 assets::symbol.nullable(),
                 assets::name.nullable(),
                 assets::data_source.nullable(),
+                activities::source_asset_id,
+                activities::withholding_tax,
+                activities::withholding_country,
             ))
             .limit(page_size)
             .offset(offset)
@@ -206,6 +299,15 @@ impl ActivityRepositoryTrait for ActivityRepository {
                 let inserted_activity = diesel::insert_into(activities::table)
                     .values(&activity_to_insert)
                     .get_result::<ActivityDB>(conn)?;
+                record_change(
+                    conn,
+                    ENTITY_TYPE_ACTIVITY,
+                    &inserted_activity.id,
+                    CHANGE_OPERATION_CREATE,
+                    CHANGE_ACTOR_USER,
+                    None,
+                    Some(&inserted_activity),
+                )?;
                 Ok(Activity::from(inserted_activity))
             })
             .await
@@ -223,12 +325,22 @@ impl ActivityRepositoryTrait for ActivityRepository {
                     .find(&activity_id_owned)
                     .first::<ActivityDB>(conn)?;
 
-                activity_to_update.created_at = existing.created_at;
+                activity_to_update.created_at = existing.created_at.clone();
+                activity_to_update.import_session_id = existing.import_session_id.clone();
                 activity_to_update.updated_at = chrono::Utc::now().to_rfc3339();
 
                 let updated_activity = diesel::update(activities::table.find(&activity_to_update.id))
                     .set(&activity_to_update)
                     .get_result::<ActivityDB>(conn)?;
+                record_change(
+                    conn,
+                    ENTITY_TYPE_ACTIVITY,
+                    &updated_activity.id,
+                    CHANGE_OPERATION_UPDATE,
+                    CHANGE_ACTOR_USER,
+                    Some(&existing),
+                    Some(&updated_activity),
+                )?;
                 Ok(Activity::from(updated_activity))
             })
             .await
@@ -242,6 +354,15 @@ impl ActivityRepositoryTrait for ActivityRepository {
                     .first::<ActivityDB>(conn)?;
                 diesel::delete(activities::table.filter(activities::id.eq(&activity_id)))
                     .execute(conn)?;
+                record_change(
+                    conn,
+                    ENTITY_TYPE_ACTIVITY,
+                    &activity.id,
+                    CHANGE_OPERATION_DELETE,
+                    CHANGE_ACTOR_USER,
+                    Some(&activity),
+                    None,
+                )?;
                 Ok(activity.into())
             })
             .await
@@ -344,6 +465,45 @@ impl ActivityRepositoryTrait for ActivityRepository {
             .await
     }
 
+    fn get_saved_views(&self) -> Result<Vec<ActivitySavedView>> {
+        let mut conn = get_connection(&self.pool)?;
+        let views = activity_saved_views::table
+            .order(activity_saved_views::name.asc())
+            .load::<ActivitySavedViewDB>(&mut conn)?;
+        Ok(views
+            .iter()
+            .map(ActivitySavedViewDB::to_view)
+            .collect::<std::result::Result<Vec<_>, _>>()?)
+    }
+
+    async fn save_view(&self, view: ActivitySavedViewDB) -> Result<ActivitySavedView> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<ActivitySavedView> {
+                let saved = diesel::insert_into(activity_saved_views::table)
+                    .values(&view)
+                    .on_conflict(activity_saved_views::id)
+                    .do_update()
+                    .set((
+                        activity_saved_views::name.eq(&view.name),
+                        activity_saved_views::filters.eq(&view.filters),
+                        activity_saved_views::sort.eq(&view.sort),
+                        activity_saved_views::updated_at.eq(&view.updated_at),
+                    ))
+                    .get_result::<ActivitySavedViewDB>(conn)?;
+                Ok(saved.to_view()?)
+            })
+            .await
+    }
+
+    async fn delete_saved_view(&self, view_id: String) -> Result<()> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::delete(activity_saved_views::table.find(&view_id)).execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+
     async fn create_activities(&self, activities_vec: Vec<NewActivity>) -> Result<usize> {
         if activities_vec.is_empty() {
             return Ok(0);
@@ -367,11 +527,228 @@ impl ActivityRepositoryTrait for ActivityRepository {
                 let num_inserted = diesel::insert_into(activities::table)
                     .values(&activities_db_owned)
                     .execute(conn)?;
+                Self::log_bulk_changes(
+                    conn,
+                    &activities_db_owned,
+                    CHANGE_OPERATION_CREATE,
+                    CHANGE_ACTOR_IMPORT,
+                )?;
                 Ok(num_inserted)
             })
             .await
     }
 
+    async fn bulk_edit_activities(
+        &self,
+        request: ActivityBulkRequest,
+        dry_run: bool,
+    ) -> Result<ActivityBulkResult> {
+        if dry_run {
+            let mut conn = get_connection(&self.pool)?;
+            let rows = Self::select_bulk_rows(&mut conn, &request.filter)?;
+            let planned = plan_bulk_edit(&rows, &request.action, &Utc::now().to_rfc3339());
+            return Ok(ActivityBulkResult {
+                dry_run,
+                before: rows.into_iter().map(Activity::from).collect(),
+                after: planned.into_iter().map(Activity::from).collect(),
+            });
+        }
+
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<ActivityBulkResult> {
+                let rows = Self::select_bulk_rows(conn, &request.filter)?;
+                let planned = plan_bulk_edit(&rows, &request.action, &Utc::now().to_rfc3339());
+
+                match request.action {
+                    ActivityBulkAction::Delete => {
+                        let ids: Vec<&str> = rows.iter().map(|row| row.id.as_str()).collect();
+                        for chunk in ids.chunks(500) {
+                            diesel::delete(activities::table.filter(activities::id.eq_any(chunk)))
+                                .execute(conn)?;
+                        }
+                        Self::log_bulk_changes(conn, &rows, CHANGE_OPERATION_DELETE, CHANGE_ACTOR_USER)?;
+                    }
+                    ActivityBulkAction::Update(_) => {
+                        let mut entries = Vec::with_capacity(planned.len());
+                        for (before, after) in rows.iter().zip(&planned) {
+                            diesel::update(activities::table.find(&after.id))
+                                .set(after)
+                                .execute(conn)?;
+                            entries.push(ChangeLogEntry::from_rows(
+                                ENTITY_TYPE_ACTIVITY,
+                                &after.id,
+                                CHANGE_OPERATION_UPDATE,
+                                CHANGE_ACTOR_USER,
+                                Some(before),
+                                Some(after),
+                            )?);
+                        }
+                        record_changes(conn, &entries)?;
+                    }
+                }
+
+                Ok(ActivityBulkResult {
+                    dry_run,
+                    before: rows.into_iter().map(Activity::from).collect(),
+                    after: planned.into_iter().map(Activity::from).collect(),
+                })
+            })
+            .await
+    }
+
+    async fn create_import_session(
+        &self,
+        session: ImportSession,
+        activities_vec: Vec<NewActivity>,
+        merges: Vec<ActivityUpdate>,
+    ) -> Result<ImportSession> {
+        for new_act in &activities_vec {
+            new_act.validate()?;
+        }
+        for merge in &merges {
+            merge.validate()?;
+        }
+        let activities_db_owned: Vec<ActivityDB> = activities_vec
+            .into_iter()
+            .map(|new_act| {
+                let mut db: ActivityDB = new_act.into();
+                db.id = Uuid::new_v4().to_string();
+                db.import_session_id = Some(session.id.clone());
+                db
+            })
+            .collect();
+        let merges_db_owned: Vec<ActivityDB> = merges.into_iter().map(ActivityDB::from).collect();
+
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<ImportSession> {
+                let inserted_session = diesel::insert_into(import_sessions::table)
+                    .values(&session)
+                    .get_result::<ImportSession>(conn)?;
+                if !activities_db_owned.is_empty() {
+                    diesel::insert_into(activities::table)
+                        .values(&activities_db_owned)
+                        .execute(conn)?;
+                    Self::log_bulk_changes(
+                        conn,
+                        &activities_db_owned,
+                        CHANGE_OPERATION_CREATE,
+                        CHANGE_ACTOR_IMPORT,
+                    )?;
+                }
+                for mut merged in merges_db_owned {
+                    let existing = activities::table
+                        .find(&merged.id)
+                        .first::<ActivityDB>(conn)?;
+                    diesel::insert_into(import_session_merges::table)
+                        .values(&ImportSessionMerge {
+                            session_id: session.id.clone(),
+                            activity_id: existing.id.clone(),
+                            before_json: serde_json::to_string(&existing)?,
+                        })
+                        .execute(conn)?;
+                    merged.created_at = existing.created_at.clone();
+                    merged.import_session_id = Some(session.id.clone());
+                    merged.updated_at = Utc::now().to_rfc3339();
+                    let updated = diesel::update(activities::table.find(&merged.id))
+                        .set(&merged)
+                        .get_result::<ActivityDB>(conn)?;
+                    record_change(
+                        conn,
+                        ENTITY_TYPE_ACTIVITY,
+                        &updated.id,
+                        CHANGE_OPERATION_UPDATE,
+                        CHANGE_ACTOR_IMPORT,
+                        Some(&existing),
+                        Some(&updated),
+                    )?;
+                }
+                Ok(inserted_session)
+            })
+            .await
+    }
+
+    fn get_import_sessions(&self, account_id: Option<&str>) -> Result<Vec<ImportSession>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = import_sessions::table
+            .order(import_sessions::created_at.desc())
+            .into_boxed();
+        if let Some(id) = account_id {
+            query = query.filter(import_sessions::account_id.eq(id));
+        }
+        Ok(query.load::<ImportSession>(&mut conn)?)
+    }
+
+    fn get_activities_by_import_session(&self, session_id: &str) -> Result<Vec<Activity>> {
+        let mut conn = get_connection(&self.pool)?;
+        let activities_db = activities::table
+            .filter(activities::import_session_id.eq(session_id))
+            .order(activities::activity_date.asc())
+            .load::<ActivityDB>(&mut conn)?;
+        Ok(activities_db.into_iter().map(Activity::from).collect())
+    }
+
+    async fn rollback_import_session(&self, session_id: String) -> Result<Vec<Activity>> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Vec<Activity>> {
+                let session = import_sessions::table
+                    .find(&session_id)
+                    .first::<ImportSession>(conn)
+                    .map_err(|e| Error::from(ActivityError::NotFound(e.to_string())))?;
+                if session.status == IMPORT_SESSION_STATUS_ROLLED_BACK {
+                    return Err(ActivityError::InvalidData(format!(
+                        "Import session {} has already been rolled back",
+                        session_id
+                    ))
+                    .into());
+                }
+
+                // Merged rows go back to their state before the import, which no
+                // longer carries this session, so the delete below leaves them be.
+                let merges = import_session_merges::table
+                    .filter(import_session_merges::session_id.eq(&session_id))
+                    .load::<ImportSessionMerge>(conn)?;
+                let mut restored = Vec::with_capacity(merges.len());
+                for merge in merges {
+                    let before: ActivityDB = serde_json::from_str(&merge.before_json)?;
+                    let Some(current) = activities::table
+                        .find(&merge.activity_id)
+                        .first::<ActivityDB>(conn)
+                        .optional()?
+                    else {
+                        continue;
+                    };
+                    let reverted = diesel::update(activities::table.find(&merge.activity_id))
+                        .set(&before)
+                        .get_result::<ActivityDB>(conn)?;
+                    record_change(
+                        conn,
+                        ENTITY_TYPE_ACTIVITY,
+                        &reverted.id,
+                        CHANGE_OPERATION_UPDATE,
+                        CHANGE_ACTOR_IMPORT,
+                        Some(&current),
+                        Some(&reverted),
+                    )?;
+                    restored.push(reverted);
+                }
+
+                let removed = activities::table
+                    .filter(activities::import_session_id.eq(&session_id))
+                    .load::<ActivityDB>(conn)?;
+                diesel::delete(activities::table.filter(activities::import_session_id.eq(&session_id)))
+                    .execute(conn)?;
+                Self::log_bulk_changes(conn, &removed, CHANGE_OPERATION_DELETE, CHANGE_ACTOR_IMPORT)?;
+                diesel::update(import_sessions::table.find(&session_id))
+                    .set((
+                        import_sessions::status.eq(IMPORT_SESSION_STATUS_ROLLED_BACK),
+                        import_sessions::rolled_back_at.eq(Some(Utc::now().to_rfc3339())),
+                    ))
+                    .execute(conn)?;
+                Ok(removed.into_iter().chain(restored).map(Activity::from).collect())
+            })
+            .await
+    }
+
     /// Retrieves deposit activities for specified accounts within a year as raw data
     fn get_deposit_activities(
         &self,
@@ -422,16 +799,20 @@ impl ActivityRepositoryTrait for ActivityRepository {
     fn get_income_activities_data(&self) -> Result<Vec<IncomeData>> {
         let mut conn = get_connection(&self.pool)?;
 
+        // Reinvested dividends are reported as dividend income
         let query = "SELECT strftime('%Y-%m', a.activity_date) as date,
-             a.activity_type as income_type,
+             CASE a.activity_type WHEN 'DIVIDEND_REINVESTMENT' THEN 'DIVIDEND' ELSE a.activity_type END as income_type,
              a.asset_id as symbol,
              COALESCE(ast.name, 'Unknown') as symbol_name,
              a.currency,
-             a.amount
+             a.amount,
+             COALESCE(a.withholding_tax, '0') as withholding_tax,
+             a.withholding_country,
+             acc.profile_id
              FROM activities a
              LEFT JOIN assets ast ON a.asset_id = ast.id
              INNER JOIN accounts acc ON a.account_id = acc.id
-             WHERE a.activity_type IN ('DIVIDEND', 'INTEREST', 'OTHER_INCOME')
+             WHERE a.activity_type IN ('DIVIDEND', 'DIVIDEND_REINVESTMENT', 'INTEREST', 'OTHER_INCOME')
              AND acc.is_active = 1
              ORDER BY a.activity_date";
 
@@ -450,6 +831,12 @@ impl ActivityRepositoryTrait for ActivityRepository {
             pub currency: String,
             #[diesel(sql_type = diesel::sql_types::Text)]
             pub amount: String,
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            pub withholding_tax: String,
+            #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+            pub withholding_country: Option<String>,
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            pub profile_id: String,
         }
 
         let raw_results = diesel::sql_query(query)
@@ -461,6 +848,8 @@ impl ActivityRepositoryTrait for ActivityRepository {
             .into_iter()
             .map(|raw| {
                 let amount = Decimal::from_str(&raw.amount).unwrap_or_else(|_| Decimal::zero());
+                let withholding_tax =
+                    Decimal::from_str(&raw.withholding_tax).unwrap_or_else(|_| Decimal::zero());
                 Ok(IncomeData {
                     date: raw.date,
                     income_type: raw.income_type,
@@ -468,6 +857,9 @@ impl ActivityRepositoryTrait for ActivityRepository {
                     symbol_name: raw.symbol_name,
                     currency: raw.currency,
                     amount,
+                    withholding_tax,
+                    withholding_country: raw.withholding_country,
+                    profile_id: raw.profile_id,
                 })
             })
             .collect::<Result<Vec<IncomeData>>>()?; // Collect into Result
@@ -475,13 +867,59 @@ impl ActivityRepositoryTrait for ActivityRepository {
         Ok(results)
     }
 
+    fn get_foreign_tax_data(&self, year: i32) -> Result<Vec<ForeignTaxData>> {
+        let mut conn = get_connection(&self.pool)?;
+
+        let (start, end) = match (
+            NaiveDate::from_ymd_opt(year, 1, 1),
+            NaiveDate::from_ymd_opt(year + 1, 1, 1),
+        ) {
+            (Some(start), Some(end)) => (start, end),
+            _ => {
+                return Err(ActivityError::InvalidData(format!("Invalid tax year {}", year)).into())
+            }
+        };
+
+        // Closed accounts are included: tax withheld there can still be claimed.
+        let rows = activities::table
+            .inner_join(accounts::table.on(accounts::id.eq(activities::account_id)))
+            .filter(activities::activity_type.eq_any(INCOME_ACTIVITY_TYPES))
+            .filter(activities::is_draft.eq(false))
+            .filter(activities::withholding_tax.is_not_null())
+            .filter(activities::activity_date.ge(Utc.from_utc_datetime(&start.and_time(NaiveTime::MIN)).to_rfc3339()))
+            .filter(activities::activity_date.lt(Utc.from_utc_datetime(&end.and_time(NaiveTime::MIN)).to_rfc3339()))
+            .select((ActivityDB::as_select(), accounts::name, accounts::profile_id))
+            .order(activities::activity_date.asc())
+            .load::<(ActivityDB, String, String)>(&mut conn)?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(activity_db, account_name, profile_id)| {
+                let activity = Activity::from(activity_db);
+                let withholding_tax = activity.withholding_tax?;
+                Some(ForeignTaxData {
+                    account_id: activity.account_id,
+                    account_name,
+                    profile_id,
+                    activity_date: activity.activity_date.naive_utc().date(),
+                    currency: activity.currency,
+                    amount: activity
+                        .amount
+                        .unwrap_or(activity.quantity * activity.unit_price),
+                    withholding_tax,
+                    withholding_country: activity.withholding_country.unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
     fn get_first_activity_date_overall(&self) -> Result<DateTime<Utc>> {
         let mut conn = get_connection(&self.pool)?;
 
         let min_date_str = activities::table
             .inner_join(accounts::table.on(activities::account_id.eq(accounts::id)))
             .filter(accounts::is_active.eq(true))
-            .select(min(activities::activity_date))
+            .select(diesel::dsl::min(activities::activity_date))
             .first::<Option<String>>(&mut conn)
             .map_err(Error::from)?
             .ok_or(ActivityError::NotFound("No activities found.".to_string()))?;
@@ -502,7 +940,7 @@ impl ActivityRepositoryTrait for ActivityRepository {
         let mut query = activities::table
             .inner_join(accounts::table.on(activities::account_id.eq(accounts::id)))
             .filter(accounts::is_active.eq(true))
-            .select(min(activities::activity_date))
+            .select(diesel::dsl::min(activities::activity_date))
             .into_boxed();
 
         if let Some(ids) = account_ids {
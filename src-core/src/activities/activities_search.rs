@@ -0,0 +1,117 @@
+use chrono::{Days, NaiveDate};
+
+use super::activities_errors::ActivityError;
+use super::activities_model::{ActivitySearchFilters, Sort};
+
+/// Sort ids accepted by `search_activities`
+pub const SEARCH_SORT_IDS: [&str; 9] = [
+    "date",
+    "activityType",
+    "assetSymbol",
+    "accountName",
+    "quantity",
+    "unitPrice",
+    "amount",
+    "currency",
+    "isDraft",
+];
+
+/// Bounds on the stored RFC3339 `activity_date`, compared as strings: the start
+/// date inclusive and the day after the end date exclusive.
+pub(crate) fn activity_date_bounds(
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> (Option<String>, Option<String>) {
+    let lower = start_date.map(|d| d.format("%Y-%m-%d").to_string());
+    let upper = end_date
+        .and_then(|d| d.checked_add_days(Days::new(1)))
+        .map(|d| d.format("%Y-%m-%d").to_string());
+    (lower, upper)
+}
+
+/// Builds an FTS5 query requiring every word of `input` as a word prefix. Words are
+/// quoted so operators and punctuation typed by the user are matched literally.
+pub(crate) fn fts_prefix_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|word| word.replace('"', ""))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"*", word))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+impl ActivitySearchFilters {
+    /// Rejects inverted date or amount ranges.
+    pub fn validate(&self) -> Result<(), ActivityError> {
+        if let (Some(start), Some(end)) = (self.start_date, self.end_date) {
+            if start > end {
+                return Err(ActivityError::InvalidData(format!(
+                    "Start date {} is after end date {}",
+                    start, end
+                )));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_amount, self.max_amount) {
+            if min > max {
+                return Err(ActivityError::InvalidData(format!(
+                    "Minimum amount {} is above maximum amount {}",
+                    min, max
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn comment_match_query(&self) -> Option<String> {
+        self.comment_query.as_deref().and_then(fts_prefix_query)
+    }
+}
+
+/// Rejects unknown sort ids so a typo does not silently fall back to the default order.
+pub fn validate_sort(sort: &[Sort]) -> Result<(), ActivityError> {
+    match sort.iter().find(|s| !SEARCH_SORT_IDS.contains(&s.id.as_str())) {
+        Some(unknown) => Err(ActivityError::InvalidData(format!(
+            "Cannot sort activities by '{}'",
+            unknown.id
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_fts_prefix_query_quotes_every_word() {
+        assert_eq!(fts_prefix_query("  "), None);
+        assert_eq!(fts_prefix_query("divid"), Some("\"divid\"*".to_string()));
+        assert_eq!(
+            fts_prefix_query("drip OR \"tax\" -x"),
+            Some("\"drip\"* \"OR\"* \"tax\"* \"-x\"*".to_string())
+        );
+    }
+
+    #[test]
+    fn test_search_filter_validation() {
+        assert!(ActivitySearchFilters::default().validate().is_ok());
+        let inverted_amounts = ActivitySearchFilters {
+            min_amount: Some(dec!(100)),
+            max_amount: Some(dec!(10)),
+            ..Default::default()
+        };
+        assert!(inverted_amounts.validate().is_err());
+        let inverted_dates = ActivitySearchFilters {
+            start_date: NaiveDate::from_ymd_opt(2024, 2, 1),
+            end_date: NaiveDate::from_ymd_opt(2024, 1, 1),
+            ..Default::default()
+        };
+        assert!(inverted_dates.validate().is_err());
+
+        let sort = |id: &str| Sort { id: id.to_string(), desc: false };
+        assert!(validate_sort(&[sort("date"), sort("amount")]).is_ok());
+        assert!(validate_sort(&[sort("comment")]).is_err());
+    }
+}
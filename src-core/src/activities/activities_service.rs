@@ -1,14 +1,23 @@
 use chrono::Utc;
-use log::debug;
-use std::sync::Arc;
+use log::{debug, warn};
+use std::sync::{Arc, RwLock};
 
+use crate::activities::activities_constants::{IMPORT_SESSION_STATUS_COMPLETED, IMPORT_SOURCE_CSV};
 use crate::activities::activities_errors::ActivityError;
 use crate::accounts::{Account, AccountServiceTrait};
 use crate::activities::activities_model::*;
+use crate::activities::activities_search::validate_sort;
+use crate::activities::activities_corporate_actions::validate_corporate_action;
+use crate::activities::activities_withholding::validate_withholding;
+use crate::activities::activities_fingerprint::{
+    ActivityFingerprint, DuplicateMatcher, DuplicateTolerance, DUPLICATE_ERROR_KEY,
+};
+use crate::activities::statement_parsers;
 use crate::activities::{ActivityRepositoryTrait, ActivityServiceTrait};
 use crate::Result;
-use crate::assets::AssetServiceTrait;
+use crate::assets::{AssetServiceTrait, IdentifierServiceTrait, IdentifierType, SymbolIdentifier};
 use crate::fx::FxServiceTrait;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Service for managing activities
@@ -17,6 +26,8 @@ pub struct ActivityService {
     account_service: Arc<dyn AccountServiceTrait>,
     asset_service: Arc<dyn AssetServiceTrait>,
     fx_service: Arc<dyn FxServiceTrait>,
+    identifier_service: Arc<dyn IdentifierServiceTrait>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
 }
 
 impl ActivityService {
@@ -26,14 +37,85 @@ impl ActivityService {
         account_service: Arc<dyn AccountServiceTrait>,
         asset_service: Arc<dyn AssetServiceTrait>,
         fx_service: Arc<dyn FxServiceTrait>,
+        identifier_service: Arc<dyn IdentifierServiceTrait>,
+        active_profile_id: Arc<RwLock<Option<String>>>,
     ) -> Self {
         Self {
             activity_repository,
             account_service,
             asset_service,
             fx_service,
+            identifier_service,
+            active_profile_id,
         }
     }
+
+    /// Replaces an ISIN, CUSIP, SEDOL or FIGI in the row's symbol (or a bare
+    /// ISIN when the symbol is empty) with the provider symbol it resolves to.
+    /// Symbols that already name a local asset are left alone. Lookups are
+    /// memoized in `resolved` so repeated rows search once.
+    async fn resolve_import_identifier(
+        &self,
+        activity: &mut ActivityImport,
+        resolved: &mut HashMap<String, Option<SymbolIdentifier>>,
+    ) {
+        let identifier = if activity.symbol.trim().is_empty() {
+            activity.isin.clone().unwrap_or_default()
+        } else {
+            activity.symbol.trim().to_uppercase()
+        };
+        if IdentifierType::detect(&identifier).is_none() {
+            return;
+        }
+
+        if !resolved.contains_key(&identifier) {
+            let mapping = if self.asset_service.get_asset_by_id(&identifier).is_ok() {
+                None
+            } else {
+                self.identifier_service
+                    .resolve(&identifier)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("Failed to resolve identifier {}: {}", identifier, e);
+                        None
+                    })
+            };
+            resolved.insert(identifier.clone(), mapping);
+        }
+
+        if let Some(Some(mapping)) = resolved.get(&identifier) {
+            activity.symbol = mapping.symbol.clone();
+            if activity.isin.is_none() {
+                activity.isin = mapping.isin.clone();
+            }
+        }
+    }
+
+    /// Adds resolved identifiers to the account's symbol mappings, so the next
+    /// import of the same file maps them without a lookup.
+    async fn learn_symbol_mappings(
+        &self,
+        account_id: &str,
+        resolved: &HashMap<String, Option<SymbolIdentifier>>,
+    ) -> Result<()> {
+        let mut mapping_data = self.get_import_mapping(account_id.to_string())?;
+        let mut learned = 0;
+        for (identifier, mapping) in resolved {
+            if let Some(mapping) = mapping {
+                if !mapping_data.symbol_mappings.contains_key(identifier) {
+                    mapping_data
+                        .symbol_mappings
+                        .insert(identifier.clone(), mapping.symbol.clone());
+                    learned += 1;
+                }
+            }
+        }
+        if learned > 0 {
+            debug!("Learned {} symbol mappings for account {}", learned, account_id);
+            self.save_import_mapping(mapping_data).await?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -74,19 +156,24 @@ impl ActivityServiceTrait for ActivityService {
         &self,
         page: i64,
         page_size: i64,
-        account_id_filter: Option<Vec<String>>,
-        activity_type_filter: Option<Vec<String>>,
-        asset_id_keyword: Option<String>,
-        sort: Option<Sort>,
+        filters: ActivitySearchFilters,
+        sort: Vec<Sort>,
     ) -> Result<ActivitySearchResponse> {
-        self.activity_repository.search_activities(
-            page,
-            page_size,
-            account_id_filter,
-            activity_type_filter,
-            asset_id_keyword,
-            sort,
-        )
+        filters.validate()?;
+        validate_sort(&sort)?;
+        // Without an account filter, a profile sees the activities of its own accounts.
+        let mut filters = filters;
+        if filters.account_ids.is_none() && self.active_profile_id.read().unwrap().is_some() {
+            filters.account_ids = Some(
+                self.account_service
+                    .get_all_accounts()?
+                    .into_iter()
+                    .map(|a| a.id)
+                    .collect(),
+            );
+        }
+        self.activity_repository
+            .search_activities(page, page_size, filters, sort)
     }
 
     /// Creates a new activity
@@ -105,6 +192,24 @@ impl ActivityServiceTrait for ActivityService {
             account.currency.clone() // Fallback to account currency for context
         };
 
+        activity.source_asset_id = validate_corporate_action(
+            &activity.activity_type,
+            &activity.asset_id,
+            activity.source_asset_id.as_deref(),
+            activity.amount,
+        )?;
+        (activity.withholding_tax, activity.withholding_country) = validate_withholding(
+            &activity.activity_type,
+            activity.amount,
+            activity.withholding_tax,
+            activity.withholding_country.as_deref(),
+        )?;
+        if let Some(ref source_asset_id) = activity.source_asset_id {
+            self.asset_service
+                .get_or_create_asset(source_asset_id, Some(asset_context_currency.clone()))
+                .await?;
+        }
+
         let asset = self
             .asset_service
             .get_or_create_asset(&activity.asset_id, Some(asset_context_currency))
@@ -141,6 +246,24 @@ impl ActivityServiceTrait for ActivityService {
             account.currency.clone() // Fallback
         };
 
+        activity.source_asset_id = validate_corporate_action(
+            &activity.activity_type,
+            &activity.asset_id,
+            activity.source_asset_id.as_deref(),
+            activity.amount,
+        )?;
+        (activity.withholding_tax, activity.withholding_country) = validate_withholding(
+            &activity.activity_type,
+            activity.amount,
+            activity.withholding_tax,
+            activity.withholding_country.as_deref(),
+        )?;
+        if let Some(ref source_asset_id) = activity.source_asset_id {
+            self.asset_service
+                .get_or_create_asset(source_asset_id, Some(asset_context_currency.clone()))
+                .await?;
+        }
+
         let asset = self
             .asset_service
             .get_or_create_asset(&activity.asset_id, Some(asset_context_currency))
@@ -165,6 +288,87 @@ impl ActivityServiceTrait for ActivityService {
         self.activity_repository.delete_activity(activity_id).await
     }
 
+    /// Updates or deletes activities in bulk. Before writing, the target asset is
+    /// created if needed and currency pairs are registered for every account and
+    /// currency combination the edit produces.
+    async fn bulk_edit_activities(
+        &self,
+        request: ActivityBulkRequest,
+        dry_run: bool,
+    ) -> Result<ActivityBulkResult> {
+        request.filter.validate()?;
+        // Without an account filter, a profile edits the activities of its own accounts.
+        let mut request = request;
+        if request.filter.account_ids.is_none() && self.active_profile_id.read().unwrap().is_some() {
+            request.filter.account_ids = Some(
+                self.account_service
+                    .get_all_accounts()?
+                    .into_iter()
+                    .map(|a| a.id)
+                    .collect(),
+            );
+        }
+        let request = match request.action {
+            ActivityBulkAction::Update(changes) => {
+                let changes = changes.normalized()?;
+                if let Some(ref account_id) = changes.account_id {
+                    self.account_service.get_account(account_id)?;
+                }
+                ActivityBulkRequest {
+                    filter: request.filter,
+                    action: ActivityBulkAction::Update(changes),
+                }
+            }
+            ActivityBulkAction::Delete => request,
+        };
+
+        let preview = self
+            .activity_repository
+            .bulk_edit_activities(request.clone(), true)
+            .await?;
+        if dry_run || preview.before.is_empty() {
+            return Ok(preview);
+        }
+
+        if let ActivityBulkAction::Update(ref changes) = request.action {
+            if let Some(ref asset_id) = changes.asset_id {
+                let context_currency = changes
+                    .currency
+                    .clone()
+                    .unwrap_or_else(|| preview.before[0].currency.clone());
+                self.asset_service
+                    .get_or_create_asset(asset_id, Some(context_currency))
+                    .await?;
+            }
+
+            let mut account_currencies: HashMap<String, String> = HashMap::new();
+            let mut pairs: Vec<(String, String)> = Vec::new();
+            for activity in &preview.after {
+                if !account_currencies.contains_key(&activity.account_id) {
+                    let account = self.account_service.get_account(&activity.account_id)?;
+                    account_currencies.insert(activity.account_id.clone(), account.currency);
+                }
+                let pair = (
+                    account_currencies[&activity.account_id].clone(),
+                    activity.currency.clone(),
+                );
+                if pair.0 != pair.1 && !pairs.contains(&pair) {
+                    pairs.push(pair);
+                }
+            }
+            for (account_currency, activity_currency) in pairs {
+                self.fx_service
+                    .register_currency_pair(&account_currency, &activity_currency)
+                    .await?;
+            }
+        }
+
+        debug!("Applying bulk edit to {} activities", preview.before.len());
+        self.activity_repository
+            .bulk_edit_activities(request, false)
+            .await
+    }
+
     /// Verifies the activities import from CSV file
     async fn check_activities_import(
         &self,
@@ -176,16 +380,27 @@ impl ActivityServiceTrait for ActivityService {
             .get_account(&account_id)
             ?;
 
+        // Rows already present in the account are flagged so overlapping statements can be re-imported safely.
+        let existing_activities = self
+            .activity_repository
+            .get_activities_by_account_id(&account_id)?;
+        let mut duplicate_matcher =
+            DuplicateMatcher::new(&existing_activities, DuplicateTolerance::default());
+
         let mut activities_with_status: Vec<ActivityImport> = Vec::new();
+        let mut resolved_identifiers: HashMap<String, Option<SymbolIdentifier>> = HashMap::new();
 
         for mut activity in activities {
             activity.id = Some(Uuid::new_v4().to_string());
+            activity.duplicate_of = None;
             if activity.account_name.is_none() {
                 activity.account_name = Some(account.name.clone());
             }
             if activity.account_id.is_none() {
                 activity.account_id = Some(account_id.clone());
             }
+            self.resolve_import_identifier(&mut activity, &mut resolved_identifiers)
+                .await;
         
             // Determine context currency for potential asset creation during check
             let asset_context_currency = if !activity.currency.is_empty() {
@@ -204,6 +419,11 @@ impl ActivityServiceTrait for ActivityService {
 
             match symbol_profile_result {
                 Ok(asset) => { // symbol_profile_result now returns Asset
+                    if let (None, Some(isin)) = (&asset.isin, &activity.isin) {
+                        if let Err(e) = self.asset_service.update_asset_isin(&asset.id, isin.clone()).await {
+                            warn!("Failed to record ISIN {} for asset {}: {}", isin, asset.id, e);
+                        }
+                    }
                     activity.symbol_name = asset.name; // Use asset name
                     
                     // Check if activity currency (from import) is valid and handle FX
@@ -232,16 +452,83 @@ impl ActivityServiceTrait for ActivityService {
                 }
             };
 
+            match validate_corporate_action(
+                &activity.activity_type,
+                &activity.symbol,
+                activity.source_asset_id.as_deref(),
+                activity.amount,
+            ) {
+                Ok(Some(source_asset_id)) => {
+                    if let Err(e) = self
+                        .asset_service
+                        .get_or_create_asset(&source_asset_id, Some(activity.currency.clone()))
+                        .await
+                    {
+                        if is_valid {
+                            is_valid = false;
+                            error_message = Some(format!(
+                                "Failed to resolve source asset '{}': {}",
+                                source_asset_id, e
+                            ));
+                        }
+                    }
+                    activity.source_asset_id = Some(source_asset_id);
+                }
+                Ok(None) => activity.source_asset_id = None,
+                Err(e) if is_valid => {
+                    is_valid = false;
+                    error_message = Some(e.to_string());
+                }
+                Err(_) => {}
+            }
+
+            match validate_withholding(
+                &activity.activity_type,
+                activity.amount,
+                activity.withholding_tax,
+                activity.withholding_country.as_deref(),
+            ) {
+                Ok((tax, country)) => {
+                    activity.withholding_tax = tax;
+                    activity.withholding_country = country;
+                }
+                Err(e) if is_valid => {
+                    is_valid = false;
+                    error_message = Some(e.to_string());
+                }
+                Err(_) => {}
+            }
+
             activity.is_valid = is_valid;
+            let mut errors = std::collections::HashMap::new();
             if let Some(error_msg) = error_message {
-                let mut errors = std::collections::HashMap::new();
                 errors.insert(activity.symbol.clone(), vec![error_msg]); 
+            }
+
+            if let Some(existing_id) = ActivityFingerprint::from_import(&activity)
+                .and_then(|fingerprint| duplicate_matcher.take_match(&fingerprint))
+            {
+                errors.insert(
+                    DUPLICATE_ERROR_KEY.to_string(),
+                    vec![format!("Possible duplicate of existing activity {}", existing_id)],
+                );
+                activity.duplicate_of = Some(existing_id);
+            }
+
+            if !errors.is_empty() {
                 activity.errors = Some(errors);
             }
 
             activities_with_status.push(activity);
         }
 
+        if let Err(e) = self
+            .learn_symbol_mappings(&account_id, &resolved_identifiers)
+            .await
+        {
+            warn!("Failed to save learned symbol mappings: {}", e);
+        }
+
         Ok(activities_with_status)
     }
 
@@ -250,47 +537,148 @@ impl ActivityServiceTrait for ActivityService {
         &self,
         account_id: String,
         activities: Vec<ActivityImport>,
+        duplicate_strategy: DuplicateStrategy,
+        source: NewImportSession,
     ) -> Result<Vec<ActivityImport>> {
-        let validated_activities = self
+        let mut validated_activities = self
             .check_activities_import(account_id.clone(), activities)
             .await?;
 
+        // Duplicate flags do not block the import; they are resolved by `duplicate_strategy`.
         let has_errors = validated_activities.iter().any(|activity| {
             !activity.is_valid
-                || activity
-                    .errors
-                    .as_ref()
-                    .map_or(false, |errors| !errors.is_empty())
+                || activity.errors.as_ref().is_some_and(|errors| {
+                    errors.keys().any(|key| key != DUPLICATE_ERROR_KEY)
+                })
         });
 
         if has_errors {
             return Ok(validated_activities);
         }
 
-        let new_activities: Vec<NewActivity> = validated_activities
-            .iter()
-            .map(|activity| NewActivity {
-                id: activity.id.clone(),
-                account_id: activity.account_id.clone().unwrap_or_default(),
-                asset_id: activity.symbol.clone(),
-                activity_type: activity.activity_type.clone(),
-                activity_date: activity.date.clone(),
-                quantity: Some(activity.quantity),
-                unit_price: Some(activity.unit_price),
-                currency: activity.currency.clone(),
-                fee: Some(activity.fee),
-                amount: activity.amount,
-                is_draft: activity.is_draft,
-                comment: activity.comment.clone(),
-            })
-            .collect();
+        let mut new_activities: Vec<NewActivity> = Vec::new();
+        let mut merges: Vec<ActivityUpdate> = Vec::new();
+        let mut skipped = 0;
+        for activity in validated_activities.iter_mut() {
+            match (&activity.duplicate_of, duplicate_strategy) {
+                (None, _) => new_activities.push(NewActivity {
+                    id: activity.id.clone(),
+                    account_id: activity.account_id.clone().unwrap_or_default(),
+                    asset_id: activity.symbol.clone(),
+                    activity_type: activity.activity_type.clone(),
+                    activity_date: activity.date.clone(),
+                    quantity: Some(activity.quantity),
+                    unit_price: Some(activity.unit_price),
+                    currency: activity.currency.clone(),
+                    fee: Some(activity.fee),
+                    amount: activity.amount,
+                    is_draft: activity.is_draft,
+                    comment: activity.comment.clone(),
+                    import_session_id: None,
+                    source_asset_id: activity.source_asset_id.clone(),
+                    withholding_tax: activity.withholding_tax,
+                    withholding_country: activity.withholding_country.clone(),
+                }),
+                (Some(_), DuplicateStrategy::Skip) => skipped += 1,
+                (Some(existing_id), DuplicateStrategy::Merge) => {
+                    // Duplicates match within a day, so the stored date stands, as do the
+                    // user's draft flag and any comment the statement does not replace.
+                    // The row reports the stored date so its range is recalculated.
+                    let existing = self.activity_repository.get_activity(existing_id)?;
+                    activity.date = existing.activity_date.to_rfc3339();
+                    merges.push(ActivityUpdate {
+                        id: existing_id.clone(),
+                        account_id: activity.account_id.clone().unwrap_or_default(),
+                        asset_id: activity.symbol.clone(),
+                        activity_type: activity.activity_type.clone(),
+                        activity_date: activity.date.clone(),
+                        quantity: Some(activity.quantity),
+                        unit_price: Some(activity.unit_price),
+                        currency: activity.currency.clone(),
+                        fee: Some(activity.fee),
+                        amount: activity.amount,
+                        is_draft: existing.is_draft,
+                        comment: activity.comment.clone().or(existing.comment),
+                        source_asset_id: activity.source_asset_id.clone(),
+                        withholding_tax: activity.withholding_tax,
+                        withholding_country: activity.withholding_country.clone(),
+                    })
+                }
+            }
+        }
 
-        let count = self.activity_repository.create_activities(new_activities).await?;
-        debug!("Successfully imported {} activities", count);
+        let (count, merged) = (new_activities.len(), merges.len());
+        let session = ImportSession {
+            id: Uuid::new_v4().to_string(),
+            account_id: account_id.clone(),
+            source: if source.source.trim().is_empty() {
+                IMPORT_SOURCE_CSV.to_string()
+            } else {
+                source.source
+            },
+            file_name: source.file_name,
+            file_hash: source.file_hash,
+            row_count: i32::try_from(validated_activities.len()).unwrap_or(i32::MAX),
+            status: IMPORT_SESSION_STATUS_COMPLETED.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            rolled_back_at: None,
+        };
+        let session = self
+            .activity_repository
+            .create_import_session(session, new_activities, merges)
+            .await?;
+        debug!(
+            "Import session {} ({}): imported {} activities ({} duplicates merged, {} skipped)",
+            session.id, session.source, count, merged, skipped
+        );
+
+        for activity in validated_activities.iter_mut().filter(|activity| {
+            activity.duplicate_of.is_none() || duplicate_strategy == DuplicateStrategy::Merge
+        }) {
+            activity.import_session_id = Some(session.id.clone());
+        }
 
         Ok(validated_activities)
     }
 
+    fn get_import_sessions(&self, account_id: Option<String>) -> Result<Vec<ImportSession>> {
+        let sessions = self
+            .activity_repository
+            .get_import_sessions(account_id.as_deref())?;
+        // Without an account filter, a profile sees the imports into its own accounts.
+        if account_id.is_some() || self.active_profile_id.read().unwrap().is_none() {
+            return Ok(sessions);
+        }
+        let account_ids: HashSet<String> = self
+            .account_service
+            .get_all_accounts()?
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+        Ok(sessions
+            .into_iter()
+            .filter(|s| account_ids.contains(&s.account_id))
+            .collect())
+    }
+
+    fn get_activities_by_import_session(&self, session_id: &str) -> Result<Vec<Activity>> {
+        self.activity_repository
+            .get_activities_by_import_session(session_id)
+    }
+
+    async fn rollback_import_session(&self, session_id: String) -> Result<Vec<Activity>> {
+        let removed = self
+            .activity_repository
+            .rollback_import_session(session_id.clone())
+            .await?;
+        debug!(
+            "Rolled back import session {}: removed or restored {} activities",
+            session_id,
+            removed.len()
+        );
+        Ok(removed)
+    }
+
     /// Gets the first activity date for given account IDs
     fn get_first_activity_date(
         &self,
@@ -317,6 +705,53 @@ impl ActivityServiceTrait for ActivityService {
     }
 
     /// Saves or updates an import mapping
+    fn get_saved_views(&self) -> Result<Vec<ActivitySavedView>> {
+        self.activity_repository.get_saved_views()
+    }
+
+    /// Saves a named view, rejecting names already used by another view.
+    async fn save_view(&self, view: NewActivitySavedView) -> Result<ActivitySavedView> {
+        let name = view.name.trim().to_string();
+        if name.is_empty() {
+            return Err(ActivityError::InvalidData("View name cannot be empty".to_string()).into());
+        }
+        view.filters.validate()?;
+        validate_sort(&view.sort)?;
+
+        let existing = self.activity_repository.get_saved_views()?;
+        if existing
+            .iter()
+            .any(|v| v.name.eq_ignore_ascii_case(&name) && Some(&v.id) != view.id.as_ref())
+        {
+            return Err(ActivityError::InvalidData(format!(
+                "A view named '{}' already exists",
+                name
+            ))
+            .into());
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let created_at = view
+            .id
+            .as_ref()
+            .and_then(|id| existing.iter().find(|v| &v.id == id))
+            .map(|v| v.created_at.clone())
+            .unwrap_or_else(|| now.clone());
+        let view_db = ActivitySavedViewDB {
+            id: view.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+            name,
+            filters: serde_json::to_string(&view.filters)?,
+            sort: serde_json::to_string(&view.sort)?,
+            created_at,
+            updated_at: now,
+        };
+        self.activity_repository.save_view(view_db).await
+    }
+
+    async fn delete_saved_view(&self, view_id: String) -> Result<()> {
+        self.activity_repository.delete_saved_view(view_id).await
+    }
+
     async fn save_import_mapping(
         &self,
         mapping_data: ImportMappingData,
@@ -325,4 +760,38 @@ impl ActivityServiceTrait for ActivityService {
         self.activity_repository.save_import_mapping(&mapping).await?;
         Ok(mapping_data)
     }
+
+    async fn parse_statement(
+        &self,
+        account_id: String,
+        parser_id: Option<String>,
+        content: String,
+    ) -> Result<Vec<ActivityImport>> {
+        let account = self.account_service.get_account(&account_id)?;
+        let parser = match parser_id {
+            Some(id) => statement_parsers::get_statement_parser(&id).ok_or_else(|| {
+                ActivityError::ParseError(format!("Unknown statement parser: {}", id))
+            })?,
+            None => statement_parsers::detect_statement_parser(&content).ok_or_else(|| {
+                ActivityError::ParseError("Unrecognized statement format".to_string())
+            })?,
+        };
+
+        let activities = parser.parse(&content, &account.currency)?;
+        debug!(
+            "Parsed {} activities from {} statement",
+            activities.len(),
+            parser.id()
+        );
+        // Rows the parser could not map stay invalid whatever the check finds.
+        let parser_errors: Vec<_> = activities.iter().map(|a| a.errors.clone()).collect();
+        let mut checked = self.check_activities_import(account_id, activities).await?;
+        for (activity, errors) in checked.iter_mut().zip(parser_errors) {
+            if let Some(errors) = errors {
+                activity.is_valid = false;
+                activity.errors.get_or_insert_with(HashMap::new).extend(errors);
+            }
+        }
+        Ok(checked)
+    }
 }
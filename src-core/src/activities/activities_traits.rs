@@ -21,25 +21,52 @@ pub trait ActivityRepositoryTrait: Send + Sync {
         start_date: NaiveDateTime,
         end_date: NaiveDateTime,
     ) -> Result<Vec<(String, Decimal, Decimal, String, Option<Decimal>)>>;
+    /// Pages through activities of active accounts matching `filters`, ordered by
+    /// each entry of `sort` in turn and then by date, newest first.
     fn search_activities(
         &self,
         page: i64,
         page_size: i64,
-        account_id_filter: Option<Vec<String>>,
-        activity_type_filter: Option<Vec<String>>,
-        asset_id_keyword: Option<String>,
-        sort: Option<Sort>,
+        filters: ActivitySearchFilters,
+        sort: Vec<Sort>,
     ) -> Result<ActivitySearchResponse>;
     async fn create_activity(&self, new_activity: NewActivity) -> Result<Activity>;
     async fn update_activity(&self, activity_update: ActivityUpdate) -> Result<Activity>;
     async fn delete_activity(&self, activity_id: String) -> Result<Activity>;
     async fn create_activities(&self, activities: Vec<NewActivity>) -> Result<usize>;
+    /// Applies `request.action` to every activity matching its filter in one transaction.
+    /// With `dry_run` nothing is written and the result previews the edit.
+    async fn bulk_edit_activities(
+        &self,
+        request: ActivityBulkRequest,
+        dry_run: bool,
+    ) -> Result<ActivityBulkResult>;
+    /// Inserts the session and its activities and applies `merges` to existing activities,
+    /// tagging all of them with the session id, in one transaction. The merged rows' prior
+    /// state is kept so a rollback can restore them.
+    async fn create_import_session(
+        &self,
+        session: ImportSession,
+        activities: Vec<NewActivity>,
+        merges: Vec<ActivityUpdate>,
+    ) -> Result<ImportSession>;
+    fn get_import_sessions(&self, account_id: Option<&str>) -> Result<Vec<ImportSession>>;
+    fn get_activities_by_import_session(&self, session_id: &str) -> Result<Vec<Activity>>;
+    /// Restores the activities the session merged into, deletes the ones it inserted, marks
+    /// it rolled back and returns the deleted rows followed by the restored ones.
+    async fn rollback_import_session(&self, session_id: String) -> Result<Vec<Activity>>;
     fn get_first_activity_date(&self, account_ids: Option<&[String]>) -> Result<Option<DateTime<Utc>>>;
     fn get_import_mapping(&self, account_id: &str) -> Result<Option<ImportMapping>>;
     async fn save_import_mapping(&self, mapping: &ImportMapping) -> Result<()>;
+    fn get_saved_views(&self) -> Result<Vec<ActivitySavedView>>;
+    /// Inserts the view, or overwrites the one with the same id.
+    async fn save_view(&self, view: ActivitySavedViewDB) -> Result<ActivitySavedView>;
+    async fn delete_saved_view(&self, view_id: String) -> Result<()>;
     // Add other repository methods if necessary, e.g., calculate_average_cost, get_deposit_activities
     fn calculate_average_cost(&self, account_id: &str, asset_id: &str) -> Result<Decimal>;
     fn get_income_activities_data(&self) -> Result<Vec<IncomeData>>;
+    /// Income activities dated in `year` that carry a withholding tax, across all accounts.
+    fn get_foreign_tax_data(&self, year: i32) -> Result<Vec<ForeignTaxData>>;
     fn get_first_activity_date_overall(&self) -> Result<DateTime<Utc>>;
 }
 
@@ -52,31 +79,59 @@ pub trait ActivityServiceTrait: Send + Sync {
     fn get_activities_by_account_ids(&self, account_ids: &[String]) -> Result<Vec<Activity>>;
     fn get_trading_activities(&self) -> Result<Vec<Activity>>;
     fn get_income_activities(&self) -> Result<Vec<Activity>>;
+    /// Pages through activities of active accounts matching `filters`, ordered by
+    /// each entry of `sort` in turn and then by date, newest first.
     fn search_activities(
         &self,
         page: i64,
         page_size: i64,
-        account_id_filter: Option<Vec<String>>,
-        activity_type_filter: Option<Vec<String>>,
-        asset_id_keyword: Option<String>,
-        sort: Option<Sort>,
+        filters: ActivitySearchFilters,
+        sort: Vec<Sort>,
     ) -> Result<ActivitySearchResponse>;
     fn get_first_activity_date(&self, account_ids: Option<&[String]>) -> Result<Option<DateTime<Utc>>>;
     fn get_import_mapping(&self, account_id: String) -> Result<ImportMappingData>;
     async fn create_activity(&self, activity: NewActivity) -> Result<Activity>;
     async fn update_activity(&self, activity: ActivityUpdate) -> Result<Activity>;
     async fn delete_activity(&self, activity_id: String) -> Result<Activity>;
+    /// Updates or deletes every activity matching the request filter as one batch,
+    /// or only previews the outcome when `dry_run` is set.
+    async fn bulk_edit_activities(
+        &self,
+        request: ActivityBulkRequest,
+        dry_run: bool,
+    ) -> Result<ActivityBulkResult>;
     async fn check_activities_import(
         &self,
         account_id: String,
         activities: Vec<ActivityImport>,
     ) -> Result<Vec<ActivityImport>>;
+    /// Imports activities after validation. Rows flagged as duplicates of existing
+    /// activities are skipped or merged according to `duplicate_strategy`; the
+    /// remaining rows are created under a new import session recording `source`.
     async fn import_activities(
         &self,
         account_id: String,
         activities: Vec<ActivityImport>,
+        duplicate_strategy: DuplicateStrategy,
+        source: NewImportSession,
     ) -> Result<Vec<ActivityImport>>;
+    fn get_import_sessions(&self, account_id: Option<String>) -> Result<Vec<ImportSession>>;
+    fn get_activities_by_import_session(&self, session_id: &str) -> Result<Vec<Activity>>;
+    /// Removes every activity created by the session. Duplicates merged into
+    /// existing activities during the import are left as they are.
+    async fn rollback_import_session(&self, session_id: String) -> Result<Vec<Activity>>;
     async fn save_import_mapping(&self, mapping_data: ImportMappingData) -> Result<ImportMappingData>;
+    fn get_saved_views(&self) -> Result<Vec<ActivitySavedView>>;
+    async fn save_view(&self, view: NewActivitySavedView) -> Result<ActivitySavedView>;
+    async fn delete_saved_view(&self, view_id: String) -> Result<()>;
+    /// Parses a broker statement with the given parser, or the detected one when
+    /// `parser_id` is None, and checks the rows as `check_activities_import` does.
+    async fn parse_statement(
+        &self,
+        account_id: String,
+        parser_id: Option<String>,
+        content: String,
+    ) -> Result<Vec<ActivityImport>>;
   
 
 }
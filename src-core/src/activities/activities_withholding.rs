@@ -0,0 +1,78 @@
+use rust_decimal::Decimal;
+
+use super::activities_constants::INCOME_ACTIVITY_TYPES;
+use super::activities_errors::ActivityError;
+
+/// Checks the withholding tax recorded on an activity and returns it with the country
+/// code normalised. Only income activities carry withholding; a zero amount with no
+/// country is dropped. A negative withholding is a refund of tax withheld earlier and
+/// may not exceed the `amount` received with it.
+pub(crate) fn validate_withholding(
+    activity_type: &str,
+    amount: Option<Decimal>,
+    withholding_tax: Option<Decimal>,
+    withholding_country: Option<&str>,
+) -> Result<(Option<Decimal>, Option<String>), ActivityError> {
+    let country = withholding_country
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty());
+    let tax = withholding_tax.filter(|t| !t.is_zero() || country.is_some());
+    if tax.is_none() && country.is_none() {
+        return Ok((None, None));
+    }
+
+    if !INCOME_ACTIVITY_TYPES.contains(&activity_type) {
+        return Err(ActivityError::InvalidData(format!(
+            "Withholding tax can only be recorded on income activities, not {}",
+            activity_type
+        )));
+    }
+    if let Some(tax) = tax.filter(|t| *t + amount.unwrap_or_default() < Decimal::ZERO) {
+        return Err(ActivityError::InvalidData(format!(
+            "A withholding tax refund of {} exceeds the amount received",
+            -tax
+        )));
+    }
+    let Some(country) = country else {
+        return Err(ActivityError::InvalidData(
+            "Withholding tax needs the country that withheld it".to_string(),
+        ));
+    };
+    if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(ActivityError::InvalidData(format!(
+            "Withholding country must be a two-letter ISO code, got '{}'",
+            country
+        )));
+    }
+    Ok((Some(tax.unwrap_or(Decimal::ZERO)), Some(country)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_normalises_withholding_on_dividends() {
+        let result = validate_withholding("DIVIDEND", None, Some(dec!(15)), Some(" us ")).unwrap();
+        assert_eq!(result, (Some(dec!(15)), Some("US".to_string())));
+
+        let result = validate_withholding("DIVIDEND", None, Some(dec!(0)), None).unwrap();
+        assert_eq!(result, (None, None));
+    }
+
+    #[test]
+    fn test_rejects_invalid_withholding() {
+        assert!(validate_withholding("BUY", None, Some(dec!(1)), Some("US")).is_err());
+        assert!(validate_withholding("DIVIDEND", None, Some(dec!(-1)), Some("US")).is_err());
+        assert!(validate_withholding("DIVIDEND", None, Some(dec!(1)), None).is_err());
+        assert!(validate_withholding("INTEREST", None, Some(dec!(1)), Some("USA")).is_err());
+        assert!(validate_withholding("DIVIDEND", Some(dec!(1)), Some(dec!(-2)), Some("US")).is_err());
+    }
+
+    #[test]
+    fn test_accepts_withholding_refund_covered_by_amount() {
+        let result = validate_withholding("DIVIDEND", Some(dec!(2)), Some(dec!(-2)), Some("US")).unwrap();
+        assert_eq!(result, (Some(dec!(-2)), Some("US".to_string())));
+    }
+}
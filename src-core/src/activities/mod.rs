@@ -1,13 +1,22 @@
+pub(crate) mod activities_bulk;
 pub(crate) mod activities_constants;
+pub(crate) mod activities_corporate_actions;
 pub(crate) mod activities_errors;
+pub(crate) mod activities_fingerprint;
 pub(crate) mod activities_model;
 pub(crate) mod activities_repository;
+pub(crate) mod activities_search;
 pub(crate) mod activities_service;
 pub(crate) mod activities_traits;
+pub(crate) mod activities_withholding;
+pub mod statement_parsers;
 
 pub use activities_constants::*;
+pub use activities_corporate_actions::corporate_action_cost_ratio;
 pub use activities_errors::ActivityError;
-pub use activities_model::{Activity, ActivityType, ActivityDB, ActivityDetails, ActivityImport, ActivitySearchResponse, ActivitySearchResponseMeta, ActivityUpdate, ImportMapping, ImportMappingData, NewActivity, Sort};
+pub use activities_fingerprint::{ActivityFingerprint, DuplicateMatcher, DuplicateTolerance, DUPLICATE_ERROR_KEY};
+pub use activities_model::{Activity, ActivityBulkAction, ActivityBulkChanges, ActivityBulkFilter, ActivityBulkRequest, ActivityBulkResult, ActivityType, ActivityDB, ActivityDetails, ActivityImport, ActivitySavedView, ActivitySearchFilters, ActivitySearchResponse, ActivitySearchResponseMeta, ActivityUpdate, DuplicateStrategy, ImportMapping, ImportMappingData, ImportSession, NewActivity, NewActivitySavedView, NewImportSession, Sort};
 pub use activities_repository::ActivityRepository;
 pub use activities_service::ActivityService;
-pub use activities_traits::{ActivityRepositoryTrait, ActivityServiceTrait}; 
\ No newline at end of file
+pub use activities_traits::{ActivityRepositoryTrait, ActivityServiceTrait};
+pub use statement_parsers::{StatementParser, StatementParserInfo}; 
\ No newline at end of file
@@ -0,0 +1,226 @@
+use super::statement_parser::{
+    cash_symbol, has_csv_header, parse_amount, parse_date, statement_activity, CsvTable,
+    StatementParser,
+};
+use crate::activities::activities_constants::*;
+use crate::activities::activities_errors::ActivityError;
+use crate::activities::activities_model::ActivityImport;
+use csv::StringRecord;
+use log::warn;
+use rust_decimal::Decimal;
+
+const TRANSACTIONS_COLUMNS: [&str; 5] = ["Date", "Product", "ISIN", "Reference exchange", "Quantity"];
+const ACCOUNT_COLUMNS: [&str; 5] = ["Date", "Value date", "Product", "Description", "Change"];
+const DATE_FORMATS: [&str; 2] = ["%d-%m-%Y", "%d/%m/%Y"];
+const TRANSACTION_FEES_COLUMN: &str = "Transaction and/or third party fees";
+
+/// Degiro CSV exports.
+///
+/// Two exports are understood: `Transactions.csv` for trades and
+/// `Account.csv` for dividends, taxes, deposits, fees and currency
+/// conversions. Trades listed in the account statement are ignored since they
+/// come from the transactions export. Degiro does not publish tickers, so
+/// securities are identified by ISIN.
+///
+/// Amounts and their currencies sit in adjacent columns of which only the
+/// first is named: `Price` is followed by its currency, while `Change` holds
+/// the currency and is followed by the amount.
+#[derive(Default)]
+pub struct DegiroParser;
+
+impl DegiroParser {
+    pub fn new() -> Self {
+        DegiroParser
+    }
+
+    /// Value of the unnamed column right after `name`.
+    fn next_cell<'a>(table: &CsvTable, record: &'a StringRecord, name: &str) -> &'a str {
+        table
+            .column(name)
+            .and_then(|index| record.get(index + 1))
+            .unwrap_or("")
+    }
+
+    fn parse_transactions(content: &str) -> Result<Vec<ActivityImport>, ActivityError> {
+        let table = CsvTable::from_content(content, &TRANSACTIONS_COLUMNS)?;
+
+        let mut activities = Vec::new();
+        for (line, record) in &table.rows {
+            let Some(date) = parse_date(table.get(record, "Date"), &DATE_FORMATS) else {
+                warn!("Degiro line {}: invalid trade date", line);
+                continue;
+            };
+            let quantity = parse_amount(table.get(record, "Quantity")).unwrap_or_default();
+            if quantity.is_zero() {
+                continue;
+            }
+            let activity_type = if quantity.is_sign_negative() {
+                ACTIVITY_TYPE_SELL
+            } else {
+                ACTIVITY_TYPE_BUY
+            };
+            let currency = Self::next_cell(&table, record, "Price");
+            let mut activity = statement_activity(*line, date, activity_type, table.get(record, "ISIN"), currency);
+            activity.quantity = quantity.abs();
+            activity.unit_price = parse_amount(table.get(record, "Price")).unwrap_or_default();
+            activity.symbol_name = Some(table.get(record, "Product").to_string());
+            activity.isin = Some(activity.symbol.clone());
+
+            let fee = parse_amount(table.get(record, TRANSACTION_FEES_COLUMN)).unwrap_or_default().abs();
+            let fee_currency = Self::next_cell(&table, record, TRANSACTION_FEES_COLUMN);
+            if fee.is_zero() || fee_currency.eq_ignore_ascii_case(currency) {
+                activity.fee = fee;
+                activities.push(activity);
+            } else {
+                let mut fee_activity = statement_activity(*line, date, ACTIVITY_TYPE_FEE, &cash_symbol(fee_currency), fee_currency);
+                fee_activity.amount = Some(fee);
+                fee_activity.comment = Some(format!("Transaction fees for {}", activity.symbol));
+                activities.push(activity);
+                activities.push(fee_activity);
+            }
+        }
+        Ok(activities)
+    }
+
+    fn account_activity_type(description: &str, amount: Decimal) -> Option<&'static str> {
+        let description = description.to_lowercase();
+        let outflow = amount.is_sign_negative();
+        // Covers "Dividend", "Dividend Tax" and the Dutch "Dividendbelasting";
+        // tax refunds are positive and booked as income.
+        let activity_type = if description.contains("dividend") {
+            if outflow {
+                ACTIVITY_TYPE_TAX
+            } else {
+                ACTIVITY_TYPE_DIVIDEND
+            }
+        } else if description.starts_with("fx credit") || description.starts_with("valuta creditering") {
+            ACTIVITY_TYPE_TRANSFER_IN
+        } else if description.starts_with("fx debit") || description.starts_with("valuta debitering") {
+            ACTIVITY_TYPE_TRANSFER_OUT
+        } else if description.contains("transaction and/or third party fees")
+            || description.contains("transaction fee")
+            || description.starts_with("buy ")
+            || description.starts_with("sell ")
+            || description.contains("cash sweep")
+            || description.contains("money market fund")
+        {
+            // Part of trades or internal sweeps, already covered elsewhere
+            return None;
+        } else if description.contains("interest") {
+            if outflow {
+                ACTIVITY_TYPE_FEE
+            } else {
+                ACTIVITY_TYPE_INTEREST
+            }
+        } else if description.contains("fee") {
+            ACTIVITY_TYPE_FEE
+        } else if description.contains("withdrawal") {
+            ACTIVITY_TYPE_WITHDRAWAL
+        } else if description.contains("deposit") {
+            ACTIVITY_TYPE_DEPOSIT
+        } else {
+            return None;
+        };
+        Some(activity_type)
+    }
+
+    fn parse_account(content: &str) -> Result<Vec<ActivityImport>, ActivityError> {
+        let table = CsvTable::from_content(content, &ACCOUNT_COLUMNS)?;
+
+        let mut activities = Vec::new();
+        for (line, record) in &table.rows {
+            let Some(date) = parse_date(table.get(record, "Date"), &DATE_FORMATS) else {
+                warn!("Degiro line {}: invalid date", line);
+                continue;
+            };
+            let description = table.get(record, "Description");
+            let currency = table.get(record, "Change");
+            let Some(amount) = parse_amount(Self::next_cell(&table, record, "Change")) else {
+                continue;
+            };
+            let Some(activity_type) = Self::account_activity_type(description, amount) else {
+                continue;
+            };
+
+            let isin = table.get(record, "ISIN");
+            let symbol = match activity_type {
+                ACTIVITY_TYPE_DIVIDEND | ACTIVITY_TYPE_TAX if !isin.is_empty() => isin.to_string(),
+                _ => cash_symbol(currency),
+            };
+            let mut activity = statement_activity(*line, date, activity_type, &symbol, currency);
+            activity.amount = Some(amount.abs());
+            activity.comment = Some(description.to_string());
+            if !isin.is_empty() {
+                activity.symbol_name = Some(table.get(record, "Product").to_string());
+                activity.isin = Some(isin.to_string());
+            }
+            activities.push(activity);
+        }
+        Ok(activities)
+    }
+}
+
+impl StatementParser for DegiroParser {
+    fn id(&self) -> &'static str {
+        STATEMENT_PARSER_DEGIRO
+    }
+
+    fn name(&self) -> &'static str {
+        "Degiro (CSV)"
+    }
+
+    fn can_parse(&self, content: &str) -> bool {
+        has_csv_header(content, &TRANSACTIONS_COLUMNS) || has_csv_header(content, &ACCOUNT_COLUMNS)
+    }
+
+    fn parse(&self, content: &str, _default_currency: &str) -> Result<Vec<ActivityImport>, ActivityError> {
+        if has_csv_header(content, &TRANSACTIONS_COLUMNS) {
+            Self::parse_transactions(content)
+        } else {
+            Self::parse_account(content)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_degiro_transactions() {
+        let content = "Date,Time,Product,ISIN,Reference exchange,Venue,Quantity,Price,,Local value,,Value,,Exchange rate,Transaction and/or third party fees,,Total,,Order ID\n\
+15-01-2024,09:04,VANGUARD FTSE ALL-WORLD,IE00BK5BQT80,EAI,XAMS,10,\"108,50\",EUR,\"-1085,00\",EUR,\"-1085,00\",EUR,,\"-1,00\",EUR,\"-1086,00\",EUR,abc\n\
+16-01-2024,15:30,APPLE INC,US0378331005,NDQ,XNAS,-2,\"185,00\",USD,\"370,00\",USD,\"339,45\",EUR,\"1,09\",\"-2,00\",EUR,\"337,45\",EUR,def\n";
+        let parser = DegiroParser::new();
+        assert!(parser.can_parse(content));
+        let activities = parser.parse(content, "USD").unwrap();
+        assert_eq!(activities.len(), 3);
+
+        assert_eq!(activities[0].activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(activities[0].symbol, "IE00BK5BQT80");
+        assert_eq!(activities[0].unit_price, dec!(108.50));
+        assert_eq!(activities[0].fee, dec!(1.00));
+        assert_eq!(activities[1].activity_type, ACTIVITY_TYPE_SELL);
+        assert_eq!(activities[1].currency, "USD");
+        assert_eq!(activities[1].fee, Decimal::ZERO);
+        assert_eq!(activities[2].activity_type, ACTIVITY_TYPE_FEE);
+        assert_eq!(activities[2].symbol, "$CASH-EUR");
+        assert_eq!(activities[2].amount, Some(dec!(2.00)));
+    }
+
+    #[test]
+    fn test_parse_degiro_account() {
+        let content = "Date,Time,Value date,Product,ISIN,Description,FX,Change,,Balance,,Order Id\n\
+20-03-2024,07:31,19-03-2024,APPLE INC,US0378331005,Dividend Tax,,USD,\"-0,36\",USD,\"2,04\",\n\
+20-03-2024,07:31,19-03-2024,APPLE INC,US0378331005,Dividend,,USD,\"2,40\",USD,\"2,40\",\n\
+16-01-2024,15:30,16-01-2024,APPLE INC,US0378331005,\"Sell 2 Apple Inc@185 USD (US0378331005)\",,USD,\"370,00\",USD,\"370,00\",def\n\
+02-01-2024,10:00,02-01-2024,,,iDEAL Deposit,,EUR,\"1000,00\",EUR,\"1000,00\",\n";
+        let activities = DegiroParser::new().parse(content, "USD").unwrap();
+        let types: Vec<&str> = activities.iter().map(|a| a.activity_type.as_str()).collect();
+        assert_eq!(types, vec![ACTIVITY_TYPE_TAX, ACTIVITY_TYPE_DIVIDEND, ACTIVITY_TYPE_DEPOSIT]);
+        assert_eq!(activities[0].amount, Some(dec!(0.36)));
+        assert_eq!(activities[0].symbol, "US0378331005");
+        assert_eq!(activities[2].symbol, "$CASH-EUR");
+    }
+}
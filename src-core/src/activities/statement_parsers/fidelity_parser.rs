@@ -0,0 +1,174 @@
+use super::statement_parser::{
+    cash_symbol, has_csv_header, parse_amount, parse_date, statement_activity, CsvTable,
+    StatementParser,
+};
+use crate::activities::activities_constants::*;
+use crate::activities::activities_errors::ActivityError;
+use crate::activities::activities_model::ActivityImport;
+use log::warn;
+use rust_decimal::Decimal;
+
+const REQUIRED_COLUMNS: [&str; 4] = ["Run Date", "Action", "Symbol", "Amount ($)"];
+const DATE_FORMATS: [&str; 1] = ["%m/%d/%Y"];
+const CURRENCY: &str = "USD";
+
+/// Fidelity "Accounts History" CSV export.
+///
+/// Fidelity describes each row with a free-text action ("YOU BOUGHT APPLE INC
+/// (AAPL) (Cash)"), so rows are classified by the leading phrase. Money-market
+/// sweep positions (`SPAXX`, `FDRXX`, ...) are treated as cash.
+#[derive(Default)]
+pub struct FidelityParser;
+
+impl FidelityParser {
+    pub fn new() -> Self {
+        FidelityParser
+    }
+
+    fn activity_type(action: &str, quantity: Decimal, amount: Option<Decimal>) -> Option<&'static str> {
+        let action = action.to_uppercase();
+        let outflow = amount.is_some_and(|a| a.is_sign_negative());
+        let activity_type = if action.starts_with("YOU BOUGHT") || action.starts_with("REINVESTMENT") {
+            ACTIVITY_TYPE_BUY
+        } else if action.starts_with("YOU SOLD") {
+            ACTIVITY_TYPE_SELL
+        } else if action.contains("FOREIGN TAX") || action.contains("TAX WITHHELD") {
+            ACTIVITY_TYPE_TAX
+        } else if action.starts_with("DIVIDEND RECEIVED")
+            || action.starts_with("LONG-TERM CAP GAIN")
+            || action.starts_with("SHORT-TERM CAP GAIN")
+            || action.starts_with("IN LIEU OF FRX SHARE")
+        {
+            ACTIVITY_TYPE_DIVIDEND
+        } else if action.starts_with("INTEREST EARNED") {
+            ACTIVITY_TYPE_INTEREST
+        } else if action.contains("FEE CHARGED") || action.contains("MARGIN INTEREST") {
+            ACTIVITY_TYPE_FEE
+        } else if action.starts_with("DISTRIBUTION") && !quantity.is_zero() {
+            // Share distributions from stock splits
+            ACTIVITY_TYPE_ADD_HOLDING
+        } else if action.starts_with("TRANSFER OF ASSETS") && !quantity.is_zero() {
+            if quantity.is_sign_negative() {
+                ACTIVITY_TYPE_TRANSFER_OUT
+            } else {
+                ACTIVITY_TYPE_TRANSFER_IN
+            }
+        } else if action.contains("ELECTRONIC FUNDS TRANSFER")
+            || action.contains("CONTRIBUTION")
+            || action.contains("DIRECT DEPOSIT")
+            || action.contains("DIRECT DEBIT")
+            || action.contains("WIRE TRANSFER")
+            || action.starts_with("TRANSFERRED")
+            || action.starts_with("CHECK RECEIVED")
+            || action.starts_with("PARTIAL DISTRIBUTION")
+            || action.starts_with("NORMAL DISTRIBUTION")
+        {
+            if outflow {
+                ACTIVITY_TYPE_WITHDRAWAL
+            } else {
+                ACTIVITY_TYPE_DEPOSIT
+            }
+        } else {
+            return None;
+        };
+        Some(activity_type)
+    }
+
+    fn is_money_market(symbol: &str) -> bool {
+        symbol.is_empty() || matches!(symbol, "SPAXX" | "FDRXX" | "FZFXX" | "SPRXX" | "FCASH" | "CORE")
+    }
+}
+
+impl StatementParser for FidelityParser {
+    fn id(&self) -> &'static str {
+        STATEMENT_PARSER_FIDELITY
+    }
+
+    fn name(&self) -> &'static str {
+        "Fidelity (CSV)"
+    }
+
+    fn can_parse(&self, content: &str) -> bool {
+        has_csv_header(content, &REQUIRED_COLUMNS)
+    }
+
+    fn parse(&self, content: &str, _default_currency: &str) -> Result<Vec<ActivityImport>, ActivityError> {
+        let table = CsvTable::from_content(content, &REQUIRED_COLUMNS)?;
+
+        let mut activities = Vec::new();
+        for (line, record) in &table.rows {
+            // Disclaimer lines at the end of the export have no date
+            let Some(date) = parse_date(table.get(record, "Run Date"), &DATE_FORMATS) else {
+                continue;
+            };
+            let action = table.get(record, "Action");
+            let quantity = parse_amount(table.get(record, "Quantity")).unwrap_or_default();
+            let amount = parse_amount(table.get(record, "Amount ($)"));
+            let Some(activity_type) = Self::activity_type(action, quantity, amount) else {
+                warn!("Fidelity line {}: skipping action '{}'", line, action);
+                continue;
+            };
+
+            let raw_symbol = table.get(record, "Symbol").trim_start_matches('-');
+            let is_trade = matches!(
+                activity_type,
+                ACTIVITY_TYPE_BUY
+                    | ACTIVITY_TYPE_SELL
+                    | ACTIVITY_TYPE_ADD_HOLDING
+                    | ACTIVITY_TYPE_TRANSFER_IN
+                    | ACTIVITY_TYPE_TRANSFER_OUT
+            );
+            // Sweeps in and out of the core money-market fund are cash movements
+            if is_trade && Self::is_money_market(raw_symbol) {
+                continue;
+            }
+            let symbol = if Self::is_money_market(raw_symbol) {
+                cash_symbol(CURRENCY)
+            } else {
+                raw_symbol.to_string()
+            };
+
+            let mut activity = statement_activity(*line, date, activity_type, &symbol, CURRENCY);
+            activity.comment = Some(action.to_string());
+            activity.fee = parse_amount(table.get(record, "Commission ($)")).unwrap_or_default().abs()
+                + parse_amount(table.get(record, "Fees ($)")).unwrap_or_default().abs();
+            if is_trade {
+                activity.quantity = quantity.abs();
+                activity.unit_price = parse_amount(table.get(record, "Price ($)")).unwrap_or_default();
+            } else {
+                activity.amount = amount.map(|a| a.abs());
+            }
+            activities.push(activity);
+        }
+        Ok(activities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_fidelity_history() {
+        let content = "\n\nRun Date,Action,Symbol,Description,Type,Quantity,Price ($),Commission ($),Fees ($),Accrued Interest ($),Amount ($),Settlement Date\n\
+03/04/2024,\" YOU BOUGHT APPLE INC (AAPL) (Cash)\", AAPL,APPLE INC,Cash,5,175.10,,0.02,,-875.52,03/06/2024\n\
+03/04/2024,\" DIVIDEND RECEIVED APPLE INC (AAPL) (Cash)\", AAPL,APPLE INC,Cash,0.000,,,,,1.20,\n\
+03/05/2024,\" Electronic Funds Transfer Received (Cash)\", ,No Description,Cash,0.000,,,,,1000,\n\
+03/05/2024,\" REINVESTMENT FIDELITY GOVERNMENT MONEY MARKET (SPAXX) (Cash)\", SPAXX,FIDELITY GOVERNMENT MONEY MARKET,Cash,1.2,1,,,,-1.20,\n\
+\n\"The data and information in this spreadsheet is provided to you solely for your use.\"\n";
+        let parser = FidelityParser::new();
+        assert!(parser.can_parse(content));
+        let activities = parser.parse(content, "USD").unwrap();
+        assert_eq!(activities.len(), 3);
+
+        assert_eq!(activities[0].activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(activities[0].symbol, "AAPL");
+        assert_eq!(activities[0].quantity, dec!(5));
+        assert_eq!(activities[0].fee, dec!(0.02));
+        assert_eq!(activities[1].activity_type, ACTIVITY_TYPE_DIVIDEND);
+        assert_eq!(activities[1].amount, Some(dec!(1.20)));
+        assert_eq!(activities[2].activity_type, ACTIVITY_TYPE_DEPOSIT);
+        assert_eq!(activities[2].symbol, "$CASH-USD");
+    }
+}
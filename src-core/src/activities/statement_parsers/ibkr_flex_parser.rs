@@ -0,0 +1,540 @@
+use super::statement_parser::{
+    cash_symbol, isin_country, parse_amount, parse_date, statement_activity, StatementParser,
+};
+use crate::activities::activities_constants::*;
+use crate::activities::activities_errors::ActivityError;
+use crate::activities::activities_model::ActivityImport;
+use crate::constants::CASH_ASSET_PREFIX;
+use chrono::NaiveDate;
+use lazy_static::lazy_static;
+use log::warn;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::Regex;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+
+const DATE_FORMATS: [&str; 2] = ["%Y%m%d", "%Y-%m-%d"];
+
+lazy_static! {
+    static ref SPLIT_RATIO: Regex =
+        Regex::new(r"(?i)SPLIT\s+(\d+(?:\.\d+)?)\s+FOR\s+(\d+(?:\.\d+)?)").unwrap();
+    /// Withholding descriptions end with the withholding country: "... PER SHARE - US TAX"
+    static ref WITHHOLDING_COUNTRY: Regex = Regex::new(r"-\s*([A-Za-z]{2})\s+TAX\s*$").unwrap();
+    /// Corporate action descriptions open with the affected security: "OLD(US0000000000) ..."
+    static ref ACTION_SOURCE: Regex = Regex::new(r"^\s*([^\s(]+)\(").unwrap();
+}
+
+/// Interactive Brokers Flex Query XML reports.
+///
+/// Reads `Trade`, `CashTransaction` and `CorporateAction` elements. Summary
+/// rows (`levelOfDetail` other than execution/detail) are ignored so that
+/// reports configured with both levels are not double counted. Order rows are
+/// the detail of queries set up at order level, and are only ignored when the
+/// report also has the executions of that order.
+#[derive(Default)]
+pub struct IbkrFlexParser;
+
+impl IbkrFlexParser {
+    pub fn new() -> Self {
+        IbkrFlexParser
+    }
+
+    fn is_summary(attrs: &HashMap<String, String>, executed_orders: &HashSet<&str>) -> bool {
+        match attrs.get("levelOfDetail").map(|level| level.as_str()) {
+            None | Some("EXECUTION") | Some("DETAIL") => false,
+            Some("ORDER") => executed_orders.contains(Self::text(attrs, "ibOrderID")),
+            Some(_) => true,
+        }
+    }
+
+    fn date_of(attrs: &HashMap<String, String>, keys: &[&str]) -> Option<NaiveDate> {
+        keys.iter()
+            .filter_map(|key| attrs.get(*key))
+            .find_map(|value| parse_date(value, &DATE_FORMATS))
+    }
+
+    fn decimal(attrs: &HashMap<String, String>, key: &str) -> Decimal {
+        attrs
+            .get(key)
+            .and_then(|value| parse_amount(value))
+            .unwrap_or_default()
+    }
+
+    fn isin(attrs: &HashMap<String, String>) -> Option<String> {
+        attrs.get("isin").filter(|isin| !isin.is_empty()).cloned()
+    }
+
+    fn text<'a>(attrs: &'a HashMap<String, String>, key: &str) -> &'a str {
+        attrs.get(key).map(|value| value.as_str()).unwrap_or("")
+    }
+
+    fn parse_trade(line: usize, attrs: &HashMap<String, String>) -> Vec<ActivityImport> {
+        let Some(date) = Self::date_of(attrs, &["tradeDate", "dateTime"]) else {
+            warn!("IBKR Flex line {}: trade without a valid date", line);
+            return Vec::new();
+        };
+        let currency = Self::text(attrs, "currency");
+        let quantity = Self::decimal(attrs, "quantity");
+        let price = Self::decimal(attrs, "tradePrice");
+        let commission = Self::decimal(attrs, "ibCommission").abs();
+        let commission_currency = attrs
+            .get("ibCommissionCurrency")
+            .map(|c| c.as_str())
+            .filter(|c| !c.is_empty())
+            .unwrap_or(currency);
+
+        if Self::text(attrs, "assetCategory") == "CASH" {
+            return Self::parse_fx_trade(line, date, attrs, quantity, price, commission, commission_currency);
+        }
+
+        let is_sell = match Self::text(attrs, "buySell") {
+            "SELL" => true,
+            "BUY" => false,
+            _ => quantity.is_sign_negative(),
+        };
+        let activity_type = if is_sell { ACTIVITY_TYPE_SELL } else { ACTIVITY_TYPE_BUY };
+        let mut activity = statement_activity(line, date, activity_type, Self::text(attrs, "symbol"), currency);
+        activity.quantity = quantity.abs();
+        activity.unit_price = price;
+        activity.symbol_name = attrs.get("description").cloned();
+        activity.isin = Self::isin(attrs);
+        if commission_currency == currency {
+            activity.fee = commission;
+            vec![activity]
+        } else {
+            let mut fee = statement_activity(line, date, ACTIVITY_TYPE_FEE, &cash_symbol(commission_currency), commission_currency);
+            fee.amount = Some(commission);
+            fee.comment = Some(format!("Commission for {}", activity.symbol));
+            vec![activity, fee]
+        }
+    }
+
+    /// FX trades are reported with symbol `BASE.QUOTE`, quantity in the base
+    /// currency and price in quote per base. They become a cash transfer out of
+    /// the sold currency and into the bought one.
+    fn parse_fx_trade(
+        line: usize,
+        date: NaiveDate,
+        attrs: &HashMap<String, String>,
+        quantity: Decimal,
+        price: Decimal,
+        commission: Decimal,
+        commission_currency: &str,
+    ) -> Vec<ActivityImport> {
+        let symbol = Self::text(attrs, "symbol");
+        let Some((base, quote)) = symbol.split_once('.') else {
+            warn!("IBKR Flex line {}: unrecognized FX pair '{}'", line, symbol);
+            return Vec::new();
+        };
+        let base_amount = quantity.abs();
+        let quote_amount = (quantity * price).abs().round_dp(2);
+        let (bought, bought_amount, sold, sold_amount) = if quantity.is_sign_negative() {
+            (quote, quote_amount, base, base_amount)
+        } else {
+            (base, base_amount, quote, quote_amount)
+        };
+        let comment = format!("FX conversion {} at {}", symbol, price);
+
+        let mut transfer_out = statement_activity(line, date, ACTIVITY_TYPE_TRANSFER_OUT, &cash_symbol(sold), sold);
+        transfer_out.amount = Some(sold_amount);
+        transfer_out.comment = Some(comment.clone());
+        let mut transfer_in = statement_activity(line, date, ACTIVITY_TYPE_TRANSFER_IN, &cash_symbol(bought), bought);
+        transfer_in.amount = Some(bought_amount);
+        transfer_in.comment = Some(comment);
+
+        let mut activities = vec![transfer_out, transfer_in];
+        if !commission.is_zero() {
+            let mut fee = statement_activity(line, date, ACTIVITY_TYPE_FEE, &cash_symbol(commission_currency), commission_currency);
+            fee.amount = Some(commission);
+            fee.comment = Some(format!("Commission for FX conversion {}", symbol));
+            activities.push(fee);
+        }
+        activities
+    }
+
+    fn parse_cash_transaction(line: usize, attrs: &HashMap<String, String>) -> Option<ActivityImport> {
+        let Some(date) = Self::date_of(attrs, &["dateTime", "settleDate", "reportDate"]) else {
+            warn!("IBKR Flex line {}: cash transaction without a valid date", line);
+            return None;
+        };
+        let currency = Self::text(attrs, "currency");
+        let amount = Self::decimal(attrs, "amount");
+        let symbol = Self::text(attrs, "symbol");
+        let security_or_cash = if symbol.is_empty() { cash_symbol(currency) } else { symbol.to_string() };
+
+        let (activity_type, symbol) = match Self::text(attrs, "type") {
+            "Dividends" | "Payment In Lieu Of Dividends" => (ACTIVITY_TYPE_DIVIDEND, security_or_cash),
+            // Withholding left over by `attach_withholding`. Refunds of tax withheld
+            // earlier arrive as positive amounts and are booked below as income that
+            // lowers the withholding, with no gross income of their own.
+            "Withholding Tax" if amount.is_sign_positive() => (ACTIVITY_TYPE_DIVIDEND, security_or_cash),
+            "Withholding Tax" => (ACTIVITY_TYPE_TAX, security_or_cash),
+            "Deposits/Withdrawals" | "Deposits & Withdrawals" if amount.is_sign_negative() => {
+                (ACTIVITY_TYPE_WITHDRAWAL, cash_symbol(currency))
+            }
+            "Deposits/Withdrawals" | "Deposits & Withdrawals" => (ACTIVITY_TYPE_DEPOSIT, cash_symbol(currency)),
+            "Broker Interest Received" | "Bond Interest Received" => (ACTIVITY_TYPE_INTEREST, security_or_cash),
+            "Broker Interest Paid" | "Bond Interest Paid" | "Other Fees" | "Commission Adjustments"
+            | "Advisor Fees" | "Broker Fees"
+                if amount.is_sign_negative() =>
+            {
+                (ACTIVITY_TYPE_FEE, cash_symbol(currency))
+            }
+            "Other Fees" | "Commission Adjustments" | "Advisor Fees" | "Broker Fees" => {
+                (ACTIVITY_TYPE_INTEREST, cash_symbol(currency))
+            }
+            other => {
+                warn!("IBKR Flex line {}: skipping cash transaction type '{}'", line, other);
+                return None;
+            }
+        };
+
+        let mut activity = statement_activity(line, date, activity_type, &symbol, currency);
+        activity.amount = Some(amount.abs());
+        activity.comment = attrs.get("description").cloned();
+        if !symbol.is_empty() && !symbol.starts_with(CASH_ASSET_PREFIX) {
+            activity.isin = Self::isin(attrs);
+        }
+        if activity_type == ACTIVITY_TYPE_DIVIDEND && Self::text(attrs, "type") == "Withholding Tax" {
+            activity.withholding_tax = Some(-amount);
+            activity.withholding_country = Self::withholding_country(attrs);
+        }
+        Some(activity)
+    }
+
+    /// Country named in a withholding row's description, or the issuer country of its ISIN.
+    fn withholding_country(attrs: &HashMap<String, String>) -> Option<String> {
+        WITHHOLDING_COUNTRY
+            .captures(Self::text(attrs, "description"))
+            .map(|captures| captures[1].to_uppercase())
+            .or_else(|| Self::isin(attrs).as_deref().and_then(isin_country))
+    }
+
+    /// Records "Withholding Tax" rows on the dividend of the same security, date and
+    /// currency, whose amount becomes what was received after tax. Withholding with
+    /// no dividend in the report, such as adjustments of earlier payments, is
+    /// returned as separate rows.
+    fn attach_withholding(
+        activities: &mut [ActivityImport],
+        withholdings: Vec<(usize, HashMap<String, String>)>,
+    ) -> Vec<ActivityImport> {
+        let mut unmatched = Vec::new();
+        for (line, attrs) in withholdings {
+            let date = Self::date_of(&attrs, &["dateTime", "settleDate", "reportDate"]);
+            let dividend = date.and_then(|date| {
+                let date = date.format("%Y-%m-%d").to_string();
+                activities.iter_mut().find(|a| {
+                    a.activity_type == ACTIVITY_TYPE_DIVIDEND
+                        && a.date == date
+                        && a.symbol.eq_ignore_ascii_case(Self::text(&attrs, "symbol").trim())
+                        && a.currency.eq_ignore_ascii_case(Self::text(&attrs, "currency").trim())
+                })
+            });
+            let Some(dividend) = dividend else {
+                unmatched.extend(Self::parse_cash_transaction(line, &attrs));
+                continue;
+            };
+            // Refunds of over-withheld tax are positive and lower the withholding.
+            let withheld = -Self::decimal(&attrs, "amount");
+            dividend.withholding_tax = Some(dividend.withholding_tax.unwrap_or_default() + withheld);
+            dividend.amount = dividend.amount.map(|amount| amount - withheld);
+            if dividend.withholding_country.is_none() {
+                dividend.withholding_country = Self::withholding_country(&attrs)
+                    .or_else(|| dividend.isin.as_deref().and_then(isin_country));
+            }
+        }
+        unmatched
+    }
+
+    /// Splits carry their ratio in the description ("SPLIT 4 FOR 1"). Issue changes,
+    /// acquisitions and spin-offs are booked on the leg that adds the new security, with
+    /// the security named at the start of the description as the source; the leg removing
+    /// it is covered by the corporate action. Spin-offs leave the cost basis allocation
+    /// for the user to fill in. Other actions are returned as rows carrying an error.
+    fn parse_corporate_action(line: usize, attrs: &HashMap<String, String>) -> Option<ActivityImport> {
+        let Some(date) = Self::date_of(attrs, &["reportDate", "dateTime"]) else {
+            warn!("IBKR Flex line {}: corporate action without a valid date", line);
+            return None;
+        };
+        let currency = Self::text(attrs, "currency");
+        let symbol = Self::text(attrs, "symbol");
+        let description = Self::text(attrs, "description");
+        let action_type = Self::text(attrs, "type");
+        let quantity = Self::decimal(attrs, "quantity");
+
+        if matches!(action_type, "FS" | "RS") {
+            let captures = SPLIT_RATIO.captures(description)?;
+            let new_shares = parse_amount(&captures[1])?;
+            let old_shares = parse_amount(&captures[2]).filter(|d| !d.is_zero())?;
+            // The report has one row per leg; only the row for the original
+            // security (removed shares) carries the split.
+            if quantity > Decimal::ZERO {
+                return None;
+            }
+            let mut activity = statement_activity(line, date, ACTIVITY_TYPE_SPLIT, symbol, currency);
+            activity.amount = Some(new_shares / old_shares);
+            activity.comment = Some(description.to_string());
+            return Some(activity);
+        }
+
+        if quantity.is_zero() {
+            return None;
+        }
+        let activity_type = match action_type {
+            "IC" => Some(ACTIVITY_TYPE_TICKER_CHANGE),
+            "TC" => Some(ACTIVITY_TYPE_MERGER),
+            "SO" => Some(ACTIVITY_TYPE_SPIN_OFF),
+            _ => None,
+        };
+        let source = ACTION_SOURCE
+            .captures(description)
+            .map(|captures| captures[1].trim().to_uppercase())
+            .filter(|source| !source.eq_ignore_ascii_case(symbol));
+
+        let mut activity = match (activity_type, source) {
+            // Leg removing the source security, moved by the action on the other leg
+            (Some(_), _) if quantity.is_sign_negative() => return None,
+            (Some(activity_type), Some(source)) => {
+                let mut activity = statement_activity(line, date, activity_type, symbol, currency);
+                activity.source_asset_id = Some(source);
+                activity
+            }
+            _ => {
+                let mut activity = statement_activity(line, date, action_type, symbol, currency);
+                activity.errors = Some(HashMap::from([(
+                    activity.symbol.clone(),
+                    vec![format!(
+                        "Unsupported Interactive Brokers corporate action '{}': {}",
+                        action_type, description
+                    )],
+                )]));
+                activity
+            }
+        };
+        activity.quantity = quantity.abs();
+        activity.comment = Some(description.to_string());
+        activity.isin = Self::isin(attrs);
+        Some(activity)
+    }
+
+    fn attributes(element: &BytesStart) -> Result<HashMap<String, String>, ActivityError> {
+        element
+            .attributes()
+            .map(|attr| {
+                let attr = attr.map_err(|e| ActivityError::ParseError(format!("Invalid Flex attribute: {}", e)))?;
+                let key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+                let value = attr
+                    .unescape_value()
+                    .map_err(|e| ActivityError::ParseError(format!("Invalid Flex attribute: {}", e)))?
+                    .into_owned();
+                Ok((key, value))
+            })
+            .collect()
+    }
+}
+
+impl StatementParser for IbkrFlexParser {
+    fn id(&self) -> &'static str {
+        STATEMENT_PARSER_IBKR_FLEX
+    }
+
+    fn name(&self) -> &'static str {
+        "Interactive Brokers (Flex Query XML)"
+    }
+
+    fn can_parse(&self, content: &str) -> bool {
+        let head: String = content.chars().take(1024).collect();
+        head.contains("<FlexQueryResponse") || head.contains("<FlexStatements")
+    }
+
+    fn parse(&self, content: &str, _default_currency: &str) -> Result<Vec<ActivityImport>, ActivityError> {
+        let mut reader = Reader::from_str(content);
+        reader.config_mut().trim_text(true);
+
+        let mut elements = Vec::new();
+        loop {
+            let event = reader
+                .read_event()
+                .map_err(|e| ActivityError::ParseError(format!("Invalid Flex XML: {}", e)))?;
+            let element = match event {
+                Event::Start(element) | Event::Empty(element) => element,
+                Event::Eof => break,
+                _ => continue,
+            };
+            let name = element.name();
+            if !matches!(name.as_ref(), b"Trade" | b"CashTransaction" | b"CorporateAction") {
+                continue;
+            }
+            // Elements are single-line, so the reader position after the
+            // element identifies its line.
+            let position = (reader.buffer_position() as usize).min(content.len());
+            let line = content[..position].matches('\n').count() + 1;
+            elements.push((name.as_ref().to_vec(), line, Self::attributes(&element)?));
+        }
+
+        let executed_orders: HashSet<&str> = elements
+            .iter()
+            .filter(|(_, _, attrs)| Self::text(attrs, "levelOfDetail") == "EXECUTION")
+            .map(|(_, _, attrs)| Self::text(attrs, "ibOrderID"))
+            .collect();
+        let mut activities = Vec::new();
+        let mut withholdings = Vec::new();
+        for (name, line, attrs) in &elements {
+            if Self::is_summary(attrs, &executed_orders) {
+                continue;
+            }
+            match name.as_slice() {
+                b"Trade" => activities.extend(Self::parse_trade(*line, attrs)),
+                b"CashTransaction" if Self::text(attrs, "type") == "Withholding Tax" => {
+                    withholdings.push((*line, attrs.clone()))
+                }
+                b"CashTransaction" => activities.extend(Self::parse_cash_transaction(*line, attrs)),
+                _ => activities.extend(Self::parse_corporate_action(*line, attrs)),
+            }
+        }
+        let unmatched = Self::attach_withholding(&mut activities, withholdings);
+        activities.extend(unmatched);
+        Ok(activities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::activities::activities_withholding::validate_withholding;
+    use rust_decimal_macros::dec;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<FlexQueryResponse queryName="Activity" type="AF">
+<FlexStatements count="1">
+<FlexStatement accountId="U1234567" fromDate="20240101" toDate="20241231">
+<Trades>
+<Trade assetCategory="STK" symbol="AAPL" description="APPLE INC" currency="USD" tradeDate="20240115" quantity="10" tradePrice="185.5" ibCommission="-1" ibCommissionCurrency="USD" buySell="BUY" levelOfDetail="EXECUTION" />
+<Trade assetCategory="STK" symbol="AAPL" currency="USD" tradeDate="20240115" quantity="10" tradePrice="185.5" buySell="BUY" levelOfDetail="SYMBOL_SUMMARY" />
+<Trade assetCategory="CASH" symbol="EUR.USD" currency="USD" tradeDate="20240116" quantity="-1000" tradePrice="1.09" ibCommission="-2" ibCommissionCurrency="USD" buySell="SELL" levelOfDetail="EXECUTION" />
+</Trades>
+<CashTransactions>
+<CashTransaction type="Dividends" symbol="AAPL" currency="USD" amount="2.4" dateTime="20240215;202000" description="AAPL CASH DIVIDEND USD 0.24 PER SHARE" levelOfDetail="DETAIL" />
+<CashTransaction type="Withholding Tax" symbol="AAPL" currency="USD" amount="-0.36" dateTime="20240215" description="AAPL(US0378331005) CASH DIVIDEND USD 0.24 PER SHARE - US TAX" levelOfDetail="DETAIL" />
+<CashTransaction type="Withholding Tax" symbol="MSFT" currency="USD" amount="-0.10" dateTime="20240220" levelOfDetail="DETAIL" />
+<CashTransaction type="Withholding Tax" symbol="NESN" currency="CHF" amount="1.50" dateTime="20240225" isin="CH0038863350" description="NESN(CH0038863350) CASH DIVIDEND CHF 3.00 PER SHARE - CH TAX" levelOfDetail="DETAIL" />
+<CashTransaction type="Deposits/Withdrawals" currency="USD" amount="5000" dateTime="20240102" levelOfDetail="DETAIL" />
+</CashTransactions>
+<CorporateActions>
+<CorporateAction type="FS" symbol="NVDA" currency="USD" reportDate="20240610" quantity="-10" description="NVDA(US67066G1040) SPLIT 10 FOR 1 (NVDA, NVIDIA CORP, US67066G1040)" />
+<CorporateAction type="FS" symbol="NVDA" currency="USD" reportDate="20240610" quantity="100" description="NVDA(US67066G1040) SPLIT 10 FOR 1 (NVDA, NVIDIA CORP, US67066G1040)" />
+</CorporateActions>
+</FlexStatement>
+</FlexStatements>
+</FlexQueryResponse>"#;
+
+    #[test]
+    fn test_parse_flex_statement() {
+        let parser = IbkrFlexParser::new();
+        assert!(parser.can_parse(SAMPLE));
+        let activities = parser.parse(SAMPLE, "USD").unwrap();
+        let types: Vec<&str> = activities.iter().map(|a| a.activity_type.as_str()).collect();
+        assert_eq!(
+            types,
+            vec![
+                ACTIVITY_TYPE_BUY,
+                ACTIVITY_TYPE_TRANSFER_OUT,
+                ACTIVITY_TYPE_TRANSFER_IN,
+                ACTIVITY_TYPE_FEE,
+                ACTIVITY_TYPE_DIVIDEND,
+                ACTIVITY_TYPE_DEPOSIT,
+                ACTIVITY_TYPE_SPLIT,
+                ACTIVITY_TYPE_TAX,
+                ACTIVITY_TYPE_DIVIDEND,
+            ]
+        );
+
+        let buy = &activities[0];
+        assert_eq!(buy.date, "2024-01-15");
+        assert_eq!(buy.quantity, dec!(10));
+        assert_eq!(buy.unit_price, dec!(185.5));
+        assert_eq!(buy.fee, dec!(1));
+        assert_eq!(buy.line_number, Some(6));
+
+        // Selling EUR for USD
+        assert_eq!(activities[1].symbol, "$CASH-EUR");
+        assert_eq!(activities[1].amount, Some(dec!(1000)));
+        assert_eq!(activities[2].symbol, "$CASH-USD");
+        assert_eq!(activities[2].amount, Some(dec!(1090.00)));
+
+        // Withholding is recorded on its dividend, which keeps the net amount
+        let dividend = &activities[4];
+        assert_eq!(dividend.amount, Some(dec!(2.04)));
+        assert_eq!(dividend.withholding_tax, Some(dec!(0.36)));
+        assert_eq!(dividend.withholding_country.as_deref(), Some("US"));
+        assert_eq!(activities[5].symbol, "$CASH-USD");
+        assert_eq!(activities[6].amount, Some(dec!(10)));
+        // No MSFT dividend in the report
+        assert_eq!(activities[7].symbol, "MSFT");
+        assert_eq!(activities[7].amount, Some(dec!(0.10)));
+        // A refund of earlier withholding is cash in that lowers the withholding, not new income
+        let refund = &activities[8];
+        assert_eq!(refund.amount, Some(dec!(1.50)));
+        assert_eq!(refund.withholding_tax, Some(dec!(-1.50)));
+        assert_eq!(refund.withholding_country.as_deref(), Some("CH"));
+        assert_eq!(
+            validate_withholding(&refund.activity_type, refund.amount, refund.withholding_tax, Some("CH")).unwrap().0,
+            Some(dec!(-1.50))
+        );
+    }
+
+    #[test]
+    fn test_parse_corporate_actions_move_lots_from_source() {
+        let content = r#"<FlexQueryResponse><FlexStatements><FlexStatement><CorporateActions>
+<CorporateAction type="IC" symbol="FB" currency="USD" reportDate="20220609" quantity="-20" description="FB(US30303M1027) CUSIP/ISIN CHANGE TO (US30303M1027) (META, META PLATFORMS INC-CLASS A, US30303M1027)" />
+<CorporateAction type="IC" symbol="META" currency="USD" reportDate="20220609" quantity="20" isin="US30303M1027" description="FB(US30303M1027) CUSIP/ISIN CHANGE TO (US30303M1027) (META, META PLATFORMS INC-CLASS A, US30303M1027)" />
+<CorporateAction type="TC" symbol="ATVI" currency="USD" reportDate="20231013" quantity="-15" description="ATVI(US00507V1098) MERGED(ACQUISITION) WITH US5949181045 1 FOR 1 (MSFT, MICROSOFT CORP, US5949181045)" />
+<CorporateAction type="TC" symbol="MSFT" currency="USD" reportDate="20231013" quantity="3" description="ATVI(US00507V1098) MERGED(ACQUISITION) WITH US5949181045 1 FOR 1 (MSFT, MICROSOFT CORP, US5949181045)" />
+<CorporateAction type="SO" symbol="GEHC" currency="USD" reportDate="20230104" quantity="4" description="GE(US3696043013) SPINOFF 1 FOR 3 (GEHC, GE HEALTHCARE TECHNOLOGIES INC, US36266G1076)" />
+<CorporateAction type="DW" symbol="XYZ.RTS" currency="USD" reportDate="20230301" quantity="100" description="XYZ(US0000000001) DIVIDEND RIGHTS ISSUE (XYZ.RTS, XYZ RIGHTS, US0000000002)" />
+</CorporateActions></FlexStatement></FlexStatements></FlexQueryResponse>"#;
+
+        let activities = IbkrFlexParser::new().parse(content, "USD").unwrap();
+        let summary: Vec<(&str, &str, Option<&str>)> = activities
+            .iter()
+            .map(|a| (a.activity_type.as_str(), a.symbol.as_str(), a.source_asset_id.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ACTIVITY_TYPE_TICKER_CHANGE, "META", Some("FB")),
+                (ACTIVITY_TYPE_MERGER, "MSFT", Some("ATVI")),
+                (ACTIVITY_TYPE_SPIN_OFF, "GEHC", Some("GE")),
+                ("DW", "XYZ.RTS", None),
+            ]
+        );
+        assert_eq!(activities[0].quantity, dec!(20));
+        assert_eq!(activities[0].isin.as_deref(), Some("US30303M1027"));
+        assert_eq!(activities[1].quantity, dec!(3));
+        assert!(activities[..3].iter().all(|a| a.errors.is_none()));
+        assert!(activities[3].errors.as_ref().is_some_and(|e| e.contains_key("XYZ.RTS")));
+    }
+
+    #[test]
+    fn test_parse_order_level_trades() {
+        let order_only = r#"<FlexQueryResponse><FlexStatements><FlexStatement><Trades>
+<Trade assetCategory="STK" symbol="MSFT" currency="USD" tradeDate="20240301" quantity="5" tradePrice="410" ibCommission="-1" buySell="BUY" ibOrderID="111" levelOfDetail="ORDER" />
+<Trade assetCategory="STK" symbol="MSFT" currency="USD" tradeDate="20240301" quantity="5" tradePrice="410" buySell="BUY" levelOfDetail="SYMBOL_SUMMARY" />
+</Trades></FlexStatement></FlexStatements></FlexQueryResponse>"#;
+        let activities = IbkrFlexParser::new().parse(order_only, "USD").unwrap();
+        assert_eq!(activities.len(), 1);
+        assert_eq!(activities[0].activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(activities[0].quantity, dec!(5));
+
+        // With executions present the order row would count the trade twice
+        let both_levels = r#"<FlexQueryResponse><FlexStatements><FlexStatement><Trades>
+<Trade assetCategory="STK" symbol="MSFT" currency="USD" tradeDate="20240301" quantity="5" tradePrice="410" buySell="BUY" ibOrderID="111" levelOfDetail="ORDER" />
+<Trade assetCategory="STK" symbol="MSFT" currency="USD" tradeDate="20240301" quantity="2" tradePrice="410" buySell="BUY" ibOrderID="111" levelOfDetail="EXECUTION" />
+<Trade assetCategory="STK" symbol="MSFT" currency="USD" tradeDate="20240301" quantity="3" tradePrice="410" buySell="BUY" ibOrderID="111" levelOfDetail="EXECUTION" />
+</Trades></FlexStatement></FlexStatements></FlexQueryResponse>"#;
+        let activities = IbkrFlexParser::new().parse(both_levels, "USD").unwrap();
+        let quantities: Vec<Decimal> = activities.iter().map(|a| a.quantity).collect();
+        assert_eq!(quantities, vec![dec!(2), dec!(3)]);
+    }
+}
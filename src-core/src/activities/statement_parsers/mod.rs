@@ -0,0 +1,57 @@
+pub mod degiro_parser;
+pub mod fidelity_parser;
+pub mod ibkr_flex_parser;
+pub mod ofx_parser;
+pub mod qif_parser;
+pub mod questrade_parser;
+pub mod schwab_parser;
+pub mod statement_parser;
+
+use crate::activities::activities_constants::{
+    STATEMENT_PARSER_DEGIRO, STATEMENT_PARSER_FIDELITY, STATEMENT_PARSER_IBKR_FLEX,
+    STATEMENT_PARSER_OFX, STATEMENT_PARSER_QIF, STATEMENT_PARSER_QUESTRADE, STATEMENT_PARSER_SCHWAB,
+};
+use std::sync::Arc;
+
+pub use degiro_parser::DegiroParser;
+pub use fidelity_parser::FidelityParser;
+pub use ibkr_flex_parser::IbkrFlexParser;
+pub use ofx_parser::OfxParser;
+pub use qif_parser::QifParser;
+pub use questrade_parser::QuestradeParser;
+pub use schwab_parser::SchwabParser;
+pub use statement_parser::{StatementParser, StatementParserInfo};
+
+/// Returns all built-in statement parsers, in detection order.
+pub fn get_statement_parsers() -> Vec<Arc<dyn StatementParser>> {
+    vec![
+        Arc::new(IbkrFlexParser::new()),
+        Arc::new(SchwabParser::new()),
+        Arc::new(FidelityParser::new()),
+        Arc::new(QuestradeParser::new()),
+        Arc::new(DegiroParser::new()),
+        Arc::new(OfxParser::new()),
+        Arc::new(QifParser::new()),
+    ]
+}
+
+/// Returns the statement parser registered under `parser_id`.
+pub fn get_statement_parser(parser_id: &str) -> Option<Arc<dyn StatementParser>> {
+    match parser_id.to_uppercase().as_str() {
+        STATEMENT_PARSER_IBKR_FLEX => Some(Arc::new(IbkrFlexParser::new())),
+        STATEMENT_PARSER_SCHWAB => Some(Arc::new(SchwabParser::new())),
+        STATEMENT_PARSER_FIDELITY => Some(Arc::new(FidelityParser::new())),
+        STATEMENT_PARSER_QUESTRADE => Some(Arc::new(QuestradeParser::new())),
+        STATEMENT_PARSER_DEGIRO => Some(Arc::new(DegiroParser::new())),
+        STATEMENT_PARSER_OFX => Some(Arc::new(OfxParser::new())),
+        STATEMENT_PARSER_QIF => Some(Arc::new(QifParser::new())),
+        _ => None,
+    }
+}
+
+/// Returns the first parser that recognizes `content`.
+pub fn detect_statement_parser(content: &str) -> Option<Arc<dyn StatementParser>> {
+    get_statement_parsers()
+        .into_iter()
+        .find(|parser| parser.can_parse(content))
+}
@@ -0,0 +1,489 @@
+use super::statement_parser::{cash_symbol, isin_country, parse_amount, statement_activity, StatementParser};
+use crate::activities::activities_constants::*;
+use crate::activities::activities_errors::ActivityError;
+use crate::activities::activities_model::ActivityImport;
+use chrono::NaiveDate;
+use lazy_static::lazy_static;
+use log::warn;
+use regex::Regex;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref OFX_TAG: Regex = Regex::new(r"<(/?)([A-Za-z0-9_.]+)>([^<]*)").unwrap();
+}
+
+/// An OFX aggregate or element. Elements carry a value and no children.
+#[derive(Debug, Default)]
+struct OfxNode {
+    name: String,
+    value: Option<String>,
+    line: usize,
+    children: Vec<OfxNode>,
+}
+
+impl OfxNode {
+    fn child(&self, name: &str) -> Option<&OfxNode> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// Value of the element at `path` below this node.
+    fn value_at(&self, path: &[&str]) -> Option<&str> {
+        let mut node = self;
+        for name in path {
+            node = node.child(name)?;
+        }
+        node.value.as_deref()
+    }
+
+    fn decimal_at(&self, path: &[&str]) -> Decimal {
+        self.value_at(path).and_then(parse_amount).unwrap_or_default()
+    }
+
+    /// All nodes named `name` in this subtree, in document order.
+    fn descendants<'a>(&'a self, name: &str, found: &mut Vec<&'a OfxNode>) {
+        for child in &self.children {
+            if child.name == name {
+                found.push(child);
+            } else {
+                child.descendants(name, found);
+            }
+        }
+    }
+}
+
+/// Builds the aggregate tree of an OFX body. OFX 1.x (SGML) leaves elements
+/// unclosed, so a tag followed by text is treated as an element and its
+/// optional closing tag (OFX 2.x XML) is skipped.
+fn parse_tree(body: &str, first_line: usize) -> OfxNode {
+    let mut stack = vec![OfxNode::default()];
+    let mut line = first_line;
+    let mut last_end = 0;
+
+    for captures in OFX_TAG.captures_iter(body) {
+        let whole = captures.get(0).unwrap();
+        line += body[last_end..whole.start()].matches('\n').count();
+        last_end = whole.start();
+
+        let closing = !captures[1].is_empty();
+        let name = captures[2].to_uppercase();
+        let text = captures[3].trim();
+
+        if closing {
+            // Pop to the matching aggregate; closing tags of elements and
+            // stray tags without an open aggregate are ignored.
+            if let Some(depth) = stack.iter().rposition(|node| node.name == name) {
+                if depth == 0 {
+                    continue;
+                }
+                while stack.len() > depth {
+                    let node = stack.pop().unwrap();
+                    stack.last_mut().unwrap().children.push(node);
+                }
+            }
+        } else if !text.is_empty() {
+            stack.last_mut().unwrap().children.push(OfxNode {
+                name,
+                value: Some(decode_entities(text)),
+                line,
+                children: Vec::new(),
+            });
+        } else {
+            stack.push(OfxNode {
+                name,
+                value: None,
+                line,
+                children: Vec::new(),
+            });
+        }
+    }
+
+    while stack.len() > 1 {
+        let node = stack.pop().unwrap();
+        stack.last_mut().unwrap().children.push(node);
+    }
+    stack.pop().unwrap()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// OFX dates are `YYYYMMDD[HHMMSS[.XXX]][[gmt offset:tz]]`.
+fn parse_ofx_date(value: &str) -> Option<NaiveDate> {
+    let digits = value.trim().get(..8)?;
+    NaiveDate::parse_from_str(digits, "%Y%m%d").ok()
+}
+
+/// A security from the statement's SECLIST.
+struct OfxSecurity {
+    ticker: Option<String>,
+    name: Option<String>,
+    isin: Option<String>,
+}
+
+/// OFX/QFX investment statements, both OFX 1.x (SGML) and 2.x (XML).
+///
+/// Maps INVTRANLIST records (buys, sells, income, reinvestments, transfers,
+/// splits, expenses and banking transactions) onto import rows. Securities
+/// are resolved through SECLIST to their ticker; when no ticker is published
+/// the CUSIP or ISIN is used as the symbol.
+#[derive(Default)]
+pub struct OfxParser;
+
+impl OfxParser {
+    pub fn new() -> Self {
+        OfxParser
+    }
+
+    fn securities(root: &OfxNode) -> HashMap<String, OfxSecurity> {
+        let mut infos = Vec::new();
+        root.descendants("SECINFO", &mut infos);
+        infos
+            .into_iter()
+            .filter_map(|info| {
+                let id = info.value_at(&["SECID", "UNIQUEID"])?.to_string();
+                let is_isin = info
+                    .value_at(&["SECID", "UNIQUEIDTYPE"])
+                    .is_some_and(|t| t.eq_ignore_ascii_case("ISIN"));
+                let security = OfxSecurity {
+                    ticker: info.value_at(&["TICKER"]).map(|t| t.to_string()),
+                    name: info.value_at(&["SECNAME"]).map(|n| n.to_string()),
+                    isin: is_isin.then(|| id.clone()),
+                };
+                Some((id, security))
+            })
+            .collect()
+    }
+
+    fn parse_statement(
+        statement: &OfxNode,
+        securities: &HashMap<String, OfxSecurity>,
+        account_currency: &str,
+    ) -> Vec<ActivityImport> {
+        let default_currency = statement.value_at(&["CURDEF"]).unwrap_or(account_currency);
+        let Some(transactions) = statement.child("INVTRANLIST") else {
+            return Vec::new();
+        };
+
+        let mut activities = Vec::new();
+        for record in &transactions.children {
+            if record.value.is_some() {
+                // DTSTART / DTEND
+                continue;
+            }
+            let parsed = OfxRecord {
+                node: record,
+                securities,
+                default_currency,
+            }
+            .into_activities();
+            if parsed.is_empty() {
+                warn!("OFX line {}: skipping {} record", record.line, record.name);
+            }
+            activities.extend(parsed);
+        }
+        activities
+    }
+}
+
+/// One INVTRANLIST record together with the statement context it needs.
+struct OfxRecord<'a> {
+    node: &'a OfxNode,
+    securities: &'a HashMap<String, OfxSecurity>,
+    default_currency: &'a str,
+}
+
+impl OfxRecord<'_> {
+    /// The aggregate holding INVTRAN, SECID and amounts: INVBUY/INVSELL for
+    /// trades, the record itself otherwise.
+    fn body(&self) -> &OfxNode {
+        self.node
+            .child("INVBUY")
+            .or_else(|| self.node.child("INVSELL"))
+            .unwrap_or(self.node)
+    }
+
+    fn date(&self) -> Option<NaiveDate> {
+        let body = self.body();
+        body.value_at(&["INVTRAN", "DTTRADE"])
+            .or_else(|| body.value_at(&["STMTTRN", "DTPOSTED"]))
+            .and_then(parse_ofx_date)
+    }
+
+    fn currency(&self) -> &str {
+        let body = self.body();
+        body.value_at(&["CURRENCY", "CURSYM"])
+            .or_else(|| body.value_at(&["ORIGCURRENCY", "CURSYM"]))
+            .or_else(|| body.value_at(&["STMTTRN", "CURRENCY", "CURSYM"]))
+            .unwrap_or(self.default_currency)
+    }
+
+    fn memo(&self) -> Option<String> {
+        let body = self.body();
+        body.value_at(&["INVTRAN", "MEMO"])
+            .or_else(|| body.value_at(&["STMTTRN", "MEMO"]))
+            .or_else(|| body.value_at(&["STMTTRN", "NAME"]))
+            .map(|memo| memo.to_string())
+    }
+
+    fn activity(&self, date: NaiveDate, activity_type: &str) -> ActivityImport {
+        let currency = self.currency();
+        let body = self.body();
+        let security = body
+            .value_at(&["SECID", "UNIQUEID"])
+            .map(|id| (id, self.securities.get(id)));
+
+        let mut activity = match security {
+            Some((id, info)) => {
+                let symbol = info.and_then(|s| s.ticker.as_deref()).unwrap_or(id);
+                let mut activity = statement_activity(self.node.line, date, activity_type, symbol, currency);
+                activity.symbol_name = info.and_then(|s| s.name.clone());
+                activity.isin = info.and_then(|s| s.isin.clone());
+                activity
+            }
+            None => statement_activity(self.node.line, date, activity_type, &cash_symbol(currency), currency),
+        };
+        activity.comment = self.memo();
+        activity
+    }
+
+    fn into_activities(self) -> Vec<ActivityImport> {
+        let Some(date) = self.date() else {
+            return Vec::new();
+        };
+        let body = self.body();
+        let fees = body.decimal_at(&["COMMISSION"]).abs()
+            + body.decimal_at(&["FEES"]).abs()
+            + body.decimal_at(&["TAXES"]).abs();
+        let total = body.decimal_at(&["TOTAL"]);
+
+        match self.node.name.as_str() {
+            name if name.starts_with("BUY") || name.starts_with("SELL") => {
+                let activity_type = if name.starts_with("BUY") {
+                    ACTIVITY_TYPE_BUY
+                } else {
+                    ACTIVITY_TYPE_SELL
+                };
+                let mut activity = self.activity(date, activity_type);
+                activity.quantity = body.decimal_at(&["UNITS"]).abs();
+                activity.unit_price = body.decimal_at(&["UNITPRICE"]);
+                activity.fee = fees;
+                vec![activity]
+            }
+            "INCOME" => {
+                let activity_type = match body.value_at(&["INCOMETYPE"]) {
+                    Some("INTEREST") => ACTIVITY_TYPE_INTEREST,
+                    _ => ACTIVITY_TYPE_DIVIDEND,
+                };
+                // TOTAL is the gross income; the row records what was received after withholding.
+                let mut activity = self.activity(date, activity_type);
+                let withholding = body.decimal_at(&["WITHHOLDING"]).abs();
+                activity.amount = Some(total.abs() - withholding);
+                if !withholding.is_zero() {
+                    activity.withholding_tax = Some(withholding);
+                    activity.withholding_country = activity.isin.as_deref().and_then(isin_country);
+                }
+                vec![activity]
+            }
+            "REINVEST" => {
+                // Reinvested dividends are one activity; reinvested interest stays income plus a buy
+                let is_interest = body.value_at(&["INCOMETYPE"]) == Some("INTEREST");
+                let mut reinvest = self.activity(
+                    date,
+                    if is_interest { ACTIVITY_TYPE_BUY } else { ACTIVITY_TYPE_DIVIDEND_REINVESTMENT },
+                );
+                reinvest.quantity = body.decimal_at(&["UNITS"]).abs();
+                reinvest.unit_price = body.decimal_at(&["UNITPRICE"]);
+                reinvest.fee = fees;
+                if is_interest {
+                    let mut income = self.activity(date, ACTIVITY_TYPE_INTEREST);
+                    income.amount = Some(total.abs());
+                    return vec![income, reinvest];
+                }
+                reinvest.amount = Some(total.abs());
+                vec![reinvest]
+            }
+            "TRANSFER" => {
+                let units = body.decimal_at(&["UNITS"]);
+                let outgoing = body
+                    .value_at(&["TFERACTION"])
+                    .map_or(units.is_sign_negative(), |action| action == "OUT");
+                let activity_type = if outgoing {
+                    ACTIVITY_TYPE_TRANSFER_OUT
+                } else {
+                    ACTIVITY_TYPE_TRANSFER_IN
+                };
+                let mut activity = self.activity(date, activity_type);
+                activity.quantity = units.abs();
+                activity.unit_price = body.decimal_at(&["UNITPRICE"]);
+                vec![activity]
+            }
+            "SPLIT" => {
+                let old_units = body.decimal_at(&["OLDUNITS"]);
+                let new_units = body.decimal_at(&["NEWUNITS"]);
+                if old_units.is_zero() || new_units.is_zero() {
+                    return Vec::new();
+                }
+                let mut activity = self.activity(date, ACTIVITY_TYPE_SPLIT);
+                activity.amount = Some(new_units / old_units);
+                vec![activity]
+            }
+            "INVEXPENSE" | "MARGININTEREST" => {
+                let mut activity = self.activity(date, ACTIVITY_TYPE_FEE);
+                activity.amount = Some(total.abs());
+                vec![activity]
+            }
+            "INVBANKTRAN" => {
+                let amount = body.decimal_at(&["STMTTRN", "TRNAMT"]);
+                let activity_type = match body.value_at(&["STMTTRN", "TRNTYPE"]) {
+                    Some("INT") if amount.is_sign_negative() => ACTIVITY_TYPE_FEE,
+                    Some("INT") => ACTIVITY_TYPE_INTEREST,
+                    Some("DIV") => ACTIVITY_TYPE_DIVIDEND,
+                    Some("FEE") | Some("SRVCHG") => ACTIVITY_TYPE_FEE,
+                    _ if amount.is_sign_negative() => ACTIVITY_TYPE_WITHDRAWAL,
+                    _ => ACTIVITY_TYPE_DEPOSIT,
+                };
+                let mut activity = self.activity(date, activity_type);
+                activity.amount = Some(amount.abs());
+                vec![activity]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl StatementParser for OfxParser {
+    fn id(&self) -> &'static str {
+        STATEMENT_PARSER_OFX
+    }
+
+    fn name(&self) -> &'static str {
+        "OFX / QFX"
+    }
+
+    fn can_parse(&self, content: &str) -> bool {
+        let head: String = content.chars().take(2048).collect::<String>().to_uppercase();
+        head.contains("OFXHEADER") || head.contains("<OFX>")
+    }
+
+    fn parse(&self, content: &str, default_currency: &str) -> Result<Vec<ActivityImport>, ActivityError> {
+        // Skip the SGML or XML header preceding the <OFX> root
+        let start = content
+            .find("<OFX>")
+            .or_else(|| content.find("<ofx>"))
+            .ok_or_else(|| ActivityError::ParseError("Missing <OFX> element".to_string()))?;
+        let first_line = content[..start].matches('\n').count() + 1;
+        let root = parse_tree(&content[start..], first_line);
+
+        let securities = Self::securities(&root);
+        let mut statements = Vec::new();
+        root.descendants("INVSTMTRS", &mut statements);
+        if statements.is_empty() {
+            return Err(ActivityError::ParseError(
+                "No investment statement (INVSTMTRS) found".to_string(),
+            ));
+        }
+
+        Ok(statements
+            .into_iter()
+            .flat_map(|statement| Self::parse_statement(statement, &securities, default_currency))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const SGML: &str = "OFXHEADER:100
+DATA:OFXSGML
+VERSION:102
+
+<OFX>
+<INVSTMTMSGSRSV1><INVSTMTTRNRS><TRNUID>1
+<INVSTMTRS><DTASOF>20240301<CURDEF>USD
+<INVACCTFROM><BROKERID>example.com<ACCTID>123</INVACCTFROM>
+<INVTRANLIST><DTSTART>20240101<DTEND>20240301
+<BUYSTOCK><INVBUY><INVTRAN><FITID>1<DTTRADE>20240115120000.000[-5:EST]<MEMO>Buy AAPL</INVTRAN>
+<SECID><UNIQUEID>037833100<UNIQUEIDTYPE>CUSIP</SECID><UNITS>10<UNITPRICE>185.50<COMMISSION>4.95<TOTAL>-1859.95
+<SUBACCTSEC>CASH<SUBACCTFUND>CASH</INVBUY><BUYTYPE>BUY</BUYSTOCK>
+<INCOME><INVTRAN><FITID>2<DTTRADE>20240215</INVTRAN><SECID><UNIQUEID>IE00B4L5Y983<UNIQUEIDTYPE>ISIN</SECID>
+<INCOMETYPE>DIV<TOTAL>12.40<WITHHOLDING>1.86<SUBACCTSEC>CASH<SUBACCTFUND>CASH</INCOME>
+<REINVEST><INVTRAN><FITID>3<DTTRADE>20240220</INVTRAN><SECID><UNIQUEID>037833100<UNIQUEIDTYPE>CUSIP</SECID>
+<INCOMETYPE>DIV<TOTAL>-2.40<SUBACCTSEC>CASH<UNITS>0.0129<UNITPRICE>186.00</REINVEST>
+<INVBANKTRAN><STMTTRN><TRNTYPE>CREDIT<DTPOSTED>20240102<TRNAMT>5000.00<FITID>4<NAME>Deposit</STMTTRN><SUBACCTFUND>CASH</INVBANKTRAN>
+</INVTRANLIST></INVSTMTRS></INVSTMTTRNRS></INVSTMTMSGSRSV1>
+<SECLISTMSGSRSV1><SECLIST>
+<STOCKINFO><SECINFO><SECID><UNIQUEID>037833100<UNIQUEIDTYPE>CUSIP</SECID><SECNAME>Apple Inc<TICKER>AAPL</SECINFO></STOCKINFO>
+<MFINFO><SECINFO><SECID><UNIQUEID>IE00B4L5Y983<UNIQUEIDTYPE>ISIN</SECID><SECNAME>iShares Core MSCI World<TICKER>IWDA.AS</SECINFO></MFINFO>
+</SECLIST></SECLISTMSGSRSV1>
+</OFX>
+";
+
+    #[test]
+    fn test_parse_ofx_sgml() {
+        let parser = OfxParser::new();
+        assert!(parser.can_parse(SGML));
+        let activities = parser.parse(SGML, "USD").unwrap();
+        let types: Vec<&str> = activities.iter().map(|a| a.activity_type.as_str()).collect();
+        assert_eq!(
+            types,
+            vec![
+                ACTIVITY_TYPE_BUY,
+                ACTIVITY_TYPE_DIVIDEND,
+                ACTIVITY_TYPE_DIVIDEND_REINVESTMENT,
+                ACTIVITY_TYPE_DEPOSIT,
+            ]
+        );
+
+        let buy = &activities[0];
+        assert_eq!(buy.symbol, "AAPL");
+        assert_eq!(buy.date, "2024-01-15");
+        assert_eq!(buy.quantity, dec!(10));
+        assert_eq!(buy.fee, dec!(4.95));
+        assert_eq!(buy.line_number, Some(10));
+
+        assert_eq!(activities[1].symbol, "IWDA.AS");
+        assert_eq!(activities[1].isin.as_deref(), Some("IE00B4L5Y983"));
+        assert_eq!(activities[1].amount, Some(dec!(10.54)));
+        assert_eq!(activities[1].withholding_tax, Some(dec!(1.86)));
+        assert_eq!(activities[1].withholding_country.as_deref(), Some("IE"));
+        assert_eq!(activities[2].amount, Some(dec!(2.40)));
+        assert_eq!(activities[2].quantity, dec!(0.0129));
+        assert_eq!(activities[2].unit_price, dec!(186.00));
+        assert_eq!(activities[3].symbol, "$CASH-USD");
+    }
+
+    #[test]
+    fn test_parse_ofx_xml() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<?OFX OFXHEADER="200" VERSION="220" SECURITY="NONE"?>
+<OFX>
+<INVSTMTMSGSRSV1><INVSTMTTRNRS><INVSTMTRS><CURDEF>CAD</CURDEF>
+<INVTRANLIST>
+<SELLMF><INVSELL><INVTRAN><FITID>9</FITID><DTTRADE>20240305</DTTRADE></INVTRAN>
+<SECID><UNIQUEID>CA46434V4073</UNIQUEID><UNIQUEIDTYPE>ISIN</UNIQUEIDTYPE></SECID>
+<UNITS>-5</UNITS><UNITPRICE>30.25</UNITPRICE><FEES>1.00</FEES><TOTAL>150.25</TOTAL>
+<CURRENCY><CURRATE>1.0</CURRATE><CURSYM>CAD</CURSYM></CURRENCY></INVSELL><SELLTYPE>SELL</SELLTYPE></SELLMF>
+<SPLIT><INVTRAN><FITID>10</FITID><DTTRADE>20240310</DTTRADE></INVTRAN>
+<SECID><UNIQUEID>CA46434V4073</UNIQUEID><UNIQUEIDTYPE>ISIN</UNIQUEIDTYPE></SECID>
+<OLDUNITS>10</OLDUNITS><NEWUNITS>30</NEWUNITS></SPLIT>
+</INVTRANLIST></INVSTMTRS></INVSTMTTRNRS></INVSTMTMSGSRSV1>
+</OFX>"#;
+        let activities = OfxParser::new().parse(content, "USD").unwrap();
+        assert_eq!(activities.len(), 2);
+        assert_eq!(activities[0].activity_type, ACTIVITY_TYPE_SELL);
+        assert_eq!(activities[0].symbol, "CA46434V4073");
+        assert_eq!(activities[0].quantity, dec!(5));
+        assert_eq!(activities[0].fee, dec!(1.00));
+        assert_eq!(activities[0].currency, "CAD");
+        assert_eq!(activities[1].activity_type, ACTIVITY_TYPE_SPLIT);
+        assert_eq!(activities[1].amount, Some(dec!(3)));
+    }
+}
@@ -0,0 +1,310 @@
+use super::statement_parser::{cash_symbol, parse_amount, statement_activity, StatementParser};
+use crate::activities::activities_constants::*;
+use crate::activities::activities_errors::ActivityError;
+use crate::activities::activities_model::ActivityImport;
+use chrono::NaiveDate;
+use log::warn;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// A QIF record: field code to value, plus the line the record starts on.
+struct QifRecord {
+    line: usize,
+    fields: HashMap<char, String>,
+}
+
+impl QifRecord {
+    fn get(&self, code: char) -> &str {
+        self.fields.get(&code).map(|v| v.as_str()).unwrap_or("")
+    }
+
+    fn decimal(&self, code: char) -> Decimal {
+        parse_amount(self.get(code)).unwrap_or_default()
+    }
+}
+
+/// Quicken Interchange Format exports.
+///
+/// Reads `!Type:Invst` sections as investment transactions and bank-style
+/// sections (`!Type:Bank`, `!Type:Cash`, ...) as deposits and withdrawals. QIF
+/// carries no currency, so rows are booked in the account currency.
+/// Securities are named rather than identified by ticker; a `!Type:Security`
+/// list, when present, maps names to tickers.
+#[derive(Default)]
+pub struct QifParser;
+
+impl QifParser {
+    pub fn new() -> Self {
+        QifParser
+    }
+
+    /// Splits the file into `(section type, records)`.
+    fn sections(content: &str) -> Vec<(String, Vec<QifRecord>)> {
+        let mut sections: Vec<(String, Vec<QifRecord>)> = Vec::new();
+        let mut current: Option<QifRecord> = None;
+
+        for (index, raw_line) in content.lines().enumerate() {
+            let line = raw_line.trim_start_matches('\u{feff}').trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('!') {
+                if let Some(section_type) = header.strip_prefix("Type:") {
+                    sections.push((section_type.trim().to_string(), Vec::new()));
+                }
+                // !Option and !Account headers carry no transactions
+                continue;
+            }
+            if line.starts_with('^') {
+                if let (Some(record), Some((_, records))) = (current.take(), sections.last_mut()) {
+                    records.push(record);
+                }
+                continue;
+            }
+            let mut chars = line.chars();
+            let Some(code) = chars.next() else {
+                continue;
+            };
+            let record = current.get_or_insert_with(|| QifRecord {
+                line: index + 1,
+                fields: HashMap::new(),
+            });
+            // Split lines (S/E/$) repeat; the first value is kept
+            record.fields.entry(code).or_insert_with(|| chars.as_str().trim().to_string());
+        }
+        sections
+    }
+
+    /// QIF dates vary by locale and Quicken version: `1/15'24`, `01/15/2024`,
+    /// `1/15/24`, `2024-01-15`. Two-digit years after an apostrophe are 2000s.
+    fn parse_qif_date(value: &str) -> Option<NaiveDate> {
+        let value: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+        if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+            return Some(date);
+        }
+        let normalized = value.replace(['\'', '-'], "/");
+        let year = normalized.rsplit('/').next()?;
+        let format = if year.len() <= 2 { "%m/%d/%y" } else { "%m/%d/%Y" };
+        NaiveDate::parse_from_str(&normalized, format).ok()
+    }
+
+    fn security_symbols(sections: &[(String, Vec<QifRecord>)]) -> HashMap<String, String> {
+        sections
+            .iter()
+            .filter(|(section_type, _)| section_type.eq_ignore_ascii_case("Security"))
+            .flat_map(|(_, records)| records.iter())
+            .filter(|record| !record.get('N').is_empty() && !record.get('S').is_empty())
+            .map(|record| (record.get('N').to_string(), record.get('S').to_string()))
+            .collect()
+    }
+
+    fn investment_activities(
+        record: &QifRecord,
+        symbols: &HashMap<String, String>,
+        currency: &str,
+    ) -> Vec<ActivityImport> {
+        let Some(date) = Self::parse_qif_date(record.get('D')) else {
+            warn!("QIF line {}: invalid date '{}'", record.line, record.get('D'));
+            return Vec::new();
+        };
+        let security = record.get('Y');
+        let symbol = match symbols.get(security) {
+            Some(ticker) => ticker.clone(),
+            None if security.is_empty() => cash_symbol(currency),
+            None => security.to_string(),
+        };
+        let total = record.decimal('T').abs().max(record.decimal('U').abs());
+        let quantity = record.decimal('Q').abs();
+        let price = record.decimal('I');
+        let commission = record.decimal('O').abs();
+
+        let build = |activity_type: &str| {
+            let mut activity = statement_activity(record.line, date, activity_type, &symbol, currency);
+            if !security.is_empty() {
+                activity.symbol_name = Some(security.to_string());
+            }
+            activity.comment = Some(record.get('M').to_string()).filter(|m| !m.is_empty());
+            activity
+        };
+        let trade = |activity_type: &str| {
+            let mut activity = build(activity_type);
+            activity.quantity = quantity;
+            activity.unit_price = price;
+            activity.fee = commission;
+            activity
+        };
+        let cash = |activity_type: &str| {
+            let mut activity = build(activity_type);
+            activity.amount = Some(total);
+            activity
+        };
+
+        let action = record.get('N');
+        match action {
+            "Buy" | "BuyX" => vec![trade(ACTIVITY_TYPE_BUY)],
+            "Sell" | "SellX" => vec![trade(ACTIVITY_TYPE_SELL)],
+            "Div" | "DivX" | "CGLong" | "CGLongX" | "CGMid" | "CGMidX" | "CGShort" | "CGShortX"
+            | "MiscInc" | "MiscIncX" | "RtrnCap" | "RtrnCapX" => vec![cash(ACTIVITY_TYPE_DIVIDEND)],
+            "IntInc" | "IntIncX" => vec![cash(ACTIVITY_TYPE_INTEREST)],
+            "ReinvDiv" | "ReinvLg" | "ReinvMd" | "ReinvSh" => {
+                let mut activity = trade(ACTIVITY_TYPE_DIVIDEND_REINVESTMENT);
+                activity.amount = Some(total);
+                vec![activity]
+            }
+            "ReinvInt" => vec![cash(ACTIVITY_TYPE_INTEREST), trade(ACTIVITY_TYPE_BUY)],
+            "ShrsIn" => vec![trade(ACTIVITY_TYPE_TRANSFER_IN)],
+            "ShrsOut" => vec![trade(ACTIVITY_TYPE_TRANSFER_OUT)],
+            "StkSplit" if !quantity.is_zero() => {
+                // Quicken stores the ratio of new to old shares times ten
+                let mut activity = build(ACTIVITY_TYPE_SPLIT);
+                activity.amount = Some(quantity / Decimal::TEN);
+                vec![activity]
+            }
+            "MiscExp" | "MiscExpX" | "MargInt" | "MargIntX" => vec![cash(ACTIVITY_TYPE_FEE)],
+            "XIn" | "ContribX" => vec![cash(ACTIVITY_TYPE_DEPOSIT)],
+            "XOut" | "WithdrwX" => vec![cash(ACTIVITY_TYPE_WITHDRAWAL)],
+            "Cash" if record.decimal('T').is_sign_negative() => vec![cash(ACTIVITY_TYPE_WITHDRAWAL)],
+            "Cash" => vec![cash(ACTIVITY_TYPE_DEPOSIT)],
+            _ => {
+                warn!("QIF line {}: skipping action '{}'", record.line, action);
+                Vec::new()
+            }
+        }
+    }
+
+    fn bank_activity(record: &QifRecord, currency: &str) -> Option<ActivityImport> {
+        let Some(date) = Self::parse_qif_date(record.get('D')) else {
+            warn!("QIF line {}: invalid date '{}'", record.line, record.get('D'));
+            return None;
+        };
+        let amount = parse_amount(record.get('T')).or_else(|| parse_amount(record.get('U')))?;
+        let activity_type = if amount.is_sign_negative() {
+            ACTIVITY_TYPE_WITHDRAWAL
+        } else {
+            ACTIVITY_TYPE_DEPOSIT
+        };
+        let mut activity = statement_activity(record.line, date, activity_type, &cash_symbol(currency), currency);
+        activity.amount = Some(amount.abs());
+        let description = [record.get('P'), record.get('M')]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" - ");
+        activity.comment = Some(description).filter(|d| !d.is_empty());
+        Some(activity)
+    }
+}
+
+impl StatementParser for QifParser {
+    fn id(&self) -> &'static str {
+        STATEMENT_PARSER_QIF
+    }
+
+    fn name(&self) -> &'static str {
+        "Quicken (QIF)"
+    }
+
+    fn can_parse(&self, content: &str) -> bool {
+        content
+            .lines()
+            .take(5)
+            .any(|line| line.trim_start_matches('\u{feff}').trim_start().starts_with("!Type:"))
+    }
+
+    fn parse(&self, content: &str, default_currency: &str) -> Result<Vec<ActivityImport>, ActivityError> {
+        let sections = Self::sections(content);
+        if sections.is_empty() {
+            return Err(ActivityError::ParseError("No !Type section found".to_string()));
+        }
+        let symbols = Self::security_symbols(&sections);
+
+        let mut activities = Vec::new();
+        for (section_type, records) in &sections {
+            match section_type.to_lowercase().as_str() {
+                "invst" => {
+                    for record in records {
+                        activities.extend(Self::investment_activities(record, &symbols, default_currency));
+                    }
+                }
+                "bank" | "cash" | "ccard" | "oth a" | "oth l" => {
+                    activities.extend(
+                        records
+                            .iter()
+                            .filter_map(|record| Self::bank_activity(record, default_currency)),
+                    );
+                }
+                _ => {}
+            }
+        }
+        Ok(activities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_qif_investments() {
+        let content = "!Type:Security
+NVanguard Total Stock Market ETF
+SVTI
+TETF
+^
+!Type:Invst
+D1/15'24
+NBuy
+YVanguard Total Stock Market ETF
+I245.10
+Q10
+O1.00
+T2452.10
+^
+D02/16/2024
+NDiv
+YVanguard Total Stock Market ETF
+T8.12
+^
+D3/1'24
+NXIn
+T500.00
+^
+D6/10'24
+NStkSplit
+YVanguard Total Stock Market ETF
+Q20
+^
+";
+        let parser = QifParser::new();
+        assert!(parser.can_parse(content));
+        let activities = parser.parse(content, "CAD").unwrap();
+        let types: Vec<&str> = activities.iter().map(|a| a.activity_type.as_str()).collect();
+        assert_eq!(
+            types,
+            vec![ACTIVITY_TYPE_BUY, ACTIVITY_TYPE_DIVIDEND, ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_SPLIT]
+        );
+
+        let buy = &activities[0];
+        assert_eq!(buy.symbol, "VTI");
+        assert_eq!(buy.date, "2024-01-15");
+        assert_eq!(buy.quantity, dec!(10));
+        assert_eq!(buy.fee, dec!(1.00));
+        assert_eq!(buy.currency, "CAD");
+        assert_eq!(buy.line_number, Some(7));
+        assert_eq!(activities[1].amount, Some(dec!(8.12)));
+        assert_eq!(activities[2].symbol, "$CASH-CAD");
+        assert_eq!(activities[3].amount, Some(dec!(2)));
+    }
+
+    #[test]
+    fn test_parse_qif_bank() {
+        let content = "!Type:Bank\nD01/02/2024\nT-1,250.00\nPRent\n^\nD01/05/2024\nT3000.00\nPPayroll\n^\n";
+        let activities = QifParser::new().parse(content, "CAD").unwrap();
+        assert_eq!(activities.len(), 2);
+        assert_eq!(activities[0].activity_type, ACTIVITY_TYPE_WITHDRAWAL);
+        assert_eq!(activities[0].amount, Some(dec!(1250.00)));
+        assert_eq!(activities[1].activity_type, ACTIVITY_TYPE_DEPOSIT);
+        assert_eq!(activities[1].comment.as_deref(), Some("Payroll"));
+    }
+}
@@ -0,0 +1,146 @@
+use super::statement_parser::{
+    cash_symbol, has_csv_header, parse_amount, parse_date, statement_activity, CsvTable,
+    StatementParser,
+};
+use crate::activities::activities_constants::*;
+use crate::activities::activities_errors::ActivityError;
+use crate::activities::activities_model::ActivityImport;
+use log::warn;
+use rust_decimal::Decimal;
+
+const REQUIRED_COLUMNS: [&str; 5] = ["Transaction Date", "Action", "Symbol", "Net Amount", "Activity Type"];
+const DATE_FORMATS: [&str; 2] = ["%Y-%m-%d", "%m/%d/%Y"];
+
+/// Questrade "Account activities" export (saved as CSV).
+///
+/// Rows are classified by the `Activity Type` column. FX conversions appear as
+/// one row per currency leg and become cash transfers; non-resident tax on
+/// dividends is reported under the `NRT` action.
+#[derive(Default)]
+pub struct QuestradeParser;
+
+impl QuestradeParser {
+    pub fn new() -> Self {
+        QuestradeParser
+    }
+
+    fn activity_type(category: &str, action: &str, quantity: Decimal, net_amount: Decimal) -> Option<&'static str> {
+        let outflow = net_amount.is_sign_negative();
+        let activity_type = match (category, action) {
+            ("Trades", "Buy") | ("Dividend reinvestment", _) => ACTIVITY_TYPE_BUY,
+            ("Trades", "Sell") => ACTIVITY_TYPE_SELL,
+            ("Dividends", "NRT") | ("Withholdings", _) => ACTIVITY_TYPE_TAX,
+            ("Dividends", _) if outflow => ACTIVITY_TYPE_TAX,
+            ("Dividends", _) => ACTIVITY_TYPE_DIVIDEND,
+            ("Interest", _) if outflow => ACTIVITY_TYPE_FEE,
+            ("Interest", _) => ACTIVITY_TYPE_INTEREST,
+            ("Deposits", _) => ACTIVITY_TYPE_DEPOSIT,
+            ("Withdrawals", _) => ACTIVITY_TYPE_WITHDRAWAL,
+            ("Fees and rebates", _) if outflow => ACTIVITY_TYPE_FEE,
+            ("Fees and rebates", _) => ACTIVITY_TYPE_INTEREST,
+            ("FX conversion", _) | ("Transfers", _) if quantity.is_zero() => {
+                if outflow {
+                    ACTIVITY_TYPE_TRANSFER_OUT
+                } else {
+                    ACTIVITY_TYPE_TRANSFER_IN
+                }
+            }
+            ("Transfers", _) | ("Corporate actions", _) | ("Other", _) if !quantity.is_zero() => {
+                match (category, quantity.is_sign_negative()) {
+                    ("Transfers", true) => ACTIVITY_TYPE_TRANSFER_OUT,
+                    ("Transfers", false) => ACTIVITY_TYPE_TRANSFER_IN,
+                    (_, true) => ACTIVITY_TYPE_REMOVE_HOLDING,
+                    (_, false) => ACTIVITY_TYPE_ADD_HOLDING,
+                }
+            }
+            _ => return None,
+        };
+        Some(activity_type)
+    }
+}
+
+impl StatementParser for QuestradeParser {
+    fn id(&self) -> &'static str {
+        STATEMENT_PARSER_QUESTRADE
+    }
+
+    fn name(&self) -> &'static str {
+        "Questrade (CSV)"
+    }
+
+    fn can_parse(&self, content: &str) -> bool {
+        has_csv_header(content, &REQUIRED_COLUMNS)
+    }
+
+    fn parse(&self, content: &str, _default_currency: &str) -> Result<Vec<ActivityImport>, ActivityError> {
+        let table = CsvTable::from_content(content, &REQUIRED_COLUMNS)?;
+
+        let mut activities = Vec::new();
+        for (line, record) in &table.rows {
+            let Some(date) = parse_date(table.get(record, "Transaction Date"), &DATE_FORMATS) else {
+                warn!("Questrade line {}: invalid transaction date", line);
+                continue;
+            };
+            let category = table.get(record, "Activity Type");
+            let action = table.get(record, "Action");
+            let quantity = parse_amount(table.get(record, "Quantity")).unwrap_or_default();
+            let net_amount = parse_amount(table.get(record, "Net Amount")).unwrap_or_default();
+            let Some(activity_type) = Self::activity_type(category, action, quantity, net_amount) else {
+                warn!("Questrade line {}: skipping '{}' ({})", line, category, action);
+                continue;
+            };
+
+            let currency = table.get(record, "Currency");
+            let symbol = match table.get(record, "Symbol") {
+                "" => cash_symbol(currency),
+                symbol => symbol.to_string(),
+            };
+            let mut activity = statement_activity(*line, date, activity_type, &symbol, currency);
+            activity.comment = Some(table.get(record, "Description").to_string()).filter(|d| !d.is_empty());
+            activity.fee = parse_amount(table.get(record, "Commission")).unwrap_or_default().abs();
+            if quantity.is_zero() {
+                activity.amount = Some(net_amount.abs());
+            } else {
+                activity.quantity = quantity.abs();
+                activity.unit_price = parse_amount(table.get(record, "Price")).unwrap_or_default();
+            }
+            activities.push(activity);
+        }
+        Ok(activities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_questrade_activities() {
+        let content = "Transaction Date,Settlement Date,Action,Symbol,Description,Quantity,Price,Gross Amount,Commission,Net Amount,Currency,Account #,Activity Type,Account Type\n\
+2024-01-15 12:00:00 AM,2024-01-17 12:00:00 AM,Buy,XEQT.TO,ISHARES CORE EQUITY ETF,20,28.50,-570.00,-4.95,-574.95,CAD,12345678,Trades,Individual TFSA\n\
+2024-01-20 12:00:00 AM,2024-01-20 12:00:00 AM,DIV,VTI,VANGUARD TOTAL STOCK MKT ETF,0,0,0,0,10.00,USD,12345678,Dividends,Individual TFSA\n\
+2024-01-20 12:00:00 AM,2024-01-20 12:00:00 AM,NRT,VTI,NON-RES TAX WITHHELD,0,0,0,0,-1.50,USD,12345678,Dividends,Individual TFSA\n\
+2024-01-22 12:00:00 AM,2024-01-22 12:00:00 AM,FXT,,AUTO CONV,0,0,0,0,-136.00,CAD,12345678,FX conversion,Individual TFSA\n\
+2024-01-22 12:00:00 AM,2024-01-22 12:00:00 AM,FXT,,AUTO CONV,0,0,0,0,100.00,USD,12345678,FX conversion,Individual TFSA\n";
+        let parser = QuestradeParser::new();
+        assert!(parser.can_parse(content));
+        let activities = parser.parse(content, "USD").unwrap();
+        let types: Vec<&str> = activities.iter().map(|a| a.activity_type.as_str()).collect();
+        assert_eq!(
+            types,
+            vec![
+                ACTIVITY_TYPE_BUY,
+                ACTIVITY_TYPE_DIVIDEND,
+                ACTIVITY_TYPE_TAX,
+                ACTIVITY_TYPE_TRANSFER_OUT,
+                ACTIVITY_TYPE_TRANSFER_IN,
+            ]
+        );
+        assert_eq!(activities[0].fee, dec!(4.95));
+        assert_eq!(activities[0].currency, "CAD");
+        assert_eq!(activities[2].amount, Some(dec!(1.50)));
+        assert_eq!(activities[3].symbol, "$CASH-CAD");
+        assert_eq!(activities[4].amount, Some(dec!(100.00)));
+    }
+}
@@ -0,0 +1,144 @@
+use super::statement_parser::{
+    cash_symbol, has_csv_header, parse_amount, parse_date, statement_activity, CsvTable,
+    StatementParser,
+};
+use crate::activities::activities_constants::*;
+use crate::activities::activities_errors::ActivityError;
+use crate::activities::activities_model::ActivityImport;
+use log::warn;
+use rust_decimal::Decimal;
+
+const REQUIRED_COLUMNS: [&str; 5] = ["Date", "Action", "Symbol", "Quantity", "Fees & Comm"];
+const DATE_FORMATS: [&str; 1] = ["%m/%d/%Y"];
+const CURRENCY: &str = "USD";
+
+/// Charles Schwab brokerage "Transactions" CSV export.
+///
+/// Amounts are in US dollars. Stock splits are reported as the number of
+/// additional shares received, so they are booked as a zero-cost
+/// `ADD_HOLDING`, which leaves the total cost basis unchanged.
+#[derive(Default)]
+pub struct SchwabParser;
+
+impl SchwabParser {
+    pub fn new() -> Self {
+        SchwabParser
+    }
+
+    fn activity_type(action: &str, amount: Option<Decimal>) -> Option<&'static str> {
+        let outflow = amount.is_some_and(|a| a.is_sign_negative());
+        let activity_type = match action {
+            "Buy" | "Reinvest Shares" | "Buy to Open" | "Buy to Close" => ACTIVITY_TYPE_BUY,
+            "Sell" | "Sell to Open" | "Sell to Close" => ACTIVITY_TYPE_SELL,
+            "Reinvest Dividend" | "Cash Dividend" | "Qualified Dividend" | "Non-Qualified Div"
+            | "Special Dividend" | "Pr Yr Div Reinvest" | "Pr Yr Cash Div" | "Special Qual Div"
+            | "Long Term Cap Gain" | "Short Term Cap Gain" | "Cash In Lieu" => ACTIVITY_TYPE_DIVIDEND,
+            "Bank Interest" | "Credit Interest" | "Bond Interest" => ACTIVITY_TYPE_INTEREST,
+            "Margin Interest" | "ADR Mgmt Fee" | "Service Fee" | "Wire Fee" => ACTIVITY_TYPE_FEE,
+            "NRA Tax Adj" | "Foreign Tax Paid" | "NRA Withholding" | "Backup Withholding" => {
+                ACTIVITY_TYPE_TAX
+            }
+            "MoneyLink Transfer" | "MoneyLink Deposit" | "Wire Funds" | "Wire Received"
+            | "Journal" | "Funds Received" | "Wire Sent" => {
+                if outflow {
+                    ACTIVITY_TYPE_WITHDRAWAL
+                } else {
+                    ACTIVITY_TYPE_DEPOSIT
+                }
+            }
+            "Stock Split" | "Stock Dividend" => ACTIVITY_TYPE_ADD_HOLDING,
+            "Security Transfer" | "Journaled Shares" => {
+                if outflow {
+                    ACTIVITY_TYPE_TRANSFER_OUT
+                } else {
+                    ACTIVITY_TYPE_TRANSFER_IN
+                }
+            }
+            _ => return None,
+        };
+        Some(activity_type)
+    }
+}
+
+impl StatementParser for SchwabParser {
+    fn id(&self) -> &'static str {
+        STATEMENT_PARSER_SCHWAB
+    }
+
+    fn name(&self) -> &'static str {
+        "Charles Schwab (CSV)"
+    }
+
+    fn can_parse(&self, content: &str) -> bool {
+        has_csv_header(content, &REQUIRED_COLUMNS)
+    }
+
+    fn parse(&self, content: &str, _default_currency: &str) -> Result<Vec<ActivityImport>, ActivityError> {
+        let table = CsvTable::from_content(content, &REQUIRED_COLUMNS)?;
+
+        let mut activities = Vec::new();
+        for (line, record) in &table.rows {
+            let action = table.get(record, "Action");
+            // The "Transactions Total" footer has no date
+            let Some(date) = parse_date(table.get(record, "Date"), &DATE_FORMATS) else {
+                continue;
+            };
+            let amount = parse_amount(table.get(record, "Amount"));
+            let Some(activity_type) = Self::activity_type(action, amount) else {
+                warn!("Schwab line {}: skipping action '{}'", line, action);
+                continue;
+            };
+
+            let symbol = match table.get(record, "Symbol") {
+                "" => cash_symbol(CURRENCY),
+                symbol => symbol.to_string(),
+            };
+            let quantity = parse_amount(table.get(record, "Quantity")).unwrap_or_default();
+            let mut activity = statement_activity(*line, date, activity_type, &symbol, CURRENCY);
+            activity.comment = Some(table.get(record, "Description").to_string()).filter(|d| !d.is_empty());
+            activity.fee = parse_amount(table.get(record, "Fees & Comm")).unwrap_or_default().abs();
+
+            // Security movements carry a quantity; cash movements only an amount
+            if quantity.is_zero() {
+                activity.amount = amount.map(|a| a.abs());
+            } else {
+                activity.quantity = quantity.abs();
+                activity.unit_price = parse_amount(table.get(record, "Price")).unwrap_or_default();
+            }
+            activities.push(activity);
+        }
+        Ok(activities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_schwab_transactions() {
+        let content = "\"Transactions  for account Individual ...123 as of 03/01/2024\"\n\
+\"Date\",\"Action\",\"Symbol\",\"Description\",\"Quantity\",\"Price\",\"Fees & Comm\",\"Amount\"\n\
+\"02/15/2024 as of 02/14/2024\",\"Buy\",\"VTI\",\"VANGUARD TOTAL STOCK MARKET ETF\",\"10\",\"$245.10\",\"$0.00\",\"-$2,451.00\"\n\
+\"02/16/2024\",\"Qualified Dividend\",\"VTI\",\"VANGUARD TOTAL STOCK MARKET ETF\",\"\",\"\",\"\",\"$8.12\"\n\
+\"02/16/2024\",\"NRA Tax Adj\",\"VTI\",\"VANGUARD TOTAL STOCK MARKET ETF\",\"\",\"\",\"\",\"-$1.22\"\n\
+\"02/20/2024\",\"MoneyLink Transfer\",\"\",\"Tfr BANK\",\"\",\"\",\"\",\"-$500.00\"\n\
+\"Transactions Total\",\"\",\"\",\"\",\"\",\"\",\"\",\"-$2,944.10\"\n";
+        let parser = SchwabParser::new();
+        assert!(parser.can_parse(content));
+        let activities = parser.parse(content, "USD").unwrap();
+        assert_eq!(activities.len(), 4);
+
+        assert_eq!(activities[0].activity_type, ACTIVITY_TYPE_BUY);
+        assert_eq!(activities[0].date, "2024-02-15");
+        assert_eq!(activities[0].quantity, dec!(10));
+        assert_eq!(activities[0].unit_price, dec!(245.10));
+        assert_eq!(activities[1].activity_type, ACTIVITY_TYPE_DIVIDEND);
+        assert_eq!(activities[1].amount, Some(dec!(8.12)));
+        assert_eq!(activities[2].activity_type, ACTIVITY_TYPE_TAX);
+        assert_eq!(activities[2].amount, Some(dec!(1.22)));
+        assert_eq!(activities[3].activity_type, ACTIVITY_TYPE_WITHDRAWAL);
+        assert_eq!(activities[3].symbol, "$CASH-USD");
+    }
+}
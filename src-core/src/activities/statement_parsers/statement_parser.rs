@@ -0,0 +1,256 @@
+use crate::activities::activities_errors::ActivityError;
+use crate::activities::activities_model::ActivityImport;
+use crate::assets::IdentifierType;
+use crate::constants::CASH_ASSET_PREFIX;
+use chrono::NaiveDate;
+use csv::StringRecord;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Number of leading lines searched for a CSV header. Broker exports often
+/// prepend a title, account number or blank lines before the table.
+const HEADER_SEARCH_LINES: usize = 20;
+
+/// Turns a broker's native export into import rows, bypassing the generic CSV
+/// column mapping.
+///
+/// Rows are returned unvalidated; `check_activities_import` resolves symbols,
+/// registers FX pairs and flags duplicates as for any other import.
+pub trait StatementParser: Send + Sync {
+    fn id(&self) -> &'static str;
+    fn name(&self) -> &'static str;
+
+    /// Returns true if `content` looks like an export this parser understands.
+    fn can_parse(&self, content: &str) -> bool;
+
+    /// Parses the export into import rows. `default_currency` (the account
+    /// currency) applies to formats that do not state one.
+    fn parse(&self, content: &str, default_currency: &str) -> Result<Vec<ActivityImport>, ActivityError>;
+}
+
+/// Identifies a statement parser for selection in the import UI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementParserInfo {
+    pub id: String,
+    pub name: String,
+}
+
+/// Builds an import row with zero quantity, price and fee.
+pub(crate) fn statement_activity(
+    line_number: usize,
+    date: NaiveDate,
+    activity_type: &str,
+    symbol: &str,
+    currency: &str,
+) -> ActivityImport {
+    ActivityImport {
+        id: None,
+        date: date.format("%Y-%m-%d").to_string(),
+        symbol: symbol.trim().to_uppercase(),
+        activity_type: activity_type.to_string(),
+        quantity: Decimal::ZERO,
+        unit_price: Decimal::ZERO,
+        currency: currency.trim().to_uppercase(),
+        fee: Decimal::ZERO,
+        amount: None,
+        comment: None,
+        account_id: None,
+        account_name: None,
+        symbol_name: None,
+        errors: None,
+        is_draft: false,
+        is_valid: false,
+        line_number: i32::try_from(line_number).ok(),
+        duplicate_of: None,
+        isin: None,
+        import_session_id: None,
+        withholding_tax: None,
+        withholding_country: None,
+        source_asset_id: None,
+    }
+}
+
+/// Asset id of the cash position in `currency`.
+pub(crate) fn cash_symbol(currency: &str) -> String {
+    format!("{}-{}", CASH_ASSET_PREFIX, currency.trim().to_uppercase())
+}
+
+/// Issuer country of a valid ISIN, taken as the jurisdiction that withholds tax
+/// on its income.
+pub(crate) fn isin_country(isin: &str) -> Option<String> {
+    let isin = isin.trim().to_uppercase();
+    (IdentifierType::detect(&isin) == Some(IdentifierType::Isin)).then(|| isin[..2].to_string())
+}
+
+/// Parses a monetary value as written by broker exports.
+///
+/// Handles currency symbols and codes (`$1,234.56`, `-12.00 USD`), accounting
+/// negatives (`(12.00)`), trailing minus signs and decimal commas
+/// (`1.234,56`). Returns `None` for empty cells and placeholders such as `--`.
+pub(crate) fn parse_amount(value: &str) -> Option<Decimal> {
+    let trimmed = value.trim().trim_matches('"');
+    let parenthesized = trimmed.starts_with('(') && trimmed.ends_with(')');
+    let mut digits: String = trimmed
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '-'))
+        .collect();
+    let trailing_minus = digits.len() > 1 && digits.ends_with('-');
+    if trailing_minus {
+        digits.pop();
+    }
+    if !digits.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let normalized = match (digits.rfind('.'), digits.rfind(',')) {
+        (Some(dot), Some(comma)) if comma > dot => digits.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => digits.replace(',', ""),
+        // A lone comma followed by exactly three digits is a thousands separator.
+        (None, Some(comma)) if digits.len() - comma - 1 != 3 => digits.replace(',', "."),
+        (None, Some(_)) => digits.replace(',', ""),
+        _ => digits,
+    };
+    let amount = Decimal::from_str(&normalized).ok()?;
+    Some(if parenthesized || trailing_minus {
+        -amount.abs()
+    } else {
+        amount
+    })
+}
+
+/// Parses the leading date of a cell, ignoring any time or trailing text
+/// (`01/15/2024 as of 01/12/2024`, `2024-01-15 12:00:00 AM`, `20240115;093000`).
+pub(crate) fn parse_date(value: &str, formats: &[&str]) -> Option<NaiveDate> {
+    let token = value
+        .trim()
+        .trim_matches('"')
+        .split([' ', ';', ',', 'T'])
+        .next()?;
+    formats
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(token, format).ok())
+}
+
+/// Returns true if one of the leading lines of `content` is a CSV header
+/// containing all `required` columns.
+pub(crate) fn has_csv_header(content: &str, required: &[&str]) -> bool {
+    find_header_line(content, required).is_some()
+}
+
+fn parse_csv_line(line: &str) -> Option<StringRecord> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(line.trim_start_matches('\u{feff}').as_bytes())
+        .records()
+        .next()?
+        .ok()
+}
+
+fn find_header_line(content: &str, required: &[&str]) -> Option<usize> {
+    content
+        .lines()
+        .take(HEADER_SEARCH_LINES)
+        .position(|line| {
+            parse_csv_line(line).is_some_and(|record| {
+                required.iter().all(|column| {
+                    record
+                        .iter()
+                        .any(|header| header.eq_ignore_ascii_case(column))
+                })
+            })
+        })
+}
+
+/// A CSV table located by its header row, with rows keyed by their line
+/// number in the original file.
+pub(crate) struct CsvTable {
+    headers: Vec<String>,
+    pub rows: Vec<(usize, StringRecord)>,
+}
+
+impl CsvTable {
+    /// Reads the table whose header contains all `required` columns. Rows
+    /// with fewer than two non-empty cells (blank lines, footers) are dropped.
+    pub fn from_content(content: &str, required: &[&str]) -> Result<Self, ActivityError> {
+        let header_index = find_header_line(content, required).ok_or_else(|| {
+            ActivityError::ParseError(format!(
+                "Could not find a header with columns: {}",
+                required.join(", ")
+            ))
+        })?;
+        let table: String = content
+            .lines()
+            .skip(header_index)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(table.trim_start_matches('\u{feff}').as_bytes());
+        let headers = reader
+            .headers()
+            .map_err(|e| ActivityError::ParseError(format!("Invalid header: {}", e)))?
+            .iter()
+            .map(|h| h.to_string())
+            .collect();
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record =
+                record.map_err(|e| ActivityError::ParseError(format!("Invalid row: {}", e)))?;
+            if record.iter().filter(|cell| !cell.is_empty()).count() < 2 {
+                continue;
+            }
+            let line = record.position().map_or(0, |p| p.line() as usize) + header_index;
+            rows.push((line, record));
+        }
+        Ok(CsvTable { headers, rows })
+    }
+
+    /// Index of the first column named `name` (case-insensitive).
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.headers.iter().position(|h| h.eq_ignore_ascii_case(name))
+    }
+
+    /// Value of column `name` in `record`, or an empty string.
+    pub fn get<'a>(&self, record: &'a StringRecord, name: &str) -> &'a str {
+        self.column(name)
+            .and_then(|index| record.get(index))
+            .unwrap_or("")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_amount_formats() {
+        assert_eq!(parse_amount("$1,234.56"), Some(dec!(1234.56)));
+        assert_eq!(parse_amount("-$12.00"), Some(dec!(-12.00)));
+        assert_eq!(parse_amount("(12.50)"), Some(dec!(-12.50)));
+        assert_eq!(parse_amount("1.234,56"), Some(dec!(1234.56)));
+        assert_eq!(parse_amount("-12,5"), Some(dec!(-12.5)));
+        assert_eq!(parse_amount("1,000"), Some(dec!(1000)));
+        assert_eq!(parse_amount("25.00-"), Some(dec!(-25.00)));
+        assert_eq!(parse_amount("--"), None);
+        assert_eq!(parse_amount(""), None);
+    }
+
+    #[test]
+    fn test_csv_table_skips_preamble() {
+        let content = "\"Transactions for account XXXX-1234\"\n\nDate,Action,Amount\n01/02/2024,Buy,$10.00\n";
+        let table = CsvTable::from_content(content, &["Date", "Action"]).unwrap();
+        assert_eq!(table.rows.len(), 1);
+        let (line, record) = &table.rows[0];
+        assert_eq!(*line, 4);
+        assert_eq!(table.get(record, "action"), "Buy");
+        assert_eq!(table.get(record, "missing"), "");
+    }
+}
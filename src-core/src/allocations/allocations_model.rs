@@ -0,0 +1,218 @@
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// What the keys of an allocation model's targets refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AllocationTargetKind {
+    /// Key is an asset id.
+    Asset,
+    /// Key is an asset class, as set on the asset profile.
+    AssetClass,
+    /// Key is a sector name; funds count towards each of their sectors by weight.
+    Sector,
+    /// Key is a user-defined tag; the target lists the assets carrying it.
+    Tag,
+}
+
+impl AllocationTargetKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AllocationTargetKind::Asset => "ASSET",
+            AllocationTargetKind::AssetClass => "ASSET_CLASS",
+            AllocationTargetKind::Sector => "SECTOR",
+            AllocationTargetKind::Tag => "TAG",
+        }
+    }
+}
+
+impl FromStr for AllocationTargetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "ASSET" => Ok(AllocationTargetKind::Asset),
+            "ASSET_CLASS" => Ok(AllocationTargetKind::AssetClass),
+            "SECTOR" => Ok(AllocationTargetKind::Sector),
+            "TAG" => Ok(AllocationTargetKind::Tag),
+            _ => Err(format!("Unknown allocation target kind: {}", s)),
+        }
+    }
+}
+
+/// Which holdings an allocation model is measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AllocationScope {
+    /// A single account, named by `scope_id`.
+    Account,
+    /// Every active account whose `group` equals `scope_id`.
+    Group,
+    /// Every active account.
+    Total,
+}
+
+impl AllocationScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AllocationScope::Account => "ACCOUNT",
+            AllocationScope::Group => "GROUP",
+            AllocationScope::Total => "TOTAL",
+        }
+    }
+}
+
+impl FromStr for AllocationScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "ACCOUNT" => Ok(AllocationScope::Account),
+            "GROUP" => Ok(AllocationScope::Group),
+            "TOTAL" => Ok(AllocationScope::Total),
+            _ => Err(format!("Unknown allocation scope: {}", s)),
+        }
+    }
+}
+
+/// One target of an allocation model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationTarget {
+    pub key: String,
+    /// Share of the scope's total value, between 0 and 1.
+    pub target_weight: Decimal,
+    /// Allowed absolute drift either side of the target before rebalancing, e.g. 0.05.
+    #[serde(default)]
+    pub tolerance: Decimal,
+    /// Assets the target buys into, in order of preference. For `TAG` targets this is
+    /// also the set of assets carrying the tag.
+    #[serde(default)]
+    pub asset_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Insertable, AsChangeset)]
+#[diesel(table_name = crate::schema::allocation_models)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct AllocationModelDB {
+    pub id: String,
+    pub name: String,
+    pub target_kind: String,
+    pub scope_type: String,
+    pub scope_id: Option<String>,
+    pub targets: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Target weights for a set of holdings. Whatever weight the targets leave is held as cash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationModel {
+    pub id: String,
+    pub name: String,
+    pub target_kind: AllocationTargetKind,
+    pub scope_type: AllocationScope,
+    pub scope_id: Option<String>,
+    pub targets: Vec<AllocationTarget>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAllocationModel {
+    pub id: Option<String>,
+    pub name: String,
+    pub target_kind: AllocationTargetKind,
+    pub scope_type: AllocationScope,
+    pub scope_id: Option<String>,
+    #[serde(default)]
+    pub targets: Vec<AllocationTarget>,
+}
+
+impl AllocationModel {
+    /// Weight left over by the targets, which the model holds as cash.
+    pub fn cash_weight(&self) -> Decimal {
+        Decimal::ONE - self.targets.iter().map(|t| t.target_weight).sum::<Decimal>()
+    }
+}
+
+impl AllocationModelDB {
+    pub fn to_model(&self) -> Result<AllocationModel> {
+        Ok(AllocationModel {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            target_kind: AllocationTargetKind::from_str(&self.target_kind)
+                .map_err(Error::Repository)?,
+            scope_type: AllocationScope::from_str(&self.scope_type)
+                .map_err(Error::Repository)?,
+            scope_id: self.scope_id.clone(),
+            targets: serde_json::from_str(&self.targets)?,
+            created_at: self.created_at.clone(),
+            updated_at: self.updated_at.clone(),
+        })
+    }
+}
+
+impl NewAllocationModel {
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: String| Err(Error::Validation(ValidationError::InvalidInput(msg)));
+
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        let has_scope_id = self
+            .scope_id
+            .as_deref()
+            .is_some_and(|id| !id.trim().is_empty());
+        if self.scope_type != AllocationScope::Total && !has_scope_id {
+            return invalid(format!(
+                "An allocation model scoped to {} needs a scope id",
+                self.scope_type.as_str()
+            ));
+        }
+
+        let mut keys = HashSet::new();
+        let mut total_weight = Decimal::ZERO;
+        for target in &self.targets {
+            let key = target.key.trim();
+            if key.is_empty() {
+                return invalid("Allocation targets need a key".to_string());
+            }
+            if !keys.insert(key.to_lowercase()) {
+                return invalid(format!("Allocation target '{}' appears more than once", key));
+            }
+            if target.target_weight < Decimal::ZERO || target.target_weight > Decimal::ONE {
+                return invalid(format!(
+                    "Target weight for '{}' must be between 0 and 1, got {}",
+                    key, target.target_weight
+                ));
+            }
+            if target.tolerance < Decimal::ZERO || target.tolerance > Decimal::ONE {
+                return invalid(format!(
+                    "Tolerance for '{}' must be between 0 and 1, got {}",
+                    key, target.tolerance
+                ));
+            }
+            if self.target_kind == AllocationTargetKind::Tag && target.asset_ids.is_empty() {
+                return invalid(format!("Tag '{}' does not list any assets", key));
+            }
+            total_weight += target.target_weight;
+        }
+        if total_weight > Decimal::ONE {
+            return invalid(format!(
+                "Target weights add up to {}, more than 1",
+                total_weight
+            ));
+        }
+        Ok(())
+    }
+}
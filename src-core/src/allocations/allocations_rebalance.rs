@@ -0,0 +1,733 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::allocations_model::{AllocationModel, AllocationTargetKind};
+use crate::constants::DECIMAL_PRECISION;
+use crate::portfolio::holdings::{Holding, HoldingType, Instrument};
+use crate::portfolio::snapshot::Lot;
+
+/// Bucket for holdings that match no target key, named as on the sectors chart.
+pub const UNCLASSIFIED_KEY: &str = "Others";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RebalanceOptions {
+    /// Only buy, using existing cash plus `contribution`; nothing is sold.
+    pub cash_only: bool,
+    /// New money in base currency, added to cash before planning.
+    pub contribution: Decimal,
+    /// Trades worth less than this (base currency) are dropped.
+    pub min_trade_size: Decimal,
+    /// Round quantities down to whole shares.
+    pub whole_shares: bool,
+    /// Only sell lots whose cost per share is at or above the current price.
+    pub avoid_selling_gains: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationDrift {
+    pub key: String,
+    pub target_weight: Decimal,
+    pub current_weight: Decimal,
+    /// Current minus target weight.
+    pub drift: Decimal,
+    pub tolerance: Decimal,
+    pub current_value: Decimal,
+    pub target_value: Decimal,
+    pub is_out_of_band: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceTrade {
+    pub account_id: String,
+    pub asset_id: String,
+    pub symbol: String,
+    pub target_key: String,
+    pub side: TradeSide,
+    pub quantity: Decimal,
+    /// Price in the asset's currency.
+    pub price: Decimal,
+    pub currency: String,
+    pub value: Decimal,
+    pub value_base: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalancePlan {
+    pub model_id: String,
+    pub base_currency: String,
+    /// Holdings plus cash plus any contribution, in base currency.
+    pub total_value: Decimal,
+    pub cash_value: Decimal,
+    pub cash_target_value: Decimal,
+    /// True when at least one target is outside its tolerance band.
+    pub needs_rebalance: bool,
+    pub drift: Vec<AllocationDrift>,
+    pub trades: Vec<RebalanceTrade>,
+    /// Cash left once every proposed trade has settled.
+    pub cash_after: Decimal,
+    pub warnings: Vec<String>,
+}
+
+/// A tradable position as the engine sees it. `exposures` splits its value across
+/// target keys; the fractions add up to 1.
+#[derive(Debug, Clone)]
+pub struct RebalancePosition {
+    pub account_id: String,
+    pub asset_id: String,
+    pub symbol: String,
+    pub currency: String,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub fx_rate: Decimal,
+    pub lots: Vec<Lot>,
+    pub exposures: Vec<(String, Decimal)>,
+}
+
+impl RebalancePosition {
+    fn unit_value_base(&self) -> Decimal {
+        self.price * self.fx_rate
+    }
+
+    fn market_value(&self) -> Decimal {
+        self.quantity * self.unit_value_base()
+    }
+
+    fn exposure_to(&self, key: &str) -> Decimal {
+        self.exposures
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, fraction)| *fraction)
+            .sum()
+    }
+
+    fn sellable_quantity(&self, avoid_selling_gains: bool) -> Decimal {
+        if !avoid_selling_gains {
+            return self.quantity;
+        }
+        self.lots
+            .iter()
+            .filter(|lot| lot.cost_basis >= lot.quantity * self.price)
+            .map(|lot| lot.quantity)
+            .sum::<Decimal>()
+            .min(self.quantity)
+    }
+}
+
+/// Maps a target key to the spelling used by the model, ignoring case.
+pub(crate) struct TargetKeys(HashMap<String, String>);
+
+impl TargetKeys {
+    pub(crate) fn new(model: &AllocationModel) -> Self {
+        TargetKeys(
+            model
+                .targets
+                .iter()
+                .map(|t| (t.key.trim().to_lowercase(), t.key.clone()))
+                .collect(),
+        )
+    }
+
+    fn resolve(&self, key: &str) -> String {
+        self.0
+            .get(&key.trim().to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+}
+
+/// How an instrument's value splits across the model's target keys.
+pub(crate) fn exposures_for(
+    model: &AllocationModel,
+    keys: &TargetKeys,
+    instrument: &Instrument,
+) -> Vec<(String, Decimal)> {
+    let unclassified = || vec![(keys.resolve(UNCLASSIFIED_KEY), Decimal::ONE)];
+    match model.target_kind {
+        AllocationTargetKind::Asset => vec![(keys.resolve(&instrument.id), Decimal::ONE)],
+        AllocationTargetKind::AssetClass => match instrument.asset_class.as_deref() {
+            Some(class) if !class.trim().is_empty() => vec![(keys.resolve(class), Decimal::ONE)],
+            _ => unclassified(),
+        },
+        AllocationTargetKind::Tag => model
+            .targets
+            .iter()
+            .find(|t| {
+                t.asset_ids
+                    .iter()
+                    .any(|id| id.eq_ignore_ascii_case(&instrument.id))
+            })
+            .map(|t| vec![(t.key.clone(), Decimal::ONE)])
+            .unwrap_or_else(unclassified),
+        AllocationTargetKind::Sector => {
+            let mut exposures = Vec::new();
+            let mut assigned = Decimal::ZERO;
+            for sector in instrument.sectors.iter().flatten() {
+                // Profiles store sector weights either as fractions or as percentages.
+                let weight = if sector.weight > 1.0 {
+                    sector.weight / 100.0
+                } else {
+                    sector.weight
+                };
+                let fraction = Decimal::from_f64_retain(weight)
+                    .unwrap_or_default()
+                    .round_dp(DECIMAL_PRECISION)
+                    .min(Decimal::ONE - assigned);
+                if fraction > Decimal::ZERO {
+                    exposures.push((keys.resolve(&sector.name), fraction));
+                    assigned += fraction;
+                }
+            }
+            if assigned < Decimal::ONE {
+                exposures.push((keys.resolve(UNCLASSIFIED_KEY), Decimal::ONE - assigned));
+            }
+            exposures
+        }
+    }
+}
+
+/// Splits holdings into tradable positions and the base-currency cash held per account.
+pub(crate) fn positions_from_holdings(
+    model: &AllocationModel,
+    holdings: &[Holding],
+) -> (Vec<RebalancePosition>, Vec<(String, Decimal)>) {
+    let keys = TargetKeys::new(model);
+    let mut positions = Vec::new();
+    let mut cash: Vec<(String, Decimal)> = Vec::new();
+
+    for holding in holdings {
+        if holding.holding_type == HoldingType::Cash {
+            match cash.iter_mut().find(|(id, _)| id == &holding.account_id) {
+                Some((_, amount)) => *amount += holding.market_value.base,
+                None => cash.push((holding.account_id.clone(), holding.market_value.base)),
+            }
+            continue;
+        }
+        let Some(instrument) = holding.instrument.as_ref() else {
+            continue;
+        };
+        if holding.quantity.is_zero() {
+            continue;
+        }
+        let price = holding
+            .price
+            .unwrap_or_else(|| holding.market_value.local / holding.quantity);
+        let fx_rate = if holding.market_value.local.is_zero() {
+            holding.fx_rate.unwrap_or(Decimal::ONE)
+        } else {
+            holding.market_value.base / holding.market_value.local
+        };
+        positions.push(RebalancePosition {
+            account_id: holding.account_id.clone(),
+            asset_id: instrument.id.clone(),
+            symbol: instrument.symbol.clone(),
+            currency: holding.local_currency.clone(),
+            quantity: holding.quantity,
+            price,
+            fx_rate,
+            lots: holding.lots.iter().flatten().cloned().collect(),
+            exposures: exposures_for(model, &keys, instrument),
+        });
+    }
+    (positions, cash)
+}
+
+/// Measures drift against the model and proposes trades back to target.
+///
+/// Nothing is traded while every target is inside its band, except in cash-only mode,
+/// which always puts spare cash to work in the most underweight targets. A full
+/// rebalance first sells overweight targets, then spends the proceeds and any cash
+/// above the model's cash weight on underweight ones, scaling buys down when sales
+/// fall short. Selling a multi-sector fund moves every sector it holds; the plan only
+/// accounts for the sector being rebalanced.
+pub fn plan_rebalance(
+    model: &AllocationModel,
+    positions: &[RebalancePosition],
+    cash: Decimal,
+    options: &RebalanceOptions,
+    base_currency: &str,
+) -> RebalancePlan {
+    let cash = cash + options.contribution;
+    let total_value = positions.iter().map(|p| p.market_value()).sum::<Decimal>() + cash;
+    let cash_target_value = total_value * model.cash_weight();
+
+    let mut current: HashMap<String, Decimal> = HashMap::new();
+    for position in positions {
+        for (key, fraction) in &position.exposures {
+            *current.entry(key.clone()).or_default() += position.market_value() * fraction;
+        }
+    }
+
+    let weight_of = |value: Decimal| {
+        if total_value > Decimal::ZERO {
+            (value / total_value).round_dp(DECIMAL_PRECISION)
+        } else {
+            Decimal::ZERO
+        }
+    };
+    let mut drift: Vec<AllocationDrift> = model
+        .targets
+        .iter()
+        .map(|t| (t.key.clone(), t.target_weight, t.tolerance))
+        .chain({
+            let mut untargeted: Vec<_> = current
+                .keys()
+                .filter(|key| !model.targets.iter().any(|t| &t.key == *key))
+                .map(|key| (key.clone(), Decimal::ZERO, Decimal::ZERO))
+                .collect();
+            untargeted.sort();
+            untargeted
+        })
+        .map(|(key, target_weight, tolerance)| {
+            let current_value = current.get(&key).copied().unwrap_or_default();
+            let current_weight = weight_of(current_value);
+            let drift = current_weight - target_weight;
+            AllocationDrift {
+                key,
+                target_weight,
+                current_weight,
+                drift,
+                tolerance,
+                current_value,
+                target_value: total_value * target_weight,
+                is_out_of_band: drift.abs() > tolerance,
+            }
+        })
+        .collect();
+    drift.retain(|d| !(d.target_weight.is_zero() && d.current_value.is_zero()));
+    let needs_rebalance = drift.iter().any(|d| d.is_out_of_band);
+
+    let mut plan = RebalancePlan {
+        model_id: model.id.clone(),
+        base_currency: base_currency.to_string(),
+        total_value,
+        cash_value: cash,
+        cash_target_value,
+        needs_rebalance,
+        drift,
+        trades: Vec::new(),
+        cash_after: cash,
+        warnings: Vec::new(),
+    };
+    if total_value <= Decimal::ZERO || !(needs_rebalance || options.cash_only) {
+        return plan;
+    }
+
+    let mut sold = Decimal::ZERO;
+    if !options.cash_only {
+        let overweight: Vec<(String, Decimal)> = plan
+            .drift
+            .iter()
+            .filter(|d| d.current_value > d.target_value)
+            .map(|d| (d.key.clone(), d.current_value - d.target_value))
+            .collect();
+        for (key, excess) in overweight {
+            sold += sell_from_target(&key, excess, positions, options, &mut plan);
+        }
+    }
+
+    let available = (cash - cash_target_value).max(Decimal::ZERO) + sold;
+    let underweight: Vec<(String, Decimal)> = plan
+        .drift
+        .iter()
+        .filter(|d| d.target_value > d.current_value)
+        .map(|d| (d.key.clone(), d.target_value - d.current_value))
+        .collect();
+    let needed: Decimal = underweight.iter().map(|(_, shortfall)| *shortfall).sum();
+    let scale = if needed > available && needed > Decimal::ZERO {
+        available / needed
+    } else {
+        Decimal::ONE
+    };
+
+    let mut bought = Decimal::ZERO;
+    for (key, shortfall) in underweight {
+        bought += buy_into_target(model, &key, shortfall * scale, positions, options, &mut plan);
+    }
+
+    plan.cash_after = cash + sold - bought;
+    plan
+}
+
+fn round_quantity(quantity: Decimal, whole_shares: bool) -> Decimal {
+    let dp = if whole_shares { 0 } else { DECIMAL_PRECISION };
+    quantity.round_dp_with_strategy(dp, RoundingStrategy::ToZero)
+}
+
+/// Adds a trade worth at most `value_base` in `position` and returns its actual value,
+/// or zero when rounding or the minimum trade size rules it out.
+fn push_trade(
+    plan: &mut RebalancePlan,
+    position: &RebalancePosition,
+    key: &str,
+    side: TradeSide,
+    max_quantity: Decimal,
+    value_base: Decimal,
+    options: &RebalanceOptions,
+) -> Decimal {
+    let unit_value = position.unit_value_base();
+    if unit_value <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    let quantity = round_quantity((value_base / unit_value).min(max_quantity), options.whole_shares);
+    let trade_value = quantity * unit_value;
+    if quantity <= Decimal::ZERO || trade_value < options.min_trade_size {
+        return Decimal::ZERO;
+    }
+    plan.trades.push(RebalanceTrade {
+        account_id: position.account_id.clone(),
+        asset_id: position.asset_id.clone(),
+        symbol: position.symbol.clone(),
+        target_key: key.to_string(),
+        side,
+        quantity,
+        price: position.price,
+        currency: position.currency.clone(),
+        value: (quantity * position.price).round_dp(DECIMAL_PRECISION),
+        value_base: trade_value.round_dp(DECIMAL_PRECISION),
+    });
+    trade_value
+}
+
+fn sell_from_target(
+    key: &str,
+    excess: Decimal,
+    positions: &[RebalancePosition],
+    options: &RebalanceOptions,
+    plan: &mut RebalancePlan,
+) -> Decimal {
+    let mut holders: Vec<&RebalancePosition> = positions
+        .iter()
+        .filter(|p| p.quantity > Decimal::ZERO && p.exposure_to(key) > Decimal::ZERO)
+        .collect();
+    holders.sort_by(|a, b| {
+        (b.market_value() * b.exposure_to(key)).cmp(&(a.market_value() * a.exposure_to(key)))
+    });
+
+    let mut remaining = excess;
+    let mut proceeds = Decimal::ZERO;
+    for position in holders {
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        let fraction = position.exposure_to(key);
+        let sellable = position.sellable_quantity(options.avoid_selling_gains);
+        let value = push_trade(
+            plan,
+            position,
+            key,
+            TradeSide::Sell,
+            sellable,
+            remaining / fraction,
+            options,
+        );
+        remaining -= value * fraction;
+        proceeds += value;
+    }
+    if options.avoid_selling_gains && remaining >= options.min_trade_size.max(Decimal::ONE) {
+        plan.warnings.push(format!(
+            "{} of '{}' was not sold to avoid realising gains",
+            remaining.round_dp(2),
+            key
+        ));
+    }
+    proceeds
+}
+
+fn largest<'a>(
+    candidates: impl Iterator<Item = &'a RebalancePosition>,
+) -> Option<&'a RebalancePosition> {
+    candidates.max_by(|a, b| a.market_value().cmp(&b.market_value()))
+}
+
+fn buy_into_target(
+    model: &AllocationModel,
+    key: &str,
+    amount: Decimal,
+    positions: &[RebalancePosition],
+    options: &RebalanceOptions,
+    plan: &mut RebalancePlan,
+) -> Decimal {
+    if amount <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    let preferred = model
+        .targets
+        .iter()
+        .find(|t| t.key == key)
+        .map(|t| t.asset_ids.clone())
+        .unwrap_or_default();
+    let candidate = preferred
+        .iter()
+        .find_map(|asset_id| {
+            largest(
+                positions
+                    .iter()
+                    .filter(|p| p.asset_id.eq_ignore_ascii_case(asset_id)),
+            )
+        })
+        .or_else(|| {
+            largest(
+                positions
+                    .iter()
+                    .filter(|p| p.exposure_to(key) > Decimal::ZERO),
+            )
+        });
+
+    let Some(position) = candidate else {
+        plan.warnings.push(format!(
+            "No priced asset to buy for '{}'; add one to the target's assets",
+            key
+        ));
+        return Decimal::ZERO;
+    };
+    let fraction = position.exposure_to(key);
+    let fraction = if fraction > Decimal::ZERO {
+        fraction
+    } else {
+        Decimal::ONE
+    };
+    push_trade(
+        plan,
+        position,
+        key,
+        TradeSide::Buy,
+        Decimal::MAX,
+        amount / fraction,
+        options,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allocations::allocations_model::{AllocationScope, AllocationTarget};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn model(kind: AllocationTargetKind, targets: Vec<(&str, Decimal, Decimal)>) -> AllocationModel {
+        AllocationModel {
+            id: "m1".to_string(),
+            name: "Model".to_string(),
+            target_kind: kind,
+            scope_type: AllocationScope::Total,
+            scope_id: None,
+            targets: targets
+                .into_iter()
+                .map(|(key, target_weight, tolerance)| AllocationTarget {
+                    key: key.to_string(),
+                    target_weight,
+                    tolerance,
+                    asset_ids: Vec::new(),
+                })
+                .collect(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn position(asset_id: &str, quantity: Decimal, price: Decimal, cost_per_share: Decimal) -> RebalancePosition {
+        RebalancePosition {
+            account_id: "acc1".to_string(),
+            asset_id: asset_id.to_string(),
+            symbol: asset_id.to_string(),
+            currency: "USD".to_string(),
+            quantity,
+            price,
+            fx_rate: Decimal::ONE,
+            lots: vec![Lot {
+                id: format!("lot-{}", asset_id),
+                position_id: asset_id.to_string(),
+                acquisition_date: Utc::now(),
+                quantity,
+                cost_basis: quantity * cost_per_share,
+                acquisition_price: cost_per_share,
+                acquisition_fees: Decimal::ZERO,
+            }],
+            exposures: vec![(asset_id.to_string(), Decimal::ONE)],
+        }
+    }
+
+    fn trade<'a>(plan: &'a RebalancePlan, asset_id: &str) -> Option<&'a RebalanceTrade> {
+        plan.trades.iter().find(|t| t.asset_id == asset_id)
+    }
+
+    #[test]
+    fn test_drift_inside_band_proposes_no_trades() {
+        let model = model(
+            AllocationTargetKind::Asset,
+            vec![("VTI", dec!(0.5), dec!(0.05)), ("BND", dec!(0.5), dec!(0.05))],
+        );
+        let positions = vec![
+            position("VTI", dec!(52), dec!(10), dec!(8)),
+            position("BND", dec!(48), dec!(10), dec!(8)),
+        ];
+        let plan = plan_rebalance(&model, &positions, dec!(0), &RebalanceOptions::default(), "USD");
+
+        assert!(!plan.needs_rebalance);
+        assert_eq!(plan.drift[0].current_weight, dec!(0.52));
+        assert_eq!(plan.drift[0].drift, dec!(0.02));
+        assert!(plan.trades.is_empty());
+    }
+
+    #[test]
+    fn test_full_rebalance_sells_overweight_and_buys_underweight() {
+        let model = model(
+            AllocationTargetKind::Asset,
+            vec![("VTI", dec!(0.5), dec!(0.05)), ("BND", dec!(0.5), dec!(0.05))],
+        );
+        let positions = vec![
+            position("VTI", dec!(70), dec!(10), dec!(8)),
+            position("BND", dec!(30), dec!(10), dec!(8)),
+        ];
+        let plan = plan_rebalance(&model, &positions, dec!(0), &RebalanceOptions::default(), "USD");
+
+        assert!(plan.needs_rebalance);
+        let sell = trade(&plan, "VTI").unwrap();
+        assert_eq!(sell.side, TradeSide::Sell);
+        assert_eq!(sell.quantity, dec!(20));
+        let buy = trade(&plan, "BND").unwrap();
+        assert_eq!(buy.side, TradeSide::Buy);
+        assert_eq!(buy.quantity, dec!(20));
+        assert_eq!(plan.cash_after, dec!(0));
+    }
+
+    #[test]
+    fn test_cash_only_spends_contribution_on_underweight_targets() {
+        let model = model(
+            AllocationTargetKind::Asset,
+            vec![("VTI", dec!(0.6), dec!(0.05)), ("BND", dec!(0.4), dec!(0.05))],
+        );
+        let positions = vec![
+            position("VTI", dec!(70), dec!(10), dec!(8)),
+            position("BND", dec!(30), dec!(10), dec!(8)),
+        ];
+        let options = RebalanceOptions {
+            cash_only: true,
+            contribution: dec!(500),
+            ..Default::default()
+        };
+        let plan = plan_rebalance(&model, &positions, dec!(0), &options, "USD");
+
+        // Total 1,500: VTI wants 900 (has 700), BND wants 600 (has 300).
+        // The 500 is split in proportion to the 200 and 300 shortfalls.
+        assert!(plan.trades.iter().all(|t| t.side == TradeSide::Buy));
+        assert_eq!(trade(&plan, "VTI").unwrap().value_base, dec!(200));
+        assert_eq!(trade(&plan, "BND").unwrap().value_base, dec!(300));
+        assert_eq!(plan.cash_after, dec!(0));
+    }
+
+    #[test]
+    fn test_whole_shares_and_min_trade_size() {
+        let model = model(
+            AllocationTargetKind::Asset,
+            vec![("VTI", dec!(0.5), dec!(0)), ("BND", dec!(0.5), dec!(0))],
+        );
+        let positions = vec![
+            position("VTI", dec!(5), dec!(100), dec!(50)),
+            position("BND", dec!(501), dec!(1), dec!(1)),
+        ];
+        let options = RebalanceOptions {
+            cash_only: true,
+            contribution: dec!(250),
+            whole_shares: true,
+            min_trade_size: dec!(50),
+            ..Default::default()
+        };
+        let plan = plan_rebalance(&model, &positions, dec!(0), &options, "USD");
+
+        // Total 1,251: VTI is short 125.5 and BND 124.5, exactly the 250 contributed.
+        // VTI rounds down to one share; BND buys 124 units.
+        assert_eq!(trade(&plan, "VTI").unwrap().quantity, dec!(1));
+        assert_eq!(trade(&plan, "BND").unwrap().quantity, dec!(124));
+
+        let options = RebalanceOptions {
+            min_trade_size: dec!(150),
+            ..options
+        };
+        let plan = plan_rebalance(&model, &positions, dec!(0), &options, "USD");
+        assert!(plan.trades.is_empty());
+        assert_eq!(plan.cash_after, dec!(250));
+    }
+
+    #[test]
+    fn test_avoid_selling_gains_keeps_profitable_lots() {
+        let model = model(
+            AllocationTargetKind::Asset,
+            vec![("VTI", dec!(0.5), dec!(0.05)), ("BND", dec!(0.5), dec!(0.05))],
+        );
+        let mut vti = position("VTI", dec!(70), dec!(10), dec!(8));
+        vti.lots.push(Lot {
+            id: "lot-VTI-2".to_string(),
+            position_id: "VTI".to_string(),
+            acquisition_date: Utc::now(),
+            quantity: dec!(5),
+            cost_basis: dec!(60),
+            acquisition_price: dec!(12),
+            acquisition_fees: Decimal::ZERO,
+        });
+        vti.lots[0].quantity = dec!(65);
+        vti.lots[0].cost_basis = dec!(520);
+        let positions = vec![vti, position("BND", dec!(30), dec!(10), dec!(8))];
+        let options = RebalanceOptions {
+            avoid_selling_gains: true,
+            ..Default::default()
+        };
+        let plan = plan_rebalance(&model, &positions, dec!(0), &options, "USD");
+
+        // Only the 5 shares bought at 12 are under water.
+        assert_eq!(trade(&plan, "VTI").unwrap().quantity, dec!(5));
+        assert_eq!(trade(&plan, "BND").unwrap().quantity, dec!(5));
+        assert_eq!(plan.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_sector_exposure_splits_fund_value() {
+        let model = model(
+            AllocationTargetKind::Sector,
+            vec![("Technology", dec!(0.3), dec!(0.05)), ("Healthcare", dec!(0.3), dec!(0.05))],
+        );
+        let instrument = Instrument {
+            id: "QQQ".to_string(),
+            symbol: "QQQ".to_string(),
+            name: None,
+            currency: "USD".to_string(),
+            notes: None,
+            data_source: None,
+            asset_class: None,
+            asset_subclass: None,
+            countries: None,
+            sectors: Some(vec![
+                crate::portfolio::holdings::Sector {
+                    name: "technology".to_string(),
+                    weight: 60.0,
+                },
+                crate::portfolio::holdings::Sector {
+                    name: "Healthcare".to_string(),
+                    weight: 25.0,
+                },
+            ]),
+        };
+        let exposures = exposures_for(&model, &TargetKeys::new(&model), &instrument);
+
+        assert_eq!(
+            exposures,
+            vec![
+                ("Technology".to_string(), dec!(0.6)),
+                ("Healthcare".to_string(), dec!(0.25)),
+                (UNCLASSIFIED_KEY.to_string(), dec!(0.15)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+
+use super::allocations_model::{AllocationModel, AllocationModelDB};
+use super::allocations_traits::AllocationRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::allocation_models;
+
+pub struct AllocationRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl AllocationRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        AllocationRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl AllocationRepositoryTrait for AllocationRepository {
+    fn get_allocation_model(&self, id: &str) -> Result<AllocationModel> {
+        let mut conn = get_connection(&self.pool)?;
+        allocation_models::table
+            .find(id)
+            .first::<AllocationModelDB>(&mut conn)?
+            .to_model()
+    }
+
+    fn get_allocation_models(&self) -> Result<Vec<AllocationModel>> {
+        let mut conn = get_connection(&self.pool)?;
+        allocation_models::table
+            .order(allocation_models::name.asc())
+            .load::<AllocationModelDB>(&mut conn)?
+            .iter()
+            .map(AllocationModelDB::to_model)
+            .collect()
+    }
+
+    async fn create_allocation_model(&self, model: AllocationModelDB) -> Result<AllocationModel> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<AllocationModel> {
+                diesel::insert_into(allocation_models::table)
+                    .values(&model)
+                    .get_result::<AllocationModelDB>(conn)?
+                    .to_model()
+            })
+            .await
+    }
+
+    async fn update_allocation_model(&self, model: AllocationModelDB) -> Result<AllocationModel> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<AllocationModel> {
+                diesel::update(allocation_models::table.find(&model.id))
+                    .set((
+                        allocation_models::name.eq(&model.name),
+                        allocation_models::target_kind.eq(&model.target_kind),
+                        allocation_models::scope_type.eq(&model.scope_type),
+                        allocation_models::scope_id.eq(&model.scope_id),
+                        allocation_models::targets.eq(&model.targets),
+                        allocation_models::updated_at.eq(&model.updated_at),
+                    ))
+                    .get_result::<AllocationModelDB>(conn)?
+                    .to_model()
+            })
+            .await
+    }
+
+    async fn delete_allocation_model(&self, id: &str) -> Result<()> {
+        let id = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::delete(allocation_models::table.find(id)).execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+}
@@ -0,0 +1,232 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use log::warn;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::allocations_model::{
+    AllocationModel, AllocationModelDB, AllocationScope, AllocationTargetKind, NewAllocationModel,
+};
+use super::allocations_rebalance::{
+    plan_rebalance, positions_from_holdings, RebalanceOptions, RebalancePlan, RebalancePosition,
+};
+use super::allocations_traits::{AllocationRepositoryTrait, AllocationServiceTrait};
+use crate::accounts::AccountServiceTrait;
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::fx_traits::FxServiceTrait;
+use crate::market_data::MarketDataServiceTrait;
+use crate::portfolio::holdings::HoldingsServiceTrait;
+
+pub struct AllocationService {
+    repository: Arc<dyn AllocationRepositoryTrait>,
+    account_service: Arc<dyn AccountServiceTrait>,
+    holdings_service: Arc<dyn HoldingsServiceTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+}
+
+impl AllocationService {
+    pub fn new(
+        repository: Arc<dyn AllocationRepositoryTrait>,
+        account_service: Arc<dyn AccountServiceTrait>,
+        holdings_service: Arc<dyn HoldingsServiceTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+    ) -> Self {
+        AllocationService {
+            repository,
+            account_service,
+            holdings_service,
+            market_data_service,
+            fx_service,
+        }
+    }
+
+    fn to_db(
+        &self,
+        id: String,
+        model: NewAllocationModel,
+        created_at: String,
+    ) -> Result<AllocationModelDB> {
+        model.validate()?;
+        let scope_id = match model.scope_type {
+            AllocationScope::Total => None,
+            _ => model.scope_id.map(|id| id.trim().to_string()),
+        };
+        if let (AllocationScope::Account, Some(account_id)) = (model.scope_type, &scope_id) {
+            self.account_service.get_account(account_id)?;
+        }
+        Ok(AllocationModelDB {
+            id,
+            name: model.name.trim().to_string(),
+            target_kind: model.target_kind.as_str().to_string(),
+            scope_type: model.scope_type.as_str().to_string(),
+            scope_id,
+            targets: serde_json::to_string(&model.targets)?,
+            created_at,
+            updated_at: Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Ids of the active accounts an allocation model covers.
+    fn scope_account_ids(&self, model: &AllocationModel) -> Result<Vec<String>> {
+        let scope_id = model.scope_id.as_deref().unwrap_or_default();
+        let account_ids: Vec<String> = match model.scope_type {
+            AllocationScope::Account => vec![self.account_service.get_account(scope_id)?.id],
+            AllocationScope::Group => self
+                .account_service
+                .get_active_accounts()?
+                .into_iter()
+                .filter(|a| a.group.as_deref().map(str::trim) == Some(scope_id))
+                .map(|a| a.id)
+                .collect(),
+            AllocationScope::Total => self
+                .account_service
+                .get_active_accounts()?
+                .into_iter()
+                .map(|a| a.id)
+                .collect(),
+        };
+        if account_ids.is_empty() {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Allocation model '{}' covers no active accounts",
+                model.name
+            ))));
+        }
+        Ok(account_ids)
+    }
+
+    /// Prices the first asset a target prefers when none of its preferred assets is held,
+    /// so the plan can buy into targets the scope does not hold yet. The position goes to
+    /// the account holding the most cash.
+    fn unheld_target_positions(
+        &self,
+        model: &AllocationModel,
+        positions: &[RebalancePosition],
+        cash_by_account: &[(String, Decimal)],
+        default_account_id: &str,
+        base_currency: &str,
+        warnings: &mut Vec<String>,
+    ) -> Vec<RebalancePosition> {
+        let account_id = cash_by_account
+            .iter()
+            .max_by(|a, b| a.1.cmp(&b.1))
+            .map(|(id, _)| id.as_str())
+            .unwrap_or(default_account_id);
+
+        let mut unheld = Vec::new();
+        for target in &model.targets {
+            let preferred = if target.asset_ids.is_empty()
+                && model.target_kind == AllocationTargetKind::Asset
+            {
+                vec![target.key.clone()]
+            } else {
+                target.asset_ids.clone()
+            };
+            let is_held = preferred.iter().any(|asset_id| {
+                positions
+                    .iter()
+                    .any(|p| p.asset_id.eq_ignore_ascii_case(asset_id))
+            });
+            let Some(asset_id) = preferred.first().filter(|_| !is_held) else {
+                continue;
+            };
+
+            let priced = self
+                .market_data_service
+                .get_latest_quote_for_symbol(asset_id)
+                .and_then(|quote| {
+                    let fx_rate = self
+                        .fx_service
+                        .get_latest_exchange_rate(&quote.currency, base_currency)?;
+                    Ok((quote, fx_rate))
+                });
+            match priced {
+                Ok((quote, fx_rate)) => unheld.push(RebalancePosition {
+                    account_id: account_id.to_string(),
+                    asset_id: asset_id.clone(),
+                    symbol: quote.symbol.clone(),
+                    currency: quote.currency.clone(),
+                    quantity: Decimal::ZERO,
+                    price: quote.close,
+                    fx_rate,
+                    lots: Vec::new(),
+                    exposures: vec![(target.key.clone(), Decimal::ONE)],
+                }),
+                Err(e) => {
+                    warn!("Could not price {} for rebalancing: {}", asset_id, e);
+                    warnings.push(format!("No price for {}, which '{}' buys into", asset_id, target.key));
+                }
+            }
+        }
+        unheld
+    }
+}
+
+#[async_trait]
+impl AllocationServiceTrait for AllocationService {
+    fn get_allocation_models(&self) -> Result<Vec<AllocationModel>> {
+        self.repository.get_allocation_models()
+    }
+
+    async fn create_allocation_model(&self, new_model: NewAllocationModel) -> Result<AllocationModel> {
+        let id = new_model
+            .id
+            .clone()
+            .filter(|id| !id.trim().is_empty())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let model_db = self.to_db(id, new_model, Utc::now().to_rfc3339())?;
+        self.repository.create_allocation_model(model_db).await
+    }
+
+    async fn update_allocation_model(
+        &self,
+        id: &str,
+        updated_model: NewAllocationModel,
+    ) -> Result<AllocationModel> {
+        let existing = self.repository.get_allocation_model(id)?;
+        let model_db = self.to_db(existing.id, updated_model, existing.created_at)?;
+        self.repository.update_allocation_model(model_db).await
+    }
+
+    async fn delete_allocation_model(&self, id: &str) -> Result<()> {
+        self.repository.delete_allocation_model(id).await
+    }
+
+    async fn calculate_rebalance(
+        &self,
+        model_id: &str,
+        options: RebalanceOptions,
+        base_currency: &str,
+    ) -> Result<RebalancePlan> {
+        let model = self.repository.get_allocation_model(model_id)?;
+        let account_ids = self.scope_account_ids(&model)?;
+
+        let mut holdings = Vec::new();
+        for account_id in &account_ids {
+            holdings.extend(
+                self.holdings_service
+                    .get_holdings(account_id, base_currency)
+                    .await?,
+            );
+        }
+
+        let (mut positions, cash_by_account) = positions_from_holdings(&model, &holdings);
+        let mut warnings = Vec::new();
+        let unheld = self.unheld_target_positions(
+            &model,
+            &positions,
+            &cash_by_account,
+            &account_ids[0],
+            base_currency,
+            &mut warnings,
+        );
+        positions.extend(unheld);
+
+        let cash: Decimal = cash_by_account.iter().map(|(_, amount)| *amount).sum();
+        let mut plan = plan_rebalance(&model, &positions, cash, &options, base_currency);
+        plan.warnings.extend(warnings);
+        Ok(plan)
+    }
+}
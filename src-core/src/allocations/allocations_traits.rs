@@ -0,0 +1,34 @@
+use super::allocations_model::{AllocationModel, AllocationModelDB, NewAllocationModel};
+use super::allocations_rebalance::{RebalanceOptions, RebalancePlan};
+use crate::errors::Result;
+use async_trait::async_trait;
+
+/// Trait defining the contract for allocation model repository operations.
+#[async_trait]
+pub trait AllocationRepositoryTrait: Send + Sync {
+    fn get_allocation_model(&self, id: &str) -> Result<AllocationModel>;
+    fn get_allocation_models(&self) -> Result<Vec<AllocationModel>>;
+    async fn create_allocation_model(&self, model: AllocationModelDB) -> Result<AllocationModel>;
+    async fn update_allocation_model(&self, model: AllocationModelDB) -> Result<AllocationModel>;
+    async fn delete_allocation_model(&self, id: &str) -> Result<()>;
+}
+
+/// Trait defining the contract for allocation model and rebalancing operations.
+#[async_trait]
+pub trait AllocationServiceTrait: Send + Sync {
+    fn get_allocation_models(&self) -> Result<Vec<AllocationModel>>;
+    async fn create_allocation_model(&self, new_model: NewAllocationModel) -> Result<AllocationModel>;
+    async fn update_allocation_model(
+        &self,
+        id: &str,
+        updated_model: NewAllocationModel,
+    ) -> Result<AllocationModel>;
+    async fn delete_allocation_model(&self, id: &str) -> Result<()>;
+    /// Measures the model's scope against its targets and proposes trades back to target.
+    async fn calculate_rebalance(
+        &self,
+        model_id: &str,
+        options: RebalanceOptions,
+        base_currency: &str,
+    ) -> Result<RebalancePlan>;
+}
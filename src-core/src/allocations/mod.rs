@@ -0,0 +1,16 @@
+mod allocations_model;
+mod allocations_rebalance;
+mod allocations_repository;
+mod allocations_service;
+mod allocations_traits;
+
+pub use allocations_model::{
+    AllocationModel, AllocationScope, AllocationTarget, AllocationTargetKind, NewAllocationModel,
+};
+pub use allocations_rebalance::{
+    plan_rebalance, AllocationDrift, RebalanceOptions, RebalancePlan, RebalancePosition,
+    RebalanceTrade, TradeSide, UNCLASSIFIED_KEY,
+};
+pub use allocations_repository::AllocationRepository;
+pub use allocations_service::AllocationService;
+pub use allocations_traits::{AllocationRepositoryTrait, AllocationServiceTrait};
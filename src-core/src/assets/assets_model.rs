@@ -32,6 +32,10 @@ pub struct Asset {
     pub data_source: String,
     pub sectors: Option<String>,
     pub url: Option<String>,
+    /// Forward dividend yield from the market data provider, e.g. 0.025.
+    pub dividend_yield: Option<f64>,
+    /// Total expense ratio of a fund, entered by the user, e.g. 0.0020 for 0.20%.
+    pub expense_ratio: Option<f64>,
 }
 
 
@@ -84,6 +88,10 @@ pub struct NewAsset {
     pub data_source: String,
     pub sectors: Option<String>,
     pub url: Option<String>,
+    /// Forward dividend yield from the market data provider, e.g. 0.025.
+    pub dividend_yield: Option<f64>,
+    /// Total expense ratio of a fund, entered by the user, e.g. 0.0020 for 0.20%.
+    pub expense_ratio: Option<f64>,
 }
 
 impl NewAsset {
@@ -161,6 +169,8 @@ impl From<crate::market_data::providers::models::AssetProfile> for NewAsset {
             data_source: profile.data_source,
             sectors: profile.sectors,
             url: profile.url,
+            dividend_yield: profile.dividend_yield,
+            expense_ratio: None,
         }
     }
 }
@@ -226,6 +236,10 @@ pub struct AssetDB {
     pub data_source: String,
     pub sectors: Option<String>,
     pub url: Option<String>,
+    /// Forward dividend yield from the market data provider, e.g. 0.025.
+    pub dividend_yield: Option<f64>,
+    /// Total expense ratio of a fund, entered by the user, e.g. 0.0020 for 0.20%.
+    pub expense_ratio: Option<f64>,
 }
 
 // Conversion implementations
@@ -251,6 +265,8 @@ impl From<AssetDB> for Asset {
             data_source: db.data_source,
             sectors: db.sectors,
             url: db.url,
+            dividend_yield: db.dividend_yield,
+            expense_ratio: db.expense_ratio,
         }
     }
 }
@@ -278,6 +294,8 @@ impl From<NewAsset> for AssetDB {
             data_source: domain.data_source,
             sectors: domain.sectors,
             url: domain.url,
+            dividend_yield: domain.dividend_yield,
+            expense_ratio: domain.expense_ratio,
         }
     }
 }
@@ -5,6 +5,11 @@ use log::debug;
 use std::sync::Arc;
 use async_trait::async_trait;
 
+use crate::audit::audit_repository::record_change;
+use crate::audit::{
+    CHANGE_ACTOR_IMPORT, CHANGE_ACTOR_SYSTEM, CHANGE_ACTOR_USER, CHANGE_OPERATION_CREATE,
+    CHANGE_OPERATION_UPDATE, ENTITY_TYPE_ASSET,
+};
 use crate::db::{get_connection, WriteHandle};
 use crate::schema::assets;
 use crate::errors::Result;
@@ -18,6 +23,19 @@ pub struct AssetRepository {
     writer: WriteHandle,
 }
 
+/// Logs an asset update, inside the caller's transaction.
+fn record_update(conn: &mut SqliteConnection, before: &AssetDB, after: &AssetDB, actor: &str) -> Result<()> {
+    record_change(
+        conn,
+        ENTITY_TYPE_ASSET,
+        &after.id,
+        CHANGE_OPERATION_UPDATE,
+        actor,
+        Some(before),
+        Some(after),
+    )
+}
+
 impl AssetRepository {
     /// Creates a new AssetRepository instance
     pub fn new(pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
@@ -80,6 +98,16 @@ impl AssetRepositoryTrait for AssetRepository {
                 let result_db = diesel::insert_into(assets::table)
                     .values(&asset_db)
                     .get_result::<AssetDB>(conn)?;
+                // Assets are created on demand when a new symbol is first used
+                record_change(
+                    conn,
+                    ENTITY_TYPE_ASSET,
+                    &result_db.id,
+                    CHANGE_OPERATION_CREATE,
+                    CHANGE_ACTOR_SYSTEM,
+                    None,
+                    Some(&result_db),
+                )?;
                 Ok(result_db.into())
             })
             .await
@@ -93,6 +121,7 @@ impl AssetRepositoryTrait for AssetRepository {
 
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<Asset> {
+                let existing = assets::table.find(&asset_id_owned).first::<AssetDB>(conn)?;
                 let result_db = diesel::update(assets::table.filter(assets::id.eq(asset_id_owned)))
                     .set((
                         assets::name.eq(&payload_owned.name),
@@ -103,6 +132,7 @@ impl AssetRepositoryTrait for AssetRepository {
                         assets::asset_class.eq(&payload_owned.asset_class),
                     ))
                     .get_result::<AssetDB>(conn)?;
+                record_update(conn, &existing, &result_db, CHANGE_ACTOR_USER)?;
                 Ok(result_db.into())
             })
             .await
@@ -114,9 +144,47 @@ impl AssetRepositoryTrait for AssetRepository {
         let asset_id_owned = asset_id.to_string();
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<Asset> {
+                let existing = assets::table.find(&asset_id_owned).first::<AssetDB>(conn)?;
                 let result_db = diesel::update(assets::table.filter(assets::id.eq(asset_id_owned)))
                     .set(assets::data_source.eq(data_source))
                     .get_result::<AssetDB>(conn)?;
+                record_update(conn, &existing, &result_db, CHANGE_ACTOR_USER)?;
+                Ok(result_db.into())
+            })
+            .await
+    }
+
+    /// Updates the ISIN of an asset
+    async fn update_isin(&self, asset_id: &str, isin: String) -> Result<Asset> {
+        debug!("Updating ISIN for asset {} to {}", asset_id, isin);
+        let asset_id_owned = asset_id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Asset> {
+                let existing = assets::table.find(&asset_id_owned).first::<AssetDB>(conn)?;
+                let result_db = diesel::update(assets::table.filter(assets::id.eq(asset_id_owned)))
+                    .set(assets::isin.eq(isin))
+                    .get_result::<AssetDB>(conn)?;
+                record_update(conn, &existing, &result_db, CHANGE_ACTOR_IMPORT)?;
+                Ok(result_db.into())
+            })
+            .await
+    }
+
+    /// Sets or clears the total expense ratio of an asset
+    async fn update_expense_ratio(
+        &self,
+        asset_id: &str,
+        expense_ratio: Option<f64>,
+    ) -> Result<Asset> {
+        debug!("Updating expense ratio for asset {} to {:?}", asset_id, expense_ratio);
+        let asset_id_owned = asset_id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Asset> {
+                let existing = assets::table.find(&asset_id_owned).first::<AssetDB>(conn)?;
+                let result_db = diesel::update(assets::table.filter(assets::id.eq(asset_id_owned)))
+                    .set(assets::expense_ratio.eq(expense_ratio))
+                    .get_result::<AssetDB>(conn)?;
+                record_update(conn, &existing, &result_db, CHANGE_ACTOR_USER)?;
                 Ok(result_db.into())
             })
             .await
@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use crate::market_data::market_data_traits::MarketDataServiceTrait;
 
-use crate::errors::{Error, Result, DatabaseError};
+use crate::errors::{Error, Result, DatabaseError, ValidationError};
 use diesel::result::Error as DieselError;
 use super::assets_model::{Asset, NewAsset, UpdateAssetProfile};
 use super::assets_traits::{AssetRepositoryTrait, AssetServiceTrait};
@@ -98,6 +98,30 @@ impl AssetServiceTrait for AssetService {
         self.asset_repository.update_data_source(asset_id, data_source).await
     }
 
+    /// Records the ISIN of an asset, as reported by an imported statement
+    async fn update_asset_isin(&self, asset_id: &str, isin: String) -> Result<Asset> {
+        self.asset_repository.update_isin(asset_id, isin).await
+    }
+
+    /// Sets or clears the fund's total expense ratio
+    async fn update_asset_expense_ratio(
+        &self,
+        asset_id: &str,
+        expense_ratio: Option<f64>,
+    ) -> Result<Asset> {
+        if let Some(ratio) = expense_ratio {
+            if !ratio.is_finite() || !(0.0..1.0).contains(&ratio) {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "Expense ratio must be a fraction between 0 and 1, got {}",
+                    ratio
+                ))));
+            }
+        }
+        self.asset_repository
+            .update_expense_ratio(asset_id, expense_ratio)
+            .await
+    }
+
     async fn get_assets_by_symbols(&self, symbols: &Vec<String>) -> Result<Vec<Asset>> {
         self.asset_repository.list_by_symbols(symbols)
     }
@@ -11,6 +11,13 @@ pub trait AssetServiceTrait: Send + Sync {
     async fn create_cash_asset(&self, currency: &str) -> Result<Asset>;
     async fn get_or_create_asset(&self, asset_id: &str, context_currency: Option<String>) -> Result<Asset>;
     async fn update_asset_data_source(&self, asset_id: &str, data_source: String) -> Result<Asset>;
+    async fn update_asset_isin(&self, asset_id: &str, isin: String) -> Result<Asset>;
+    /// Sets or clears the fund's total expense ratio, as a yearly fraction.
+    async fn update_asset_expense_ratio(
+        &self,
+        asset_id: &str,
+        expense_ratio: Option<f64>,
+    ) -> Result<Asset>;
     async fn get_assets_by_symbols(&self, symbols: &Vec<String>) -> Result<Vec<Asset>>;
 }
 
@@ -20,6 +27,9 @@ pub trait AssetRepositoryTrait: Send + Sync {
     async fn create(&self, new_asset: NewAsset) -> Result<Asset>;
     async fn update_profile(&self, asset_id: &str, payload: UpdateAssetProfile) -> Result<Asset>;
     async fn update_data_source(&self, asset_id: &str, data_source: String) -> Result<Asset>;
+    async fn update_isin(&self, asset_id: &str, isin: String) -> Result<Asset>;
+    async fn update_expense_ratio(&self, asset_id: &str, expense_ratio: Option<f64>)
+        -> Result<Asset>;
     fn get_by_id(&self, asset_id: &str) -> Result<Asset>;
     fn list(&self) -> Result<Vec<Asset>>;
     fn list_cash_assets(&self, base_currency: &str) -> Result<Vec<Asset>>;
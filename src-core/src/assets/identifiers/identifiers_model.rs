@@ -0,0 +1,205 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Where a cached identifier mapping came from
+pub const IDENTIFIER_SOURCE_ASSET: &str = "ASSET";
+pub const IDENTIFIER_SOURCE_SEARCH: &str = "SEARCH";
+pub const IDENTIFIER_SOURCE_MANUAL: &str = "MANUAL";
+
+/// Security identifier schemes that can be resolved to a provider symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum IdentifierType {
+    Isin,
+    Cusip,
+    Sedol,
+    Figi,
+}
+
+impl IdentifierType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentifierType::Isin => "ISIN",
+            IdentifierType::Cusip => "CUSIP",
+            IdentifierType::Sedol => "SEDOL",
+            IdentifierType::Figi => "FIGI",
+        }
+    }
+
+    /// Recognizes `value` by length and check digit. Returns None for
+    /// anything that is not a well-formed identifier, including tickers.
+    pub fn detect(value: &str) -> Option<Self> {
+        let value = value.trim().to_uppercase();
+        if !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+        match value.len() {
+            // FIGIs start with BBG and would otherwise pass as ISINs from a "BB" country
+            12 if is_valid_figi(&value) => Some(IdentifierType::Figi),
+            12 if is_valid_isin(&value) => Some(IdentifierType::Isin),
+            9 if is_valid_cusip(&value) => Some(IdentifierType::Cusip),
+            7 if is_valid_sedol(&value) => Some(IdentifierType::Sedol),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for IdentifierType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IdentifierType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ISIN" => Ok(IdentifierType::Isin),
+            "CUSIP" => Ok(IdentifierType::Cusip),
+            "SEDOL" => Ok(IdentifierType::Sedol),
+            "FIGI" => Ok(IdentifierType::Figi),
+            _ => Err(format!("Unknown identifier type: {}", s)),
+        }
+    }
+}
+
+/// Cached mapping from a security identifier to a provider symbol
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, Queryable, Selectable, Insertable, AsChangeset,
+)]
+#[diesel(table_name = crate::schema::symbol_identifiers)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolIdentifier {
+    pub identifier: String,
+    pub identifier_type: String,
+    pub symbol: String,
+    /// ISIN of the security, when the identifier is one or was derived from one
+    pub isin: Option<String>,
+    pub name: Option<String>,
+    pub exchange: Option<String>,
+    pub source: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Numeric value of an identifier character: digits as is, letters from 10.
+fn char_value(c: char) -> Option<u32> {
+    c.to_digit(36)
+}
+
+/// Sum of the digits of `value`, as used by Luhn-style checks.
+fn digit_sum(value: u32) -> u32 {
+    value / 10 + value % 10
+}
+
+/// Check digit shared by CUSIP and FIGI: every second character is doubled
+/// and the digits of each product are summed.
+fn modulus10_double_add_double(payload: &str) -> Option<u32> {
+    let mut sum = 0;
+    for (index, c) in payload.chars().enumerate() {
+        let mut value = char_value(c)?;
+        if index % 2 == 1 {
+            value *= 2;
+        }
+        sum += digit_sum(value);
+    }
+    Some((10 - sum % 10) % 10)
+}
+
+fn check_digit_matches(value: &str, expected: Option<u32>) -> bool {
+    value.chars().last().and_then(|c| c.to_digit(10)) == expected && expected.is_some()
+}
+
+/// ISIN: two-letter country code, nine-character national id and a Luhn
+/// check digit computed over the letters expanded to two digits.
+pub fn is_valid_isin(value: &str) -> bool {
+    if value.len() != 12 || !value[..2].chars().all(|c| c.is_ascii_uppercase()) {
+        return false;
+    }
+    let digits: Option<String> = value[..11]
+        .chars()
+        .map(|c| char_value(c).map(|v| v.to_string()))
+        .collect();
+    let Some(digits) = digits else {
+        return false;
+    };
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .enumerate()
+        .map(|(index, c)| {
+            let value = c.to_digit(10).unwrap_or(0);
+            if index % 2 == 0 {
+                digit_sum(value * 2)
+            } else {
+                value
+            }
+        })
+        .sum();
+    check_digit_matches(value, Some((10 - sum % 10) % 10))
+}
+
+pub fn is_valid_cusip(value: &str) -> bool {
+    value.len() == 9 && check_digit_matches(value, modulus10_double_add_double(&value[..8]))
+}
+
+/// SEDOL: six characters without vowels and a weighted check digit.
+pub fn is_valid_sedol(value: &str) -> bool {
+    const WEIGHTS: [u32; 6] = [1, 3, 1, 7, 3, 9];
+    if value.len() != 7 || value.chars().any(|c| "AEIOU".contains(c)) {
+        return false;
+    }
+    let sum: Option<u32> = value[..6]
+        .chars()
+        .zip(WEIGHTS)
+        .map(|(c, weight)| char_value(c).map(|v| v * weight))
+        .sum();
+    check_digit_matches(value, sum.map(|s| (10 - s % 10) % 10))
+}
+
+/// FIGI: `BBG` prefix, eight characters without vowels and a CUSIP-style check digit.
+pub fn is_valid_figi(value: &str) -> bool {
+    value.len() == 12
+        && value.starts_with("BBG")
+        && !value[3..11].chars().any(|c| "AEIOU".contains(c))
+        && check_digit_matches(value, modulus10_double_add_double(&value[..11]))
+}
+
+/// Builds an ISIN from a country code and national id by appending its check digit.
+pub fn isin_from_national_id(country_code: &str, national_id: &str) -> Option<String> {
+    let payload = format!("{}{:0>9}", country_code, national_id);
+    if payload.len() != 11 {
+        return None;
+    }
+    (0..10)
+        .map(|digit| format!("{}{}", payload, digit))
+        .find(|candidate| is_valid_isin(candidate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_identifier_types() {
+        assert_eq!(IdentifierType::detect("US0378331005"), Some(IdentifierType::Isin));
+        assert_eq!(IdentifierType::detect("ie00b4l5y983"), Some(IdentifierType::Isin));
+        assert_eq!(IdentifierType::detect("037833100"), Some(IdentifierType::Cusip));
+        assert_eq!(IdentifierType::detect("2046251"), Some(IdentifierType::Sedol));
+        assert_eq!(IdentifierType::detect("BBG000B9XRY4"), Some(IdentifierType::Figi));
+
+        assert_eq!(IdentifierType::detect("US0378331006"), None);
+        assert_eq!(IdentifierType::detect("AAPL"), None);
+        assert_eq!(IdentifierType::detect("XEQT.TO"), None);
+    }
+
+    #[test]
+    fn test_isin_from_national_id() {
+        assert_eq!(isin_from_national_id("US", "037833100").as_deref(), Some("US0378331005"));
+        assert_eq!(isin_from_national_id("GB", "BH4HKS3").as_deref(), Some("GB00BH4HKS39"));
+    }
+}
@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{self, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::{assets, symbol_identifiers};
+
+use super::identifiers_model::SymbolIdentifier;
+use super::identifiers_traits::IdentifierRepositoryTrait;
+
+/// Repository for the identifier to symbol cache
+pub struct IdentifierRepository {
+    pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl IdentifierRepository {
+    pub fn new(pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        Self { pool, writer }
+    }
+}
+
+#[async_trait]
+impl IdentifierRepositoryTrait for IdentifierRepository {
+    fn get(&self, identifier: &str) -> Result<Option<SymbolIdentifier>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(symbol_identifiers::table
+            .find(identifier)
+            .first::<SymbolIdentifier>(&mut conn)
+            .optional()?)
+    }
+
+    fn list(&self) -> Result<Vec<SymbolIdentifier>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(symbol_identifiers::table
+            .order(symbol_identifiers::identifier.asc())
+            .load::<SymbolIdentifier>(&mut conn)?)
+    }
+
+    fn find_asset_id_by_isin(&self, isin: &str) -> Result<Option<String>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(assets::table
+            .filter(assets::isin.eq(isin))
+            .select(assets::id)
+            .first::<String>(&mut conn)
+            .optional()?)
+    }
+
+    async fn upsert(&self, mapping: SymbolIdentifier) -> Result<SymbolIdentifier> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<SymbolIdentifier> {
+                let saved = diesel::insert_into(symbol_identifiers::table)
+                    .values(&mapping)
+                    .on_conflict(symbol_identifiers::identifier)
+                    .do_update()
+                    .set((
+                        symbol_identifiers::identifier_type.eq(&mapping.identifier_type),
+                        symbol_identifiers::symbol.eq(&mapping.symbol),
+                        symbol_identifiers::isin.eq(&mapping.isin),
+                        symbol_identifiers::name.eq(&mapping.name),
+                        symbol_identifiers::exchange.eq(&mapping.exchange),
+                        symbol_identifiers::source.eq(&mapping.source),
+                        symbol_identifiers::updated_at.eq(&mapping.updated_at),
+                    ))
+                    .get_result::<SymbolIdentifier>(conn)?;
+                Ok(saved)
+            })
+            .await
+    }
+
+    async fn delete(&self, identifier: &str) -> Result<()> {
+        let identifier_owned = identifier.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::delete(symbol_identifiers::table.find(identifier_owned)).execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+}
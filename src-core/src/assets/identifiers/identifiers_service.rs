@@ -0,0 +1,161 @@
+use chrono::Utc;
+use log::debug;
+use std::sync::Arc;
+
+use crate::errors::{Error, Result};
+use crate::market_data::market_data_traits::MarketDataServiceTrait;
+use crate::market_data::QuoteSummary;
+
+use super::identifiers_model::*;
+use super::identifiers_traits::{IdentifierRepositoryTrait, IdentifierServiceTrait};
+
+/// Resolves security identifiers to provider symbols, caching every hit.
+///
+/// Lookup order: the cache, local assets carrying the ISIN, then a provider
+/// search. CUSIPs and SEDOLs are also searched as the US and GB ISINs they
+/// embed, since providers index ISINs far more consistently.
+pub struct IdentifierService {
+    repository: Arc<dyn IdentifierRepositoryTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+}
+
+impl IdentifierService {
+    pub fn new(
+        repository: Arc<dyn IdentifierRepositoryTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            market_data_service,
+        }
+    }
+
+    /// Identifiers to search for, most specific first, with the ISIN each implies.
+    fn search_queries(identifier: &str, identifier_type: IdentifierType) -> Vec<(String, Option<String>)> {
+        match identifier_type {
+            IdentifierType::Isin => vec![(identifier.to_string(), Some(identifier.to_string()))],
+            IdentifierType::Cusip => {
+                let mut queries = vec![(identifier.to_string(), None)];
+                if let Some(isin) = isin_from_national_id("US", identifier) {
+                    queries.push((isin.clone(), Some(isin)));
+                }
+                queries
+            }
+            IdentifierType::Sedol => {
+                let mut queries = vec![(identifier.to_string(), None)];
+                if let Some(isin) = isin_from_national_id("GB", identifier) {
+                    queries.push((isin.clone(), Some(isin)));
+                }
+                queries
+            }
+            IdentifierType::Figi => vec![(identifier.to_string(), None)],
+        }
+    }
+
+    fn best_match(results: Vec<QuoteSummary>) -> Option<QuoteSummary> {
+        results
+            .into_iter()
+            .filter(|result| !result.symbol.is_empty())
+            .max_by(|a, b| a.score.total_cmp(&b.score))
+    }
+
+    fn new_mapping(
+        identifier: &str,
+        identifier_type: IdentifierType,
+        symbol: String,
+        isin: Option<String>,
+        source: &str,
+    ) -> SymbolIdentifier {
+        let now = Utc::now().to_rfc3339();
+        SymbolIdentifier {
+            identifier: identifier.to_string(),
+            identifier_type: identifier_type.as_str().to_string(),
+            symbol,
+            isin,
+            name: None,
+            exchange: None,
+            source: source.to_string(),
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IdentifierServiceTrait for IdentifierService {
+    async fn resolve(&self, identifier: &str) -> Result<Option<SymbolIdentifier>> {
+        let identifier = identifier.trim().to_uppercase();
+        let Some(identifier_type) = IdentifierType::detect(&identifier) else {
+            return Ok(None);
+        };
+        if let Some(cached) = self.repository.get(&identifier)? {
+            return Ok(Some(cached));
+        }
+
+        let queries = Self::search_queries(&identifier, identifier_type);
+        for (_, isin) in &queries {
+            if let Some(isin) = isin {
+                if let Some(asset_id) = self.repository.find_asset_id_by_isin(isin)? {
+                    let mapping = Self::new_mapping(
+                        &identifier,
+                        identifier_type,
+                        asset_id,
+                        Some(isin.clone()),
+                        IDENTIFIER_SOURCE_ASSET,
+                    );
+                    return self.repository.upsert(mapping).await.map(Some);
+                }
+            }
+        }
+
+        for (query, isin) in queries {
+            let results = match self.market_data_service.search_symbol(&query).await {
+                Ok(results) => results,
+                Err(e) => {
+                    debug!("Identifier search for '{}' failed: {}", query, e);
+                    continue;
+                }
+            };
+            if let Some(found) = Self::best_match(results) {
+                debug!("Resolved {} {} to {}", identifier_type, identifier, found.symbol);
+                let mut mapping = Self::new_mapping(
+                    &identifier,
+                    identifier_type,
+                    found.symbol,
+                    isin,
+                    IDENTIFIER_SOURCE_SEARCH,
+                );
+                mapping.name = Some(found.long_name)
+                    .filter(|n| !n.is_empty())
+                    .or(Some(found.short_name).filter(|n| !n.is_empty()));
+                mapping.exchange = Some(found.exchange).filter(|e| !e.is_empty());
+                return self.repository.upsert(mapping).await.map(Some);
+            }
+        }
+
+        debug!("No symbol found for {} {}", identifier_type, identifier);
+        Ok(None)
+    }
+
+    fn get_identifier_mappings(&self) -> Result<Vec<SymbolIdentifier>> {
+        self.repository.list()
+    }
+
+    async fn save_identifier_mapping(&self, identifier: &str, symbol: &str) -> Result<SymbolIdentifier> {
+        let identifier = identifier.trim().to_uppercase();
+        let identifier_type = IdentifierType::detect(&identifier).ok_or_else(|| {
+            Error::Asset(format!("'{}' is not a valid ISIN, CUSIP, SEDOL or FIGI", identifier))
+        })?;
+        let symbol = symbol.trim().to_uppercase();
+        if symbol.is_empty() {
+            return Err(Error::Asset("Symbol cannot be empty".to_string()));
+        }
+        let isin = (identifier_type == IdentifierType::Isin).then(|| identifier.clone());
+        let mapping = Self::new_mapping(&identifier, identifier_type, symbol, isin, IDENTIFIER_SOURCE_MANUAL);
+        self.repository.upsert(mapping).await
+    }
+
+    async fn delete_identifier_mapping(&self, identifier: &str) -> Result<()> {
+        self.repository.delete(&identifier.trim().to_uppercase()).await
+    }
+}
@@ -0,0 +1,25 @@
+use super::identifiers_model::SymbolIdentifier;
+use crate::errors::Result;
+
+/// Trait defining the contract for the identifier cache.
+#[async_trait::async_trait]
+pub trait IdentifierRepositoryTrait: Send + Sync {
+    fn get(&self, identifier: &str) -> Result<Option<SymbolIdentifier>>;
+    fn list(&self) -> Result<Vec<SymbolIdentifier>>;
+    /// Id of a local asset carrying `isin`, if any.
+    fn find_asset_id_by_isin(&self, isin: &str) -> Result<Option<String>>;
+    async fn upsert(&self, mapping: SymbolIdentifier) -> Result<SymbolIdentifier>;
+    async fn delete(&self, identifier: &str) -> Result<()>;
+}
+
+/// Trait defining the contract for identifier resolution.
+#[async_trait::async_trait]
+pub trait IdentifierServiceTrait: Send + Sync {
+    /// Resolves an ISIN, CUSIP, SEDOL or FIGI to a provider symbol. Returns
+    /// None if `identifier` is not one, or no provider knows it.
+    async fn resolve(&self, identifier: &str) -> Result<Option<SymbolIdentifier>>;
+    fn get_identifier_mappings(&self) -> Result<Vec<SymbolIdentifier>>;
+    /// Records a user-supplied mapping, replacing any cached resolution.
+    async fn save_identifier_mapping(&self, identifier: &str, symbol: &str) -> Result<SymbolIdentifier>;
+    async fn delete_identifier_mapping(&self, identifier: &str) -> Result<()>;
+}
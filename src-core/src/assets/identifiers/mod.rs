@@ -0,0 +1,12 @@
+pub(crate) mod identifiers_model;
+pub(crate) mod identifiers_repository;
+pub(crate) mod identifiers_service;
+pub(crate) mod identifiers_traits;
+
+pub use identifiers_model::{
+    IdentifierType, SymbolIdentifier, IDENTIFIER_SOURCE_ASSET, IDENTIFIER_SOURCE_MANUAL,
+    IDENTIFIER_SOURCE_SEARCH,
+};
+pub use identifiers_repository::IdentifierRepository;
+pub use identifiers_service::IdentifierService;
+pub use identifiers_traits::{IdentifierRepositoryTrait, IdentifierServiceTrait};
@@ -4,6 +4,7 @@ pub(crate) mod assets_model;
 pub(crate) mod assets_repository;
 pub(crate) mod assets_service;
 pub(crate) mod assets_traits;
+pub mod identifiers;
 
 // Re-export the public interface
 pub use assets_constants::*;
@@ -11,6 +12,7 @@ pub use assets_model::{Asset, NewAsset, UpdateAssetProfile};
 pub use assets_repository::AssetRepository;
 pub use assets_service::AssetService;
 pub use assets_traits::{AssetServiceTrait, AssetRepositoryTrait};
+pub use identifiers::{IdentifierRepository, IdentifierService, IdentifierServiceTrait, IdentifierType, SymbolIdentifier};
 
 // Re-export error types for convenience
 // pub use assets_errors::{AssetError, Result};
@@ -0,0 +1,17 @@
+/// Entity types tracked in the change log
+pub const ENTITY_TYPE_ACTIVITY: &str = "ACTIVITY";
+pub const ENTITY_TYPE_ACCOUNT: &str = "ACCOUNT";
+pub const ENTITY_TYPE_ASSET: &str = "ASSET";
+
+/// Change log operations
+pub const CHANGE_OPERATION_CREATE: &str = "CREATE";
+pub const CHANGE_OPERATION_UPDATE: &str = "UPDATE";
+pub const CHANGE_OPERATION_DELETE: &str = "DELETE";
+pub const CHANGE_OPERATION_RESTORE: &str = "RESTORE";
+
+/// Origin of a change. Edits made in the app are attributed to the user;
+/// imports and background updates (asset profiles fetched from providers)
+/// are recorded separately so they can be told apart in the history.
+pub const CHANGE_ACTOR_USER: &str = "USER";
+pub const CHANGE_ACTOR_IMPORT: &str = "IMPORT";
+pub const CHANGE_ACTOR_SYSTEM: &str = "SYSTEM";
@@ -0,0 +1,199 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::accounts::AccountDB;
+use crate::activities::{Activity, ActivityDB};
+use crate::errors::Result;
+
+use super::audit_constants::{ENTITY_TYPE_ACCOUNT, ENTITY_TYPE_ACTIVITY};
+
+/// One append-only change log row: the state of an entity before and after
+/// an operation, serialized as the entity's database row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::change_log)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeLogEntry {
+    pub id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub operation: String,
+    pub actor: String,
+    /// Row before the change; None for creates
+    pub before_json: Option<String>,
+    /// Row after the change; None for deletes
+    pub after_json: Option<String>,
+    pub created_at: String,
+}
+
+impl ChangeLogEntry {
+    pub fn new(
+        entity_type: &str,
+        entity_id: &str,
+        operation: &str,
+        actor: &str,
+        before_json: Option<String>,
+        after_json: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.to_string(),
+            operation: operation.to_string(),
+            actor: actor.to_string(),
+            before_json,
+            after_json,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Builds an entry from the entity's database rows.
+    pub fn from_rows<T: Serialize>(
+        entity_type: &str,
+        entity_id: &str,
+        operation: &str,
+        actor: &str,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Result<Self> {
+        Ok(Self::new(
+            entity_type,
+            entity_id,
+            operation,
+            actor,
+            before.map(serde_json::to_string).transpose()?,
+            after.map(serde_json::to_string).transpose()?,
+        ))
+    }
+
+    /// Activity states on either side of the change, used to find the
+    /// history that needs recalculating. Empty for other entity types.
+    pub fn activity_snapshots(&self) -> Vec<Activity> {
+        if self.entity_type != ENTITY_TYPE_ACTIVITY {
+            return Vec::new();
+        }
+        [&self.before_json, &self.after_json]
+            .into_iter()
+            .flatten()
+            .filter_map(|json| serde_json::from_str::<ActivityDB>(json).ok())
+            .map(Activity::from)
+            .collect()
+    }
+
+    /// Profile owning the changed entity, with activities resolved through
+    /// `account_profiles` (account id to profile id). None for entities outside
+    /// any profile, such as assets.
+    pub fn profile_id(&self, account_profiles: &HashMap<String, String>) -> Option<String> {
+        let row = self.after_json.as_ref().or(self.before_json.as_ref())?;
+        match self.entity_type.as_str() {
+            ENTITY_TYPE_ACCOUNT => account_profiles.get(&self.entity_id).cloned().or_else(|| {
+                serde_json::from_str::<AccountDB>(row)
+                    .ok()
+                    .map(|account| account.profile_id)
+            }),
+            ENTITY_TYPE_ACTIVITY => serde_json::from_str::<ActivityDB>(row)
+                .ok()
+                .and_then(|activity| account_profiles.get(&activity.account_id).cloned()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::audit_constants::*;
+
+    fn activity_row(id: &str, quantity: &str) -> ActivityDB {
+        ActivityDB {
+            id: id.to_string(),
+            account_id: "acc-1".to_string(),
+            asset_id: "AAPL".to_string(),
+            activity_type: "BUY".to_string(),
+            activity_date: "2024-03-01T00:00:00+00:00".to_string(),
+            quantity: quantity.to_string(),
+            unit_price: "170".to_string(),
+            currency: "USD".to_string(),
+            fee: "0".to_string(),
+            amount: None,
+            is_draft: false,
+            comment: None,
+            created_at: "2024-03-01T00:00:00+00:00".to_string(),
+            updated_at: "2024-03-01T00:00:00+00:00".to_string(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
+        }
+    }
+
+    #[test]
+    fn test_activity_snapshots_round_trip_rows() {
+        let before = activity_row("act-1", "10");
+        let after = activity_row("act-1", "12");
+        let entry = ChangeLogEntry::from_rows(
+            ENTITY_TYPE_ACTIVITY,
+            "act-1",
+            CHANGE_OPERATION_UPDATE,
+            CHANGE_ACTOR_USER,
+            Some(&before),
+            Some(&after),
+        )
+        .unwrap();
+
+        let snapshots = entry.activity_snapshots();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].quantity.to_string(), "10");
+        assert_eq!(snapshots[1].quantity.to_string(), "12");
+
+        let deleted = ChangeLogEntry::from_rows(
+            ENTITY_TYPE_ACTIVITY,
+            "act-1",
+            CHANGE_OPERATION_DELETE,
+            CHANGE_ACTOR_USER,
+            Some(&before),
+            None,
+        )
+        .unwrap();
+        assert!(deleted.after_json.is_none());
+        assert_eq!(deleted.activity_snapshots().len(), 1);
+    }
+
+    #[test]
+    fn test_profile_id_follows_the_activity_account() {
+        let account_profiles = HashMap::from([("acc-1".to_string(), "alice".to_string())]);
+        let activity = ChangeLogEntry::from_rows(
+            ENTITY_TYPE_ACTIVITY,
+            "act-1",
+            CHANGE_OPERATION_CREATE,
+            CHANGE_ACTOR_USER,
+            None,
+            Some(&activity_row("act-1", "10")),
+        )
+        .unwrap();
+        assert_eq!(activity.profile_id(&account_profiles).as_deref(), Some("alice"));
+
+        let account = ChangeLogEntry::new(
+            ENTITY_TYPE_ACCOUNT,
+            "acc-1",
+            CHANGE_OPERATION_UPDATE,
+            CHANGE_ACTOR_USER,
+            None,
+            Some("{}".to_string()),
+        );
+        assert_eq!(account.profile_id(&account_profiles).as_deref(), Some("alice"));
+
+        let asset = ChangeLogEntry::new(
+            ENTITY_TYPE_ASSET,
+            "AAPL",
+            CHANGE_OPERATION_UPDATE,
+            CHANGE_ACTOR_USER,
+            None,
+            Some("{}".to_string()),
+        );
+        assert_eq!(asset.profile_id(&account_profiles), None);
+    }
+}
@@ -0,0 +1,273 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{self, Pool};
+use diesel::sqlite::SqliteConnection;
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::accounts::accounts_model::AccountDB;
+use crate::activities::ActivityDB;
+use crate::assets::assets_model::AssetDB;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::{Error, Result, ValidationError};
+use crate::schema::{accounts, activities, assets, change_log};
+
+use super::audit_constants::*;
+use super::audit_model::ChangeLogEntry;
+use super::audit_traits::AuditRepositoryTrait;
+
+/// Rows per INSERT when logging bulk writes, well under SQLite's bind limit.
+const CHANGE_LOG_CHUNK_SIZE: usize = 500;
+
+/// Appends one change log row on `conn`, inside the caller's transaction.
+pub(crate) fn record_change<T: Serialize>(
+    conn: &mut SqliteConnection,
+    entity_type: &str,
+    entity_id: &str,
+    operation: &str,
+    actor: &str,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result<()> {
+    let entry = ChangeLogEntry::from_rows(entity_type, entity_id, operation, actor, before, after)?;
+    record_changes(conn, &[entry])
+}
+
+/// Appends prepared change log rows on `conn`, inside the caller's transaction.
+pub(crate) fn record_changes(conn: &mut SqliteConnection, entries: &[ChangeLogEntry]) -> Result<()> {
+    for chunk in entries.chunks(CHANGE_LOG_CHUNK_SIZE) {
+        diesel::insert_into(change_log::table)
+            .values(chunk)
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+fn invalid(message: String) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message))
+}
+
+/// Current row of the entity serialized as in the change log, or None if it does not exist.
+fn load_row_json(conn: &mut SqliteConnection, entity_type: &str, entity_id: &str) -> Result<Option<String>> {
+    let json = match entity_type {
+        ENTITY_TYPE_ACTIVITY => activities::table
+            .find(entity_id)
+            .first::<ActivityDB>(conn)
+            .optional()?
+            .map(|row| serde_json::to_string(&row))
+            .transpose()?,
+        ENTITY_TYPE_ACCOUNT => accounts::table
+            .find(entity_id)
+            .first::<AccountDB>(conn)
+            .optional()?
+            .map(|row| serde_json::to_string(&row))
+            .transpose()?,
+        ENTITY_TYPE_ASSET => assets::table
+            .find(entity_id)
+            .first::<AssetDB>(conn)
+            .optional()?
+            .map(|row| serde_json::to_string(&row))
+            .transpose()?,
+        other => return Err(invalid(format!("Unknown entity type: {}", other))),
+    };
+    Ok(json)
+}
+
+/// Writes a logged row back, updating the entity if it exists or inserting it otherwise.
+fn write_row_json(conn: &mut SqliteConnection, entity_type: &str, json: &str, exists: bool) -> Result<()> {
+    match entity_type {
+        ENTITY_TYPE_ACTIVITY => {
+            let row: ActivityDB = serde_json::from_str(json)?;
+            if exists {
+                diesel::update(activities::table.find(&row.id)).set(&row).execute(conn)?;
+            } else {
+                diesel::insert_into(activities::table).values(&row).execute(conn)?;
+            }
+        }
+        ENTITY_TYPE_ACCOUNT => {
+            let row: AccountDB = serde_json::from_str(json)?;
+            if exists {
+                diesel::update(accounts::table.find(&row.id)).set(&row).execute(conn)?;
+            } else {
+                diesel::insert_into(accounts::table).values(&row).execute(conn)?;
+            }
+        }
+        ENTITY_TYPE_ASSET => {
+            let row: AssetDB = serde_json::from_str(json)?;
+            if exists {
+                diesel::update(assets::table.find(&row.id)).set(&row).execute(conn)?;
+            } else {
+                diesel::insert_into(assets::table).values(&row).execute(conn)?;
+            }
+        }
+        other => return Err(invalid(format!("Unknown entity type: {}", other))),
+    }
+    Ok(())
+}
+
+/// Re-inserts a deleted entity from its last DELETE entry and logs a RESTORE.
+fn undelete_in_transaction(
+    conn: &mut SqliteConnection,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<ChangeLogEntry> {
+    let last_change = change_log::table
+        .filter(change_log::entity_type.eq(entity_type))
+        .filter(change_log::entity_id.eq(entity_id))
+        .order(change_log::created_at.desc())
+        .first::<ChangeLogEntry>(conn)?;
+    let snapshot = match (&last_change.operation[..], &last_change.before_json) {
+        (CHANGE_OPERATION_DELETE, Some(snapshot)) => snapshot.clone(),
+        _ => {
+            return Err(invalid(format!(
+                "{} {} is not deleted",
+                entity_type.to_lowercase(),
+                entity_id
+            )))
+        }
+    };
+    if load_row_json(conn, entity_type, entity_id)?.is_some() {
+        return Err(invalid(format!(
+            "{} {} already exists",
+            entity_type.to_lowercase(),
+            entity_id
+        )));
+    }
+
+    write_row_json(conn, entity_type, &snapshot, false)?;
+    let restore = ChangeLogEntry::new(
+        entity_type,
+        entity_id,
+        CHANGE_OPERATION_RESTORE,
+        CHANGE_ACTOR_USER,
+        None,
+        Some(snapshot),
+    );
+    record_changes(conn, std::slice::from_ref(&restore))?;
+    Ok(restore)
+}
+
+/// Repository for the activity, account and asset change log
+pub struct AuditRepository {
+    pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl AuditRepository {
+    pub fn new(pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        Self { pool, writer }
+    }
+}
+
+#[async_trait]
+impl AuditRepositoryTrait for AuditRepository {
+    fn get_entity_history(&self, entity_type: &str, entity_id: &str) -> Result<Vec<ChangeLogEntry>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(change_log::table
+            .filter(change_log::entity_type.eq(entity_type))
+            .filter(change_log::entity_id.eq(entity_id))
+            .order(change_log::created_at.desc())
+            .load::<ChangeLogEntry>(&mut conn)?)
+    }
+
+    fn get_changes(
+        &self,
+        entity_type: Option<&str>,
+        operation: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<ChangeLogEntry>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = change_log::table.into_boxed();
+        if let Some(entity_type) = entity_type {
+            query = query.filter(change_log::entity_type.eq(entity_type));
+        }
+        if let Some(operation) = operation {
+            query = query.filter(change_log::operation.eq(operation));
+        }
+        Ok(query
+            .order(change_log::created_at.desc())
+            .limit(limit)
+            .load::<ChangeLogEntry>(&mut conn)?)
+    }
+
+    async fn restore_version(&self, change_id: String) -> Result<ChangeLogEntry> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<ChangeLogEntry> {
+                let change = change_log::table
+                    .find(&change_id)
+                    .first::<ChangeLogEntry>(conn)?;
+                let snapshot = change.after_json.clone().ok_or_else(|| {
+                    invalid(format!(
+                        "Change {} deleted the {}; undelete it instead",
+                        change_id,
+                        change.entity_type.to_lowercase()
+                    ))
+                })?;
+
+                let current = load_row_json(conn, &change.entity_type, &change.entity_id)?;
+                write_row_json(conn, &change.entity_type, &snapshot, current.is_some())?;
+
+                let restore = ChangeLogEntry::new(
+                    &change.entity_type,
+                    &change.entity_id,
+                    CHANGE_OPERATION_RESTORE,
+                    CHANGE_ACTOR_USER,
+                    current,
+                    Some(snapshot),
+                );
+                record_changes(conn, std::slice::from_ref(&restore))?;
+                Ok(restore)
+            })
+            .await
+    }
+
+    async fn undelete(&self, entity_type: String, entity_id: String) -> Result<Vec<ChangeLogEntry>> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Vec<ChangeLogEntry>> {
+                // Deleting an account leaves its activities in place, so they
+                // reappear with it and only the account row needs restoring.
+                Ok(vec![undelete_in_transaction(conn, &entity_type, &entity_id)?])
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DEFAULT_PROFILE_ID;
+    use crate::db::MIGRATIONS;
+    use diesel_migrations::MigrationHarness;
+
+    fn migrated_connection() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(MIGRATIONS).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_undelete_restores_account_logged_before_profiles() {
+        let mut conn = migrated_connection();
+        // Account row as logged before account groups and profiles were added.
+        let snapshot = r#"{"id":"acc-old","name":"Old Brokerage","account_type":"SECURITIES","group":null,"currency":"USD","is_default":false,"is_active":true,"created_at":"2024-01-01T00:00:00","updated_at":"2024-01-01T00:00:00","platform_id":null}"#;
+        let deleted = ChangeLogEntry::new(
+            ENTITY_TYPE_ACCOUNT,
+            "acc-old",
+            CHANGE_OPERATION_DELETE,
+            CHANGE_ACTOR_USER,
+            Some(snapshot.to_string()),
+            None,
+        );
+        record_changes(&mut conn, std::slice::from_ref(&deleted)).unwrap();
+
+        undelete_in_transaction(&mut conn, ENTITY_TYPE_ACCOUNT, "acc-old").unwrap();
+
+        let restored = accounts::table
+            .find("acc-old")
+            .first::<AccountDB>(&mut conn)
+            .unwrap();
+        assert_eq!(restored.name, "Old Brokerage");
+        assert_eq!(restored.profile_id, DEFAULT_PROFILE_ID);
+        assert_eq!(restored.group_id, None);
+    }
+}
@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use super::audit_constants::*;
+use super::audit_model::ChangeLogEntry;
+use super::audit_traits::{AuditRepositoryTrait, AuditServiceTrait};
+use crate::accounts::AccountRepositoryTrait;
+use crate::errors::{Error, Result, ValidationError};
+use crate::profiles::profile_in_scope;
+
+/// Number of entries returned by `get_changes` when no limit is given
+const DEFAULT_CHANGES_LIMIT: i64 = 100;
+
+/// Service for browsing the change log and reverting entities from it
+pub struct AuditService {
+    repository: Arc<dyn AuditRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
+}
+
+impl AuditService {
+    pub fn new(
+        repository: Arc<dyn AuditRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        active_profile_id: Arc<RwLock<Option<String>>>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            active_profile_id,
+        }
+    }
+
+    // Drops entries of accounts and activities outside the active profile.
+    fn in_active_profile(&self, entries: Vec<ChangeLogEntry>) -> Result<Vec<ChangeLogEntry>> {
+        let account_profiles: HashMap<String, String> = self
+            .account_repository
+            .list(None, None)?
+            .into_iter()
+            .map(|a| (a.id, a.profile_id))
+            .collect();
+        Ok(entries
+            .into_iter()
+            .filter(|entry| {
+                entry
+                    .profile_id(&account_profiles)
+                    .is_none_or(|profile_id| profile_in_scope(&self.active_profile_id, &profile_id))
+            })
+            .collect())
+    }
+
+    fn validate_entity_type(entity_type: &str) -> Result<()> {
+        match entity_type {
+            ENTITY_TYPE_ACTIVITY | ENTITY_TYPE_ACCOUNT | ENTITY_TYPE_ASSET => Ok(()),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown entity type: {}",
+                other
+            )))),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditServiceTrait for AuditService {
+    fn get_entity_history(&self, entity_type: &str, entity_id: &str) -> Result<Vec<ChangeLogEntry>> {
+        Self::validate_entity_type(entity_type)?;
+        self.repository.get_entity_history(entity_type, entity_id)
+    }
+
+    fn get_changes(
+        &self,
+        entity_type: Option<String>,
+        operation: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<Vec<ChangeLogEntry>> {
+        if let Some(entity_type) = entity_type.as_deref() {
+            Self::validate_entity_type(entity_type)?;
+        }
+        let limit = limit.filter(|l| *l > 0).unwrap_or(DEFAULT_CHANGES_LIMIT);
+        if self.active_profile_id.read().unwrap().is_none() {
+            return self
+                .repository
+                .get_changes(entity_type.as_deref(), operation.as_deref(), limit);
+        }
+
+        // The limit applies to the profile's own entries, so the whole log is filtered first.
+        let mut entries = self.in_active_profile(self.repository.get_changes(
+            entity_type.as_deref(),
+            operation.as_deref(),
+            i64::MAX,
+        )?)?;
+        entries.truncate(limit as usize);
+        Ok(entries)
+    }
+
+    async fn restore_version(&self, change_id: String) -> Result<ChangeLogEntry> {
+        self.repository.restore_version(change_id).await
+    }
+
+    async fn undelete(&self, entity_type: String, entity_id: String) -> Result<Vec<ChangeLogEntry>> {
+        Self::validate_entity_type(&entity_type)?;
+        self.repository.undelete(entity_type, entity_id).await
+    }
+}
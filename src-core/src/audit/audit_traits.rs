@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+
+use super::audit_model::ChangeLogEntry;
+use crate::errors::Result;
+
+/// Trait defining the contract for change log repository operations.
+#[async_trait]
+pub trait AuditRepositoryTrait: Send + Sync {
+    /// Entries for one entity, newest first.
+    fn get_entity_history(&self, entity_type: &str, entity_id: &str) -> Result<Vec<ChangeLogEntry>>;
+    fn get_changes(
+        &self,
+        entity_type: Option<&str>,
+        operation: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<ChangeLogEntry>>;
+    /// Writes the entity back to the state recorded after `change_id` and logs a RESTORE.
+    async fn restore_version(&self, change_id: String) -> Result<ChangeLogEntry>;
+    /// Re-inserts a deleted entity from its last DELETE entry.
+    async fn undelete(&self, entity_type: String, entity_id: String) -> Result<Vec<ChangeLogEntry>>;
+}
+
+/// Trait defining the contract for change log service operations.
+#[async_trait]
+pub trait AuditServiceTrait: Send + Sync {
+    fn get_entity_history(&self, entity_type: &str, entity_id: &str) -> Result<Vec<ChangeLogEntry>>;
+    fn get_changes(
+        &self,
+        entity_type: Option<String>,
+        operation: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<Vec<ChangeLogEntry>>;
+    async fn restore_version(&self, change_id: String) -> Result<ChangeLogEntry>;
+    async fn undelete(&self, entity_type: String, entity_id: String) -> Result<Vec<ChangeLogEntry>>;
+}
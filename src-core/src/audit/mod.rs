@@ -0,0 +1,11 @@
+pub(crate) mod audit_constants;
+pub(crate) mod audit_model;
+pub(crate) mod audit_repository;
+pub(crate) mod audit_service;
+pub(crate) mod audit_traits;
+
+pub use audit_constants::*;
+pub use audit_model::ChangeLogEntry;
+pub use audit_repository::AuditRepository;
+pub use audit_service::AuditService;
+pub use audit_traits::{AuditRepositoryTrait, AuditServiceTrait};
@@ -2,6 +2,9 @@
 /// Total account ID
 pub const PORTFOLIO_TOTAL_ACCOUNT_ID: &str = "TOTAL";
 
+/// Profile that existing data belongs to and that cannot be deleted
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
 /// Cash asset ID prefix
 pub const CASH_ASSET_PREFIX: &str = "$CASH";
 
@@ -12,4 +15,9 @@ pub const DECIMAL_PRECISION: u32 = 6;
 pub const DISPLAY_DECIMAL_PRECISION: u32 = 2;
 
 /// Quantity threshold for significant positions
-pub const QUANTITY_THRESHOLD: &str = "0.00000001";
\ No newline at end of file
+pub const QUANTITY_THRESHOLD: &str = "0.00000001";
+/// Upper bound on the workers used to calculate accounts in parallel
+pub const MAX_CALCULATION_WORKERS: usize = 8;
+
+/// Number of accounts whose recalculated rows are written in one writer transaction
+pub const ACCOUNT_WRITE_BATCH_SIZE: usize = 8;
@@ -1,235 +1,310 @@
 use crate::fx::fx_errors::FxError;
-use crate::fx::fx_model::ExchangeRate;
+use crate::fx::fx_model::{
+    ExchangeRate, FxConversionExplanation, FxConversionLeg, FxConversionPath, FxConversionPolicy,
+};
+use crate::market_data::market_data_model::DataSource;
 use rust_decimal::Decimal;
 use chrono::NaiveDate;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap};
+
+/// A stored rate for one pair on one day.
+#[derive(Debug, Clone)]
+struct RateObservation {
+    date: NaiveDate,
+    rate: Decimal,
+    source: DataSource,
+}
+
+/// A resolved conversion rate together with the stored rates it was derived from.
+struct ResolvedRate {
+    rate: Decimal,
+    path: FxConversionPath,
+    legs: Vec<FxConversionLeg>,
+}
 
 /// A calculator for currency conversions, supporting historical rates based on the latest rate per day.
+///
+/// Only rates that were actually stored are kept. Inverse and transitive rates are
+/// derived at lookup time according to the active `FxConversionPolicy`, so every
+/// conversion can be traced back to the quotes it used.
 pub struct CurrencyConverter {
-    // Date -> (From, To) -> Rate
-    historical_rates: HashMap<NaiveDate, HashMap<(String, String), Decimal>>,
-    sorted_dates: Vec<NaiveDate>, // Keep track of dates in sorted order
+    // (From, To) -> observations sorted by date, one per day
+    series: HashMap<(String, String), Vec<RateObservation>>,
+    currencies: BTreeSet<String>,
+    policy: FxConversionPolicy,
 }
 
 impl CurrencyConverter {
-    /// Creates a new `CurrencyConverter` from a Vec of ExchangeRate.
-    /// Processes inverses and transitive rates.
+    /// Creates a new `CurrencyConverter` from a Vec of ExchangeRate using the default policy.
     pub fn new(exchange_rates: Vec<ExchangeRate>) -> Result<Self, FxError> {
+        Self::with_policy(exchange_rates, FxConversionPolicy::default())
+    }
+
+    /// Creates a new `CurrencyConverter` that resolves rates according to `policy`.
+    pub fn with_policy(
+        exchange_rates: Vec<ExchangeRate>,
+        policy: FxConversionPolicy,
+    ) -> Result<Self, FxError> {
         let mut converter = CurrencyConverter {
-            historical_rates: HashMap::new(),
-            sorted_dates: Vec::new(), //init the dates
+            series: HashMap::new(),
+            currencies: BTreeSet::new(),
+            policy,
         };
         converter.add_historical_rates(exchange_rates)?;
         Ok(converter)
     }
 
-    /// Adds historical FX rates, processing inverses and transitive rates.
-    /// For each day, only the latest available rate for a given pair is considered.
-    fn add_historical_rates(&mut self, rates: Vec<ExchangeRate>) -> Result<(), FxError> {
-        // Group rates by date and select the latest timestamp for each pair within that date.
-        let mut latest_rates_by_date: HashMap<NaiveDate, HashMap<(String, String), ExchangeRate>> = HashMap::new();
+    pub fn policy(&self) -> &FxConversionPolicy {
+        &self.policy
+    }
 
-        for rate in rates { // No longer mutable, no normalization
-            // Preserve original case
-            // let from_currency = rate.from_currency.clone();
-            // let to_currency = rate.to_currency.clone();
+    pub fn set_policy(&mut self, policy: FxConversionPolicy) {
+        self.policy = policy;
+    }
+
+    /// Adds historical FX rates. For each day, only the latest available rate for a given pair is kept.
+    fn add_historical_rates(&mut self, rates: Vec<ExchangeRate>) -> Result<(), FxError> {
+        // (From, To) -> Date -> latest rate of that day
+        let mut latest_rates: HashMap<(String, String), HashMap<NaiveDate, ExchangeRate>> =
+            HashMap::new();
 
+        for rate in rates {
             // Ignore self-referential rates
             if rate.from_currency == rate.to_currency {
                 continue;
             }
+            if rate.rate <= Decimal::ZERO {
+                log::error!(
+                    "Non-positive exchange rate {} encountered for {}/{} on {}. Skipping.",
+                    rate.rate,
+                    rate.from_currency,
+                    rate.to_currency,
+                    rate.timestamp.date_naive()
+                );
+                continue;
+            }
 
-            let date = rate.timestamp.date_naive(); // Group by NaiveDate
-            // Use original case for the pair key
+            let date = rate.timestamp.date_naive();
             let pair = (rate.from_currency.clone(), rate.to_currency.clone());
 
-            let date_map = latest_rates_by_date.entry(date).or_default();
-
-            // Check if we have a rate for this pair on this date, keep the latest timestamp
-            match date_map.entry(pair) {
+            match latest_rates.entry(pair).or_default().entry(date) {
                 std::collections::hash_map::Entry::Occupied(mut entry) => {
                     // Duplicate pair found for this date, keep the one with the later timestamp
                     if rate.timestamp > entry.get().timestamp {
-                        *entry.get_mut() = rate; // Update with the later rate
+                        *entry.get_mut() = rate;
                     }
-                    // Else, discard the current 'rate' as it's earlier or same timestamp
                 }
                 std::collections::hash_map::Entry::Vacant(entry) => {
-                    // No rate for this pair on this date yet, insert this one
                     entry.insert(rate);
                 }
             }
         }
 
-        // Clear existing processed rates before adding new ones based on daily latest
-        self.historical_rates.clear();
-        self.sorted_dates.clear();
-
-        // Now process the latest rates selected for each date
-        for (date, chosen_rates_for_date) in latest_rates_by_date {
-             // The chosen_rates_for_date map now contains only the latest rate per pair for this date.
-            let mut rate_map: HashMap<(String, String), Decimal> = HashMap::new();
-            let mut currencies: HashSet<String> = HashSet::new();
-
-            // Add direct and inverse rates from the chosen rates.
-            for (_pair, rate) in chosen_rates_for_date {
-                // Use original case preserved in 'rate'
-                currencies.insert(rate.from_currency.clone());
-                currencies.insert(rate.to_currency.clone());
-
-                let forward_rate = rate.rate;
-                 // Check for zero rate before division
-                if forward_rate.is_zero() {
-                     // Decide how to handle zero rate (e.g., log error, skip inverse)
-                     log::error!("Zero exchange rate encountered for {}/{} on {}. Cannot calculate inverse.",
-                                rate.from_currency, rate.to_currency, date);
-                     // Insert forward rate but skip inverse
-                     rate_map.insert(
-                        (rate.from_currency.clone(), rate.to_currency.clone()),
-                        forward_rate,
-                    );
-                    continue; // Skip inverse calculation for this rate
-                }
-                let inverse_rate = Decimal::ONE / forward_rate;
-
-                rate_map.insert(
-                    (rate.from_currency.clone(), rate.to_currency.clone()), // Use original case
-                    forward_rate,
-                );
-                rate_map.insert(
-                    (rate.to_currency.clone(), rate.from_currency.clone()), // Use original case
-                    inverse_rate,
-                );
-            }
+        self.series.clear();
+        self.currencies.clear();
+
+        for (pair, rates_by_date) in latest_rates {
+            let mut observations: Vec<RateObservation> = rates_by_date
+                .into_iter()
+                .map(|(date, rate)| RateObservation {
+                    date,
+                    rate: rate.rate,
+                    source: rate.source,
+                })
+                .collect();
+            observations.sort_by_key(|o| o.date);
+
+            self.currencies.insert(pair.0.clone());
+            self.currencies.insert(pair.1.clone());
+            self.series.insert(pair, observations);
+        }
+        Ok(())
+    }
 
-            // Build transitive rates (logic remains the same)
-            let currencies_vec: Vec<_> = currencies.into_iter().collect();
-            for i in 0..currencies_vec.len() {
-                for j in 0..currencies_vec.len() {
-                    if i == j {
-                        // Add identity rate
-                        rate_map.insert((currencies_vec[i].clone(), currencies_vec[j].clone()), Decimal::ONE);
-                        continue;
-                    }
-                    let from = &currencies_vec[i];
-                    let to = &currencies_vec[j];
-
-                    if !rate_map.contains_key(&(from.clone(), to.clone())) {
-                        // Try to find a transitive path
-                        for k in 0..currencies_vec.len() {
-                            if k == i || k == j {
-                                continue;
-                            }
-                            let via = &currencies_vec[k];
-
-                            if let (Some(rate1), Some(rate2)) = (
-                                rate_map.get(&(from.clone(), via.clone())),
-                                rate_map.get(&(via.clone(), to.clone())),
-                            ) {
-                                let transitive_rate = rate1 * rate2;
-                                rate_map.insert((from.clone(), to.clone()), transitive_rate);
-                                // Add inverse transitive rate as well
-                                if !transitive_rate.is_zero() {
-                                     rate_map.insert((to.clone(), from.clone()), Decimal::ONE / transitive_rate);
-                                } else {
-                                     log::warn!("Zero transitive rate calculated for {}->{} via {} on {}. Cannot store inverse.", from, to, via, date);
-                                }
-                                // Found *a* path, break inner loop to avoid redundant calculations for this pair?
-                                // Or let it run to potentially find a more direct path later? Current logic allows override.
-                                // break; // Optional: break if one path is enough
-                            }
-                        }
-                    }
+    /// Finds the stored observation for a pair closest to `date`. When two observations
+    /// are equally close, the later one wins.
+    fn nearest_observation(
+        &self,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+        max_staleness_days: Option<i64>,
+    ) -> Option<&RateObservation> {
+        let observations = self
+            .series
+            .get(&(from_currency.to_string(), to_currency.to_string()))?;
+
+        let nearest = match observations.binary_search_by_key(&date, |o| o.date) {
+            Ok(index) => &observations[index],
+            Err(0) => observations.first()?,
+            Err(index) if index == observations.len() => &observations[index - 1],
+            Err(index) => {
+                let prev = &observations[index - 1];
+                let next = &observations[index];
+                if (date - prev.date).num_days() < (next.date - date).num_days() {
+                    prev
+                } else {
+                    next
                 }
             }
-             // Add identity rates again just in case they were missed
-            for currency in &currencies_vec {
-                rate_map.entry((currency.clone(), currency.clone())).or_insert(Decimal::ONE);
-            }
-
+        };
 
-            self.historical_rates.insert(date, rate_map);
-            // Add date in sorted insert (logic remains the same)
-            if !self.sorted_dates.contains(&date) {
-                self.sorted_dates.push(date);
-                self.sorted_dates.sort();
-            }
+        match max_staleness_days {
+            Some(max) if (nearest.date - date).num_days().abs() > max => None,
+            _ => Some(nearest),
         }
-        Ok(())
     }
 
-    /// Gets the exchange rate between two currencies on a specific date.
-    pub fn get_rate(
+    /// Resolves a single hop using the stored pair, or its inverse when the policy allows it.
+    fn find_leg(
         &self,
         from_currency: &str,
         to_currency: &str,
         date: NaiveDate,
-    ) -> Result<Decimal, FxError> {
-        if from_currency == to_currency {
-            return Ok(Decimal::ONE);
-        }
+        max_staleness_days: Option<i64>,
+    ) -> Option<FxConversionLeg> {
+        let direct = self
+            .nearest_observation(from_currency, to_currency, date, max_staleness_days)
+            .map(|o| (o, false));
+        let inverse = if self.policy.allow_inverse {
+            self.nearest_observation(to_currency, from_currency, date, max_staleness_days)
+                .map(|o| (o, true))
+        } else {
+            None
+        };
 
-        self.historical_rates
-            .get(&date)
-            .and_then(|rate_map| {
-                rate_map.get(&(from_currency.to_string(), to_currency.to_string()))
-            })
-            .cloned()
-            .ok_or_else(|| {
-                FxError::RateNotFound(format!(
-                    "No exchange rate found for {}/{} on {}",
-                    from_currency, to_currency, date
-                ))
-            })
+        // Prefer the fresher observation; the stored direction wins ties.
+        let (observation, inverted) = match (direct, inverse) {
+            (Some(d), Some(i)) => {
+                if (i.0.date - date).num_days().abs() < (d.0.date - date).num_days().abs() {
+                    i
+                } else {
+                    d
+                }
+            }
+            (Some(d), None) => d,
+            (None, Some(i)) => i,
+            (None, None) => return None,
+        };
+
+        let (rate, source_symbol) = if inverted {
+            (
+                Decimal::ONE / observation.rate,
+                ExchangeRate::make_fx_symbol(to_currency, from_currency),
+            )
+        } else {
+            (
+                observation.rate,
+                ExchangeRate::make_fx_symbol(from_currency, to_currency),
+            )
+        };
+
+        Some(FxConversionLeg {
+            from_currency: from_currency.to_string(),
+            to_currency: to_currency.to_string(),
+            rate,
+            source_symbol,
+            source_date: observation.date,
+            source: observation.source.clone(),
+            inverted,
+        })
     }
 
-    /// Gets the exchange rate between two currencies on the nearest available date.
-    pub fn get_rate_nearest(
+    /// Resolves the rate for a pair, falling back to a one-hop transitive path when
+    /// the policy allows it. Among transitive candidates, the path whose stalest leg
+    /// is closest to `date` is chosen.
+    fn resolve(
         &self,
         from_currency: &str,
         to_currency: &str,
         date: NaiveDate,
-    ) -> Result<Decimal, FxError> {
+        max_staleness_days: Option<i64>,
+    ) -> Result<ResolvedRate, FxError> {
         if from_currency == to_currency {
-            return Ok(Decimal::ONE);
+            return Ok(ResolvedRate {
+                rate: Decimal::ONE,
+                path: FxConversionPath::Identity,
+                legs: Vec::new(),
+            });
         }
 
-        // Check if we have any dates at all
-        if self.sorted_dates.is_empty() {
-            return Err(FxError::RateNotFound(format!(
-                "No exchange rates available for any date for {}/{}",
-                from_currency, to_currency
-            )));
+        if let Some(leg) = self.find_leg(from_currency, to_currency, date, max_staleness_days) {
+            let path = if leg.inverted {
+                FxConversionPath::Inverse
+            } else {
+                FxConversionPath::Direct
+            };
+            return Ok(ResolvedRate {
+                rate: leg.rate,
+                path,
+                legs: vec![leg],
+            });
         }
 
-        // Use binary search to find the closest date
-        let closest_date = match self.sorted_dates.binary_search(&date) {
-            Ok(index) => self.sorted_dates[index], // Exact match
-            Err(index) => {
-                // No exact match. `index` is where it *would* be inserted.
-                // Check the dates before and after.
-                if index == 0 {
-                    // Requested date is before the first date.
-                    self.sorted_dates[0]
-                } else if index == self.sorted_dates.len() {
-                    // Requested date is after the last date.
-                    self.sorted_dates[index - 1]
-                } else {
-                    // Requested date is between two dates.
-                    let prev_date = self.sorted_dates[index - 1];
-                    let next_date = self.sorted_dates[index];
-                    // Compare differences in days
-                    if (date - prev_date).num_days() < (next_date - date).num_days() {
-                        prev_date
-                    } else {
-                        next_date
-                    }
+        if self.policy.allow_transitive {
+            let candidates: Vec<&String> = match &self.policy.pivot_currency {
+                Some(pivot) => self.currencies.iter().filter(|c| *c == pivot).collect(),
+                None => self.currencies.iter().collect(),
+            };
+
+            let mut best: Option<(i64, FxConversionLeg, FxConversionLeg)> = None;
+            for via in candidates {
+                if via == from_currency || via == to_currency {
+                    continue;
+                }
+                let Some(first) = self.find_leg(from_currency, via, date, max_staleness_days)
+                else {
+                    continue;
+                };
+                let Some(second) = self.find_leg(via, to_currency, date, max_staleness_days)
+                else {
+                    continue;
+                };
+                let staleness = (first.source_date - date)
+                    .num_days()
+                    .abs()
+                    .max((second.source_date - date).num_days().abs());
+                if best.as_ref().is_none_or(|(s, _, _)| staleness < *s) {
+                    best = Some((staleness, first, second));
                 }
             }
-        };
 
-        // Use the existing get_rate function with the closest date
-        self.get_rate(from_currency, to_currency, closest_date)
+            if let Some((_, first, second)) = best {
+                return Ok(ResolvedRate {
+                    rate: first.rate * second.rate,
+                    path: FxConversionPath::Transitive,
+                    legs: vec![first, second],
+                });
+            }
+        }
+
+        Err(FxError::RateNotFound(format!(
+            "No exchange rate found for {}/{} on {}",
+            from_currency, to_currency, date
+        )))
+    }
+
+    /// Gets the exchange rate between two currencies on a specific date.
+    pub fn get_rate(
+        &self,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+    ) -> Result<Decimal, FxError> {
+        self.resolve(from_currency, to_currency, date, Some(0))
+            .map(|resolved| resolved.rate)
+    }
+
+    /// Gets the exchange rate between two currencies on the nearest available date,
+    /// limited by the policy's maximum staleness.
+    pub fn get_rate_nearest(
+        &self,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+    ) -> Result<Decimal, FxError> {
+        self.resolve(from_currency, to_currency, date, self.policy.max_staleness_days)
+            .map(|resolved| resolved.rate)
     }
 
     /// Converts an amount from one currency to another on a specific date.
@@ -263,6 +338,28 @@ impl CurrencyConverter {
         let rate = self.get_rate_nearest(from_currency, to_currency, date)?;
         Ok(amount * rate)
     }
+
+    /// Converts an amount on the nearest available date and reports the stored rates used.
+    pub fn explain_conversion(
+        &self,
+        amount: Decimal,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+    ) -> Result<FxConversionExplanation, FxError> {
+        let resolved =
+            self.resolve(from_currency, to_currency, date, self.policy.max_staleness_days)?;
+        Ok(FxConversionExplanation {
+            from_currency: from_currency.to_string(),
+            to_currency: to_currency.to_string(),
+            requested_date: date,
+            amount,
+            converted_amount: amount * resolved.rate,
+            rate: resolved.rate,
+            path: resolved.path,
+            legs: resolved.legs,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -437,5 +534,62 @@ mod tests {
             .unwrap();
         assert_eq!(converted_amount, Decimal::new(87, 0)); // Should use 2023-10-28
     }
-}
 
+    #[test]
+    fn test_inverse_disabled_by_policy() {
+        let policy = FxConversionPolicy {
+            allow_inverse: false,
+            ..FxConversionPolicy::default()
+        };
+        let converter = CurrencyConverter::with_policy(test_exchange_rates(), policy).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2023, 10, 26).unwrap();
+        assert!(converter.get_rate("USD", "EUR", date).is_ok());
+        let result = converter.get_rate("EUR", "USD", date);
+        assert!(matches!(result, Err(FxError::RateNotFound(_))));
+    }
+
+    #[test]
+    fn test_explain_transitive_conversion() {
+        let policy = FxConversionPolicy {
+            pivot_currency: Some("EUR".to_string()),
+            ..FxConversionPolicy::default()
+        };
+        let converter = CurrencyConverter::with_policy(test_exchange_rates(), policy).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2023, 10, 27).unwrap();
+        let explanation = converter
+            .explain_conversion(Decimal::ONE_HUNDRED, "GBP", "USD", date)
+            .unwrap();
+
+        assert_eq!(explanation.path, FxConversionPath::Transitive);
+        assert_eq!(explanation.legs.len(), 2);
+        assert_eq!(explanation.legs[0].source_symbol, "EURGBP=X");
+        assert!(explanation.legs[0].inverted);
+        assert_eq!(explanation.legs[1].source_symbol, "USDEUR=X");
+        assert_eq!(explanation.legs[1].source_date, date);
+        let expected_rate =
+            (Decimal::ONE / Decimal::new(91, 2)) * (Decimal::ONE / Decimal::new(86, 2));
+        assert_eq!(explanation.rate, expected_rate);
+        assert_eq!(explanation.converted_amount, Decimal::ONE_HUNDRED * expected_rate);
+    }
+
+    #[test]
+    fn test_max_staleness_rejects_old_rates() {
+        let policy = FxConversionPolicy {
+            max_staleness_days: Some(2),
+            ..FxConversionPolicy::default()
+        };
+        let converter = CurrencyConverter::with_policy(test_exchange_rates(), policy).unwrap();
+
+        let within = NaiveDate::from_ymd_opt(2023, 10, 30).unwrap();
+        assert_eq!(
+            converter.get_rate_nearest("USD", "EUR", within).unwrap(),
+            Decimal::new(87, 2)
+        );
+
+        let beyond = NaiveDate::from_ymd_opt(2023, 11, 5).unwrap();
+        let result = converter.get_rate_nearest("USD", "EUR", beyond);
+        assert!(matches!(result, Err(FxError::RateNotFound(_))));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::market_data::market_data_model::{DataSource, Quote};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -48,6 +48,18 @@ impl ExchangeRate {
         }
     }
 
+    /// Returns the same observation quoted in the opposite direction.
+    pub fn inverse(&self) -> Self {
+        ExchangeRate {
+            id: Self::make_fx_symbol(&self.to_currency, &self.from_currency),
+            from_currency: self.to_currency.clone(),
+            to_currency: self.from_currency.clone(),
+            rate: Decimal::ONE / self.rate,
+            source: self.source.clone(),
+            timestamp: self.timestamp,
+        }
+    }
+
     pub fn parse_fx_symbol(symbol: &str) -> (String, String) {
         if symbol.ends_with("=X") {
             let base_symbol = &symbol[..symbol.len() - 2];
@@ -101,3 +113,69 @@ impl NewExchangeRate {
         }
     }
 }
+
+/// Rules the currency converter follows when no direct rate exists for a pair or date.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FxConversionPolicy {
+    /// Currency used as the intermediate for transitive conversions. When unset,
+    /// any currency with rates to both sides may be used.
+    pub pivot_currency: Option<String>,
+    /// Maximum distance in days between the requested date and the rate used.
+    /// When unset, the nearest available rate is used regardless of its age.
+    pub max_staleness_days: Option<i64>,
+    pub allow_inverse: bool,
+    pub allow_transitive: bool,
+}
+
+impl Default for FxConversionPolicy {
+    fn default() -> Self {
+        Self {
+            pivot_currency: None,
+            max_staleness_days: None,
+            allow_inverse: true,
+            allow_transitive: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FxConversionPath {
+    Identity,
+    Direct,
+    Inverse,
+    Transitive,
+    /// Converter could not resolve the pair; the latest stored rate was used instead.
+    LatestFallback,
+}
+
+/// A single stored rate used while converting between two currencies.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FxConversionLeg {
+    pub from_currency: String,
+    pub to_currency: String,
+    #[serde(serialize_with = "serialize_decimal_6")]
+    pub rate: Decimal,
+    /// Symbol of the stored quote the rate was taken from.
+    pub source_symbol: String,
+    pub source_date: NaiveDate,
+    pub source: DataSource,
+    /// True when the stored quote was for the opposite direction and was inverted.
+    pub inverted: bool,
+}
+
+/// Describes how an amount was converted: the effective rate and the chain of stored rates behind it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FxConversionExplanation {
+    pub from_currency: String,
+    pub to_currency: String,
+    pub requested_date: NaiveDate,
+    pub amount: Decimal,
+    pub converted_amount: Decimal,
+    pub rate: Decimal,
+    pub path: FxConversionPath,
+    pub legs: Vec<FxConversionLeg>,
+}
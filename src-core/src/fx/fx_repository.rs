@@ -153,6 +153,19 @@ impl FxRepository {
         Ok(quote_db.map(|q| ExchangeRate::from_quote(&Quote::from(q))))
     }
 
+    pub fn fx_asset_exists(&self, from: &str, to: &str) -> Result<bool> {
+        let mut conn = get_connection(&self.pool)?;
+        let symbol = ExchangeRate::make_fx_symbol(from, to);
+
+        let count = assets::table
+            .filter(assets::id.eq(symbol))
+            .filter(assets::asset_type.eq(FOREX_ASSET_TYPE))
+            .count()
+            .get_result::<i64>(&mut conn)?;
+
+        Ok(count > 0)
+    }
+
     pub fn get_historical_quotes(
         &self,
         symbol: &str,
@@ -273,6 +286,29 @@ impl FxRepository {
             .await
     }
 
+    /// Upserts a batch of exchange rates as quotes, replacing any quote for the same pair and day.
+    pub async fn save_exchange_rates(&self, rates: Vec<ExchangeRate>) -> Result<usize> {
+        if rates.is_empty() {
+            return Ok(0);
+        }
+        let db_rows: Vec<QuoteDb> = rates
+            .iter()
+            .map(|rate| QuoteDb::from(&rate.to_quote()))
+            .collect();
+
+        self.writer
+            .exec(move |conn| {
+                let mut affected_rows = 0;
+                for chunk in db_rows.chunks(1_000) {
+                    affected_rows += diesel::replace_into(quotes::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+                Ok(affected_rows)
+            })
+            .await
+    }
+
     pub async fn update_exchange_rate(&self, rate: &ExchangeRate) -> Result<ExchangeRate> {
         let rate_owned = rate.clone();
         self.writer
@@ -377,6 +413,10 @@ impl FxRepositoryTrait for FxRepository {
         self.get_exchange_rate_by_id(symbol)
     }
 
+    fn fx_asset_exists(&self, from: &str, to: &str) -> Result<bool> {
+        self.fx_asset_exists(from, to)
+    }
+
     fn get_historical_quotes(
         &self,
         symbol: &str,
@@ -400,6 +440,10 @@ impl FxRepositoryTrait for FxRepository {
         self.save_exchange_rate(rate).await
     }
 
+    async fn save_exchange_rates(&self, rates: Vec<ExchangeRate>) -> Result<usize> {
+        self.save_exchange_rates(rates).await
+    }
+
     async fn update_exchange_rate(&self, rate: &ExchangeRate) -> Result<ExchangeRate> {
         self.update_exchange_rate(rate).await
     }
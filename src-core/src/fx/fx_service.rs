@@ -1,18 +1,23 @@
 use rust_decimal::Decimal;
-use chrono::{Utc, NaiveDate};
+use chrono::{Duration, NaiveDate, Utc};
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use crate::errors::Result;
 use crate::market_data::market_data_model::DataSource;
-use super::fx_model::{ExchangeRate, NewExchangeRate};
+use super::fx_model::{
+    ExchangeRate, FxConversionExplanation, FxConversionLeg, FxConversionPath, FxConversionPolicy, NewExchangeRate,
+};
 use super::currency_converter::CurrencyConverter;
 use super::fx_traits::{FxRepositoryTrait, FxServiceTrait};
 use super::fx_errors::FxError;
+use super::providers::{get_fx_rate_provider, FxRateProvider};
 use async_trait::async_trait;
 
 #[derive(Clone)]
 pub struct FxService {
     repository: Arc<dyn FxRepositoryTrait>,
     converter: Arc<RwLock<Option<CurrencyConverter>>>,
+    policy: Arc<RwLock<FxConversionPolicy>>,
 }
 
 impl FxService {
@@ -22,6 +27,7 @@ impl FxService {
         Self {
             repository,
             converter: Arc::new(RwLock::new(None)),
+            policy: Arc::new(RwLock::new(FxConversionPolicy::default())),
         }
     }
 
@@ -38,8 +44,10 @@ impl FxService {
             return Ok(());
         }
 
+        let policy = self.current_policy()?;
+
         // Directly use the fetched rates without filling gaps
-        match CurrencyConverter::new(all_historical_rates) {
+        match CurrencyConverter::with_policy(all_historical_rates, policy) {
             Ok(converter) => {
                 let mut converter_lock = self.converter.write().map_err(|e| FxError::CacheError(e.to_string()))?;
                 *converter_lock = Some(converter);
@@ -54,6 +62,107 @@ impl FxService {
         }
     }
 
+    fn current_policy(&self) -> Result<FxConversionPolicy> {
+        let policy = self.policy.read().map_err(|e| FxError::CacheError(e.to_string()))?;
+        Ok(policy.clone())
+    }
+
+    /// Whether lookups may fall back to the latest stored rate when the converter
+    /// cannot resolve a dated conversion. A staleness limit rules this out.
+    fn allows_latest_fallback(&self) -> bool {
+        self.current_policy()
+            .map(|policy| policy.max_staleness_days.is_none())
+            .unwrap_or(true)
+    }
+
+    fn get_reference_rate_provider(&self, provider_id: &str) -> Result<Arc<dyn FxRateProvider>> {
+        get_fx_rate_provider(provider_id).ok_or_else(|| {
+            FxError::FetchError(format!("Unknown reference rate provider: {}", provider_id)).into()
+        })
+    }
+
+    /// Persists reference rates, keeping each pair in the direction it is already
+    /// registered in. Registered pairs keep their data source and only receive rates
+    /// for dates without a quote from another source, so provider quotes are never
+    /// replaced. Unregistered pairs are added as manual FX assets, but only when the
+    /// caller asked for those currencies explicitly.
+    async fn store_reference_rates(
+        &self,
+        rates: Vec<ExchangeRate>,
+        currencies: Option<Vec<String>>,
+    ) -> Result<usize> {
+        let currency_filter: Option<HashSet<String>> =
+            currencies.map(|list| list.iter().map(|c| c.to_uppercase()).collect());
+
+        let mut rates_by_pair: HashMap<(String, String), Vec<ExchangeRate>> = HashMap::new();
+        for rate in rates {
+            if let Some(filter) = &currency_filter {
+                if !filter.contains(&rate.from_currency) || !filter.contains(&rate.to_currency) {
+                    continue;
+                }
+            }
+            rates_by_pair
+                .entry((rate.from_currency.clone(), rate.to_currency.clone()))
+                .or_default()
+                .push(rate);
+        }
+
+        let mut rates_to_save = Vec::new();
+        for ((from, to), pair_rates) in rates_by_pair {
+            let (from, to, pair_rates) = if self.repository.fx_asset_exists(&from, &to)? {
+                (from, to, pair_rates)
+            } else if self.repository.fx_asset_exists(&to, &from)? {
+                (to, from, pair_rates.iter().map(ExchangeRate::inverse).collect())
+            } else if currency_filter.is_some() {
+                self.repository
+                    .create_fx_asset(&from, &to, DataSource::Manual.as_str())
+                    .await?;
+                (from, to, pair_rates)
+            } else {
+                continue;
+            };
+            rates_to_save.extend(self.dates_without_other_quotes(&from, &to, pair_rates)?);
+        }
+
+        let saved = self.repository.save_exchange_rates(rates_to_save).await?;
+        self.initialize_converter()?;
+        Ok(saved)
+    }
+
+    /// Drops the rates whose date already holds a quote for the pair from a source
+    /// other than the rate's own.
+    fn dates_without_other_quotes(
+        &self,
+        from: &str,
+        to: &str,
+        rates: Vec<ExchangeRate>,
+    ) -> Result<Vec<ExchangeRate>> {
+        let (Some(start), Some(end)) = (
+            rates.iter().map(|r| r.timestamp.date_naive()).min(),
+            rates.iter().map(|r| r.timestamp.date_naive()).max(),
+        ) else {
+            return Ok(rates);
+        };
+
+        let symbol = ExchangeRate::make_fx_symbol(from, to);
+        let end_of_range = (end + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap_or_default();
+        let quoted: HashMap<NaiveDate, DataSource> = self
+            .repository
+            .get_historical_quotes(&symbol, start.and_hms_opt(0, 0, 0).unwrap_or_default(), end_of_range)?
+            .into_iter()
+            .map(|quote| (quote.timestamp.date_naive(), quote.data_source))
+            .collect();
+
+        Ok(rates
+            .into_iter()
+            .filter(|rate| {
+                quoted
+                    .get(&rate.timestamp.date_naive())
+                    .is_none_or(|source| *source == rate.source)
+            })
+            .collect())
+    }
+
     fn load_latest_exchange_rate(&self, from: &str, to: &str) -> Result<ExchangeRate> {
         // Fetch from repository
         match self.repository.get_latest_exchange_rate(from, to)? {
@@ -218,6 +327,14 @@ impl FxServiceTrait for FxService {
             }
         }
 
+        if !self.allows_latest_fallback() {
+            return Err(FxError::RateNotFound(format!(
+                "No exchange rate for {}/{} near {} within the configured staleness limit",
+                from_currency, to_currency, date
+            ))
+            .into());
+        }
+
         // Fallback to latest rate if converter not available or failed
         log::warn!("Falling back to latest rate for {}/{} on {}", from_currency, to_currency, date);
         self.get_latest_exchange_rate(from_currency, to_currency)
@@ -339,4 +456,108 @@ impl FxServiceTrait for FxService {
         
         Ok(())
     }
+
+    async fn import_reference_rates(
+        &self,
+        provider_id: &str,
+        content: &str,
+        currencies: Option<Vec<String>>,
+    ) -> Result<usize> {
+        let provider = self.get_reference_rate_provider(provider_id)?;
+        let rates = provider.parse_rates(content)?;
+        log::info!("Parsed {} reference rates from {} file", rates.len(), provider.id());
+        self.store_reference_rates(rates, currencies).await
+    }
+
+    async fn sync_reference_rates(
+        &self,
+        provider_id: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        currencies: Option<Vec<String>>,
+    ) -> Result<usize> {
+        let provider = self.get_reference_rate_provider(provider_id)?;
+        let rates = provider.fetch_rates(start_date, end_date).await?;
+        log::info!(
+            "Fetched {} reference rates from {} for {} to {}",
+            rates.len(),
+            provider.id(),
+            start_date,
+            end_date
+        );
+        self.store_reference_rates(rates, currencies).await
+    }
+
+    fn get_conversion_policy(&self) -> Result<FxConversionPolicy> {
+        self.current_policy()
+    }
+
+    fn set_conversion_policy(&self, policy: FxConversionPolicy) -> Result<()> {
+        {
+            let mut policy_lock = self.policy.write().map_err(|e| FxError::CacheError(e.to_string()))?;
+            *policy_lock = policy.clone();
+        }
+
+        let mut converter_lock = self.converter.write().map_err(|e| FxError::CacheError(e.to_string()))?;
+        if let Some(converter) = converter_lock.as_mut() {
+            converter.set_policy(policy);
+        }
+        Ok(())
+    }
+
+    fn explain_conversion_for_date(
+        &self,
+        amount: Decimal,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+    ) -> Result<FxConversionExplanation> {
+        if let Ok(converter_lock) = self.converter.read() {
+            if let Some(converter) = &*converter_lock {
+                match converter.explain_conversion(amount, from_currency, to_currency, date) {
+                    Ok(explanation) => return Ok(explanation),
+                    Err(e) => {
+                        log::warn!("Converter failed to explain {}/{} on {}: {}",
+                            from_currency, to_currency, date, e);
+                    }
+                }
+            }
+        }
+
+        if !self.allows_latest_fallback() {
+            return Err(FxError::RateNotFound(format!(
+                "No exchange rate for {}/{} near {} within the configured staleness limit",
+                from_currency, to_currency, date
+            ))
+            .into());
+        }
+
+        // Mirror the fallback used by convert_currency_for_date
+        let latest = self.load_latest_exchange_rate(from_currency, to_currency)?;
+        let inverted = self.repository.get_latest_exchange_rate(from_currency, to_currency)?.is_none();
+        let source_symbol = if inverted {
+            ExchangeRate::make_fx_symbol(to_currency, from_currency)
+        } else {
+            latest.id.clone()
+        };
+
+        Ok(FxConversionExplanation {
+            from_currency: from_currency.to_string(),
+            to_currency: to_currency.to_string(),
+            requested_date: date,
+            amount,
+            converted_amount: amount * latest.rate,
+            rate: latest.rate,
+            path: FxConversionPath::LatestFallback,
+            legs: vec![FxConversionLeg {
+                from_currency: from_currency.to_string(),
+                to_currency: to_currency.to_string(),
+                rate: latest.rate,
+                source_symbol,
+                source_date: latest.timestamp.date_naive(),
+                source: latest.source,
+                inverted,
+            }],
+        })
+    }
 }
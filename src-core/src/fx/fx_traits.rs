@@ -1,4 +1,4 @@
-use super::fx_model::{ExchangeRate, NewExchangeRate};
+use super::fx_model::{ExchangeRate, FxConversionExplanation, FxConversionPolicy, NewExchangeRate};
 use crate::errors::Result;
 use crate::market_data::market_data_model::Quote;
 use chrono::{NaiveDate, NaiveDateTime};
@@ -12,6 +12,7 @@ pub trait FxRepositoryTrait: Send + Sync {
     fn get_historical_exchange_rates(&self) -> Result<Vec<ExchangeRate>>;
     fn get_latest_exchange_rate(&self, from: &str, to: &str) -> Result<Option<ExchangeRate>>;
     fn get_latest_exchange_rate_by_symbol(&self, symbol: &str) -> Result<Option<ExchangeRate>>;
+    fn fx_asset_exists(&self, from: &str, to: &str) -> Result<bool>;
     fn get_historical_quotes(
         &self,
         symbol: &str,
@@ -26,6 +27,7 @@ pub trait FxRepositoryTrait: Send + Sync {
         source: String,
     ) -> Result<Quote>;
     async fn save_exchange_rate(&self, rate: ExchangeRate) -> Result<ExchangeRate>;
+    async fn save_exchange_rates(&self, rates: Vec<ExchangeRate>) -> Result<usize>;
     async fn update_exchange_rate(&self, rate: &ExchangeRate) -> Result<ExchangeRate>;
     async fn delete_exchange_rate(&self, rate_id: &str) -> Result<()>;
     async fn create_fx_asset(&self, from_currency: &str, to_currency: &str, source: &str) -> Result<()>;
@@ -63,4 +65,32 @@ pub trait FxServiceTrait: Send + Sync {
     async fn delete_exchange_rate(&self, rate_id: &str) -> Result<()>;
     async fn register_currency_pair(&self, from_currency: &str, to_currency: &str) -> Result<()>;
     async fn register_currency_pair_manual(&self, from_currency: &str, to_currency: &str) -> Result<()>;
+    /// Imports a reference-rate file published by `provider_id` (e.g. `ECB`).
+    /// When `currencies` is set, only pairs between those currencies are kept.
+    async fn import_reference_rates(
+        &self,
+        provider_id: &str,
+        content: &str,
+        currencies: Option<Vec<String>>,
+    ) -> Result<usize>;
+    /// Downloads reference rates from `provider_id` for the given date range.
+    async fn sync_reference_rates(
+        &self,
+        provider_id: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        currencies: Option<Vec<String>>,
+    ) -> Result<usize>;
+    fn get_conversion_policy(&self) -> Result<FxConversionPolicy>;
+    /// Replaces the triangulation policy used by all subsequent conversions.
+    fn set_conversion_policy(&self, policy: FxConversionPolicy) -> Result<()>;
+    /// Converts `amount` as `convert_currency_for_date` would and reports the
+    /// path taken and the stored rates behind it.
+    fn explain_conversion_for_date(
+        &self,
+        amount: Decimal,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+    ) -> Result<FxConversionExplanation>;
 }
@@ -4,10 +4,15 @@ pub mod fx_model;
 pub mod fx_repository;
 pub mod fx_service;
 pub mod fx_traits;
+pub mod providers;
 
 pub use fx_errors::FxError;
-pub use fx_model::{ExchangeRate, NewExchangeRate};
+pub use fx_model::{
+    ExchangeRate, FxConversionExplanation, FxConversionLeg, FxConversionPath, FxConversionPolicy,
+    NewExchangeRate,
+};
 pub use fx_service::FxService;
 pub use fx_repository::FxRepository;
 pub use currency_converter::CurrencyConverter;
 pub use fx_traits::{FxRepositoryTrait, FxServiceTrait};
+pub use providers::FxRateProvider;
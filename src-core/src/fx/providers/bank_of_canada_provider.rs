@@ -0,0 +1,140 @@
+use super::fx_rate_provider::{
+    fetch_text, is_currency_code, parse_rate_value, reference_rate, FxRateProvider,
+};
+use crate::fx::fx_errors::FxError;
+use crate::fx::fx_model::ExchangeRate;
+use crate::market_data::market_data_constants::DATA_SOURCE_BANK_OF_CANADA;
+use crate::market_data::market_data_model::DataSource;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+const VALET_URL: &str = "https://www.bankofcanada.ca/valet/observations/group/FX_RATES_DAILY/csv";
+const OBSERVATIONS_MARKER: &str = "OBSERVATIONS";
+
+/// Daily exchange rates published by the Bank of Canada through its Valet API.
+///
+/// Series are named `FX{FROM}{TO}` (e.g. `FXUSDCAD` is Canadian dollars per US dollar).
+#[derive(Default)]
+pub struct BankOfCanadaProvider;
+
+impl BankOfCanadaProvider {
+    pub fn new() -> Self {
+        BankOfCanadaProvider
+    }
+
+    /// Splits a Valet series id into its currency pair.
+    fn parse_series(series: &str) -> Option<(&str, &str)> {
+        let pair = series.strip_prefix("FX")?;
+        if pair.len() != 6 {
+            return None;
+        }
+        let (from, to) = pair.split_at(3);
+        (is_currency_code(from) && is_currency_code(to)).then_some((from, to))
+    }
+}
+
+#[async_trait]
+impl FxRateProvider for BankOfCanadaProvider {
+    fn id(&self) -> &'static str {
+        DATA_SOURCE_BANK_OF_CANADA
+    }
+
+    fn data_source(&self) -> DataSource {
+        DataSource::BankOfCanada
+    }
+
+    fn parse_rates(&self, content: &str) -> Result<Vec<ExchangeRate>, FxError> {
+        // Valet CSV exports prepend terms and series metadata sections; the data
+        // table follows the "OBSERVATIONS" marker line.
+        let observations = match content
+            .lines()
+            .position(|line| line.trim().trim_matches('"') == OBSERVATIONS_MARKER)
+        {
+            Some(index) => content.lines().skip(index + 1).collect::<Vec<_>>().join("\n"),
+            None => content.to_string(),
+        };
+
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(observations.as_bytes());
+        let header = reader
+            .headers()
+            .map_err(|e| FxError::ParseError(format!("Invalid Bank of Canada file: {}", e)))?
+            .clone();
+        if header.get(0) != Some("date") {
+            return Err(FxError::ParseError(
+                "Bank of Canada file is missing the observations table".to_string(),
+            ));
+        }
+
+        let mut rates = Vec::new();
+        for record in reader.records() {
+            let record = record
+                .map_err(|e| FxError::ParseError(format!("Invalid Bank of Canada row: {}", e)))?;
+            let Some(date_str) = record.get(0).filter(|d| !d.is_empty()) else {
+                continue;
+            };
+            let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|e| {
+                FxError::ParseError(format!("Invalid Bank of Canada date '{}': {}", date_str, e))
+            })?;
+
+            for (index, series) in header.iter().enumerate().skip(1) {
+                let Some((from, to)) = Self::parse_series(series) else {
+                    continue;
+                };
+                if let Some(rate) = record.get(index).and_then(parse_rate_value) {
+                    rates.push(reference_rate(from, to, date, rate, self.data_source()));
+                }
+            }
+        }
+
+        Ok(rates)
+    }
+
+    async fn fetch_rates(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<ExchangeRate>, FxError> {
+        let start = start_date.format("%Y-%m-%d").to_string();
+        let end = end_date.format("%Y-%m-%d").to_string();
+        let url = reqwest::Url::parse_with_params(
+            VALET_URL,
+            &[("start_date", start.as_str()), ("end_date", end.as_str())],
+        )
+        .map_err(|e| FxError::FetchError(format!("Failed to build Bank of Canada URL: {}", e)))?;
+
+        let body = fetch_text("Bank of Canada", url).await?;
+        self.parse_rates(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_valet_csv() {
+        let content = "\"TERMS AND CONDITIONS\"\n\
+            \"https://www.bankofcanada.ca/terms/\"\n\
+            \n\
+            \"SERIES\"\n\
+            id,label,description\n\
+            FXUSDCAD,USD/CAD,\"US dollar to Canadian dollar daily exchange rate\"\n\
+            \n\
+            \"OBSERVATIONS\"\n\
+            date,FXUSDCAD,FXEURCAD\n\
+            2024-01-02,1.3316,1.4599\n\
+            2024-01-03,1.3356,\n";
+        let rates = BankOfCanadaProvider::new().parse_rates(content).unwrap();
+
+        assert_eq!(rates.len(), 3);
+        assert_eq!(rates[0].id, "USDCAD=X");
+        assert_eq!(rates[0].from_currency, "USD");
+        assert_eq!(rates[0].to_currency, "CAD");
+        assert_eq!(rates[0].rate, dec!(1.3316));
+        assert_eq!(rates[2].rate, dec!(1.3356));
+    }
+}
@@ -0,0 +1,186 @@
+use super::fx_rate_provider::{
+    fetch_text, is_currency_code, parse_rate_value, reference_rate, FxRateProvider,
+};
+use crate::fx::fx_errors::FxError;
+use crate::fx::fx_model::ExchangeRate;
+use crate::market_data::market_data_constants::DATA_SOURCE_ECB;
+use crate::market_data::market_data_model::DataSource;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+const ECB_API_URL: &str = "https://data-api.ecb.europa.eu/service/data/EXR/D..EUR.SP00.A";
+const ECB_BASE_CURRENCY: &str = "EUR";
+
+/// Euro foreign exchange reference rates published by the European Central Bank.
+///
+/// Accepts both the `eurofxref-hist.csv` file (one column per currency) and the
+/// SDMX `csvdata` export returned by the ECB data portal (one row per observation).
+#[derive(Default)]
+pub struct EcbProvider;
+
+impl EcbProvider {
+    pub fn new() -> Self {
+        EcbProvider
+    }
+
+    fn parse_eurofxref(
+        &self,
+        records: &[csv::StringRecord],
+    ) -> Result<Vec<ExchangeRate>, FxError> {
+        let header = &records[0];
+        let mut rates = Vec::new();
+
+        for record in &records[1..] {
+            let Some(date_str) = record.get(0) else {
+                continue;
+            };
+            let date = NaiveDate::parse_from_str(date_str.trim(), "%Y-%m-%d").map_err(|e| {
+                FxError::ParseError(format!("Invalid ECB date '{}': {}", date_str, e))
+            })?;
+
+            for (index, currency) in header.iter().enumerate().skip(1) {
+                let currency = currency.trim();
+                if !is_currency_code(currency) {
+                    continue;
+                }
+                if let Some(rate) = record.get(index).and_then(parse_rate_value) {
+                    rates.push(reference_rate(
+                        ECB_BASE_CURRENCY,
+                        currency,
+                        date,
+                        rate,
+                        self.data_source(),
+                    ));
+                }
+            }
+        }
+
+        Ok(rates)
+    }
+
+    fn parse_sdmx(&self, records: &[csv::StringRecord]) -> Result<Vec<ExchangeRate>, FxError> {
+        let header = &records[0];
+        let column = |name: &str| {
+            header.iter().position(|h| h == name).ok_or_else(|| {
+                FxError::ParseError(format!("ECB SDMX file is missing the {} column", name))
+            })
+        };
+        let currency_col = column("CURRENCY")?;
+        let denom_col = column("CURRENCY_DENOM")?;
+        let date_col = column("TIME_PERIOD")?;
+        let value_col = column("OBS_VALUE")?;
+
+        let mut rates = Vec::new();
+        for record in &records[1..] {
+            let (Some(currency), Some(denom), Some(date_str)) = (
+                record.get(currency_col),
+                record.get(denom_col),
+                record.get(date_col),
+            ) else {
+                continue;
+            };
+            let Some(rate) = record.get(value_col).and_then(parse_rate_value) else {
+                continue;
+            };
+            let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|e| {
+                FxError::ParseError(format!("Invalid ECB date '{}': {}", date_str, e))
+            })?;
+            // SDMX series are quoted as units of CURRENCY per one CURRENCY_DENOM.
+            rates.push(reference_rate(denom, currency, date, rate, self.data_source()));
+        }
+
+        Ok(rates)
+    }
+}
+
+#[async_trait]
+impl FxRateProvider for EcbProvider {
+    fn id(&self) -> &'static str {
+        DATA_SOURCE_ECB
+    }
+
+    fn data_source(&self) -> DataSource {
+        DataSource::Ecb
+    }
+
+    fn parse_rates(&self, content: &str) -> Result<Vec<ExchangeRate>, FxError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(content.as_bytes());
+        let records = reader
+            .records()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| FxError::ParseError(format!("Invalid ECB file: {}", e)))?;
+
+        let Some(header) = records.first() else {
+            return Ok(Vec::new());
+        };
+
+        if header.iter().any(|h| h == "OBS_VALUE") {
+            self.parse_sdmx(&records)
+        } else if header.get(0) == Some("Date") {
+            self.parse_eurofxref(&records)
+        } else {
+            Err(FxError::ParseError(
+                "Unrecognized ECB file: expected eurofxref or SDMX csvdata format".to_string(),
+            ))
+        }
+    }
+
+    async fn fetch_rates(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<ExchangeRate>, FxError> {
+        let start = start_date.format("%Y-%m-%d").to_string();
+        let end = end_date.format("%Y-%m-%d").to_string();
+        let url = reqwest::Url::parse_with_params(
+            ECB_API_URL,
+            &[
+                ("startPeriod", start.as_str()),
+                ("endPeriod", end.as_str()),
+                ("format", "csvdata"),
+            ],
+        )
+        .map_err(|e| FxError::FetchError(format!("Failed to build ECB URL: {}", e)))?;
+
+        let body = fetch_text("ECB", url).await?;
+        self.parse_rates(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_eurofxref_hist() {
+        let content = "Date,USD,JPY,CYP,\n2024-01-03,1.0919,155.48,N/A,\n2024-01-02,1.0956,155.76,N/A,\n";
+        let rates = EcbProvider::new().parse_rates(content).unwrap();
+
+        assert_eq!(rates.len(), 4);
+        let usd = rates
+            .iter()
+            .find(|r| r.to_currency == "USD" && r.timestamp.date_naive().to_string() == "2024-01-02")
+            .unwrap();
+        assert_eq!(usd.from_currency, "EUR");
+        assert_eq!(usd.id, "EURUSD=X");
+        assert_eq!(usd.rate, dec!(1.0956));
+        assert_eq!(usd.source, DataSource::Ecb);
+    }
+
+    #[test]
+    fn test_parse_sdmx_csvdata() {
+        let content = "KEY,FREQ,CURRENCY,CURRENCY_DENOM,EXR_TYPE,EXR_SUFFIX,TIME_PERIOD,OBS_VALUE\n\
+            EXR.D.USD.EUR.SP00.A,D,USD,EUR,SP00,A,2024-01-02,1.0956\n\
+            EXR.D.GBP.EUR.SP00.A,D,GBP,EUR,SP00,A,2024-01-02,0.86518\n";
+        let rates = EcbProvider::new().parse_rates(content).unwrap();
+
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[1].id, "EURGBP=X");
+        assert_eq!(rates[1].rate, dec!(0.86518));
+    }
+}
@@ -0,0 +1,177 @@
+use super::fx_rate_provider::{is_currency_code, parse_rate_value, reference_rate, FxRateProvider};
+use crate::fx::fx_errors::FxError;
+use crate::fx::fx_model::ExchangeRate;
+use crate::market_data::market_data_constants::DATA_SOURCE_FEDERAL_RESERVE;
+use crate::market_data::market_data_model::DataSource;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+const USD: &str = "USD";
+
+/// H.10 series quoted as US dollars per unit of foreign currency, keyed by the
+/// Federal Reserve country code at the end of the series identifier.
+const USD_PER_CURRENCY_SERIES: &[(&str, &str)] =
+    &[("EU", "EUR"), ("UK", "GBP"), ("AL", "AUD"), ("NZ", "NZD")];
+
+/// Foreign exchange rates (H.10) from the Federal Reserve Board Data Download Program.
+///
+/// Expects a CSV download with labels included and one column per series. The
+/// metadata rows preceding the data give each column's currency and multiplier.
+#[derive(Default)]
+pub struct FedH10Provider;
+
+impl FedH10Provider {
+    pub fn new() -> Self {
+        FedH10Provider
+    }
+
+    /// Resolves a column to its `(from, to)` pair, or `None` for non-currency series
+    /// such as the trade-weighted dollar indexes.
+    fn resolve_pair(series_id: &str, currency: &str) -> Option<(String, String)> {
+        let series_id = series_id.trim();
+        if series_id.contains("$US") {
+            let country = series_id.rsplit('.').next()?;
+            USD_PER_CURRENCY_SERIES
+                .iter()
+                .find(|(code, _)| *code == country)
+                .map(|(_, foreign)| (foreign.to_string(), USD.to_string()))
+        } else if is_currency_code(currency) && currency != USD {
+            Some((USD.to_string(), currency.to_string()))
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl FxRateProvider for FedH10Provider {
+    fn id(&self) -> &'static str {
+        DATA_SOURCE_FEDERAL_RESERVE
+    }
+
+    fn data_source(&self) -> DataSource {
+        DataSource::FederalReserve
+    }
+
+    fn parse_rates(&self, content: &str) -> Result<Vec<ExchangeRate>, FxError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(content.as_bytes());
+
+        let mut currencies: Option<csv::StringRecord> = None;
+        let mut multipliers: Option<csv::StringRecord> = None;
+        let mut pairs: Option<Vec<Option<(String, String)>>> = None;
+        let mut rates = Vec::new();
+
+        for record in reader.records() {
+            let record =
+                record.map_err(|e| FxError::ParseError(format!("Invalid H.10 file: {}", e)))?;
+            let label = record.get(0).unwrap_or_default();
+
+            let Some(pairs) = &pairs else {
+                match label {
+                    "Currency:" => currencies = Some(record.clone()),
+                    "Multiplier:" => multipliers = Some(record.clone()),
+                    "Time Period" => {
+                        let currencies = currencies.as_ref().ok_or_else(|| {
+                            FxError::ParseError(
+                                "H.10 file is missing the Currency row; download it with labels included"
+                                    .to_string(),
+                            )
+                        })?;
+                        pairs = Some(
+                            record
+                                .iter()
+                                .enumerate()
+                                .map(|(index, series_id)| {
+                                    if index == 0 {
+                                        return None;
+                                    }
+                                    Self::resolve_pair(
+                                        series_id,
+                                        currencies.get(index).unwrap_or_default(),
+                                    )
+                                })
+                                .collect(),
+                        );
+                    }
+                    _ => {}
+                }
+                continue;
+            };
+
+            let date = NaiveDate::parse_from_str(label, "%Y-%m-%d").map_err(|e| {
+                FxError::ParseError(format!("Invalid H.10 date '{}': {}", label, e))
+            })?;
+
+            for (index, pair) in pairs.iter().enumerate() {
+                let Some((from, to)) = pair else {
+                    continue;
+                };
+                let Some(value) = record.get(index).and_then(parse_rate_value) else {
+                    continue;
+                };
+                let multiplier = multipliers
+                    .as_ref()
+                    .and_then(|m| m.get(index))
+                    .and_then(|m| Decimal::from_str(m).ok())
+                    .unwrap_or(Decimal::ONE);
+                rates.push(reference_rate(
+                    from,
+                    to,
+                    date,
+                    value * multiplier,
+                    self.data_source(),
+                ));
+            }
+        }
+
+        if pairs.is_none() {
+            return Err(FxError::ParseError(
+                "H.10 file is missing the Time Period header row".to_string(),
+            ));
+        }
+
+        Ok(rates)
+    }
+
+    async fn fetch_rates(
+        &self,
+        _start_date: NaiveDate,
+        _end_date: NaiveDate,
+    ) -> Result<Vec<ExchangeRate>, FxError> {
+        Err(FxError::FetchError(
+            "The Federal Reserve publishes H.10 rates through its Data Download Program only; import the downloaded CSV file instead".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_h10_csv() {
+        let content = "\"Series Description\",\"Japan -- Spot Exchange Rate, Yen/US$\",\"Euro Area -- Spot Exchange Rate US$/Euro\",\"Nominal Broad Dollar Index\"\n\
+            \"Unit:\",\"Currency:_Per_USD\",\"USD:_Per_Currency\",\"Index:_Jan_2006_100\"\n\
+            \"Multiplier:\",\"1\",\"1\",\"1\"\n\
+            \"Currency:\",\"JPY\",\"USD\",\"NA\"\n\
+            \"Unique Identifier: \",\"H10/H10/RXI_N.B.JA\",\"H10/H10/RXI$US_N.B.EU\",\"H10/H10/JRXWTFB_N.B\"\n\
+            \"Time Period\",\"RXI_N.B.JA\",\"RXI$US_N.B.EU\",\"JRXWTFB_N.B\"\n\
+            2024-01-02,141.2700,1.0956,120.1\n\
+            2024-01-15,ND,ND,ND\n";
+        let rates = FedH10Provider::new().parse_rates(content).unwrap();
+
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].id, "USDJPY=X");
+        assert_eq!(rates[0].rate, dec!(141.27));
+        assert_eq!(rates[1].id, "EURUSD=X");
+        assert_eq!(rates[1].rate, dec!(1.0956));
+        assert_eq!(rates[1].source, DataSource::FederalReserve);
+    }
+}
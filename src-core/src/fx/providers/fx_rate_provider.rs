@@ -0,0 +1,78 @@
+use crate::fx::fx_errors::FxError;
+use crate::fx::fx_model::ExchangeRate;
+use crate::market_data::market_data_constants::MARKET_DATA_QUOTE_TIME;
+use crate::market_data::market_data_model::DataSource;
+use async_trait::async_trait;
+use chrono::{NaiveDate, TimeZone, Utc};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Source of official reference exchange rates, such as a central bank.
+///
+/// Unlike `MarketDataProvider`, which fetches quotes per symbol, reference-rate
+/// providers publish one table covering many currencies against a single base.
+#[async_trait]
+pub trait FxRateProvider: Send + Sync {
+    fn id(&self) -> &'static str;
+    fn data_source(&self) -> DataSource;
+
+    /// Parses a reference-rate file as published by the provider.
+    fn parse_rates(&self, content: &str) -> Result<Vec<ExchangeRate>, FxError>;
+
+    /// Downloads the provider's reference rates for the given date range.
+    async fn fetch_rates(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<ExchangeRate>, FxError>;
+}
+
+/// Builds an `ExchangeRate` stamped at the standard quote time of `date`.
+pub(crate) fn reference_rate(
+    from_currency: &str,
+    to_currency: &str,
+    date: NaiveDate,
+    rate: Decimal,
+    source: DataSource,
+) -> ExchangeRate {
+    let (hour, minute, second) = MARKET_DATA_QUOTE_TIME;
+    let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(hour, minute, second).unwrap());
+    ExchangeRate {
+        id: ExchangeRate::make_fx_symbol(from_currency, to_currency),
+        from_currency: from_currency.to_string(),
+        to_currency: to_currency.to_string(),
+        rate,
+        source,
+        timestamp,
+    }
+}
+
+/// Parses a published rate value. Returns `None` for the "no data" markers
+/// used by the various publishers (`N/A`, `ND`, empty cells) and for
+/// non-positive values.
+pub(crate) fn parse_rate_value(value: &str) -> Option<Decimal> {
+    let trimmed = value.trim().trim_matches('"');
+    if trimmed.is_empty() {
+        return None;
+    }
+    Decimal::from_str(trimmed)
+        .ok()
+        .filter(|rate| *rate > Decimal::ZERO)
+}
+
+pub(crate) fn is_currency_code(value: &str) -> bool {
+    value.len() == 3 && value.chars().all(|c| c.is_ascii_uppercase())
+}
+
+pub(crate) fn fetch_error(provider: &str, e: impl std::fmt::Display) -> FxError {
+    FxError::FetchError(format!("{} request failed: {}", provider, e))
+}
+
+/// Downloads a text document, mapping transport and HTTP status failures to `FxError`.
+pub(crate) async fn fetch_text(provider: &str, url: reqwest::Url) -> Result<String, FxError> {
+    let response = reqwest::get(url).await.map_err(|e| fetch_error(provider, e))?;
+    if !response.status().is_success() {
+        return Err(fetch_error(provider, response.status()));
+    }
+    response.text().await.map_err(|e| fetch_error(provider, e))
+}
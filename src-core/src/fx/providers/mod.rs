@@ -0,0 +1,24 @@
+pub mod bank_of_canada_provider;
+pub mod ecb_provider;
+pub mod fed_h10_provider;
+pub mod fx_rate_provider;
+
+use crate::market_data::market_data_constants::{
+    DATA_SOURCE_BANK_OF_CANADA, DATA_SOURCE_ECB, DATA_SOURCE_FEDERAL_RESERVE,
+};
+use std::sync::Arc;
+
+pub use bank_of_canada_provider::BankOfCanadaProvider;
+pub use ecb_provider::EcbProvider;
+pub use fed_h10_provider::FedH10Provider;
+pub use fx_rate_provider::FxRateProvider;
+
+/// Returns the reference-rate provider registered under `provider_id`.
+pub fn get_fx_rate_provider(provider_id: &str) -> Option<Arc<dyn FxRateProvider>> {
+    match provider_id.to_uppercase().as_str() {
+        DATA_SOURCE_ECB => Some(Arc::new(EcbProvider::new())),
+        DATA_SOURCE_BANK_OF_CANADA => Some(Arc::new(BankOfCanadaProvider::new())),
+        DATA_SOURCE_FEDERAL_RESERVE => Some(Arc::new(FedH10Provider::new())),
+        _ => None,
+    }
+}
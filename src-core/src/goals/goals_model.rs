@@ -1,9 +1,20 @@
+use chrono::NaiveDate;
 use diesel::prelude::*;
 use diesel::Queryable;
 use diesel::Selectable;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use crate::accounts::Account;
 
+pub const CONTRIBUTION_FREQUENCY_WEEKLY: &str = "WEEKLY";
+pub const CONTRIBUTION_FREQUENCY_MONTHLY: &str = "MONTHLY";
+pub const CONTRIBUTION_FREQUENCY_QUARTERLY: &str = "QUARTERLY";
+pub const CONTRIBUTION_FREQUENCY_YEARLY: &str = "YEARLY";
+
+fn default_contribution_frequency() -> String {
+    CONTRIBUTION_FREQUENCY_MONTHLY.to_string()
+}
+
 #[derive(
     Queryable,
     Identifiable,
@@ -23,7 +34,22 @@ pub struct Goal {
     pub title: String,
     pub description: Option<String>,
     pub target_amount: f64,
+    /// Cached from the goal's funded value by the progress service; not user-editable.
+    #[serde(default)]
     pub is_achieved: bool,
+    #[serde(default)]
+    pub target_date: Option<NaiveDate>,
+    /// Savings towards the goal held outside the allocated accounts.
+    #[serde(default)]
+    pub starting_amount: f64,
+    /// Planned contribution per `contribution_frequency` period.
+    #[serde(default)]
+    pub contribution_amount: f64,
+    #[serde(default = "default_contribution_frequency")]
+    pub contribution_frequency: String,
+    /// Profile the goal belongs to; kept as stored on update.
+    #[serde(default)]
+    pub profile_id: String,
 }
 
 #[derive(Insertable, Serialize, Deserialize, Debug, Clone)]
@@ -34,7 +60,19 @@ pub struct NewGoal {
     pub title: String,
     pub description: Option<String>,
     pub target_amount: f64,
+    #[serde(default)]
     pub is_achieved: bool,
+    #[serde(default)]
+    pub target_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub starting_amount: f64,
+    #[serde(default)]
+    pub contribution_amount: f64,
+    #[serde(default = "default_contribution_frequency")]
+    pub contribution_frequency: String,
+    /// Defaults to the active profile.
+    #[serde(default)]
+    pub profile_id: Option<String>,
 }
 
 #[derive(
@@ -61,3 +99,44 @@ pub struct GoalsAllocation {
     pub account_id: String,
     pub percent_allocation: i32,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GoalStatus {
+    Achieved,
+    /// Funded value plus planned contributions reaches the target by its date.
+    OnTrack,
+    Behind,
+    /// No target date, so there is no schedule to be on or behind.
+    Unscheduled,
+}
+
+/// A goal's funded value on one day: its share of each allocated account plus the starting amount.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalFundingPoint {
+    pub date: NaiveDate,
+    pub value: Decimal,
+}
+
+/// Where a goal stands against its target and plan, in base currency.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgressReport {
+    pub goal_id: String,
+    pub title: String,
+    pub target_amount: Decimal,
+    pub funded_value: Decimal,
+    /// Funded value over target amount; can exceed 1.
+    pub progress: Decimal,
+    pub target_date: Option<NaiveDate>,
+    pub months_remaining: Option<u32>,
+    pub planned_monthly_contribution: Decimal,
+    /// Monthly contribution that closes the gap by the target date, ignoring returns.
+    pub required_monthly_contribution: Option<Decimal>,
+    /// Funded value plus planned contributions at the target date.
+    pub projected_value: Option<Decimal>,
+    pub status: GoalStatus,
+    pub is_achieved: bool,
+    pub history: Vec<GoalFundingPoint>,
+}
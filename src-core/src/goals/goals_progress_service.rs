@@ -0,0 +1,395 @@
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::goals::goals_model::{
+    Goal, GoalFundingPoint, GoalProgressReport, GoalStatus, GoalsAllocation,
+    CONTRIBUTION_FREQUENCY_MONTHLY, CONTRIBUTION_FREQUENCY_QUARTERLY,
+    CONTRIBUTION_FREQUENCY_WEEKLY, CONTRIBUTION_FREQUENCY_YEARLY,
+};
+use crate::goals::goals_traits::{GoalProgressServiceTrait, GoalRepositoryTrait};
+use crate::portfolio::valuation::{DailyAccountValuation, ValuationServiceTrait};
+use crate::profiles::profile_in_scope;
+
+/// Planned contribution converted to a monthly amount.
+pub(crate) fn monthly_contribution(amount: Decimal, frequency: &str) -> Result<Decimal> {
+    let per_month = match frequency {
+        f if f == CONTRIBUTION_FREQUENCY_WEEKLY => amount * dec!(52) / dec!(12),
+        f if f == CONTRIBUTION_FREQUENCY_MONTHLY => amount,
+        f if f == CONTRIBUTION_FREQUENCY_QUARTERLY => amount / dec!(3),
+        f if f == CONTRIBUTION_FREQUENCY_YEARLY => amount / dec!(12),
+        _ => {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown contribution frequency: {}",
+                frequency
+            ))))
+        }
+    };
+    Ok(per_month)
+}
+
+/// Checks the plan fields a user can set on a goal.
+pub(crate) fn validate_goal_plan(
+    target_amount: f64,
+    starting_amount: f64,
+    contribution_amount: f64,
+    contribution_frequency: &str,
+) -> Result<()> {
+    for (field, value) in [
+        ("targetAmount", target_amount),
+        ("startingAmount", starting_amount),
+        ("contributionAmount", contribution_amount),
+    ] {
+        if !value.is_finite() || value < 0.0 {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{} must be zero or more",
+                field
+            ))));
+        }
+    }
+    monthly_contribution(Decimal::ZERO, contribution_frequency).map(|_| ())
+}
+
+/// Whole months from `today` until `target_date`, zero once it has passed.
+fn months_until(today: NaiveDate, target_date: NaiveDate) -> u32 {
+    let mut months = (target_date.year() - today.year()) * 12 + target_date.month() as i32
+        - today.month() as i32;
+    if target_date.day() < today.day() {
+        months -= 1;
+    }
+    months.max(0) as u32
+}
+
+fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64_retain(value).unwrap_or_default().round_dp(2)
+}
+
+/// Sizes a goal's funded value against its target and contribution plan as of `today`.
+pub(crate) fn assess_goal(
+    goal: &Goal,
+    funded_value: Decimal,
+    today: NaiveDate,
+) -> Result<GoalProgressReport> {
+    let target_amount = to_decimal(goal.target_amount);
+    let planned_monthly_contribution = monthly_contribution(
+        to_decimal(goal.contribution_amount),
+        &goal.contribution_frequency,
+    )?
+    .round_dp(2);
+    let is_achieved = funded_value >= target_amount;
+    let shortfall = (target_amount - funded_value).max(Decimal::ZERO);
+
+    let months_remaining = goal.target_date.map(|date| months_until(today, date));
+    let required_monthly_contribution = months_remaining.map(|months| {
+        if months == 0 {
+            shortfall
+        } else {
+            (shortfall / Decimal::from(months)).round_dp(2)
+        }
+    });
+    let projected_value = months_remaining
+        .map(|months| funded_value + planned_monthly_contribution * Decimal::from(months));
+    let status = match projected_value {
+        _ if is_achieved => GoalStatus::Achieved,
+        None => GoalStatus::Unscheduled,
+        Some(projected) if projected >= target_amount => GoalStatus::OnTrack,
+        Some(_) => GoalStatus::Behind,
+    };
+
+    Ok(GoalProgressReport {
+        goal_id: goal.id.clone(),
+        title: goal.title.clone(),
+        target_amount,
+        funded_value,
+        progress: if target_amount > Decimal::ZERO {
+            (funded_value / target_amount).round_dp(4)
+        } else {
+            Decimal::ONE
+        },
+        target_date: goal.target_date,
+        months_remaining,
+        planned_monthly_contribution,
+        required_monthly_contribution,
+        projected_value,
+        status,
+        is_achieved,
+        history: Vec::new(),
+    })
+}
+
+/// Base-currency share of an account valuation allocated to a goal.
+fn allocated_value(valuation: &DailyAccountValuation, percent_allocation: i32) -> Decimal {
+    valuation.total_value * valuation.fx_rate_to_base * Decimal::from(percent_allocation)
+        / dec!(100)
+}
+
+/// Funded value of a goal on each valuation day. An account without a valuation on some
+/// day counts at its last known value.
+pub(crate) fn funding_history(
+    allocations: &[GoalsAllocation],
+    valuations: &[DailyAccountValuation],
+    starting_amount: Decimal,
+) -> Vec<GoalFundingPoint> {
+    let percent_by_account: HashMap<&str, i32> = allocations
+        .iter()
+        .map(|a| (a.account_id.as_str(), a.percent_allocation))
+        .collect();
+    let mut by_date: BTreeMap<NaiveDate, Vec<(&str, Decimal)>> = BTreeMap::new();
+    for valuation in valuations {
+        if let Some(percent) = percent_by_account.get(valuation.account_id.as_str()) {
+            by_date
+                .entry(valuation.valuation_date)
+                .or_default()
+                .push((valuation.account_id.as_str(), allocated_value(valuation, *percent)));
+        }
+    }
+
+    let mut latest: HashMap<&str, Decimal> = HashMap::new();
+    by_date
+        .into_iter()
+        .map(|(date, values)| {
+            latest.extend(values);
+            GoalFundingPoint {
+                date,
+                value: (starting_amount + latest.values().copied().sum::<Decimal>()).round_dp(2),
+            }
+        })
+        .collect()
+}
+
+pub struct GoalProgressService {
+    goal_repo: Arc<dyn GoalRepositoryTrait>,
+    valuation_service: Arc<dyn ValuationServiceTrait>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
+}
+
+impl GoalProgressService {
+    pub fn new(
+        goal_repo: Arc<dyn GoalRepositoryTrait>,
+        valuation_service: Arc<dyn ValuationServiceTrait>,
+        active_profile_id: Arc<RwLock<Option<String>>>,
+    ) -> Self {
+        GoalProgressService {
+            goal_repo,
+            valuation_service,
+            active_profile_id,
+        }
+    }
+
+    fn allocations_by_goal(&self) -> Result<HashMap<String, Vec<GoalsAllocation>>> {
+        let mut by_goal: HashMap<String, Vec<GoalsAllocation>> = HashMap::new();
+        for allocation in self.goal_repo.load_allocations()? {
+            by_goal
+                .entry(allocation.goal_id.clone())
+                .or_default()
+                .push(allocation);
+        }
+        Ok(by_goal)
+    }
+}
+
+#[async_trait]
+impl GoalProgressServiceTrait for GoalProgressService {
+    fn get_goal_progress(
+        &self,
+        goal_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<GoalProgressReport> {
+        let goal = self
+            .goal_repo
+            .load_goals()?
+            .into_iter()
+            .find(|g| g.id == goal_id)
+            .ok_or_else(|| Error::Repository(format!("Goal {} not found", goal_id)))?;
+        let allocations = self
+            .allocations_by_goal()?
+            .remove(goal_id)
+            .unwrap_or_default();
+
+        let mut valuations = Vec::new();
+        for allocation in &allocations {
+            valuations.extend(self.valuation_service.get_historical_valuations(
+                &allocation.account_id,
+                start_date,
+                end_date,
+            )?);
+        }
+        let history = funding_history(&allocations, &valuations, to_decimal(goal.starting_amount));
+
+        let account_ids: Vec<String> = allocations.iter().map(|a| a.account_id.clone()).collect();
+        let latest = self.valuation_service.get_latest_valuations(&account_ids)?;
+        let funded_value = funding_history(&allocations, &latest, to_decimal(goal.starting_amount))
+            .last()
+            .map(|point| point.value)
+            .unwrap_or_else(|| to_decimal(goal.starting_amount));
+
+        let mut report = assess_goal(&goal, funded_value, Utc::now().date_naive())?;
+        report.history = history;
+        Ok(report)
+    }
+
+    async fn get_goals_progress(&self) -> Result<Vec<GoalProgressReport>> {
+        let goals: Vec<Goal> = self
+            .goal_repo
+            .load_goals()?
+            .into_iter()
+            .filter(|g| profile_in_scope(&self.active_profile_id, &g.profile_id))
+            .collect();
+        let allocations_by_goal = self.allocations_by_goal()?;
+        let mut account_ids: Vec<String> = allocations_by_goal
+            .values()
+            .flatten()
+            .map(|a| a.account_id.clone())
+            .collect();
+        account_ids.sort();
+        account_ids.dedup();
+        let latest = self.valuation_service.get_latest_valuations(&account_ids)?;
+        let today = Utc::now().date_naive();
+
+        let mut reports = Vec::with_capacity(goals.len());
+        let mut changes = Vec::new();
+        for goal in &goals {
+            let allocations = allocations_by_goal
+                .get(&goal.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let funded_value = allocations
+                .iter()
+                .filter_map(|allocation| {
+                    latest
+                        .iter()
+                        .find(|v| v.account_id == allocation.account_id)
+                        .map(|v| allocated_value(v, allocation.percent_allocation))
+                })
+                .sum::<Decimal>()
+                + to_decimal(goal.starting_amount);
+            let report = assess_goal(goal, funded_value.round_dp(2), today)?;
+            if report.is_achieved != goal.is_achieved {
+                changes.push((goal.id.clone(), report.is_achieved));
+            }
+            reports.push(report);
+        }
+        if !changes.is_empty() {
+            self.goal_repo.update_goals_achieved(changes).await?;
+        }
+        Ok(reports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(target_amount: f64, target_date: Option<NaiveDate>, contribution: f64) -> Goal {
+        Goal {
+            id: "g1".to_string(),
+            title: "House".to_string(),
+            description: None,
+            target_amount,
+            is_achieved: false,
+            target_date,
+            starting_amount: 0.0,
+            contribution_amount: contribution,
+            contribution_frequency: CONTRIBUTION_FREQUENCY_MONTHLY.to_string(),
+            profile_id: "default".to_string(),
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn valuation(account_id: &str, day: NaiveDate, total: Decimal, fx: Decimal) -> DailyAccountValuation {
+        DailyAccountValuation {
+            id: format!("{}-{}", account_id, day),
+            account_id: account_id.to_string(),
+            valuation_date: day,
+            account_currency: "USD".to_string(),
+            base_currency: "USD".to_string(),
+            fx_rate_to_base: fx,
+            cash_balance: Decimal::ZERO,
+            investment_market_value: total,
+            total_value: total,
+            cost_basis: total,
+            net_contribution: total,
+            calculated_at: Utc::now(),
+            accrued_interest: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_months_until_counts_whole_months() {
+        assert_eq!(months_until(date(2025, 1, 15), date(2026, 1, 15)), 12);
+        assert_eq!(months_until(date(2025, 1, 15), date(2026, 1, 14)), 11);
+        assert_eq!(months_until(date(2025, 1, 15), date(2024, 6, 1)), 0);
+    }
+
+    #[test]
+    fn test_monthly_contribution_frequencies() {
+        assert_eq!(monthly_contribution(dec!(120), "YEARLY").unwrap(), dec!(10));
+        assert_eq!(monthly_contribution(dec!(300), "QUARTERLY").unwrap(), dec!(100));
+        assert_eq!(monthly_contribution(dec!(12), "WEEKLY").unwrap(), dec!(52));
+        assert!(monthly_contribution(dec!(1), "DAILY").is_err());
+    }
+
+    #[test]
+    fn test_assess_goal_status() {
+        let today = date(2025, 1, 1);
+        let house = goal(10_000.0, Some(date(2026, 1, 1)), 500.0);
+
+        // 12 months at 500 on top of 4,000 reaches 10,000.
+        let report = assess_goal(&house, dec!(4000), today).unwrap();
+        assert_eq!(report.status, GoalStatus::OnTrack);
+        assert_eq!(report.required_monthly_contribution, Some(dec!(500)));
+        assert_eq!(report.projected_value, Some(dec!(10000)));
+
+        let report = assess_goal(&house, dec!(3000), today).unwrap();
+        assert_eq!(report.status, GoalStatus::Behind);
+        assert_eq!(report.required_monthly_contribution, Some(dec!(583.33)));
+
+        let report = assess_goal(&house, dec!(10500), today).unwrap();
+        assert_eq!(report.status, GoalStatus::Achieved);
+        assert!(report.is_achieved);
+        assert_eq!(report.required_monthly_contribution, Some(dec!(0)));
+
+        let report = assess_goal(&goal(10_000.0, None, 0.0), dec!(3000), today).unwrap();
+        assert_eq!(report.status, GoalStatus::Unscheduled);
+        assert_eq!(report.progress, dec!(0.3));
+    }
+
+    #[test]
+    fn test_funding_history_applies_allocation_and_carries_values_forward() {
+        let allocations = vec![
+            GoalsAllocation {
+                id: "a1".to_string(),
+                goal_id: "g1".to_string(),
+                account_id: "acc1".to_string(),
+                percent_allocation: 50,
+            },
+            GoalsAllocation {
+                id: "a2".to_string(),
+                goal_id: "g1".to_string(),
+                account_id: "acc2".to_string(),
+                percent_allocation: 100,
+            },
+        ];
+        let valuations = vec![
+            valuation("acc1", date(2025, 1, 1), dec!(1000), dec!(1)),
+            valuation("acc1", date(2025, 1, 2), dec!(1200), dec!(1)),
+            valuation("acc2", date(2025, 1, 2), dec!(100), dec!(1.5)),
+            valuation("other", date(2025, 1, 2), dec!(9999), dec!(1)),
+            valuation("acc2", date(2025, 1, 3), dec!(200), dec!(1.5)),
+        ];
+        let history = funding_history(&allocations, &valuations, dec!(50));
+
+        assert_eq!(
+            history.iter().map(|p| p.value).collect::<Vec<_>>(),
+            vec![dec!(550), dec!(800), dec!(950)]
+        );
+    }
+}
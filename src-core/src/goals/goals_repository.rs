@@ -89,6 +89,13 @@ impl GoalRepositoryTrait for GoalRepository {
         self.load_allocations_for_non_achieved_goals_impl()
     }
 
+    fn load_allocations(&self) -> Result<Vec<GoalsAllocation>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(goals_allocation::table
+            .select(GoalsAllocation::as_select())
+            .load::<GoalsAllocation>(&mut conn)?)
+    }
+
     async fn upsert_goal_allocations(&self, allocations: Vec<GoalsAllocation>) -> Result<usize> {
         let allocations_owned = allocations.clone();
 
@@ -107,4 +114,18 @@ impl GoalRepositoryTrait for GoalRepository {
             })
             .await
     }
+
+    async fn update_goals_achieved(&self, changes: Vec<(String, bool)>) -> Result<usize> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                let mut affected_rows = 0;
+                for (goal_id, achieved) in changes {
+                    affected_rows += diesel::update(goals.find(goal_id))
+                        .set(is_achieved.eq(achieved))
+                        .execute(conn)?;
+                }
+                Ok(affected_rows)
+            })
+            .await
+    }
 }
@@ -1,17 +1,22 @@
 use crate::goals::goals_model::{Goal, GoalsAllocation, NewGoal};
 use crate::errors::Result;
+use crate::goals::goals_progress_service::validate_goal_plan;
 use crate::goals::goals_traits::{GoalRepositoryTrait, GoalServiceTrait};
 use async_trait::async_trait;
-use std::sync::Arc;
+use crate::constants::DEFAULT_PROFILE_ID;
+use crate::profiles::profile_in_scope;
+use std::sync::{Arc, RwLock};
 
 pub struct GoalService<T: GoalRepositoryTrait> {
     goal_repo: Arc<T>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
 }
 
 impl<T: GoalRepositoryTrait> GoalService<T> {
-    pub fn new(goal_repo: Arc<T>) -> Self {
+    pub fn new(goal_repo: Arc<T>, active_profile_id: Arc<RwLock<Option<String>>>) -> Self {
         GoalService {
             goal_repo,
+            active_profile_id,
         }
     }
 }
@@ -19,13 +24,35 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
 #[async_trait]
 impl<T: GoalRepositoryTrait + Send + Sync> GoalServiceTrait for GoalService<T> {
     fn get_goals(&self) -> Result<Vec<Goal>> {
-        self.goal_repo.load_goals()
+        Ok(self
+            .goal_repo
+            .load_goals()?
+            .into_iter()
+            .filter(|g| profile_in_scope(&self.active_profile_id, &g.profile_id))
+            .collect())
     }
 
     async fn create_goal(
         &self,
         new_goal: NewGoal,
     ) -> Result<Goal> {
+        validate_goal_plan(
+            new_goal.target_amount,
+            new_goal.starting_amount,
+            new_goal.contribution_amount,
+            &new_goal.contribution_frequency,
+        )?;
+        let mut new_goal = new_goal;
+        new_goal.is_achieved = false;
+        if new_goal.profile_id.is_none() {
+            new_goal.profile_id = Some(
+                self.active_profile_id
+                    .read()
+                    .unwrap()
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_PROFILE_ID.to_string()),
+            );
+        }
         self.goal_repo.insert_new_goal(new_goal).await
     }
 
@@ -33,6 +60,24 @@ impl<T: GoalRepositoryTrait + Send + Sync> GoalServiceTrait for GoalService<T> {
         &self,
         updated_goal_data: Goal,
     ) -> Result<Goal> {
+        validate_goal_plan(
+            updated_goal_data.target_amount,
+            updated_goal_data.starting_amount,
+            updated_goal_data.contribution_amount,
+            &updated_goal_data.contribution_frequency,
+        )?;
+        // The achieved flag is derived by the progress service and the profile is set at
+        // creation, so keep the stored ones.
+        let mut updated_goal_data = updated_goal_data;
+        if let Some(existing) = self
+            .goal_repo
+            .load_goals()?
+            .into_iter()
+            .find(|g| g.id == updated_goal_data.id)
+        {
+            updated_goal_data.is_achieved = existing.is_achieved;
+            updated_goal_data.profile_id = existing.profile_id;
+        }
         self.goal_repo.update_goal(updated_goal_data).await
     }
 
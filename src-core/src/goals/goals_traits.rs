@@ -1,6 +1,7 @@
-use crate::goals::goals_model::{Goal, GoalsAllocation, NewGoal};
+use crate::goals::goals_model::{Goal, GoalProgressReport, GoalsAllocation, NewGoal};
 use crate::errors::Result;
 use async_trait::async_trait;
+use chrono::NaiveDate;
 
 /// Trait for goal repository operations
 #[async_trait]
@@ -10,7 +11,10 @@ pub trait GoalRepositoryTrait: Send + Sync {
     async fn update_goal(&self, goal_update: Goal) -> Result<Goal>;
     async fn delete_goal(&self, goal_id_to_delete: String) -> Result<usize>;
     fn load_allocations_for_non_achieved_goals(&self) -> Result<Vec<GoalsAllocation>>;
+    fn load_allocations(&self) -> Result<Vec<GoalsAllocation>>;
     async fn upsert_goal_allocations(&self, allocations: Vec<GoalsAllocation>) -> Result<usize>;
+    /// Stores the derived achieved flag for each `(goal_id, is_achieved)` pair.
+    async fn update_goals_achieved(&self, changes: Vec<(String, bool)>) -> Result<usize>;
 }
 
 /// Trait for goal service operations
@@ -22,4 +26,18 @@ pub trait GoalServiceTrait: Send + Sync {
     async fn delete_goal(&self, goal_id_to_delete: String) -> Result<usize>;
     async fn upsert_goal_allocations(&self, allocations: Vec<GoalsAllocation>) -> Result<usize>;
     fn load_goals_allocations(&self) -> Result<Vec<GoalsAllocation>>;
-} 
\ No newline at end of file
+}
+
+/// Trait for goal progress tracking
+#[async_trait]
+pub trait GoalProgressServiceTrait: Send + Sync {
+    /// Progress of one goal, with its funded value for each valuation day in the range.
+    fn get_goal_progress(
+        &self,
+        goal_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<GoalProgressReport>;
+    /// Current progress of every goal, without history. Stores each goal's achieved flag.
+    async fn get_goals_progress(&self) -> Result<Vec<GoalProgressReport>>;
+}
@@ -1,8 +1,10 @@
 pub mod goals_repository;
 pub mod goals_service;
 pub mod goals_model;
+pub mod goals_progress_service;
 pub mod goals_traits;
 
 pub use goals_service::GoalService;
+pub use goals_progress_service::GoalProgressService;
 pub use goals_repository::GoalRepository;
-pub use goals_traits::{GoalRepositoryTrait, GoalServiceTrait};
+pub use goals_traits::{GoalProgressServiceTrait, GoalRepositoryTrait, GoalServiceTrait};
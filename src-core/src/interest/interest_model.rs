@@ -0,0 +1,325 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::constants::DISPLAY_DECIMAL_PRECISION;
+use crate::errors::{Error, Result, ValidationError};
+
+/// Day count used to turn an annual rate into a daily one (actual/365).
+pub const INTEREST_DAYS_PER_YEAR: u32 = 365;
+
+/// Whether interest accrued during the month earns interest before it is paid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InterestCompounding {
+    /// Each day's interest accrues on the cash balance plus the unpaid interest.
+    Daily,
+    /// Interest accrues on the cash balance alone and compounds once it is paid out.
+    Monthly,
+}
+
+impl InterestCompounding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InterestCompounding::Daily => "DAILY",
+            InterestCompounding::Monthly => "MONTHLY",
+        }
+    }
+}
+
+impl FromStr for InterestCompounding {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "DAILY" => Ok(InterestCompounding::Daily),
+            "MONTHLY" => Ok(InterestCompounding::Monthly),
+            _ => Err(format!("Unknown interest compounding: {}", s)),
+        }
+    }
+}
+
+/// Annual interest paid on an account's cash in one currency from `effective_date`
+/// until the next rate for the same account and currency takes over.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashInterestRate {
+    pub id: String,
+    pub account_id: String,
+    pub currency: String,
+    /// Annual rate as a fraction, e.g. 0.045 for 4.5%.
+    pub annual_rate: Decimal,
+    pub effective_date: NaiveDate,
+    pub compounding: InterestCompounding,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Input model for adding a rate to an account's schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewCashInterestRate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub account_id: String,
+    pub currency: String,
+    pub annual_rate: Decimal,
+    pub effective_date: NaiveDate,
+    pub compounding: InterestCompounding,
+}
+
+impl NewCashInterestRate {
+    pub fn validate(&self) -> Result<()> {
+        if self.account_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "accountId".to_string(),
+            )));
+        }
+        validate_rate(&self.currency, self.annual_rate)
+    }
+}
+
+/// Input model for changing a rate in an account's schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CashInterestRateUpdate {
+    pub id: String,
+    pub currency: String,
+    pub annual_rate: Decimal,
+    pub effective_date: NaiveDate,
+    pub compounding: InterestCompounding,
+}
+
+impl CashInterestRateUpdate {
+    pub fn validate(&self) -> Result<()> {
+        validate_rate(&self.currency, self.annual_rate)
+    }
+}
+
+fn validate_rate(currency: &str, annual_rate: Decimal) -> Result<()> {
+    if currency.trim().len() != 3 {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Currency must be a 3-letter code, got '{}'",
+            currency
+        ))));
+    }
+    if annual_rate < Decimal::ZERO || annual_rate >= Decimal::ONE {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Annual interest rate must be a fraction between 0 and 1, got {}",
+            annual_rate
+        ))));
+    }
+    Ok(())
+}
+
+/// Database model for cash interest rates
+#[derive(Queryable, Identifiable, Insertable, AsChangeset, Selectable, PartialEq, Debug, Clone)]
+#[diesel(table_name = crate::schema::cash_interest_rates)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct CashInterestRateDB {
+    pub id: String,
+    pub account_id: String,
+    pub currency: String,
+    pub annual_rate: String,
+    pub effective_date: NaiveDate,
+    pub compounding: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl CashInterestRateDB {
+    pub fn to_model(&self) -> Result<CashInterestRate> {
+        Ok(CashInterestRate {
+            id: self.id.clone(),
+            account_id: self.account_id.clone(),
+            currency: self.currency.clone(),
+            annual_rate: Decimal::from_str(&self.annual_rate).map_err(|e| {
+                Error::Repository(format!(
+                    "Invalid annual rate '{}' for interest rate {}: {}",
+                    self.annual_rate, self.id, e
+                ))
+            })?,
+            effective_date: self.effective_date,
+            compounding: InterestCompounding::from_str(&self.compounding)
+                .map_err(Error::Repository)?,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
+    }
+}
+
+/// Interest paid into the account's cash at the end of a month.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterestPayout {
+    /// First day whose interest the payout covers.
+    pub period_start: NaiveDate,
+    pub payout_date: NaiveDate,
+    pub amount: Decimal,
+}
+
+/// Result of running a rate schedule over a span of daily cash balances.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InterestAccrual {
+    /// Interest accrued but not yet paid at the end of each day.
+    pub daily: BTreeMap<NaiveDate, Decimal>,
+    pub payouts: Vec<InterestPayout>,
+}
+
+/// The rate in effect on `date`, if the schedule has started by then.
+pub fn rate_on(schedule: &[CashInterestRate], date: NaiveDate) -> Option<&CashInterestRate> {
+    schedule
+        .iter()
+        .filter(|rate| rate.effective_date <= date)
+        .max_by_key(|rate| rate.effective_date)
+}
+
+fn is_month_end(date: NaiveDate) -> bool {
+    date.succ_opt()
+        .is_none_or(|next| next.month() != date.month())
+}
+
+/// Accrues interest day by day from `start` to `end` on the end-of-day cash
+/// balances of one currency, paying it out on the last day of every month.
+///
+/// `schedule` holds the rates of a single account and currency. `balances` only
+/// needs an entry when the balance changes; days before the first entry hold no
+/// cash. Overdrawn balances earn nothing. `start` should be the first day of a
+/// month, otherwise interest from earlier in that month is missed.
+pub fn accrue_interest(
+    schedule: &[CashInterestRate],
+    balances: &BTreeMap<NaiveDate, Decimal>,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> InterestAccrual {
+    let days_per_year = Decimal::from(INTEREST_DAYS_PER_YEAR);
+    let mut accrual = InterestAccrual::default();
+    let mut unpaid = Decimal::ZERO;
+    let mut period_start = start;
+
+    for day in start.iter_days().take_while(|day| *day <= end) {
+        if let Some(rate) = rate_on(schedule, day) {
+            let balance = balances
+                .range(..=day)
+                .next_back()
+                .map(|(_, balance)| *balance)
+                .unwrap_or_default();
+            let principal = match rate.compounding {
+                InterestCompounding::Daily => balance + unpaid,
+                InterestCompounding::Monthly => balance,
+            };
+            if principal > Decimal::ZERO {
+                unpaid += principal * rate.annual_rate / days_per_year;
+            }
+        }
+
+        if is_month_end(day) {
+            let amount = unpaid.round_dp(DISPLAY_DECIMAL_PRECISION);
+            if amount > Decimal::ZERO {
+                accrual.payouts.push(InterestPayout {
+                    period_start,
+                    payout_date: day,
+                    amount,
+                });
+            }
+            unpaid = Decimal::ZERO;
+            if let Some(next) = day.succ_opt() {
+                period_start = next;
+            }
+        }
+        accrual.daily.insert(day, unpaid);
+    }
+    accrual
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn rate(
+        from: NaiveDate,
+        annual_rate: Decimal,
+        compounding: InterestCompounding,
+    ) -> CashInterestRate {
+        CashInterestRate {
+            id: from.to_string(),
+            account_id: "acc".to_string(),
+            currency: "USD".to_string(),
+            annual_rate,
+            effective_date: from,
+            compounding,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn test_monthly_interest_is_paid_at_month_end_and_resets() {
+        // 36,500 at 5% earns exactly 5.00 a day.
+        let schedule = vec![rate(
+            date(2024, 1, 1),
+            dec!(0.05),
+            InterestCompounding::Monthly,
+        )];
+        let balances = BTreeMap::from([(date(2023, 12, 15), dec!(36500))]);
+
+        let accrual = accrue_interest(&schedule, &balances, date(2024, 1, 1), date(2024, 2, 10));
+
+        assert_eq!(accrual.daily[&date(2024, 1, 1)], dec!(5));
+        assert_eq!(accrual.daily[&date(2024, 1, 30)], dec!(150));
+        assert_eq!(accrual.daily[&date(2024, 1, 31)], Decimal::ZERO);
+        assert_eq!(accrual.daily[&date(2024, 2, 10)], dec!(50));
+        assert_eq!(
+            accrual.payouts,
+            vec![InterestPayout {
+                period_start: date(2024, 1, 1),
+                payout_date: date(2024, 1, 31),
+                amount: dec!(155),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_daily_compounding_earns_interest_on_unpaid_interest() {
+        let balances = BTreeMap::from([(date(2024, 1, 1), dec!(36500))]);
+        let run = |compounding| {
+            let schedule = vec![rate(date(2024, 1, 1), dec!(0.05), compounding)];
+            accrue_interest(&schedule, &balances, date(2024, 1, 1), date(2024, 1, 31)).payouts[0]
+                .amount
+        };
+
+        let daily = run(InterestCompounding::Daily);
+        assert!(daily > run(InterestCompounding::Monthly));
+        // 30 days of interest on interest add a few cents over the simple 155.00.
+        assert_eq!(daily, dec!(155.32));
+    }
+
+    #[test]
+    fn test_rate_changes_and_balance_changes_apply_from_their_dates() {
+        let schedule = vec![
+            rate(date(2024, 3, 16), dec!(0.10), InterestCompounding::Monthly),
+            rate(date(2024, 3, 1), dec!(0.05), InterestCompounding::Monthly),
+        ];
+        // No cash before the 6th, then overdrawn from the 26th.
+        let balances = BTreeMap::from([
+            (date(2024, 3, 6), dec!(36500)),
+            (date(2024, 3, 26), dec!(-100)),
+        ]);
+
+        let accrual = accrue_interest(&schedule, &balances, date(2024, 3, 1), date(2024, 3, 31));
+
+        // 10 days at 5.00, then 10 days at 10.00, then nothing.
+        assert_eq!(accrual.daily[&date(2024, 3, 5)], Decimal::ZERO);
+        assert_eq!(accrual.daily[&date(2024, 3, 30)], dec!(150));
+        assert_eq!(accrual.payouts[0].amount, dec!(150));
+        assert_eq!(rate_on(&schedule, date(2024, 2, 29)), None);
+    }
+}
@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::r2d2::{self, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::{Result, ValidationError};
+use crate::schema::cash_interest_rates;
+use crate::Error;
+
+use super::interest_model::{
+    CashInterestRate, CashInterestRateDB, CashInterestRateUpdate, NewCashInterestRate,
+};
+use super::interest_traits::CashInterestRepositoryTrait;
+
+/// Repository for managing cash interest rates in the database
+pub struct CashInterestRepository {
+    pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl CashInterestRepository {
+    pub fn new(
+        pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+        writer: WriteHandle,
+    ) -> Self {
+        Self { pool, writer }
+    }
+}
+
+/// Fails if another rate of the account already starts on `effective_date` for `currency`.
+fn ensure_date_free(
+    conn: &mut SqliteConnection,
+    rate_id: &str,
+    account_id: &str,
+    currency: &str,
+    effective_date: NaiveDate,
+) -> Result<()> {
+    let taken = cash_interest_rates::table
+        .filter(cash_interest_rates::account_id.eq(account_id))
+        .filter(cash_interest_rates::currency.eq(currency))
+        .filter(cash_interest_rates::effective_date.eq(effective_date))
+        .filter(cash_interest_rates::id.ne(rate_id))
+        .select(cash_interest_rates::id)
+        .first::<String>(conn)
+        .optional()?;
+    match taken {
+        Some(_) => Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "A {} interest rate for this account already starts on {}",
+            currency, effective_date
+        )))),
+        None => Ok(()),
+    }
+}
+
+#[async_trait]
+impl CashInterestRepositoryTrait for CashInterestRepository {
+    fn list(&self) -> Result<Vec<CashInterestRate>> {
+        let mut conn = get_connection(&self.pool)?;
+        cash_interest_rates::table
+            .select(CashInterestRateDB::as_select())
+            .order((
+                cash_interest_rates::account_id.asc(),
+                cash_interest_rates::currency.asc(),
+                cash_interest_rates::effective_date.asc(),
+            ))
+            .load::<CashInterestRateDB>(&mut conn)?
+            .iter()
+            .map(CashInterestRateDB::to_model)
+            .collect()
+    }
+
+    async fn create(&self, new_rate: NewCashInterestRate) -> Result<CashInterestRate> {
+        new_rate.validate()?;
+        self.writer
+            .exec(move |conn| {
+                let now = chrono::Utc::now().naive_utc();
+                let rate_db = CashInterestRateDB {
+                    id: new_rate
+                        .id
+                        .filter(|id| !id.trim().is_empty())
+                        .unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    account_id: new_rate.account_id,
+                    currency: new_rate.currency.trim().to_uppercase(),
+                    annual_rate: new_rate.annual_rate.to_string(),
+                    effective_date: new_rate.effective_date,
+                    compounding: new_rate.compounding.as_str().to_string(),
+                    created_at: now,
+                    updated_at: now,
+                };
+                ensure_date_free(
+                    conn,
+                    &rate_db.id,
+                    &rate_db.account_id,
+                    &rate_db.currency,
+                    rate_db.effective_date,
+                )?;
+                diesel::insert_into(cash_interest_rates::table)
+                    .values(&rate_db)
+                    .execute(conn)?;
+                rate_db.to_model()
+            })
+            .await
+    }
+
+    async fn update(&self, rate_update: CashInterestRateUpdate) -> Result<CashInterestRate> {
+        rate_update.validate()?;
+        self.writer
+            .exec(move |conn| {
+                let existing = cash_interest_rates::table
+                    .find(&rate_update.id)
+                    .select(CashInterestRateDB::as_select())
+                    .first::<CashInterestRateDB>(conn)?;
+                let rate_db = CashInterestRateDB {
+                    currency: rate_update.currency.trim().to_uppercase(),
+                    annual_rate: rate_update.annual_rate.to_string(),
+                    effective_date: rate_update.effective_date,
+                    compounding: rate_update.compounding.as_str().to_string(),
+                    updated_at: chrono::Utc::now().naive_utc(),
+                    ..existing
+                };
+                ensure_date_free(
+                    conn,
+                    &rate_db.id,
+                    &rate_db.account_id,
+                    &rate_db.currency,
+                    rate_db.effective_date,
+                )?;
+                diesel::update(cash_interest_rates::table.find(&rate_db.id))
+                    .set(&rate_db)
+                    .execute(conn)?;
+                rate_db.to_model()
+            })
+            .await
+    }
+
+    async fn delete(&self, rate_id: &str) -> Result<usize> {
+        let rate_id = rate_id.to_string();
+        self.writer
+            .exec(move |conn| {
+                Ok(diesel::delete(cash_interest_rates::table.find(&rate_id)).execute(conn)?)
+            })
+            .await
+    }
+}
@@ -0,0 +1,282 @@
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate};
+use log::{debug, warn};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use super::interest_model::{
+    accrue_interest, CashInterestRate, CashInterestRateUpdate, InterestAccrual, NewCashInterestRate,
+};
+use super::interest_traits::{CashInterestRepositoryTrait, CashInterestServiceTrait};
+use crate::accounts::account_groups_model::group_memberships;
+use crate::accounts::{AccountGroupRepositoryTrait, AccountRepositoryTrait};
+use crate::activities::{Activity, ActivityServiceTrait, NewActivity, ACTIVITY_TYPE_INTEREST};
+use crate::constants::{CASH_ASSET_PREFIX, PORTFOLIO_TOTAL_ACCOUNT_ID};
+use crate::errors::Result;
+use crate::fx::fx_traits::FxServiceTrait;
+use crate::portfolio::snapshot::SnapshotServiceTrait;
+use crate::profiles::profile_in_scope;
+
+/// Service for cash interest rate schedules and the interest they accrue
+pub struct CashInterestService {
+    repository: Arc<dyn CashInterestRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    account_group_repository: Arc<dyn AccountGroupRepositoryTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    snapshot_service: Arc<dyn SnapshotServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
+}
+
+impl CashInterestService {
+    pub fn new(
+        repository: Arc<dyn CashInterestRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        account_group_repository: Arc<dyn AccountGroupRepositoryTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        snapshot_service: Arc<dyn SnapshotServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        active_profile_id: Arc<RwLock<Option<String>>>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            account_group_repository,
+            activity_service,
+            snapshot_service,
+            fx_service,
+            active_profile_id,
+        }
+    }
+
+    /// Accounts whose cash `account_id` stands for: the account itself, every
+    /// active account for `TOTAL`, or the active accounts of a group or profile.
+    fn member_account_ids(&self, account_id: &str) -> Result<Vec<String>> {
+        let accounts = self.account_repository.list(None, None)?;
+        if accounts.iter().any(|a| a.id == account_id) {
+            return Ok(vec![account_id.to_string()]);
+        }
+        let active = accounts.iter().filter(|a| a.is_active);
+        if account_id == PORTFOLIO_TOTAL_ACCOUNT_ID {
+            return Ok(active.map(|a| a.id.clone()).collect());
+        }
+        let groups = self.account_group_repository.list()?;
+        if let Some(membership) = group_memberships(&groups, &accounts)
+            .into_iter()
+            .find(|m| m.group.id == account_id)
+        {
+            return Ok(membership.account_ids);
+        }
+        Ok(active
+            .filter(|a| a.profile_id == account_id)
+            .map(|a| a.id.clone())
+            .collect())
+    }
+
+    /// Runs each currency schedule of an account over its daily cash balances.
+    fn accrue_account(
+        &self,
+        account_id: &str,
+        rates: &[CashInterestRate],
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<(String, InterestAccrual)>> {
+        let mut schedules: BTreeMap<&str, Vec<CashInterestRate>> = BTreeMap::new();
+        for rate in rates.iter().filter(|r| r.account_id == account_id) {
+            schedules
+                .entry(rate.currency.as_str())
+                .or_default()
+                .push(rate.clone());
+        }
+        if schedules.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let snapshots = self.snapshot_service.get_daily_holdings_snapshots(
+            account_id,
+            Some(start_date),
+            Some(end_date),
+        )?;
+        Ok(schedules
+            .into_iter()
+            .map(|(currency, schedule)| {
+                let balances: BTreeMap<NaiveDate, Decimal> = snapshots
+                    .iter()
+                    .map(|s| {
+                        let balance = s.cash_balances.get(currency).copied();
+                        (s.snapshot_date, balance.unwrap_or_default())
+                    })
+                    .collect();
+                let accrual = accrue_interest(&schedule, &balances, start_date, end_date);
+                (currency.to_string(), accrual)
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl CashInterestServiceTrait for CashInterestService {
+    fn get_interest_rates(&self, account_id: Option<&str>) -> Result<Vec<CashInterestRate>> {
+        let rates = self.repository.list()?;
+        Ok(match account_id {
+            Some(id) => rates.into_iter().filter(|r| r.account_id == id).collect(),
+            None => {
+                let in_scope: HashSet<String> = self
+                    .account_repository
+                    .list(None, None)?
+                    .into_iter()
+                    .filter(|a| profile_in_scope(&self.active_profile_id, &a.profile_id))
+                    .map(|a| a.id)
+                    .collect();
+                rates
+                    .into_iter()
+                    .filter(|r| in_scope.contains(&r.account_id))
+                    .collect()
+            }
+        })
+    }
+
+    async fn create_interest_rate(
+        &self,
+        new_rate: NewCashInterestRate,
+    ) -> Result<CashInterestRate> {
+        debug!(
+            "Adding {} interest rate for account {} from {}",
+            new_rate.currency, new_rate.account_id, new_rate.effective_date
+        );
+        self.repository.create(new_rate).await
+    }
+
+    async fn update_interest_rate(
+        &self,
+        rate_update: CashInterestRateUpdate,
+    ) -> Result<CashInterestRate> {
+        debug!("Updating interest rate {}", rate_update.id);
+        self.repository.update(rate_update).await
+    }
+
+    async fn delete_interest_rate(&self, rate_id: &str) -> Result<()> {
+        debug!("Deleting interest rate {}", rate_id);
+        self.repository.delete(rate_id).await?;
+        Ok(())
+    }
+
+    fn get_accrued_interest(
+        &self,
+        account_id: &str,
+        currency: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<BTreeMap<NaiveDate, Decimal>> {
+        let rates = self.repository.list()?;
+        let mut accrued_by_date: BTreeMap<NaiveDate, Decimal> = BTreeMap::new();
+        if rates.is_empty() || start_date > end_date {
+            return Ok(accrued_by_date);
+        }
+
+        // Interest accrues from the start of the month in which `start_date` falls.
+        let period_start = start_date.with_day(1).unwrap_or(start_date);
+        for member_id in self.member_account_ids(account_id)? {
+            for (rate_currency, accrual) in
+                self.accrue_account(&member_id, &rates, period_start, end_date)?
+            {
+                for (date, accrued) in accrual.daily.range(start_date..) {
+                    if accrued.is_zero() {
+                        continue;
+                    }
+                    let converted = if rate_currency == currency {
+                        *accrued
+                    } else {
+                        self.fx_service
+                            .convert_currency_for_date(*accrued, &rate_currency, currency, *date)
+                            .unwrap_or_else(|e| {
+                                warn!(
+                                    "Failed to convert accrued interest {} {} to {} on {}: {}. Using original amount.",
+                                    accrued, rate_currency, currency, date, e
+                                );
+                                *accrued
+                            })
+                    };
+                    *accrued_by_date.entry(*date).or_default() += converted;
+                }
+            }
+        }
+        Ok(accrued_by_date)
+    }
+
+    async fn generate_interest_activities(
+        &self,
+        account_id: Option<&str>,
+        end_date: NaiveDate,
+    ) -> Result<Vec<Activity>> {
+        let rates = self.get_interest_rates(account_id)?;
+        let account_ids: Vec<String> = rates
+            .iter()
+            .map(|r| r.account_id.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let mut created = Vec::new();
+        for account_id in account_ids {
+            let Some(first_effective) = rates
+                .iter()
+                .filter(|r| r.account_id == account_id)
+                .map(|r| r.effective_date)
+                .min()
+            else {
+                continue;
+            };
+            let start_date = first_effective.with_day(1).unwrap_or(first_effective);
+            if start_date > end_date {
+                continue;
+            }
+
+            let recorded: HashSet<(String, NaiveDate)> = self
+                .activity_service
+                .get_activities_by_account_ids(std::slice::from_ref(&account_id))?
+                .into_iter()
+                .filter(|a| a.activity_type == ACTIVITY_TYPE_INTEREST)
+                .map(|a| (a.currency, a.activity_date.date_naive()))
+                .collect();
+
+            for (currency, accrual) in
+                self.accrue_account(&account_id, &rates, start_date, end_date)?
+            {
+                for payout in accrual.payouts {
+                    if recorded.contains(&(currency.clone(), payout.payout_date)) {
+                        continue;
+                    }
+                    let activity = self
+                        .activity_service
+                        .create_activity(NewActivity {
+                            id: None,
+                            account_id: account_id.clone(),
+                            asset_id: format!("{}-{}", CASH_ASSET_PREFIX, currency),
+                            activity_type: ACTIVITY_TYPE_INTEREST.to_string(),
+                            activity_date: payout.payout_date.format("%Y-%m-%d").to_string(),
+                            quantity: None,
+                            unit_price: None,
+                            currency: currency.clone(),
+                            fee: None,
+                            amount: Some(payout.amount),
+                            is_draft: true,
+                            comment: Some(format!(
+                                "Interest accrued {} to {}",
+                                payout.period_start, payout.payout_date
+                            )),
+                            import_session_id: None,
+                            source_asset_id: None,
+                            withholding_tax: None,
+                            withholding_country: None,
+                        })
+                        .await?;
+                    created.push(activity);
+                }
+            }
+        }
+        debug!("Generated {} draft interest activities", created.len());
+        Ok(created)
+    }
+}
@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+use super::interest_model::{CashInterestRate, CashInterestRateUpdate, NewCashInterestRate};
+use crate::activities::Activity;
+use crate::errors::Result;
+
+/// Trait defining the contract for cash interest rate repository operations.
+#[async_trait]
+pub trait CashInterestRepositoryTrait: Send + Sync {
+    /// Every rate, by account, currency and effective date.
+    fn list(&self) -> Result<Vec<CashInterestRate>>;
+    async fn create(&self, new_rate: NewCashInterestRate) -> Result<CashInterestRate>;
+    async fn update(&self, rate_update: CashInterestRateUpdate) -> Result<CashInterestRate>;
+    async fn delete(&self, rate_id: &str) -> Result<usize>;
+}
+
+/// Trait defining the contract for cash interest service operations.
+#[async_trait]
+pub trait CashInterestServiceTrait: Send + Sync {
+    /// Rate schedules of every account in the active profile, or only of `account_id`.
+    fn get_interest_rates(&self, account_id: Option<&str>) -> Result<Vec<CashInterestRate>>;
+    async fn create_interest_rate(&self, new_rate: NewCashInterestRate)
+        -> Result<CashInterestRate>;
+    async fn update_interest_rate(
+        &self,
+        rate_update: CashInterestRateUpdate,
+    ) -> Result<CashInterestRate>;
+    async fn delete_interest_rate(&self, rate_id: &str) -> Result<()>;
+    /// Interest accrued but not yet paid at the end of each day from `start_date`
+    /// to `end_date`, in `currency`. `account_id` may also be `TOTAL`, an account
+    /// group or a profile, which sum the accrual of their accounts. Days without
+    /// accrued interest are left out.
+    fn get_accrued_interest(
+        &self,
+        account_id: &str,
+        currency: &str,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<BTreeMap<NaiveDate, Decimal>>;
+    /// Records each interest payout up to `end_date` as a draft INTEREST activity,
+    /// for every account in the active profile or only `account_id`. Payouts
+    /// already matched by an INTEREST activity on the same date and currency are
+    /// skipped.
+    async fn generate_interest_activities(
+        &self,
+        account_id: Option<&str>,
+        end_date: NaiveDate,
+    ) -> Result<Vec<Activity>>;
+}
@@ -0,0 +1,15 @@
+// Module declarations
+pub(crate) mod interest_model;
+pub(crate) mod interest_repository;
+pub(crate) mod interest_service;
+pub(crate) mod interest_traits;
+
+// Re-export the public interface
+pub use interest_model::{
+    accrue_interest, rate_on, CashInterestRate, CashInterestRateDB, CashInterestRateUpdate,
+    InterestAccrual, InterestCompounding, InterestPayout, NewCashInterestRate,
+    INTEREST_DAYS_PER_YEAR,
+};
+pub use interest_repository::CashInterestRepository;
+pub use interest_service::CashInterestService;
+pub use interest_traits::{CashInterestRepositoryTrait, CashInterestServiceTrait};
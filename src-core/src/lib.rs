@@ -2,14 +2,19 @@ pub mod db;
 pub mod constants;
 pub mod accounts;
 pub mod activities;
+pub mod allocations;
 pub mod assets;
+pub mod audit;
 
 pub mod errors;
 pub mod fx;
 pub mod goals;
+pub mod interest;
 pub mod limits;
 pub mod market_data;
 pub mod portfolio;
+pub mod profiles;
+pub mod reports;
 pub mod schema;
 pub mod settings;
 pub mod secrets;
@@ -17,6 +17,14 @@ pub struct ContributionLimit {
     pub updated_at: NaiveDateTime,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    /// Built-in rule set, e.g. `TFSA`; see [`contribution_limit_presets`](super::contribution_limit_presets).
+    pub preset: Option<String>,
+    pub birth_year: Option<i32>,
+    /// First year room accrues; defaults to the year the holder became eligible.
+    pub room_start_year: Option<i32>,
+    /// JSON `ContributionRules` replacing the preset's rules.
+    pub rules: Option<String>,
+    pub profile_id: String,
 }
 
 #[derive(Insertable, AsChangeset, Serialize, Deserialize, Debug, Clone)]
@@ -30,6 +38,17 @@ pub struct NewContributionLimit {
     pub account_ids: Option<String>,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    #[serde(default)]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub birth_year: Option<i32>,
+    #[serde(default)]
+    pub room_start_year: Option<i32>,
+    #[serde(default)]
+    pub rules: Option<String>,
+    /// Defaults to the active profile on create; `None` keeps the stored one on update.
+    #[serde(default)]
+    pub profile_id: Option<String>,
 }
 
 #[derive(Serialize, Debug)]
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use uuid::Uuid;
 use async_trait::async_trait;
 
+use crate::constants::DEFAULT_PROFILE_ID;
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::{Error, Result}; 
 use super::limits_model::{ContributionLimit, NewContributionLimit};
@@ -64,6 +65,13 @@ impl ContributionLimitRepositoryTrait for ContributionLimitRepository {
                     contribution_limits::account_ids.eq(new_limit_owned.account_ids),
                     contribution_limits::start_date.eq(new_limit_owned.start_date),
                     contribution_limits::end_date.eq(new_limit_owned.end_date),
+                    contribution_limits::preset.eq(new_limit_owned.preset),
+                    contribution_limits::birth_year.eq(new_limit_owned.birth_year),
+                    contribution_limits::room_start_year.eq(new_limit_owned.room_start_year),
+                    contribution_limits::rules.eq(new_limit_owned.rules),
+                    contribution_limits::profile_id.eq(new_limit_owned
+                        .profile_id
+                        .unwrap_or_else(|| DEFAULT_PROFILE_ID.to_string())),
                     contribution_limits::created_at.eq(chrono::Utc::now().naive_utc()),
                     contribution_limits::updated_at.eq(chrono::Utc::now().naive_utc()),
                 );
@@ -86,6 +94,11 @@ impl ContributionLimitRepositoryTrait for ContributionLimitRepository {
 
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<ContributionLimit> {
+                if let Some(profile_id) = &updated_limit_owned.profile_id {
+                    diesel::update(contribution_limits::table.find(&id_owned))
+                        .set(contribution_limits::profile_id.eq(profile_id))
+                        .execute(conn)?;
+                }
                 let target = contribution_limits::table.find(id_owned); // id_owned is moved
                 diesel::update(target)
                     .set((
@@ -95,6 +108,10 @@ impl ContributionLimitRepositoryTrait for ContributionLimitRepository {
                         contribution_limits::account_ids.eq(updated_limit_owned.account_ids),
                         contribution_limits::start_date.eq(updated_limit_owned.start_date),
                         contribution_limits::end_date.eq(updated_limit_owned.end_date),
+                        contribution_limits::preset.eq(updated_limit_owned.preset),
+                        contribution_limits::birth_year.eq(updated_limit_owned.birth_year),
+                        contribution_limits::room_start_year.eq(updated_limit_owned.room_start_year),
+                        contribution_limits::rules.eq(updated_limit_owned.rules),
                         contribution_limits::updated_at.eq(chrono::Utc::now().naive_utc()),
                     ))
                     .get_result(conn)
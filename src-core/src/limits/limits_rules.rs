@@ -0,0 +1,434 @@
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const LIMIT_PRESET_TFSA: &str = "TFSA";
+pub const LIMIT_PRESET_RRSP: &str = "RRSP";
+pub const LIMIT_PRESET_ISA: &str = "ISA";
+pub const LIMIT_PRESET_IRA: &str = "IRA";
+pub const LIMIT_PRESET_401K: &str = "401K";
+
+/// Extra room once the holder reaches `min_age` during the year.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CatchUpRule {
+    pub min_age: i32,
+    /// Catch-up amount by year; later years reuse the last published amount.
+    pub amounts: BTreeMap<i32, Decimal>,
+}
+
+/// How contribution room accrues for an account type.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ContributionRules {
+    /// Currency the limits are published in; the base currency when unset.
+    pub currency: Option<String>,
+    /// Published maximum by tax year. Later years reuse the last published maximum.
+    pub annual_limits: BTreeMap<i32, Decimal>,
+    /// Unused room, or an over-contribution, carries into the next year.
+    pub carry_forward: bool,
+    /// Withdrawals are added back to the room on the first day of the next year.
+    pub restore_withdrawals_next_year: bool,
+    pub catch_up: Option<CatchUpRule>,
+    /// Room only accrues from the year the holder reaches this age.
+    pub min_age: Option<i32>,
+    pub tax_year_start_month: u32,
+    pub tax_year_start_day: u32,
+    /// Room is this share of the previous year's earned income, capped at the annual limit.
+    pub income_percent: Option<Decimal>,
+    pub earned_income: BTreeMap<i32, Decimal>,
+    /// Over-contribution tolerated before it is penalised.
+    pub over_contribution_allowance: Decimal,
+}
+
+impl Default for ContributionRules {
+    fn default() -> Self {
+        ContributionRules {
+            currency: None,
+            annual_limits: BTreeMap::new(),
+            carry_forward: false,
+            restore_withdrawals_next_year: false,
+            catch_up: None,
+            min_age: None,
+            tax_year_start_month: 1,
+            tax_year_start_day: 1,
+            income_percent: None,
+            earned_income: BTreeMap::new(),
+            over_contribution_allowance: Decimal::ZERO,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributionLimitPreset {
+    pub id: String,
+    pub name: String,
+    pub rules: ContributionRules,
+}
+
+fn limits(entries: &[(i32, Decimal)]) -> BTreeMap<i32, Decimal> {
+    entries.iter().copied().collect()
+}
+
+/// Rules for the registered account types with published yearly maximums.
+pub fn contribution_limit_presets() -> Vec<ContributionLimitPreset> {
+    vec![
+        ContributionLimitPreset {
+            id: LIMIT_PRESET_TFSA.to_string(),
+            name: "Tax-Free Savings Account (Canada)".to_string(),
+            rules: ContributionRules {
+                currency: Some("CAD".to_string()),
+                annual_limits: limits(&[
+                    (2009, dec!(5000)),
+                    (2013, dec!(5500)),
+                    (2015, dec!(10000)),
+                    (2016, dec!(5500)),
+                    (2019, dec!(6000)),
+                    (2023, dec!(6500)),
+                    (2024, dec!(7000)),
+                ]),
+                carry_forward: true,
+                restore_withdrawals_next_year: true,
+                min_age: Some(18),
+                ..Default::default()
+            },
+        },
+        ContributionLimitPreset {
+            id: LIMIT_PRESET_RRSP.to_string(),
+            name: "Registered Retirement Savings Plan (Canada)".to_string(),
+            rules: ContributionRules {
+                currency: Some("CAD".to_string()),
+                annual_limits: limits(&[
+                    (2015, dec!(24930)),
+                    (2016, dec!(25370)),
+                    (2017, dec!(26010)),
+                    (2018, dec!(26230)),
+                    (2019, dec!(26500)),
+                    (2020, dec!(27230)),
+                    (2021, dec!(27830)),
+                    (2022, dec!(29210)),
+                    (2023, dec!(30780)),
+                    (2024, dec!(31560)),
+                    (2025, dec!(32490)),
+                ]),
+                carry_forward: true,
+                income_percent: Some(dec!(0.18)),
+                over_contribution_allowance: dec!(2000),
+                ..Default::default()
+            },
+        },
+        ContributionLimitPreset {
+            id: LIMIT_PRESET_ISA.to_string(),
+            name: "Individual Savings Account (UK)".to_string(),
+            rules: ContributionRules {
+                currency: Some("GBP".to_string()),
+                annual_limits: limits(&[
+                    (2014, dec!(15000)),
+                    (2015, dec!(15240)),
+                    (2017, dec!(20000)),
+                ]),
+                min_age: Some(18),
+                tax_year_start_month: 4,
+                tax_year_start_day: 6,
+                ..Default::default()
+            },
+        },
+        ContributionLimitPreset {
+            id: LIMIT_PRESET_IRA.to_string(),
+            name: "Individual Retirement Account (US)".to_string(),
+            rules: ContributionRules {
+                currency: Some("USD".to_string()),
+                annual_limits: limits(&[
+                    (2013, dec!(5500)),
+                    (2019, dec!(6000)),
+                    (2023, dec!(6500)),
+                    (2024, dec!(7000)),
+                ]),
+                catch_up: Some(CatchUpRule {
+                    min_age: 50,
+                    amounts: limits(&[(2013, dec!(1000))]),
+                }),
+                ..Default::default()
+            },
+        },
+        ContributionLimitPreset {
+            id: LIMIT_PRESET_401K.to_string(),
+            name: "401(k) employee deferrals (US)".to_string(),
+            rules: ContributionRules {
+                currency: Some("USD".to_string()),
+                annual_limits: limits(&[
+                    (2018, dec!(18500)),
+                    (2019, dec!(19000)),
+                    (2020, dec!(19500)),
+                    (2022, dec!(20500)),
+                    (2023, dec!(22500)),
+                    (2024, dec!(23000)),
+                    (2025, dec!(23500)),
+                ]),
+                catch_up: Some(CatchUpRule {
+                    min_age: 50,
+                    amounts: limits(&[(2018, dec!(6000)), (2020, dec!(6500)), (2023, dec!(7500))]),
+                }),
+                ..Default::default()
+            },
+        },
+    ]
+}
+
+pub fn preset_rules(preset: &str) -> Option<ContributionRules> {
+    contribution_limit_presets()
+        .into_iter()
+        .find(|p| p.id.eq_ignore_ascii_case(preset))
+        .map(|p| p.rules)
+}
+
+/// Room and contributions for one tax year, in the rules' currency.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributionRoomYear {
+    pub year: i32,
+    pub annual_limit: Decimal,
+    pub catch_up: Decimal,
+    /// Unused room brought forward; negative after an over-contribution.
+    pub carried_forward: Decimal,
+    pub restored_withdrawals: Decimal,
+    pub total_room: Decimal,
+    pub contributions: Decimal,
+    pub withdrawals: Decimal,
+    /// Negative when the year is over-contributed.
+    pub remaining_room: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributionRoom {
+    pub limit_id: String,
+    pub preset: Option<String>,
+    pub year: i32,
+    pub currency: String,
+    pub total_room: Decimal,
+    pub contributions: Decimal,
+    pub remaining_room: Decimal,
+    pub over_contribution: Decimal,
+    pub warnings: Vec<String>,
+    pub years: Vec<ContributionRoomYear>,
+}
+
+impl ContributionRules {
+    /// Tax year a date falls in, named after the calendar year it starts in.
+    pub fn tax_year(&self, date: NaiveDate) -> i32 {
+        if (date.month(), date.day()) < (self.tax_year_start_month, self.tax_year_start_day) {
+            date.year() - 1
+        } else {
+            date.year()
+        }
+    }
+
+    pub fn first_year(&self) -> Option<i32> {
+        self.annual_limits.keys().next().copied()
+    }
+
+    fn published(amounts: &BTreeMap<i32, Decimal>, year: i32) -> Decimal {
+        amounts
+            .range(..=year)
+            .next_back()
+            .map(|(_, amount)| *amount)
+            .unwrap_or_default()
+    }
+
+    fn annual_limit(&self, year: i32, warnings: &mut Vec<String>) -> Decimal {
+        let maximum = Self::published(&self.annual_limits, year);
+        if let Some(last) = self.annual_limits.keys().next_back() {
+            if year > *last && self.income_percent.is_some() {
+                warnings.push(format!(
+                    "No published maximum for {}; using the {} maximum",
+                    year, last
+                ));
+            }
+        }
+        let Some(percent) = self.income_percent else {
+            return maximum;
+        };
+        match self.earned_income.get(&(year - 1)) {
+            Some(income) => (income * percent).round_dp(2).min(maximum),
+            None => {
+                warnings.push(format!(
+                    "No earned income for {}; assuming the {} maximum",
+                    year - 1,
+                    year
+                ));
+                maximum
+            }
+        }
+    }
+
+    fn catch_up(&self, year: i32, birth_year: Option<i32>) -> Decimal {
+        match (&self.catch_up, birth_year) {
+            (Some(rule), Some(born)) if year - born >= rule.min_age => {
+                Self::published(&rule.amounts, year)
+            }
+            _ => Decimal::ZERO,
+        }
+    }
+
+    /// Walks the tax years from `start_year` to `year`, accruing room and applying each
+    /// year's deposits (positive) and withdrawals (negative) from `flows`.
+    pub fn calculate_room(
+        &self,
+        flows: &[(NaiveDate, Decimal)],
+        start_year: i32,
+        year: i32,
+        birth_year: Option<i32>,
+    ) -> (Vec<ContributionRoomYear>, Vec<String>) {
+        let mut by_year: BTreeMap<i32, (Decimal, Decimal)> = BTreeMap::new();
+        for (date, amount) in flows {
+            let entry = by_year.entry(self.tax_year(*date)).or_default();
+            if *amount >= Decimal::ZERO {
+                entry.0 += amount;
+            } else {
+                entry.1 -= amount;
+            }
+        }
+
+        let mut warnings = Vec::new();
+        let mut years = Vec::new();
+        let mut previous: Option<ContributionRoomYear> = None;
+        for y in start_year..=year {
+            let mut year_warnings = Vec::new();
+            let annual_limit = self.annual_limit(y, &mut year_warnings);
+            let catch_up = self.catch_up(y, birth_year);
+            let carried_forward = match &previous {
+                Some(prev) if self.carry_forward => prev.remaining_room,
+                _ => Decimal::ZERO,
+            };
+            let restored_withdrawals = match &previous {
+                Some(prev) if self.restore_withdrawals_next_year => prev.withdrawals,
+                _ => Decimal::ZERO,
+            };
+            let total_room = annual_limit + catch_up + carried_forward + restored_withdrawals;
+            let (contributions, withdrawals) = by_year.get(&y).copied().unwrap_or_default();
+            let remaining_room = total_room - contributions;
+
+            let over = -remaining_room;
+            if over > self.over_contribution_allowance {
+                year_warnings.push(format!(
+                    "Over-contributed by {} in {}",
+                    (over - self.over_contribution_allowance).round_dp(2).normalize(),
+                    y
+                ));
+            } else if over > Decimal::ZERO {
+                year_warnings.push(format!(
+                    "{} over the {} room, within the {} allowance",
+                    over.round_dp(2).normalize(),
+                    y,
+                    self.over_contribution_allowance
+                ));
+            }
+            // Only the year asked for reports missing data; earlier years would repeat it.
+            if y == year {
+                warnings.extend(year_warnings);
+            } else {
+                warnings.extend(
+                    year_warnings
+                        .into_iter()
+                        .filter(|w| w.starts_with("Over-contributed")),
+                );
+            }
+
+            let row = ContributionRoomYear {
+                year: y,
+                annual_limit,
+                catch_up,
+                carried_forward,
+                restored_withdrawals,
+                total_room,
+                contributions,
+                withdrawals,
+                remaining_room,
+            };
+            years.push(row.clone());
+            previous = Some(row);
+        }
+        (years, warnings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_tfsa_carries_room_forward_and_restores_withdrawals() {
+        let rules = preset_rules("tfsa").unwrap();
+        let flows = vec![
+            (date(2023, 3, 1), dec!(10000)),
+            (date(2023, 9, 1), dec!(-4000)),
+            (date(2024, 2, 1), dec!(5000)),
+        ];
+        let (years, warnings) = rules.calculate_room(&flows, 2022, 2024, Some(1990));
+
+        assert_eq!(years.len(), 3);
+        // 2022: 6,000 unused. 2023: 6,500 + 6,000 - 10,000 = 2,500.
+        assert_eq!(years[1].remaining_room, dec!(2500));
+        // 2024: 7,000 + 2,500 + 4,000 withdrawn in 2023 - 5,000 = 8,500.
+        assert_eq!(years[2].restored_withdrawals, dec!(4000));
+        assert_eq!(years[2].total_room, dec!(13500));
+        assert_eq!(years[2].remaining_room, dec!(8500));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_tfsa_over_contribution_warns() {
+        let rules = preset_rules(LIMIT_PRESET_TFSA).unwrap();
+        let flows = vec![(date(2024, 1, 10), dec!(8000))];
+        let (years, warnings) = rules.calculate_room(&flows, 2024, 2024, None);
+
+        assert_eq!(years[0].remaining_room, dec!(-1000));
+        assert_eq!(warnings, vec!["Over-contributed by 1000 in 2024".to_string()]);
+    }
+
+    #[test]
+    fn test_isa_uses_april_tax_year_without_carry_forward() {
+        let rules = preset_rules(LIMIT_PRESET_ISA).unwrap();
+        assert_eq!(rules.tax_year(date(2025, 4, 5)), 2024);
+        assert_eq!(rules.tax_year(date(2025, 4, 6)), 2025);
+
+        let flows = vec![(date(2024, 5, 1), dec!(5000)), (date(2025, 4, 1), dec!(3000))];
+        let (years, _) = rules.calculate_room(&flows, 2023, 2024, None);
+        assert_eq!(years[1].carried_forward, dec!(0));
+        assert_eq!(years[1].contributions, dec!(8000));
+        assert_eq!(years[1].remaining_room, dec!(12000));
+    }
+
+    #[test]
+    fn test_401k_catch_up_from_age_50() {
+        let rules = preset_rules(LIMIT_PRESET_401K).unwrap();
+        let (years, _) = rules.calculate_room(&[], 2024, 2025, Some(1975));
+
+        assert_eq!(years[0].catch_up, dec!(0));
+        assert_eq!(years[1].catch_up, dec!(7500));
+        assert_eq!(years[1].total_room, dec!(31000));
+    }
+
+    #[test]
+    fn test_rrsp_room_from_income_with_allowance() {
+        let mut rules = preset_rules(LIMIT_PRESET_RRSP).unwrap();
+        rules.earned_income.insert(2023, dec!(100000));
+        let flows = vec![(date(2024, 6, 1), dec!(19000))];
+        let (years, warnings) = rules.calculate_room(&flows, 2024, 2024, None);
+
+        assert_eq!(years[0].annual_limit, dec!(18000));
+        assert_eq!(years[0].remaining_room, dec!(-1000));
+        assert_eq!(
+            warnings,
+            vec!["1000 over the 2024 room, within the 2000 allowance".to_string()]
+        );
+    }
+}
@@ -1,16 +1,22 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use rust_decimal::Decimal;
 
+use crate::activities::activities_constants::{ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_WITHDRAWAL};
 use crate::activities::activities_traits::ActivityRepositoryTrait;
 use crate::errors::{Error, Result, ValidationError};
 use crate::fx::fx_traits::FxServiceTrait;
+use crate::profiles::profile_in_scope;
 
 use super::limits_model::{
     AccountDeposit, ContributionLimit, DepositsCalculation, NewContributionLimit,
 };
+use super::limits_rules::{
+    contribution_limit_presets, preset_rules, ContributionLimitPreset, ContributionRoom,
+    ContributionRules,
+};
 use super::limits_traits::{ContributionLimitRepositoryTrait, ContributionLimitServiceTrait};
 use async_trait::async_trait;
 
@@ -18,6 +24,7 @@ pub struct ContributionLimitService {
     fx_service: Arc<dyn FxServiceTrait>,
     limit_repository: Arc<dyn ContributionLimitRepositoryTrait>,
     activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
 }
 
 impl ContributionLimitService {
@@ -25,12 +32,51 @@ impl ContributionLimitService {
         fx_service: Arc<dyn FxServiceTrait>,
         limit_repository: Arc<dyn ContributionLimitRepositoryTrait>,
         activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        active_profile_id: Arc<RwLock<Option<String>>>,
     ) -> Self {
         ContributionLimitService {
             fx_service,
             limit_repository,
             activity_repository,
+            active_profile_id,
+        }
+    }
+
+    fn account_ids(limit: &ContributionLimit) -> Vec<String> {
+        limit
+            .account_ids
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// Custom rules win over the preset; a limit with neither keeps its flat amount for
+    /// its own year.
+    fn rules_for(limit: &ContributionLimit) -> Result<ContributionRules> {
+        if let Some(rules) = limit.rules.as_deref().filter(|r| !r.trim().is_empty()) {
+            return Ok(serde_json::from_str(rules)?);
+        }
+        if let Some(preset) = limit.preset.as_deref().filter(|p| !p.trim().is_empty()) {
+            return preset_rules(preset).ok_or_else(|| {
+                Error::Validation(ValidationError::InvalidInput(format!(
+                    "Unknown contribution limit preset: {}",
+                    preset
+                )))
+            });
         }
+        let limit_amount = Decimal::try_from(limit.limit_amount).map_err(|e| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid limit amount {}: {}",
+                limit.limit_amount, e
+            )))
+        })?;
+        Ok(ContributionRules {
+            annual_limits: [(limit.contribution_year, limit_amount)].into_iter().collect(),
+            ..Default::default()
+        })
     }
 
     fn calculate_deposits_by_period(
@@ -91,13 +137,22 @@ impl ContributionLimitService {
 #[async_trait]
 impl ContributionLimitServiceTrait for ContributionLimitService {
     fn get_contribution_limits(&self) -> Result<Vec<ContributionLimit>> {
-        self.limit_repository.get_contribution_limits()
+        Ok(self
+            .limit_repository
+            .get_contribution_limits()?
+            .into_iter()
+            .filter(|l| profile_in_scope(&self.active_profile_id, &l.profile_id))
+            .collect())
     }
 
     async fn create_contribution_limit(
         &self,
         new_limit: NewContributionLimit,
     ) -> Result<ContributionLimit> {
+        let mut new_limit = new_limit;
+        if new_limit.profile_id.is_none() {
+            new_limit.profile_id = self.active_profile_id.read().unwrap().clone();
+        }
         self.limit_repository.create_contribution_limit(new_limit).await
     }
 
@@ -157,4 +212,78 @@ impl ContributionLimitServiceTrait for ContributionLimitService {
             self.calculate_deposits_by_period(&account_ids, start, end, base_currency)
         }
     }
+
+    fn calculate_contribution_room(
+        &self,
+        limit_id: &str,
+        base_currency: &str,
+    ) -> Result<ContributionRoom> {
+        let limit = self.limit_repository.get_contribution_limit(limit_id)?;
+        let rules = Self::rules_for(&limit)?;
+        let currency = rules
+            .currency
+            .clone()
+            .unwrap_or_else(|| base_currency.to_string());
+        let year = limit.contribution_year;
+
+        let account_ids = Self::account_ids(&limit);
+        let activities = if account_ids.is_empty() {
+            Vec::new()
+        } else {
+            self.activity_repository
+                .get_activities_by_account_ids(&account_ids)?
+        };
+
+        let mut flows: Vec<(NaiveDate, Decimal)> = Vec::new();
+        for activity in activities.iter().filter(|a| !a.is_draft) {
+            let sign = match activity.activity_type.as_str() {
+                ACTIVITY_TYPE_DEPOSIT => Decimal::ONE,
+                ACTIVITY_TYPE_WITHDRAWAL => -Decimal::ONE,
+                _ => continue,
+            };
+            let date = activity.activity_date.date_naive();
+            let amount = activity
+                .amount
+                .unwrap_or(activity.quantity * activity.unit_price);
+            let converted = self.fx_service.convert_currency_for_date(
+                amount.abs(),
+                &activity.currency,
+                &currency,
+                date,
+            )?;
+            flows.push((date, sign * converted));
+        }
+
+        let first_activity_year = flows.iter().map(|(date, _)| rules.tax_year(*date)).min();
+        let start_year = limit
+            .room_start_year
+            .or_else(|| Some(limit.birth_year? + rules.min_age?))
+            .or(first_activity_year)
+            .unwrap_or(year)
+            .max(rules.first_year().unwrap_or(year))
+            .min(year);
+
+        let (years, warnings) = rules.calculate_room(&flows, start_year, year, limit.birth_year);
+        let current = years.last();
+        let total_room = current.map(|y| y.total_room).unwrap_or_default();
+        let contributions = current.map(|y| y.contributions).unwrap_or_default();
+        let remaining_room = current.map(|y| y.remaining_room).unwrap_or_default();
+
+        Ok(ContributionRoom {
+            limit_id: limit.id,
+            preset: limit.preset,
+            year,
+            currency,
+            total_room,
+            contributions,
+            remaining_room,
+            over_contribution: (-remaining_room).max(Decimal::ZERO),
+            warnings,
+            years,
+        })
+    }
+
+    fn get_contribution_limit_presets(&self) -> Vec<ContributionLimitPreset> {
+        contribution_limit_presets()
+    }
 }
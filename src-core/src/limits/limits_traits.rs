@@ -1,4 +1,5 @@
 use super::limits_model::{ ContributionLimit, DepositsCalculation, NewContributionLimit};
+use super::limits_rules::{ContributionLimitPreset, ContributionRoom};
 use crate::errors::Result; 
 use async_trait::async_trait;
 
@@ -32,5 +33,13 @@ pub trait ContributionLimitServiceTrait: Send + Sync {
         limit_id: &str,
         base_currency: &str,
     ) -> Result<DepositsCalculation>;
+    /// Remaining room and over-contribution warnings for the limit's year, applying its
+    /// preset or custom rules to the full deposit and withdrawal history of its accounts.
+    fn calculate_contribution_room(
+        &self,
+        limit_id: &str,
+        base_currency: &str,
+    ) -> Result<ContributionRoom>;
+    fn get_contribution_limit_presets(&self) -> Vec<ContributionLimitPreset>;
     // Note: calculate_deposits_by_period might be better as a private helper or part of the trait if needed elsewhere
 } 
\ No newline at end of file
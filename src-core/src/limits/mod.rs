@@ -1,9 +1,15 @@
 mod limits_model;
 mod limits_repository;
+mod limits_rules;
 mod limits_service;
 mod limits_traits;
 
 pub use limits_model::{AccountDeposit, ContributionLimit, DepositsCalculation, NewContributionLimit};
+pub use limits_rules::{
+    contribution_limit_presets, preset_rules, CatchUpRule, ContributionLimitPreset,
+    ContributionRoom, ContributionRoomYear, ContributionRules, LIMIT_PRESET_401K,
+    LIMIT_PRESET_IRA, LIMIT_PRESET_ISA, LIMIT_PRESET_RRSP, LIMIT_PRESET_TFSA,
+};
 pub use limits_service::ContributionLimitService;
 pub use limits_repository::ContributionLimitRepository;
 pub use limits_traits::ContributionLimitServiceTrait;
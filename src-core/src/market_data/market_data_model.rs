@@ -8,7 +8,7 @@ use rust_decimal::Decimal;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
-use crate::market_data::market_data_constants::{DATA_SOURCE_YAHOO, DATA_SOURCE_MANUAL, DATA_SOURCE_MARKET_DATA_APP, DATA_SOURCE_ALPHA_VANTAGE, DATA_SOURCE_METAL_PRICE_API};
+use crate::market_data::market_data_constants::{DATA_SOURCE_YAHOO, DATA_SOURCE_MANUAL, DATA_SOURCE_MARKET_DATA_APP, DATA_SOURCE_ALPHA_VANTAGE, DATA_SOURCE_METAL_PRICE_API, DATA_SOURCE_ECB, DATA_SOURCE_BANK_OF_CANADA, DATA_SOURCE_FEDERAL_RESERVE};
 
 #[derive(Queryable, Identifiable, Selectable, Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
@@ -132,6 +132,9 @@ pub enum DataSource {
     MarketDataApp,
     AlphaVantage,
     MetalPriceApi,
+    Ecb,
+    BankOfCanada,
+    FederalReserve,
     #[default]
     Manual,
 }
@@ -143,6 +146,9 @@ impl DataSource {
             DataSource::MarketDataApp => DATA_SOURCE_MARKET_DATA_APP,
             DataSource::AlphaVantage => DATA_SOURCE_ALPHA_VANTAGE,
             DataSource::MetalPriceApi => DATA_SOURCE_METAL_PRICE_API,
+            DataSource::Ecb => DATA_SOURCE_ECB,
+            DataSource::BankOfCanada => DATA_SOURCE_BANK_OF_CANADA,
+            DataSource::FederalReserve => DATA_SOURCE_FEDERAL_RESERVE,
             DataSource::Manual => DATA_SOURCE_MANUAL,
         }
     }
@@ -161,6 +167,9 @@ impl From<&str> for DataSource {
             DATA_SOURCE_MARKET_DATA_APP => DataSource::MarketDataApp,
             DATA_SOURCE_ALPHA_VANTAGE => DataSource::AlphaVantage,
             DATA_SOURCE_METAL_PRICE_API => DataSource::MetalPriceApi,
+            DATA_SOURCE_ECB => DataSource::Ecb,
+            DATA_SOURCE_BANK_OF_CANADA => DataSource::BankOfCanada,
+            DATA_SOURCE_FEDERAL_RESERVE => DataSource::FederalReserve,
             _ => DataSource::Manual,
         }
     }
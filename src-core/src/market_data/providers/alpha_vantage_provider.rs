@@ -310,6 +310,8 @@ impl AssetProfiler for AlphaVantageProvider {
             data_source: DataSource::AlphaVantage.as_str().to_string(),
             currency: overview.currency,
             notes: Some(overview.description),
+            // "None" or "0" when the company pays no dividend
+            dividend_yield: overview.dividend_yield.parse::<f64>().ok().filter(|y| *y > 0.0),
             ..Default::default()
         };
 
@@ -108,6 +108,7 @@ impl AssetProfiler for MetalPriceApiProvider {
             data_source: "METAL_PRICE_API".to_string(),
             sectors: Some("Materials,Commodities".to_string()),
             url: Some(format!("https://api.metalpriceapi.com/metals/{}", symbol.to_lowercase())),
+            dividend_yield: None,
         })
     }
 
@@ -20,6 +20,17 @@ pub struct QuoteSummaryResult {
     pub price: Option<Price>,
     pub summary_profile: Option<SummaryProfile>,
     pub top_holdings: Option<TopHoldings>,
+    pub summary_detail: Option<SummaryDetail>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryDetail {
+    pub dividend_yield: Option<PriceDetail>,
+    /// Distribution yield, reported for funds instead of `dividend_yield`.
+    #[serde(rename = "yield")]
+    pub fund_yield: Option<PriceDetail>,
+    pub trailing_annual_dividend_yield: Option<PriceDetail>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -213,4 +224,5 @@ pub struct AssetProfile {
     pub data_source: String,
     pub sectors: Option<String>,
     pub url: Option<String>,
+    pub dividend_yield: Option<f64>,
 }
\ No newline at end of file
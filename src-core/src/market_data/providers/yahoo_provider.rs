@@ -436,6 +436,11 @@ impl YahooProvider {
                 .summary_profile
                 .as_ref()
                 .and_then(|sp| sp.website.clone()),
+            dividend_yield: asset_profile.summary_detail.as_ref().and_then(|detail| {
+                [&detail.dividend_yield, &detail.fund_yield]
+                    .into_iter()
+                    .find_map(|y| y.as_ref().and_then(|y| y.raw))
+            }),
         };
 
         Ok(new_asset)
@@ -453,7 +458,7 @@ impl YahooProvider {
         };
 
         let url = format!(
-            "https://query1.finance.yahoo.com/v10/finance/quoteSummary/{}?modules=price,summaryProfile,topHoldings&crumb={}",
+            "https://query1.finance.yahoo.com/v10/finance/quoteSummary/{}?modules=price,summaryProfile,topHoldings,summaryDetail&crumb={}",
             symbol,
             crumb_data.crumb
         );
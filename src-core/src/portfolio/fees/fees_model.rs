@@ -0,0 +1,225 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::activities::activities_constants::{
+    ACTIVITY_TYPE_BUY, ACTIVITY_TYPE_DIVIDEND_REINVESTMENT, ACTIVITY_TYPE_FEE, ACTIVITY_TYPE_SELL,
+};
+use crate::activities::Activity;
+
+/// Asset sub-classes that charge an expense ratio.
+pub const FUND_ASSET_SUB_CLASSES: [&str; 2] = ["ETF", "Mutual Fund"];
+pub const DEFAULT_DRAG_PROJECTION_YEARS: u32 = 30;
+/// Gross yearly return assumed for the cost drag projection, 5%.
+pub const DEFAULT_EXPECTED_RETURN: Decimal = Decimal::from_parts(5, 0, 0, false, 2);
+const DAYS_PER_YEAR: Decimal = Decimal::from_parts(365, 0, 0, false, 0);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeAnalysisOptions {
+    /// First year analysed; defaults to the year of the first activity.
+    pub start_year: Option<i32>,
+    /// Last year analysed; defaults to the current year.
+    pub end_year: Option<i32>,
+    /// Horizon of the cost drag projection, in years.
+    pub projection_years: Option<u32>,
+    /// Gross yearly return before costs, as a fraction.
+    pub expected_return: Option<Decimal>,
+}
+
+/// How an explicit fee was charged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeKind {
+    /// Commission on buying or selling.
+    Trading,
+    /// FEE activities and charges on deposits, withdrawals, transfers and income.
+    Account,
+}
+
+/// What one account paid in one year, in base currency.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountFeeYear {
+    /// Account id, or `TOTAL` for the sum of all accounts.
+    pub account_id: String,
+    pub account_name: String,
+    pub year: i32,
+    pub trading_fees: Decimal,
+    pub account_fees: Decimal,
+    /// Expense ratios accrued daily on the funds held.
+    pub fund_fees: Decimal,
+    pub total_fees: Decimal,
+    /// Average daily account value over the days valued in the year.
+    pub average_value: Decimal,
+    /// `total_fees / average_value`; `None` when the account had no value.
+    pub cost_ratio: Option<Decimal>,
+}
+
+/// A fund currently held and what its expense ratio costs per year at today's value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundFeeHolding {
+    pub asset_id: String,
+    pub symbol: String,
+    pub name: Option<String>,
+    pub expense_ratio: Decimal,
+    pub market_value: Decimal,
+    pub annual_cost: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostDragYear {
+    pub year: u32,
+    pub value_without_costs: Decimal,
+    pub value_with_costs: Decimal,
+    /// Growth lost to costs so far.
+    pub cumulative_drag: Decimal,
+}
+
+/// The portfolio grown at the expected return with and without today's cost rate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostDragProjection {
+    pub starting_value: Decimal,
+    pub expected_return: Decimal,
+    /// Yearly expense ratio cost of the funds held over the portfolio value.
+    pub fund_cost_rate: Decimal,
+    /// Explicit fees of the last 12 months over the portfolio value.
+    pub explicit_cost_rate: Decimal,
+    pub annual_cost_rate: Decimal,
+    pub years: Vec<CostDragYear>,
+    pub total_drag: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeAnalysis {
+    pub currency: String,
+    pub as_of: NaiveDate,
+    /// Per year: one row per account followed by the `TOTAL` row.
+    pub by_account_year: Vec<AccountFeeYear>,
+    pub fund_holdings: Vec<FundFeeHolding>,
+    /// Symbols of funds held without an expense ratio, so their cost is missing.
+    pub missing_expense_ratios: Vec<String>,
+    pub projection: CostDragProjection,
+}
+
+/// The explicit fee charged by `activity`, in the activity's currency.
+///
+/// FEE activities carry their charge in `fee` or, failing that, in `amount`, as the
+/// holdings calculator books them; every other activity only through `fee`.
+pub fn explicit_fee(activity: &Activity) -> Option<(FeeKind, Decimal)> {
+    let (kind, fee) = match activity.activity_type.as_str() {
+        ACTIVITY_TYPE_BUY | ACTIVITY_TYPE_SELL | ACTIVITY_TYPE_DIVIDEND_REINVESTMENT => {
+            (FeeKind::Trading, activity.fee)
+        }
+        ACTIVITY_TYPE_FEE if activity.fee.is_zero() => {
+            (FeeKind::Account, activity.amount.unwrap_or(Decimal::ZERO))
+        }
+        _ => (FeeKind::Account, activity.fee),
+    };
+    let fee = fee.abs();
+    (!fee.is_zero()).then_some((kind, fee))
+}
+
+/// One day's share of a fund's yearly expense ratio on `market_value`.
+pub fn daily_fund_fee(market_value: Decimal, expense_ratio: Decimal) -> Decimal {
+    market_value * expense_ratio / DAYS_PER_YEAR
+}
+
+/// Grows `starting_value` for `years` at `expected_return`, once gross and once net of
+/// `annual_cost_rate` taken from each year's grown value.
+pub fn project_cost_drag(
+    starting_value: Decimal,
+    expected_return: Decimal,
+    annual_cost_rate: Decimal,
+    years: u32,
+) -> Vec<CostDragYear> {
+    let gross_factor = Decimal::ONE + expected_return;
+    let net_factor = gross_factor * (Decimal::ONE - annual_cost_rate);
+    let mut without_costs = starting_value;
+    let mut with_costs = starting_value;
+    (1..=years)
+        .map(|year| {
+            without_costs *= gross_factor;
+            with_costs *= net_factor;
+            CostDragYear {
+                year,
+                value_without_costs: without_costs,
+                value_with_costs: with_costs,
+                cumulative_drag: without_costs - with_costs,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn activity(activity_type: &str, fee: Decimal, amount: Option<Decimal>) -> Activity {
+        Activity {
+            id: "a".to_string(),
+            account_id: "acc".to_string(),
+            asset_id: "VTI".to_string(),
+            activity_type: activity_type.to_string(),
+            activity_date: Utc::now(),
+            quantity: dec!(1),
+            unit_price: dec!(100),
+            currency: "USD".to_string(),
+            fee,
+            amount,
+            is_draft: false,
+            comment: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
+        }
+    }
+
+    #[test]
+    fn test_explicit_fees_are_split_by_how_they_were_charged() {
+        assert_eq!(
+            explicit_fee(&activity(ACTIVITY_TYPE_BUY, dec!(4.95), None)),
+            Some((FeeKind::Trading, dec!(4.95)))
+        );
+        assert_eq!(
+            explicit_fee(&activity(ACTIVITY_TYPE_FEE, Decimal::ZERO, Some(dec!(-25)))),
+            Some((FeeKind::Account, dec!(25)))
+        );
+        assert_eq!(
+            explicit_fee(&activity("WITHDRAWAL", dec!(1.5), Some(dec!(500)))),
+            Some((FeeKind::Account, dec!(1.5)))
+        );
+        assert_eq!(
+            explicit_fee(&activity(ACTIVITY_TYPE_SELL, Decimal::ZERO, None)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_a_year_of_daily_fund_fees_adds_up_to_the_expense_ratio() {
+        let year: Decimal = (0..365)
+            .map(|_| daily_fund_fee(dec!(10000), dec!(0.0020)))
+            .sum();
+        assert_eq!(year.round_dp(6), dec!(20));
+    }
+
+    #[test]
+    fn test_cost_drag_compounds() {
+        let years = project_cost_drag(dec!(100000), dec!(0.05), dec!(0.01), 2);
+        assert_eq!(years.len(), 2);
+        assert_eq!(years[0].value_without_costs, dec!(105000));
+        assert_eq!(years[0].value_with_costs, dec!(103950));
+        assert_eq!(years[1].value_without_costs, dec!(110250));
+        assert_eq!(years[1].value_with_costs, dec!(108056.025));
+        assert_eq!(years[1].cumulative_drag, dec!(2193.975));
+        assert!(project_cost_drag(dec!(1), dec!(0.05), dec!(0.01), 0).is_empty());
+    }
+}
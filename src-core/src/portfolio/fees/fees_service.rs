@@ -0,0 +1,409 @@
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use log::{debug, warn};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use super::fees_model::{
+    daily_fund_fee, explicit_fee, project_cost_drag, AccountFeeYear, CostDragProjection,
+    FeeAnalysis, FeeAnalysisOptions, FeeKind, FundFeeHolding, DEFAULT_DRAG_PROJECTION_YEARS,
+    DEFAULT_EXPECTED_RETURN, FUND_ASSET_SUB_CLASSES,
+};
+use crate::accounts::{Account, AccountServiceTrait};
+use crate::activities::ActivityRepositoryTrait;
+use crate::assets::{Asset, AssetRepositoryTrait};
+use crate::constants::{DISPLAY_DECIMAL_PRECISION, PORTFOLIO_TOTAL_ACCOUNT_ID};
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::fx_traits::FxServiceTrait;
+use crate::market_data::MarketDataServiceTrait;
+use crate::portfolio::snapshot::SnapshotServiceTrait;
+use crate::portfolio::valuation::ValuationServiceTrait;
+
+/// Days of quotes loaded before the first day analysed, so a fund whose market was
+/// closed that day still has a price to carry forward.
+const QUOTE_LOOKBACK_DAYS: i64 = 14;
+const RATIO_PRECISION: u32 = 6;
+
+pub trait FeeServiceTrait: Send + Sync {
+    /// Explicit and fund fees per account and year, the funds' current cost and the
+    /// long-run drag of today's cost rate, all in base currency.
+    fn get_fee_analysis(&self, options: FeeAnalysisOptions) -> Result<FeeAnalysis>;
+}
+
+pub struct FeeService {
+    account_service: Arc<dyn AccountServiceTrait>,
+    activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    asset_repository: Arc<dyn AssetRepositoryTrait>,
+    snapshot_service: Arc<dyn SnapshotServiceTrait>,
+    valuation_service: Arc<dyn ValuationServiceTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl FeeService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        account_service: Arc<dyn AccountServiceTrait>,
+        activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        asset_repository: Arc<dyn AssetRepositoryTrait>,
+        snapshot_service: Arc<dyn SnapshotServiceTrait>,
+        valuation_service: Arc<dyn ValuationServiceTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        FeeService {
+            account_service,
+            activity_repository,
+            asset_repository,
+            snapshot_service,
+            valuation_service,
+            market_data_service,
+            fx_service,
+            base_currency,
+        }
+    }
+
+    // Rates are looked up once per currency and day; a missing rate leaves the
+    // amount unconverted, as elsewhere in the portfolio calculations.
+    fn rate_to_base(
+        &self,
+        cache: &mut HashMap<(String, NaiveDate), Decimal>,
+        currency: &str,
+        base_currency: &str,
+        date: NaiveDate,
+    ) -> Decimal {
+        if currency == base_currency {
+            return Decimal::ONE;
+        }
+        *cache
+            .entry((currency.to_string(), date))
+            .or_insert_with(|| {
+                self.fx_service
+                    .get_exchange_rate_for_date(currency, base_currency, date)
+                    .unwrap_or_else(|e| {
+                        warn!(
+                            "Fee analysis: no {}->{} rate on {}: {}. Using 1.",
+                            currency, base_currency, date, e
+                        );
+                        Decimal::ONE
+                    })
+            })
+    }
+}
+
+fn year_row<'a>(
+    rows: &'a mut BTreeMap<i32, AccountFeeYear>,
+    account: &Account,
+    year: i32,
+) -> &'a mut AccountFeeYear {
+    rows.entry(year).or_insert_with(|| AccountFeeYear {
+        account_id: account.id.clone(),
+        account_name: account.name.clone(),
+        year,
+        ..Default::default()
+    })
+}
+
+fn expense_ratio(asset: &Asset) -> Option<Decimal> {
+    asset
+        .expense_ratio
+        .and_then(Decimal::from_f64)
+        .filter(|ratio| ratio.is_sign_positive() && !ratio.is_zero())
+}
+
+impl FeeServiceTrait for FeeService {
+    fn get_fee_analysis(&self, options: FeeAnalysisOptions) -> Result<FeeAnalysis> {
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let today = Utc::now().naive_utc().date();
+
+        let accounts = self.account_service.get_active_accounts()?;
+        let account_ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+        let activities: Vec<_> = self
+            .activity_repository
+            .get_activities_by_account_ids(&account_ids)?
+            .into_iter()
+            .filter(|a| !a.is_draft)
+            .collect();
+
+        let first_activity_year = activities
+            .iter()
+            .map(|a| a.activity_date.year())
+            .min()
+            .unwrap_or(today.year());
+        let start_year = options.start_year.unwrap_or(first_activity_year);
+        let end_year = options.end_year.unwrap_or(today.year());
+        if start_year > end_year {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Fee analysis start year {} is after end year {}",
+                start_year, end_year
+            ))));
+        }
+        let start_date = NaiveDate::from_ymd_opt(start_year, 1, 1).unwrap_or(today);
+        let end_date = NaiveDate::from_ymd_opt(end_year, 12, 31)
+            .unwrap_or(today)
+            .min(today);
+        debug!(
+            "Analysing fees of {} accounts from {} to {}",
+            accounts.len(),
+            start_date,
+            end_date
+        );
+
+        let assets: HashMap<String, Asset> = self
+            .asset_repository
+            .list()?
+            .into_iter()
+            .map(|a| (a.id.clone(), a))
+            .collect();
+        let fund_ratios: HashMap<&str, Decimal> = assets
+            .values()
+            .filter_map(|a| expense_ratio(a).map(|ratio| (a.id.as_str(), ratio)))
+            .collect();
+
+        // Closing prices of the funds by day, carried forward over days without a quote.
+        let fund_ids: HashSet<String> = fund_ratios.keys().map(|id| id.to_string()).collect();
+        let mut fund_prices: HashMap<String, BTreeMap<NaiveDate, (Decimal, String)>> =
+            HashMap::new();
+        if !fund_ids.is_empty() && start_date <= end_date {
+            for quote in self
+                .market_data_service
+                .get_historical_quotes_for_symbols_in_range(
+                    &fund_ids,
+                    start_date - Duration::days(QUOTE_LOOKBACK_DAYS),
+                    end_date,
+                )?
+            {
+                fund_prices
+                    .entry(quote.symbol.clone())
+                    .or_default()
+                    .insert(quote.timestamp.date_naive(), (quote.close, quote.currency));
+            }
+        }
+        let price_on = |asset_id: &str, date: NaiveDate| {
+            fund_prices
+                .get(asset_id)
+                .and_then(|prices| prices.range(..=date).next_back())
+                .map(|(_, price)| price)
+        };
+
+        let mut rates = HashMap::new();
+        let mut rows: BTreeMap<(i32, usize), AccountFeeYear> = BTreeMap::new();
+        let mut trailing_explicit = Decimal::ZERO;
+        let trailing_start = today - Duration::days(365);
+
+        for (index, account) in accounts.iter().enumerate() {
+            let mut account_rows: BTreeMap<i32, AccountFeeYear> = BTreeMap::new();
+
+            for activity in activities.iter().filter(|a| a.account_id == account.id) {
+                let Some((kind, fee)) = explicit_fee(activity) else {
+                    continue;
+                };
+                let date = activity.activity_date.naive_utc().date();
+                let fee_base =
+                    fee * self.rate_to_base(&mut rates, &activity.currency, &base_currency, date);
+                if date > trailing_start {
+                    trailing_explicit += fee_base;
+                }
+                if date < start_date || date > end_date {
+                    continue;
+                }
+                let entry = year_row(&mut account_rows, account, date.year());
+                match kind {
+                    FeeKind::Trading => entry.trading_fees += fee_base,
+                    FeeKind::Account => entry.account_fees += fee_base,
+                }
+            }
+
+            if !fund_ratios.is_empty() && start_date <= end_date {
+                for snapshot in self.snapshot_service.get_daily_holdings_snapshots(
+                    &account.id,
+                    Some(start_date),
+                    Some(end_date),
+                )? {
+                    let date = snapshot.snapshot_date;
+                    let mut fund_fees = Decimal::ZERO;
+                    for (asset_id, position) in &snapshot.positions {
+                        let Some(ratio) = fund_ratios.get(asset_id.as_str()) else {
+                            continue;
+                        };
+                        let Some((close, currency)) = price_on(asset_id, date) else {
+                            continue;
+                        };
+                        let value = position.quantity
+                            * close
+                            * self.rate_to_base(&mut rates, currency, &base_currency, date);
+                        fund_fees += daily_fund_fee(value, *ratio);
+                    }
+                    if !fund_fees.is_zero() {
+                        year_row(&mut account_rows, account, date.year()).fund_fees += fund_fees;
+                    }
+                }
+            }
+
+            let mut values_by_year: BTreeMap<i32, (Decimal, u32)> = BTreeMap::new();
+            for valuation in self.valuation_service.get_historical_valuations(
+                &account.id,
+                Some(start_date),
+                Some(end_date),
+            )? {
+                let sum = values_by_year
+                    .entry(valuation.valuation_date.year())
+                    .or_default();
+                sum.0 += valuation.total_value * valuation.fx_rate_to_base;
+                sum.1 += 1;
+            }
+            for (year, (sum, days)) in values_by_year {
+                year_row(&mut account_rows, account, year).average_value =
+                    sum / Decimal::from(days);
+            }
+            rows.extend(
+                account_rows
+                    .into_iter()
+                    .map(|(year, row)| ((year, index), row)),
+            );
+        }
+
+        let mut by_account_year = Vec::new();
+        for year in start_year..=end_year {
+            let mut total = AccountFeeYear {
+                account_id: PORTFOLIO_TOTAL_ACCOUNT_ID.to_string(),
+                account_name: "Total".to_string(),
+                year,
+                ..Default::default()
+            };
+            for mut row in rows
+                .range((year, 0)..(year + 1, 0))
+                .map(|(_, row)| row.clone())
+            {
+                row.total_fees = row.trading_fees + row.account_fees + row.fund_fees;
+                total.trading_fees += row.trading_fees;
+                total.account_fees += row.account_fees;
+                total.fund_fees += row.fund_fees;
+                total.average_value += row.average_value;
+                by_account_year.push(row);
+            }
+            total.total_fees = total.trading_fees + total.account_fees + total.fund_fees;
+            by_account_year.push(total);
+        }
+        for row in &mut by_account_year {
+            row.cost_ratio = (!row.average_value.is_zero())
+                .then(|| (row.total_fees / row.average_value).round_dp(RATIO_PRECISION));
+            for value in [
+                &mut row.trading_fees,
+                &mut row.account_fees,
+                &mut row.fund_fees,
+                &mut row.total_fees,
+                &mut row.average_value,
+            ] {
+                *value = value.round_dp(DISPLAY_DECIMAL_PRECISION);
+            }
+        }
+
+        // Funds held today, across accounts, at the latest quotes.
+        let mut held: BTreeMap<String, Decimal> = BTreeMap::new();
+        for account in &accounts {
+            let Ok(snapshot) = self
+                .snapshot_service
+                .get_latest_holdings_snapshot(&account.id)
+            else {
+                continue;
+            };
+            for (asset_id, position) in snapshot.positions {
+                if !position.quantity.is_zero() {
+                    *held.entry(asset_id).or_default() += position.quantity;
+                }
+            }
+        }
+        let held_ids: Vec<String> = held.keys().cloned().collect();
+        let latest_quotes = self
+            .market_data_service
+            .get_latest_quotes_for_symbols(&held_ids)?;
+
+        let mut fund_holdings = Vec::new();
+        let mut missing_expense_ratios = Vec::new();
+        for (asset_id, quantity) in &held {
+            let Some(asset) = assets.get(asset_id) else {
+                continue;
+            };
+            let Some(ratio) = fund_ratios.get(asset_id.as_str()) else {
+                let is_fund = asset
+                    .asset_sub_class
+                    .as_deref()
+                    .is_some_and(|sub_class| FUND_ASSET_SUB_CLASSES.contains(&sub_class));
+                if is_fund {
+                    missing_expense_ratios.push(asset.symbol.clone());
+                }
+                continue;
+            };
+            let market_value = latest_quotes.get(asset_id).map_or(Decimal::ZERO, |quote| {
+                quantity
+                    * quote.close
+                    * self.rate_to_base(&mut rates, &quote.currency, &base_currency, today)
+            });
+            fund_holdings.push(FundFeeHolding {
+                asset_id: asset_id.clone(),
+                symbol: asset.symbol.clone(),
+                name: asset.name.clone(),
+                expense_ratio: *ratio,
+                market_value: market_value.round_dp(DISPLAY_DECIMAL_PRECISION),
+                annual_cost: (market_value * ratio).round_dp(DISPLAY_DECIMAL_PRECISION),
+            });
+        }
+        fund_holdings.sort_by_key(|h| std::cmp::Reverse(h.annual_cost));
+
+        let starting_value: Decimal = self
+            .valuation_service
+            .get_latest_valuations(&account_ids)?
+            .iter()
+            .map(|v| v.total_value * v.fx_rate_to_base)
+            .sum();
+        let annual_fund_cost: Decimal = fund_holdings.iter().map(|h| h.annual_cost).sum();
+        let (fund_cost_rate, explicit_cost_rate) = if starting_value > Decimal::ZERO {
+            (
+                (annual_fund_cost / starting_value).round_dp(RATIO_PRECISION),
+                (trailing_explicit / starting_value).round_dp(RATIO_PRECISION),
+            )
+        } else {
+            (Decimal::ZERO, Decimal::ZERO)
+        };
+        let annual_cost_rate = fund_cost_rate + explicit_cost_rate;
+        let expected_return = options.expected_return.unwrap_or(DEFAULT_EXPECTED_RETURN);
+        let years: Vec<_> = project_cost_drag(
+            starting_value,
+            expected_return,
+            annual_cost_rate,
+            options
+                .projection_years
+                .unwrap_or(DEFAULT_DRAG_PROJECTION_YEARS),
+        )
+        .into_iter()
+        .map(|mut year| {
+            year.value_without_costs = year.value_without_costs.round_dp(DISPLAY_DECIMAL_PRECISION);
+            year.value_with_costs = year.value_with_costs.round_dp(DISPLAY_DECIMAL_PRECISION);
+            year.cumulative_drag = year.cumulative_drag.round_dp(DISPLAY_DECIMAL_PRECISION);
+            year
+        })
+        .collect();
+        let total_drag = years.last().map_or(Decimal::ZERO, |y| y.cumulative_drag);
+
+        Ok(FeeAnalysis {
+            currency: base_currency,
+            as_of: today,
+            by_account_year,
+            fund_holdings,
+            missing_expense_ratios,
+            projection: CostDragProjection {
+                starting_value: starting_value.round_dp(DISPLAY_DECIMAL_PRECISION),
+                expected_return,
+                fund_cost_rate,
+                explicit_cost_rate,
+                annual_cost_rate,
+                years,
+                total_drag,
+            },
+        })
+    }
+}
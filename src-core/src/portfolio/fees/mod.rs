@@ -0,0 +1,5 @@
+pub mod fees_model;
+pub mod fees_service;
+
+pub use fees_model::*;
+pub use fees_service::{FeeService, FeeServiceTrait};
@@ -1,10 +1,10 @@
 use crate::assets::AssetServiceTrait;
 use crate::assets_model::{Asset, Country as AssetCountry, Sector as AssetSector};
 use crate::portfolio::holdings::holdings_model::{Holding, Instrument, HoldingType, MonetaryValue, Country, Sector};
-use crate::portfolio::snapshot::{self, SnapshotServiceTrait, Position};
+use crate::portfolio::snapshot::{self, AccountStateSnapshot, SnapshotServiceTrait, Position};
 use crate::errors::{Error as CoreError, Result, CalculatorError};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use log::{error, debug, warn};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -25,6 +25,15 @@ pub trait HoldingsServiceTrait: Send + Sync {
         asset_id: &str,
         base_currency: &str,
     ) -> Result<Option<Holding>>;
+
+    /// Reconstructs the holdings of an account as they stood at the end of `date`, with lots,
+    /// valued at that day's quotes and FX rates.
+    async fn get_holdings_as_of(
+        &self,
+        account_id: &str,
+        base_currency: &str,
+        date: NaiveDate,
+    ) -> Result<Vec<Holding>>;
 }
 
 #[derive(Clone)]
@@ -80,173 +89,9 @@ impl HoldingsServiceTrait for HoldingsService {
             }
         };
 
-        let snapshot_positions: Vec<snapshot::Position> = latest_snapshot
-            .positions
-            .values()
-            .filter(|p| p.quantity != Decimal::ZERO)
-            .cloned()
-            .collect();
-        let cash_balances_map: &HashMap<String, Decimal> = &latest_snapshot.cash_balances;
-
-        let security_symbols: Vec<String> = snapshot_positions
-            .iter()
-            .map(|p| p.asset_id.clone())
-            .collect::<HashSet<_>>()
-            .into_iter()
-            .collect();
-
-        let instruments_map: HashMap<String, Instrument> = if !security_symbols.is_empty() {
-            match self.asset_service.get_assets_by_symbols(&security_symbols).await {
-                Ok(assets) => assets
-                    .into_iter()
-                    .filter_map(|asset: Asset| {
-                        let countries_vec = asset.countries.as_ref().and_then(|c| {
-                            serde_json::from_str::<Option<Vec<AssetCountry>>>(c)
-                                .map_err(|e| {
-                                    warn!("Failed to parse countries for {}: {}", asset.symbol, e)
-                                })
-                                .ok()
-                                .flatten()
-                        });
-                        let sectors_vec = asset.sectors.as_ref().and_then(|s| {
-                            serde_json::from_str::<Option<Vec<AssetSector>>>(s)
-                                .map_err(|e| {
-                                    warn!("Failed to parse sectors for {}: {}", asset.symbol, e)
-                                })
-                                .ok()
-                                .flatten()
-                        });
-
-                        let instrument = Instrument {
-                            id: asset.id.clone(),
-                            symbol: asset.symbol.clone(),
-                            name: asset.name,
-                            currency: asset.currency,
-                            notes: asset.notes,
-                            data_source: Some(asset.data_source),
-                            asset_class: asset.asset_class,
-                            asset_subclass: asset.asset_sub_class,
-                            countries: countries_vec.map(|c| {
-                                c.iter()
-                                    .map(|country| Country {
-                                        name: country.name.clone(),
-                                        weight: country.weight,
-                                    })
-                                    .collect()
-                            }),
-                            sectors: sectors_vec.map(|s| {
-                                s.iter()
-                                    .map(|sector| Sector {
-                                        name: sector.name.clone(),
-                                        weight: sector.weight,
-                                    })
-                                    .collect()
-                            }),
-                        };
-                        Some((asset.id, instrument))
-                    })
-                    .collect(),
-                Err(e) => {
-                    error!(
-                        "Failed to get asset details for account {}: {}. Asset info will be missing.",
-                         account_id, e
-                    );
-                    HashMap::new()
-                }
-            }
-        } else {
-            HashMap::new()
-        };
-
-        let mut holdings: Vec<Holding> = Vec::new();
-
-        for snapshot_pos in &snapshot_positions {
-            let instrument_view = instruments_map.get(&snapshot_pos.asset_id).cloned();
-
-            if instrument_view.is_none() {
-                warn!(
-                    "Instrument details not found for asset_id: {}. Skipping this security holding view.",
-                    snapshot_pos.asset_id
-                );
-                continue;
-            }
-
-            let cost_basis_local_val = snapshot_pos.total_cost_basis;
-
-            let holding_view = Holding {
-                id: format!("SEC-{}-{}", account_id, snapshot_pos.asset_id),
-                account_id: account_id.to_string(),
-                holding_type: HoldingType::Security,
-                instrument: instrument_view,
-                quantity: snapshot_pos.quantity,
-                open_date: Some(snapshot_pos.inception_date),
-                lots: None,
-                local_currency: snapshot_pos.currency.clone(),
-                base_currency: base_currency.to_string(),
-                fx_rate: None,
-                market_value: MonetaryValue::zero(),
-                cost_basis: Some(MonetaryValue {
-                    local: cost_basis_local_val,
-                    base: Decimal::ZERO,
-                }),
-                price: None,
-                unrealized_gain: None,
-                unrealized_gain_pct: None,
-                realized_gain: None,
-                realized_gain_pct: None,
-                total_gain: None,
-                total_gain_pct: None,
-                day_change: None,
-                day_change_pct: None,
-                prev_close_value: None,
-                weight: Decimal::ZERO,
-                as_of_date: today,
-            };
-            holdings.push(holding_view);
-        }
-
-        for (currency, &amount) in cash_balances_map {
-            if amount == Decimal::ZERO {
-                continue;
-            }
-
-            let holding_view = Holding {
-                id: format!("CASH-{}-{}", account_id, currency),
-                account_id: account_id.to_string(),
-                holding_type: HoldingType::Cash,
-                instrument: None,
-                quantity: amount,
-                open_date: None,
-                lots: None,
-                local_currency: currency.clone(),
-                base_currency: base_currency.to_string(),
-                fx_rate: None,
-                market_value: MonetaryValue {
-                    local: amount,
-                    base: Decimal::ZERO,
-                },
-                cost_basis: Some(MonetaryValue {
-                    local: amount,
-                    base: Decimal::ZERO,
-                }),
-                price: Some(dec!(1.0)),
-                unrealized_gain: Some(MonetaryValue::zero()),
-                unrealized_gain_pct: Some(Decimal::ZERO),
-                realized_gain: Some(MonetaryValue::zero()),
-                realized_gain_pct: Some(Decimal::ZERO),
-                total_gain: Some(MonetaryValue::zero()),
-                total_gain_pct: Some(Decimal::ZERO),
-                day_change: Some(MonetaryValue::zero()),
-                day_change_pct: Some(Decimal::ZERO),
-                prev_close_value: Some(MonetaryValue {
-                    local: amount,
-                    base: Decimal::ZERO,
-                }),
-                weight: Decimal::ZERO,
-                as_of_date: today,
-            };
-            holdings.push(holding_view);
-        }
+        let mut holdings = self
+            .build_holdings(account_id, base_currency, &latest_snapshot, false, today)
+            .await;
 
         if !holdings.is_empty() {
             match self
@@ -269,23 +114,7 @@ impl HoldingsServiceTrait for HoldingsService {
             );
         }
 
-        let total_portfolio_value_base: Decimal = holdings
-            .iter()
-            .map(|holding_view| holding_view.market_value.base)
-            .sum();
-
-        if total_portfolio_value_base > dec!(0) {
-            for holding_view in &mut holdings {
-                holding_view.weight =
-                    (holding_view.market_value.base / total_portfolio_value_base).round_dp(4);
-            }
-        } else {
-            debug!("Total portfolio base value is zero or negative for account {}. Allocations set to 0.", account_id);
-            for holding_view in &mut holdings {
-                holding_view.weight = Decimal::ZERO;
-            }
-        }
-
+        apply_weights(account_id, &mut holdings);
         Ok(holdings)
     }
 
@@ -349,45 +178,7 @@ impl HoldingsServiceTrait for HoldingsService {
             )))
         })?;
 
-        let countries_vec = asset_details.countries.as_ref().and_then(|c| {
-            serde_json::from_str::<Option<Vec<AssetCountry>>>(c)
-                .map_err(|e| warn!("Failed to parse countries for {}: {}", asset_details.symbol, e))
-                .ok()
-                .flatten()
-        });
-        let sectors_vec = asset_details.sectors.as_ref().and_then(|s| {
-            serde_json::from_str::<Option<Vec<AssetSector>>>(s)
-                .map_err(|e| warn!("Failed to parse sectors for {}: {}", asset_details.symbol, e))
-                .ok()
-                .flatten()
-        });
-
-        let instrument = Instrument {
-            id: asset_details.id.clone(),
-            symbol: asset_details.symbol.clone(),
-            name: asset_details.name,
-            currency: asset_details.currency,
-            notes: asset_details.notes,
-            data_source: Some(asset_details.data_source),
-            asset_class: asset_details.asset_class,
-            asset_subclass: asset_details.asset_sub_class,
-            countries: countries_vec.map(|c| {
-                c.iter()
-                    .map(|country| Country {
-                        name: country.name.clone(),
-                        weight: country.weight,
-                    })
-                    .collect()
-            }),
-            sectors: sectors_vec.map(|s| {
-                s.iter()
-                    .map(|sector| Sector {
-                        name: sector.name.clone(),
-                        weight: sector.weight,
-                    })
-                    .collect()
-            }),
-        };
+        let instrument = instrument_from_asset(asset_details);
 
         let holding_view = Holding {
             id: format!("SEC-{}-{}", account_id, asset_id),
@@ -444,4 +235,254 @@ impl HoldingsServiceTrait for HoldingsService {
             }
         }
     }
+
+    async fn get_holdings_as_of(
+        &self,
+        account_id: &str,
+        base_currency: &str,
+        date: NaiveDate,
+    ) -> Result<Vec<Holding>> {
+        debug!(
+            "Getting holdings for account {} as of {} in base currency {}",
+            account_id, date, base_currency
+        );
+
+        let snapshot = match self
+            .snapshot_service
+            .get_daily_holdings_snapshots(account_id, Some(date), Some(date))?
+            .pop()
+        {
+            Some(snap) => snap,
+            None => {
+                debug!(
+                    "No holdings for account {} on {}. Returning empty holdings list.",
+                    account_id, date
+                );
+                return Ok(Vec::new());
+            }
+        };
+
+        let mut holdings = self
+            .build_holdings(account_id, base_currency, &snapshot, true, date)
+            .await;
+
+        if !holdings.is_empty() {
+            if let Err(e) = self
+                .valuation_service
+                .calculate_holdings_historical_valuation(&mut holdings, date)
+                .await
+            {
+                error!(
+                    "Valuation as of {} failed for account {}: {}. Returning partially valued holdings.",
+                    date, account_id, e
+                );
+            }
+        }
+
+        apply_weights(account_id, &mut holdings);
+        Ok(holdings)
+    }
+}
+
+impl HoldingsService {
+    /// Builds unvalued holding views for the positions and cash balances of `snapshot`.
+    async fn build_holdings(
+        &self,
+        account_id: &str,
+        base_currency: &str,
+        snapshot: &AccountStateSnapshot,
+        include_lots: bool,
+        as_of: NaiveDate,
+    ) -> Vec<Holding> {
+        let snapshot_positions: Vec<snapshot::Position> = snapshot
+            .positions
+            .values()
+            .filter(|p| p.quantity != Decimal::ZERO)
+            .cloned()
+            .collect();
+        let cash_balances_map: &HashMap<String, Decimal> = &snapshot.cash_balances;
+
+        let security_symbols: Vec<String> = snapshot_positions
+            .iter()
+            .map(|p| p.asset_id.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let instruments_map: HashMap<String, Instrument> = if !security_symbols.is_empty() {
+            match self.asset_service.get_assets_by_symbols(&security_symbols).await {
+                Ok(assets) => assets
+                    .into_iter()
+                    .map(|asset: Asset| (asset.id.clone(), instrument_from_asset(asset)))
+                    .collect(),
+                Err(e) => {
+                    error!(
+                        "Failed to get asset details for account {}: {}. Asset info will be missing.",
+                         account_id, e
+                    );
+                    HashMap::new()
+                }
+            }
+        } else {
+            HashMap::new()
+        };
+
+        let mut holdings: Vec<Holding> = Vec::new();
+
+        for snapshot_pos in &snapshot_positions {
+            let instrument_view = instruments_map.get(&snapshot_pos.asset_id).cloned();
+
+            if instrument_view.is_none() {
+                warn!(
+                    "Instrument details not found for asset_id: {}. Skipping this security holding view.",
+                    snapshot_pos.asset_id
+                );
+                continue;
+            }
+
+            let cost_basis_local_val = snapshot_pos.total_cost_basis;
+
+            let holding_view = Holding {
+                id: format!("SEC-{}-{}", account_id, snapshot_pos.asset_id),
+                account_id: account_id.to_string(),
+                holding_type: HoldingType::Security,
+                instrument: instrument_view,
+                quantity: snapshot_pos.quantity,
+                open_date: Some(snapshot_pos.inception_date),
+                lots: if include_lots {
+                    Some(snapshot_pos.lots.clone())
+                } else {
+                    None
+                },
+                local_currency: snapshot_pos.currency.clone(),
+                base_currency: base_currency.to_string(),
+                fx_rate: None,
+                market_value: MonetaryValue::zero(),
+                cost_basis: Some(MonetaryValue {
+                    local: cost_basis_local_val,
+                    base: Decimal::ZERO,
+                }),
+                price: None,
+                unrealized_gain: None,
+                unrealized_gain_pct: None,
+                realized_gain: None,
+                realized_gain_pct: None,
+                total_gain: None,
+                total_gain_pct: None,
+                day_change: None,
+                day_change_pct: None,
+                prev_close_value: None,
+                weight: Decimal::ZERO,
+                as_of_date: as_of,
+            };
+            holdings.push(holding_view);
+        }
+
+        for (currency, &amount) in cash_balances_map {
+            if amount == Decimal::ZERO {
+                continue;
+            }
+
+            let holding_view = Holding {
+                id: format!("CASH-{}-{}", account_id, currency),
+                account_id: account_id.to_string(),
+                holding_type: HoldingType::Cash,
+                instrument: None,
+                quantity: amount,
+                open_date: None,
+                lots: None,
+                local_currency: currency.clone(),
+                base_currency: base_currency.to_string(),
+                fx_rate: None,
+                market_value: MonetaryValue {
+                    local: amount,
+                    base: Decimal::ZERO,
+                },
+                cost_basis: Some(MonetaryValue {
+                    local: amount,
+                    base: Decimal::ZERO,
+                }),
+                price: Some(dec!(1.0)),
+                unrealized_gain: Some(MonetaryValue::zero()),
+                unrealized_gain_pct: Some(Decimal::ZERO),
+                realized_gain: Some(MonetaryValue::zero()),
+                realized_gain_pct: Some(Decimal::ZERO),
+                total_gain: Some(MonetaryValue::zero()),
+                total_gain_pct: Some(Decimal::ZERO),
+                day_change: Some(MonetaryValue::zero()),
+                day_change_pct: Some(Decimal::ZERO),
+                prev_close_value: Some(MonetaryValue {
+                    local: amount,
+                    base: Decimal::ZERO,
+                }),
+                weight: Decimal::ZERO,
+                as_of_date: as_of,
+            };
+            holdings.push(holding_view);
+        }
+
+        holdings
+    }
+}
+
+fn instrument_from_asset(asset: Asset) -> Instrument {
+    let countries_vec = asset.countries.as_ref().and_then(|c| {
+        serde_json::from_str::<Option<Vec<AssetCountry>>>(c)
+            .map_err(|e| warn!("Failed to parse countries for {}: {}", asset.symbol, e))
+            .ok()
+            .flatten()
+    });
+    let sectors_vec = asset.sectors.as_ref().and_then(|s| {
+        serde_json::from_str::<Option<Vec<AssetSector>>>(s)
+            .map_err(|e| warn!("Failed to parse sectors for {}: {}", asset.symbol, e))
+            .ok()
+            .flatten()
+    });
+
+    Instrument {
+        id: asset.id.clone(),
+        symbol: asset.symbol.clone(),
+        name: asset.name,
+        currency: asset.currency,
+        notes: asset.notes,
+        data_source: Some(asset.data_source),
+        asset_class: asset.asset_class,
+        asset_subclass: asset.asset_sub_class,
+        countries: countries_vec.map(|c| {
+            c.iter()
+                .map(|country| Country {
+                    name: country.name.clone(),
+                    weight: country.weight,
+                })
+                .collect()
+        }),
+        sectors: sectors_vec.map(|s| {
+            s.iter()
+                .map(|sector| Sector {
+                    name: sector.name.clone(),
+                    weight: sector.weight,
+                })
+                .collect()
+        }),
+    }
+}
+
+/// Sets each holding's weight as its share of the total base-currency market value.
+fn apply_weights(account_id: &str, holdings: &mut [Holding]) {
+    let total_portfolio_value_base: Decimal = holdings
+        .iter()
+        .map(|holding_view| holding_view.market_value.base)
+        .sum();
+
+    if total_portfolio_value_base > dec!(0) {
+        for holding_view in holdings.iter_mut() {
+            holding_view.weight =
+                (holding_view.market_value.base / total_portfolio_value_base).round_dp(4);
+        }
+    } else {
+        debug!("Total portfolio base value is zero or negative for account {}. Allocations set to 0.", account_id);
+        for holding_view in holdings.iter_mut() {
+            holding_view.weight = Decimal::ZERO;
+        }
+    }
 }
@@ -4,11 +4,11 @@ use crate::market_data::market_data_model::LatestQuotePair;
 use crate::market_data::market_data_traits::MarketDataServiceTrait;
 use crate::portfolio::holdings::{Holding, HoldingType, MonetaryValue};
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{Duration, NaiveDate, Utc};
 use log::{debug, warn};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 #[async_trait]
@@ -17,8 +17,20 @@ pub trait HoldingsValuationServiceTrait: Send + Sync {
         &self,
         holdings: &mut [Holding],
     ) -> Result<()>;
+
+    /// Values holdings as of `date` using the last quotes on or before that day and
+    /// the FX rates for that day.
+    async fn calculate_holdings_historical_valuation(
+        &self,
+        holdings: &mut [Holding],
+        date: NaiveDate,
+    ) -> Result<()>;
 }
 
+/// How many days before the valuation date to look for a quote, to cover weekends
+/// and market holidays.
+const HISTORICAL_QUOTE_LOOKBACK_DAYS: i64 = 14;
+
 #[derive(Clone)]
 pub struct HoldingsValuationService {
     fx_service: Arc<dyn FxServiceTrait>,
@@ -36,14 +48,22 @@ impl HoldingsValuationService {
         }
     }
 
-    // Private helper to get FX rate with logging and fallback
+    // Private helper to get FX rate with logging and fallback.
+    // Uses the rate for `rate_date` when given, else the latest rate.
     fn get_fx_rate_or_fallback(
         &self,
         from_curr: &str,
         to_curr: &str,
+        rate_date: Option<NaiveDate>,
         context_msg: &str,
     ) -> Decimal {
-        match self.fx_service.get_latest_exchange_rate(from_curr, to_curr) {
+        let rate = match rate_date {
+            Some(date) => self
+                .fx_service
+                .get_exchange_rate_for_date(from_curr, to_curr, date),
+            None => self.fx_service.get_latest_exchange_rate(from_curr, to_curr),
+        };
+        match rate {
             Ok(rate) => rate,
             Err(e) => {
                 warn!(
@@ -83,6 +103,51 @@ impl HoldingsValuationService {
 
         Ok(latest_quote_pairs)
     }
+
+    // Builds quote pairs from the last two quotes on or before `date` for each security.
+    fn fetch_historical_quote_data(
+        &self,
+        holdings: &[Holding],
+        date: NaiveDate,
+    ) -> Result<HashMap<String, LatestQuotePair>> {
+        let required_symbols: HashSet<String> = holdings
+            .iter()
+            .filter(|holding| holding.holding_type == HoldingType::Security)
+            .filter_map(|holding| holding.instrument.as_ref().map(|inst| inst.symbol.clone()))
+            .collect();
+        if required_symbols.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let start_date = date - Duration::days(HISTORICAL_QUOTE_LOOKBACK_DAYS);
+        let mut quotes = self
+            .market_data_service
+            .get_historical_quotes_for_symbols_in_range(&required_symbols, start_date, date)?;
+        quotes.sort_by_key(|quote| quote.timestamp);
+
+        let mut quote_pairs: HashMap<String, LatestQuotePair> = HashMap::new();
+        for quote in quotes
+            .into_iter()
+            .filter(|quote| quote.timestamp.date_naive() <= date)
+        {
+            match quote_pairs.get_mut(&quote.symbol) {
+                Some(pair) => {
+                    let previous = std::mem::replace(&mut pair.latest, quote);
+                    pair.previous = Some(previous);
+                }
+                None => {
+                    quote_pairs.insert(
+                        quote.symbol.clone(),
+                        LatestQuotePair {
+                            latest: quote,
+                            previous: None,
+                        },
+                    );
+                }
+            }
+        }
+        Ok(quote_pairs)
+    }
 }
 
 #[async_trait]
@@ -117,12 +182,12 @@ impl HoldingsValuationServiceTrait for HoldingsValuationService {
                         holding.as_of_date = today;
                     }
                     let base_currency = holding.base_currency.clone();
-                    self.calculate_security_valuation(holding, &base_currency, &latest_quote_pairs).await?;
+                    self.calculate_security_valuation(holding, &base_currency, &latest_quote_pairs, None).await?;
                 }
                 HoldingType::Cash => {
                     holding.as_of_date = today;
                     let base_currency = holding.base_currency.clone();
-                    self.calculate_cash_valuation(holding, &base_currency)?;
+                    self.calculate_cash_valuation(holding, &base_currency, None)?;
                 }
             }
         }
@@ -130,6 +195,40 @@ impl HoldingsValuationServiceTrait for HoldingsValuationService {
         debug!("Finished calculate_holdings_live_valuation.");
         Ok(())
     }
+
+    async fn calculate_holdings_historical_valuation(
+        &self,
+        holdings: &mut [Holding],
+        date: NaiveDate,
+    ) -> Result<()> {
+        if holdings.is_empty() {
+            return Ok(());
+        }
+        debug!(
+            "Starting calculate_holdings_historical_valuation for {} holdings as of {}.",
+            holdings.len(),
+            date
+        );
+
+        let quote_pairs = self.fetch_historical_quote_data(holdings, date)?;
+
+        for holding in holdings.iter_mut() {
+            holding.as_of_date = date;
+            let base_currency = holding.base_currency.clone();
+            match holding.holding_type {
+                HoldingType::Security => {
+                    self.calculate_security_valuation(holding, &base_currency, &quote_pairs, Some(date))
+                        .await?;
+                }
+                HoldingType::Cash => {
+                    self.calculate_cash_valuation(holding, &base_currency, Some(date))?;
+                }
+            }
+        }
+
+        debug!("Finished calculate_holdings_historical_valuation.");
+        Ok(())
+    }
 }
 
 // --- New Helper Methods for Valuation ---
@@ -140,6 +239,7 @@ impl HoldingsValuationService {
         holding: &mut Holding,
         base_currency: &str,
         latest_quote_pairs: &HashMap<String, LatestQuotePair>,
+        rate_date: Option<NaiveDate>,
     ) -> Result<()> {
         let instrument = match &holding.instrument {
             Some(inst) => inst,
@@ -160,6 +260,7 @@ impl HoldingsValuationService {
         let fx_rate_local_to_base = self.get_fx_rate_or_fallback(
             pos_currency,
             base_currency,
+            rate_date,
             &format!("{}: FX Local->Base", context_msg),
         );
         holding.fx_rate = Some(fx_rate_local_to_base);
@@ -202,6 +303,7 @@ impl HoldingsValuationService {
                 self.get_fx_rate_or_fallback(
                     quote_currency,
                     base_currency,
+                    rate_date,
                     &format!("{}: FX Quote->Base", context_msg),
                 )
             };
@@ -216,6 +318,7 @@ impl HoldingsValuationService {
                 self.get_fx_rate_or_fallback(
                     quote_currency,
                     pos_currency,
+                    rate_date,
                     &format!("{}: FX Quote->Local", context_msg),
                 )
             };
@@ -332,6 +435,7 @@ impl HoldingsValuationService {
         &self,
         holding: &mut Holding,
         base_currency: &str,
+        rate_date: Option<NaiveDate>,
     ) -> Result<()> {
         let cash_currency = &holding.local_currency;
         let cash_amount = holding.quantity;
@@ -341,7 +445,7 @@ impl HoldingsValuationService {
         holding.price = Some(dec!(1.0));
 
         let fx_rate_cash_to_base =
-            self.get_fx_rate_or_fallback(cash_currency, base_currency, &context_msg);
+            self.get_fx_rate_or_fallback(cash_currency, base_currency, rate_date, &context_msg);
         holding.fx_rate = Some(fx_rate_cash_to_base);
 
         let value_base = cash_amount * fx_rate_cash_to_base;
@@ -22,9 +22,12 @@ mod tests {
 
 
     // --- Mock FxService ---
+    type DatedRates = HashMap<(String, String, NaiveDate), Decimal>;
+
     #[derive(Clone, Default)]
     struct MockFxService {
         rates: Arc<Mutex<HashMap<(String, String), Decimal>>>,
+        dated_rates: Arc<Mutex<DatedRates>>,
         should_fail: Arc<Mutex<HashMap<(String, String), bool>>>,
     }
 
@@ -37,6 +40,11 @@ mod tests {
             }
         }
 
+        fn add_rate_for_date(&self, from: &str, to: &str, date: NaiveDate, rate: Decimal) {
+            let mut rates = self.dated_rates.lock().unwrap();
+            rates.insert((from.to_string(), to.to_string(), date), rate);
+        }
+
         fn set_fail(&self, from: &str, to: &str, fail: bool) {
             let mut should_fail = self.should_fail.lock().unwrap();
             should_fail.insert((from.to_string(), to.to_string()), fail);
@@ -49,13 +57,24 @@ mod tests {
         async fn add_exchange_rate(&self, _new_rate: NewExchangeRate) -> Result<ExchangeRate> { unimplemented!() }
         fn get_historical_rates(&self, _from_currency: &str, _to_currency: &str, _days: i64) -> Result<Vec<ExchangeRate>> { unimplemented!() }
         async fn update_exchange_rate(&self, _from_currency: &str, _to_currency: &str, _rate: Decimal) -> Result<ExchangeRate> { unimplemented!() }
-        fn get_exchange_rate_for_date(&self, _from_currency: &str, _to_currency: &str, _date: NaiveDate) -> Result<Decimal> { unimplemented!() }
+        fn get_exchange_rate_for_date(&self, from_currency: &str, to_currency: &str, date: NaiveDate) -> Result<Decimal> {
+            let dated = self.dated_rates.lock().unwrap();
+            match dated.get(&(from_currency.to_string(), to_currency.to_string(), date)) {
+                Some(rate) => Ok(*rate),
+                None => self.get_latest_exchange_rate(from_currency, to_currency),
+            }
+        }
         fn convert_currency(&self, _amount: Decimal, _from_currency: &str, _to_currency: &str) -> Result<Decimal> { unimplemented!() }
         fn convert_currency_for_date(&self, _amount: Decimal, _from_currency: &str, _to_currency: &str, _date: NaiveDate) -> Result<Decimal> { unimplemented!() }
         fn get_latest_exchange_rates(&self) -> Result<Vec<ExchangeRate>> { unimplemented!() }
         async fn delete_exchange_rate(&self, _rate_id: &str) -> Result<()> { unimplemented!() }
         async fn register_currency_pair(&self, _from_currency: &str, _to_currency: &str) -> Result<()> { unimplemented!() }
         async fn register_currency_pair_manual(&self, _from_currency: &str, _to_currency: &str) -> Result<()> { unimplemented!() }
+        async fn import_reference_rates(&self, _provider_id: &str, _content: &str, _currencies: Option<Vec<String>>) -> Result<usize> { unimplemented!() }
+        async fn sync_reference_rates(&self, _provider_id: &str, _start_date: NaiveDate, _end_date: NaiveDate, _currencies: Option<Vec<String>>) -> Result<usize> { unimplemented!() }
+        fn get_conversion_policy(&self) -> Result<FxConversionPolicy> { unimplemented!() }
+        fn set_conversion_policy(&self, _policy: FxConversionPolicy) -> Result<()> { unimplemented!() }
+        fn explain_conversion_for_date(&self, _amount: Decimal, _from_currency: &str, _to_currency: &str, _date: NaiveDate) -> Result<FxConversionExplanation> { unimplemented!() }
 
         fn get_latest_exchange_rate(&self, from_currency: &str, to_currency: &str) -> Result<Decimal> {
             if from_currency == to_currency {
@@ -81,6 +100,7 @@ mod tests {
     #[derive(Clone, Default)]
     struct MockMarketDataService {
         quotes: Arc<Mutex<HashMap<String, LatestQuotePair>>>,
+        history: Arc<Mutex<Vec<Quote>>>,
         should_fail: Arc<Mutex<bool>>,
     }
 
@@ -90,6 +110,14 @@ mod tests {
             quotes.insert(symbol.to_string(), LatestQuotePair { latest, previous });
         }
 
+        fn add_quote_history(&self, symbol: &str, quotes: Vec<Quote>) {
+            let mut history = self.history.lock().unwrap();
+            history.extend(quotes.into_iter().map(|mut quote| {
+                quote.symbol = symbol.to_string();
+                quote
+            }));
+        }
+
     }
 
     #[async_trait]
@@ -106,7 +134,18 @@ mod tests {
         async fn get_historical_quotes_from_provider(&self, _symbol: &str, _start_date: NaiveDate, _end_date: NaiveDate) -> Result<Vec<Quote>> { unimplemented!() }
         async fn sync_market_data(&self) -> Result<((), Vec<(String, String)>)> { unimplemented!() }
         async fn resync_market_data(&self, _symbols: Option<Vec<String>>) -> Result<((), Vec<(String, String)>)> { unimplemented!() }
-        fn get_historical_quotes_for_symbols_in_range(&self, _symbols: &HashSet<String>, _start_date: NaiveDate, _end_date: NaiveDate) -> Result<Vec<Quote>> { unimplemented!() }
+        fn get_historical_quotes_for_symbols_in_range(&self, symbols: &HashSet<String>, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<Quote>> {
+            let history = self.history.lock().unwrap();
+            Ok(history
+                .iter()
+                .filter(|quote| symbols.contains(&quote.symbol))
+                .filter(|quote| {
+                    let date = quote.timestamp.date_naive();
+                    date >= start_date && date <= end_date
+                })
+                .cloned()
+                .collect())
+        }
         async fn get_daily_quotes(&self, _asset_ids: &HashSet<String>, _start_date: NaiveDate, _end_date: NaiveDate) -> Result<HashMap<NaiveDate, HashMap<String, Quote>>> { unimplemented!() }
         async fn get_market_data_providers_info(&self) -> Result<Vec<MarketDataProviderInfo>> { unimplemented!() }
         async fn get_market_data_providers_settings(&self) -> Result<Vec<MarketDataProviderSetting>> { unimplemented!() }
@@ -731,4 +770,41 @@ mod tests {
         assert!(result.is_ok());
         assert!(holdings.is_empty()); // Should remain empty
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_historical_valuation_uses_quotes_and_fx_of_the_day() {
+        let (fx_service, market_data_service, valuation_service) = setup_test_env();
+        let as_of = NaiveDate::from_str("2023-12-31").unwrap(); // A Sunday
+        fx_service.add_rate_for_date("USD", "CAD", as_of, dec!(1.35));
+        market_data_service.add_quote_history(
+            "AAPL",
+            vec![
+                create_quote("2023-12-28", dec!(100.0), "USD"),
+                create_quote("2023-12-29", dec!(110.0), "USD"),
+                create_quote("2024-01-10", dec!(150.0), "USD"),
+            ],
+        );
+
+        let mut holdings = vec![
+            create_holding("h1", HoldingType::Security, "AAPL", dec!(10), "USD", "CAD", Some(dec!(1000.0)), Some("Apple")),
+            create_holding("c1", HoldingType::Cash, "$CASH-USD", dec!(100), "USD", "CAD", None, None),
+        ];
+
+        valuation_service
+            .calculate_holdings_historical_valuation(&mut holdings, as_of)
+            .await
+            .unwrap();
+
+        let security = &holdings[0];
+        assert_eq!(security.as_of_date, as_of);
+        assert_decimal_approx(security.price, dec!(110.0), TOLERANCE, "Price");
+        assert_decimal_approx(security.fx_rate, dec!(1.35), TOLERANCE, "FX Rate");
+        assert_monetary_value_approx(Some(&security.market_value), dec!(1100.0), dec!(1485.0), TOLERANCE, "Market Value");
+        assert_monetary_value_approx(security.cost_basis.as_ref(), dec!(1000.0), dec!(1350.0), TOLERANCE, "Cost Basis");
+        assert_monetary_value_approx(security.prev_close_value.as_ref(), dec!(1000.0), dec!(1350.0), TOLERANCE, "Prev Close Value");
+
+        let cash = &holdings[1];
+        assert_eq!(cash.as_of_date, as_of);
+        assert_monetary_value_approx(Some(&cash.market_value), dec!(100.0), dec!(135.0), TOLERANCE, "Cash Value");
+    }
+}
@@ -1,7 +1,7 @@
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::activities::activities_model::IncomeData;
+use crate::activities::activities_model::{ForeignTaxData, IncomeData};
 
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -12,7 +12,13 @@ pub struct IncomeSummary {
     pub by_type: HashMap<String, Decimal>,
     pub by_symbol: HashMap<String, Decimal>,     
     pub by_currency: HashMap<String, Decimal>,
+    /// Income received, net of any tax withheld at source.
     pub total_income: Decimal,
+    /// Income before withholding tax.
+    pub gross_income: Decimal,
+    pub total_withholding: Decimal,
+    /// Withholding tax keyed by the ISO country code that levied it.
+    pub withholding_by_country: HashMap<String, Decimal>,
     pub currency: String,
     pub monthly_average: Decimal,
     pub yoy_growth: Option<Decimal>,
@@ -27,13 +33,22 @@ impl IncomeSummary {
             by_symbol: HashMap::new(),
             by_currency: HashMap::new(),
             total_income: Decimal::ZERO,
+            gross_income: Decimal::ZERO,
+            total_withholding: Decimal::ZERO,
+            withholding_by_country: HashMap::new(),
             currency,
             monthly_average: Decimal::ZERO,
             yoy_growth: None,
         }
     }
 
-    pub fn add_income(&mut self, data: &IncomeData, converted_amount: Decimal) {
+    /// Adds one payment; `converted_withholding` is its withholding tax in the summary currency.
+    pub fn add_income(
+        &mut self,
+        data: &IncomeData,
+        converted_amount: Decimal,
+        converted_withholding: Decimal,
+    ) {
         *self.by_month.entry(data.date.to_string()).or_insert_with(|| Decimal::ZERO) += &converted_amount;
         *self.by_type.entry(data.income_type.clone()).or_insert_with(|| Decimal::ZERO) += &converted_amount;
         *self
@@ -42,6 +57,14 @@ impl IncomeSummary {
             .or_insert_with(|| Decimal::ZERO) += &converted_amount;
         *self.by_currency.entry(data.currency.clone()).or_insert_with(|| Decimal::ZERO) += &data.amount;
         self.total_income += &converted_amount;
+        self.gross_income += converted_amount + converted_withholding;
+        if !converted_withholding.is_zero() {
+            self.total_withholding += converted_withholding;
+            *self
+                .withholding_by_country
+                .entry(data.withholding_country.clone().unwrap_or_default())
+                .or_insert_with(|| Decimal::ZERO) += converted_withholding;
+        }
     }
 
     pub fn calculate_monthly_average(&mut self, num_months: Option<u32>) {
@@ -52,4 +75,171 @@ impl IncomeSummary {
     }
 }
 
+/// Income and tax withheld at source for one account and source country over a tax year.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignTaxRow {
+    pub account_id: String,
+    pub account_name: String,
+    pub country: String,
+    /// Currency the payments were made in; `*_base` fields are in the report currency.
+    pub currency: String,
+    pub gross_income: Decimal,
+    pub withholding_tax: Decimal,
+    pub net_income: Decimal,
+    pub gross_income_base: Decimal,
+    pub withholding_tax_base: Decimal,
+    pub net_income_base: Decimal,
+    pub payment_count: usize,
+}
+
+/// Foreign tax paid in a calendar year, as needed for a foreign tax credit claim.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ForeignTaxReport {
+    pub year: i32,
+    pub currency: String,
+    pub rows: Vec<ForeignTaxRow>,
+    pub total_gross_income: Decimal,
+    pub total_withholding_tax: Decimal,
+}
+
+impl ForeignTaxReport {
+    pub fn new(year: i32, currency: String) -> Self {
+        ForeignTaxReport {
+            year,
+            currency,
+            rows: Vec::new(),
+            total_gross_income: Decimal::ZERO,
+            total_withholding_tax: Decimal::ZERO,
+        }
+    }
+
+    /// Adds one payment, with its net amount and withholding converted to the report currency.
+    pub fn add_payment(
+        &mut self,
+        data: &ForeignTaxData,
+        converted_amount: Decimal,
+        converted_withholding: Decimal,
+    ) {
+        let index = match self.rows.iter().position(|row| {
+            row.account_id == data.account_id
+                && row.country == data.withholding_country
+                && row.currency == data.currency
+        }) {
+            Some(index) => index,
+            None => {
+                self.rows.push(ForeignTaxRow {
+                    account_id: data.account_id.clone(),
+                    account_name: data.account_name.clone(),
+                    country: data.withholding_country.clone(),
+                    currency: data.currency.clone(),
+                    gross_income: Decimal::ZERO,
+                    withholding_tax: Decimal::ZERO,
+                    net_income: Decimal::ZERO,
+                    gross_income_base: Decimal::ZERO,
+                    withholding_tax_base: Decimal::ZERO,
+                    net_income_base: Decimal::ZERO,
+                    payment_count: 0,
+                });
+                self.rows.len() - 1
+            }
+        };
+        let row = &mut self.rows[index];
+        row.gross_income += data.amount + data.withholding_tax;
+        row.withholding_tax += data.withholding_tax;
+        row.net_income += data.amount;
+        row.gross_income_base += converted_amount + converted_withholding;
+        row.withholding_tax_base += converted_withholding;
+        row.net_income_base += converted_amount;
+        row.payment_count += 1;
+
+        self.total_gross_income += converted_amount + converted_withholding;
+        self.total_withholding_tax += converted_withholding;
+    }
 
+    /// Orders rows by account then country and rounds amounts for display.
+    pub fn finalize(&mut self, precision: u32) {
+        self.rows.sort_by(|a, b| {
+            (&a.account_name, &a.country, &a.currency).cmp(&(&b.account_name, &b.country, &b.currency))
+        });
+        for row in &mut self.rows {
+            for value in [
+                &mut row.gross_income,
+                &mut row.withholding_tax,
+                &mut row.net_income,
+                &mut row.gross_income_base,
+                &mut row.withholding_tax_base,
+                &mut row.net_income_base,
+            ] {
+                *value = value.round_dp(precision);
+            }
+        }
+        self.total_gross_income = self.total_gross_income.round_dp(precision);
+        self.total_withholding_tax = self.total_withholding_tax.round_dp(precision);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    fn income(country: Option<&str>, amount: Decimal, withholding: Decimal) -> IncomeData {
+        IncomeData {
+            date: "2024-03".to_string(),
+            income_type: "DIVIDEND".to_string(),
+            symbol: "NESN.SW".to_string(),
+            symbol_name: "Nestle".to_string(),
+            currency: "CHF".to_string(),
+            amount,
+            withholding_tax: withholding,
+            withholding_country: country.map(str::to_string),
+            profile_id: "default".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summary_reports_gross_net_and_withholding_by_country() {
+        let mut summary = IncomeSummary::new("TOTAL", "USD".to_string());
+        summary.add_income(&income(Some("CH"), dec!(65), dec!(35)), dec!(65), dec!(35));
+        summary.add_income(&income(None, dec!(10), dec!(0)), dec!(10), dec!(0));
+
+        assert_eq!(summary.total_income, dec!(75));
+        assert_eq!(summary.gross_income, dec!(110));
+        assert_eq!(summary.total_withholding, dec!(35));
+        assert_eq!(summary.withholding_by_country.len(), 1);
+        assert_eq!(summary.withholding_by_country["CH"], dec!(35));
+    }
+
+    #[test]
+    fn test_foreign_tax_report_groups_by_account_and_country() {
+        let payment = |account: &str, country: &str, amount, withholding| ForeignTaxData {
+            account_id: account.to_string(),
+            account_name: format!("Account {}", account),
+            profile_id: "default".to_string(),
+            activity_date: NaiveDate::from_ymd_opt(2024, 4, 15).unwrap(),
+            currency: "CHF".to_string(),
+            amount,
+            withholding_tax: withholding,
+            withholding_country: country.to_string(),
+        };
+        let mut report = ForeignTaxReport::new(2024, "USD".to_string());
+        report.add_payment(&payment("B", "CH", dec!(65), dec!(35)), dec!(72), dec!(38.8));
+        report.add_payment(&payment("A", "CH", dec!(13), dec!(7)), dec!(14.4), dec!(7.7));
+        report.add_payment(&payment("B", "CH", dec!(65), dec!(35)), dec!(73), dec!(39.3));
+        report.finalize(2);
+
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[0].account_id, "A");
+        let b = &report.rows[1];
+        assert_eq!(b.payment_count, 2);
+        assert_eq!(b.gross_income, dec!(200));
+        assert_eq!(b.withholding_tax, dec!(70));
+        assert_eq!(b.net_income, dec!(130));
+        assert_eq!(b.withholding_tax_base, dec!(78.1));
+        assert_eq!(report.total_withholding_tax, dec!(85.8));
+        assert_eq!(report.total_gross_income, dec!(245.2));
+    }
+}
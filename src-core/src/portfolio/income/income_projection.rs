@@ -0,0 +1,442 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::constants::DISPLAY_DECIMAL_PRECISION;
+
+/// Months of dividend history used to detect how often a symbol pays.
+pub const DIVIDEND_HISTORY_MONTHS: u32 = 24;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeProjectionOptions {
+    /// Length of the calendar, starting with the current month.
+    #[serde(default = "default_projection_months")]
+    pub months: u32,
+    /// Size payments from the provider's forward yield instead of the last payment.
+    #[serde(default)]
+    pub use_forward_yield: bool,
+}
+
+fn default_projection_months() -> u32 {
+    12
+}
+
+impl Default for IncomeProjectionOptions {
+    fn default() -> Self {
+        IncomeProjectionOptions {
+            months: default_projection_months(),
+            use_forward_yield: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PaymentFrequency {
+    Monthly,
+    Quarterly,
+    SemiAnnual,
+    Annual,
+}
+
+impl PaymentFrequency {
+    pub fn months_between(&self) -> u32 {
+        match self {
+            PaymentFrequency::Monthly => 1,
+            PaymentFrequency::Quarterly => 3,
+            PaymentFrequency::SemiAnnual => 6,
+            PaymentFrequency::Annual => 12,
+        }
+    }
+
+    pub fn payments_per_year(&self) -> u32 {
+        12 / self.months_between()
+    }
+
+    /// Classifies the median gap between consecutive payments.
+    fn from_gap_days(days: i64) -> Self {
+        match days {
+            ..=45 => PaymentFrequency::Monthly,
+            46..=135 => PaymentFrequency::Quarterly,
+            136..=270 => PaymentFrequency::SemiAnnual,
+            _ => PaymentFrequency::Annual,
+        }
+    }
+}
+
+/// Where a symbol's projected payment amount comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ProjectionBasis {
+    /// The last payment per share repeats.
+    TrailingDividends,
+    /// Price times the provider's forward yield, spread over the year's payments.
+    ForwardYield,
+}
+
+/// A past dividend, per share held on the payment date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DividendPayment {
+    pub pay_date: NaiveDate,
+    pub amount_per_share: Decimal,
+    pub currency: String,
+}
+
+/// A position to project, with what is known about its forward yield.
+#[derive(Debug, Clone)]
+pub struct ProjectionPosition {
+    pub asset_id: String,
+    pub symbol: String,
+    pub name: Option<String>,
+    pub quantity: Decimal,
+    pub currency: String,
+    pub price: Option<Decimal>,
+    pub forward_yield: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectedPayment {
+    pub asset_id: String,
+    pub symbol: String,
+    pub pay_date: NaiveDate,
+    pub amount_per_share: Decimal,
+    pub quantity: Decimal,
+    /// Amount in `currency`.
+    pub amount: Decimal,
+    pub currency: String,
+    /// Amount in the projection's base currency.
+    pub converted_amount: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectedIncomeMonth {
+    /// `YYYY-MM`, matching `IncomeSummary::by_month`.
+    pub month: String,
+    pub total: Decimal,
+    pub payments: Vec<ProjectedPayment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolIncomeProjection {
+    pub asset_id: String,
+    pub symbol: String,
+    pub name: Option<String>,
+    pub quantity: Decimal,
+    pub frequency: PaymentFrequency,
+    pub basis: ProjectionBasis,
+    pub amount_per_share: Decimal,
+    pub currency: String,
+    pub last_pay_date: Option<NaiveDate>,
+    pub next_pay_date: Option<NaiveDate>,
+    pub expected_pay_dates: Vec<NaiveDate>,
+    /// Sum of the projected payments, in base currency.
+    pub projected_income: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeProjection {
+    pub currency: String,
+    pub as_of: NaiveDate,
+    pub total_income: Decimal,
+    pub monthly_average: Decimal,
+    pub by_month: Vec<ProjectedIncomeMonth>,
+    pub by_symbol: Vec<SymbolIncomeProjection>,
+    pub warnings: Vec<String>,
+}
+
+fn add_months(date: NaiveDate, months: u32) -> NaiveDate {
+    date.checked_add_months(Months::new(months))
+        .unwrap_or(NaiveDate::MAX)
+}
+
+fn month_key(date: NaiveDate) -> String {
+    format!("{:04}-{:02}", date.year(), date.month())
+}
+
+/// Frequency from the median gap between payments; a single payment counts as annual.
+pub fn detect_frequency(payments: &[DividendPayment]) -> PaymentFrequency {
+    let mut gaps: Vec<i64> = payments
+        .windows(2)
+        .map(|pair| (pair[1].pay_date - pair[0].pay_date).num_days())
+        .filter(|days| *days > 0)
+        .collect();
+    if gaps.is_empty() {
+        return PaymentFrequency::Annual;
+    }
+    gaps.sort_unstable();
+    PaymentFrequency::from_gap_days(gaps[gaps.len() / 2])
+}
+
+/// Projects dividends over the calendar months starting with `as_of`'s month.
+///
+/// `history` maps asset ids to past payments, oldest first. Payments repeat from the last
+/// one at the detected frequency; payments already overdue on `as_of` are left out rather
+/// than bunched into the current month. `fx_rates` converts a payment currency to
+/// `base_currency`.
+pub fn project_income(
+    positions: &[ProjectionPosition],
+    history: &HashMap<String, Vec<DividendPayment>>,
+    fx_rates: &HashMap<String, Decimal>,
+    as_of: NaiveDate,
+    options: &IncomeProjectionOptions,
+    base_currency: &str,
+) -> IncomeProjection {
+    let months = options.months.max(1);
+    let first_month = as_of.with_day(1).unwrap_or(as_of);
+    let window_end = add_months(first_month, months);
+    let mut by_month: BTreeMap<String, ProjectedIncomeMonth> = (0..months)
+        .map(|i| {
+            let month = month_key(add_months(first_month, i));
+            (
+                month.clone(),
+                ProjectedIncomeMonth {
+                    month,
+                    total: Decimal::ZERO,
+                    payments: Vec::new(),
+                },
+            )
+        })
+        .collect();
+
+    let mut warnings = Vec::new();
+    let mut by_symbol = Vec::new();
+    for position in positions.iter().filter(|p| p.quantity > Decimal::ZERO) {
+        let payments = history
+            .get(&position.asset_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let last = payments.last();
+        let forward_annual = match (options.use_forward_yield, position.price, position.forward_yield)
+        {
+            (true, Some(price), Some(forward_yield)) if forward_yield > Decimal::ZERO => {
+                Some(price * forward_yield)
+            }
+            _ => None,
+        };
+
+        // A symbol with no history but a forward yield is assumed to pay quarterly.
+        let frequency = if payments.is_empty() {
+            PaymentFrequency::Quarterly
+        } else {
+            detect_frequency(payments)
+        };
+        let step = frequency.months_between();
+        let (basis, amount_per_share, currency) = match (forward_annual, last) {
+            (Some(annual), _) => (
+                ProjectionBasis::ForwardYield,
+                annual / Decimal::from(frequency.payments_per_year()),
+                position.currency.clone(),
+            ),
+            (None, Some(last)) => (
+                ProjectionBasis::TrailingDividends,
+                last.amount_per_share,
+                last.currency.clone(),
+            ),
+            (None, None) => continue,
+        };
+
+        // Stale history usually means the dividend was suspended.
+        if let Some(last) = last.filter(|_| forward_annual.is_none()) {
+            if add_months(last.pay_date, step * 2) < as_of {
+                warnings.push(format!(
+                    "{} has not paid since {}; left out of the projection",
+                    position.symbol, last.pay_date
+                ));
+                continue;
+            }
+        }
+
+        let expected_pay_dates: Vec<NaiveDate> = match last {
+            Some(last) => (1..)
+                .map(|k| add_months(last.pay_date, step * k))
+                .skip_while(|date| *date <= as_of)
+                .take_while(|date| *date < window_end)
+                .collect(),
+            None => (1..)
+                .map(|k| add_months(as_of, step * k))
+                .take_while(|date| *date < window_end)
+                .collect(),
+        };
+
+        let rate = match fx_rates.get(&currency) {
+            Some(rate) => *rate,
+            None if currency == base_currency => Decimal::ONE,
+            None => {
+                warnings.push(format!(
+                    "No exchange rate from {} to {}; {} is shown unconverted",
+                    currency, base_currency, position.symbol
+                ));
+                Decimal::ONE
+            }
+        };
+
+        let amount = amount_per_share * position.quantity;
+        let converted_amount = amount * rate;
+        for pay_date in &expected_pay_dates {
+            if let Some(month) = by_month.get_mut(&month_key(*pay_date)) {
+                month.total += converted_amount;
+                month.payments.push(ProjectedPayment {
+                    asset_id: position.asset_id.clone(),
+                    symbol: position.symbol.clone(),
+                    pay_date: *pay_date,
+                    amount_per_share: amount_per_share.round_dp(DISPLAY_DECIMAL_PRECISION),
+                    quantity: position.quantity,
+                    amount: amount.round_dp(DISPLAY_DECIMAL_PRECISION),
+                    currency: currency.clone(),
+                    converted_amount: converted_amount.round_dp(DISPLAY_DECIMAL_PRECISION),
+                });
+            }
+        }
+
+        by_symbol.push(SymbolIncomeProjection {
+            asset_id: position.asset_id.clone(),
+            symbol: position.symbol.clone(),
+            name: position.name.clone(),
+            quantity: position.quantity,
+            frequency,
+            basis,
+            amount_per_share: amount_per_share.round_dp(DISPLAY_DECIMAL_PRECISION),
+            currency,
+            last_pay_date: last.map(|p| p.pay_date),
+            next_pay_date: expected_pay_dates.first().copied(),
+            projected_income: (converted_amount * Decimal::from(expected_pay_dates.len()))
+                .round_dp(DISPLAY_DECIMAL_PRECISION),
+            expected_pay_dates,
+        });
+    }
+
+    by_symbol.sort_by(|a, b| {
+        a.next_pay_date
+            .cmp(&b.next_pay_date)
+            .then_with(|| a.symbol.cmp(&b.symbol))
+    });
+    let by_month: Vec<ProjectedIncomeMonth> = by_month
+        .into_values()
+        .map(|mut month| {
+            month.total = month.total.round_dp(DISPLAY_DECIMAL_PRECISION);
+            month.payments.sort_by_key(|p| p.pay_date);
+            month
+        })
+        .collect();
+    let total_income: Decimal = by_month.iter().map(|m| m.total).sum();
+
+    IncomeProjection {
+        currency: base_currency.to_string(),
+        as_of,
+        total_income,
+        monthly_average: (total_income / Decimal::from(months)).round_dp(DISPLAY_DECIMAL_PRECISION),
+        by_month,
+        by_symbol,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn position(asset_id: &str, quantity: Decimal) -> ProjectionPosition {
+        ProjectionPosition {
+            asset_id: asset_id.to_string(),
+            symbol: asset_id.to_string(),
+            name: None,
+            quantity,
+            currency: "USD".to_string(),
+            price: Some(dec!(100)),
+            forward_yield: Some(dec!(0.04)),
+        }
+    }
+
+    fn quarterly(asset_id: &str, amount: Decimal) -> HashMap<String, Vec<DividendPayment>> {
+        let payments = [date(2024, 2, 15), date(2024, 5, 15), date(2024, 8, 15), date(2024, 11, 15)]
+            .into_iter()
+            .map(|pay_date| DividendPayment {
+                pay_date,
+                amount_per_share: amount,
+                currency: "USD".to_string(),
+            })
+            .collect();
+        HashMap::from([(asset_id.to_string(), payments)])
+    }
+
+    #[test]
+    fn test_detect_frequency() {
+        let history = quarterly("KO", dec!(0.5));
+        assert_eq!(detect_frequency(&history["KO"]), PaymentFrequency::Quarterly);
+        assert_eq!(detect_frequency(&history["KO"][..1]), PaymentFrequency::Annual);
+    }
+
+    #[test]
+    fn test_projects_trailing_dividends_into_calendar() {
+        let projection = project_income(
+            &[position("KO", dec!(100))],
+            &quarterly("KO", dec!(0.5)),
+            &HashMap::from([("USD".to_string(), dec!(1.25))]),
+            date(2024, 12, 1),
+            &IncomeProjectionOptions::default(),
+            "CAD",
+        );
+
+        assert_eq!(projection.by_month.len(), 12);
+        assert_eq!(projection.by_month[0].month, "2024-12");
+        let symbol = &projection.by_symbol[0];
+        assert_eq!(symbol.basis, ProjectionBasis::TrailingDividends);
+        assert_eq!(symbol.next_pay_date, Some(date(2025, 2, 15)));
+        assert_eq!(
+            symbol.expected_pay_dates,
+            vec![date(2025, 2, 15), date(2025, 5, 15), date(2025, 8, 15), date(2025, 11, 15)]
+        );
+        // 100 shares x 0.50 USD x 1.25 CAD per payment.
+        assert_eq!(projection.by_month[2].total, dec!(62.5));
+        assert_eq!(projection.total_income, dec!(250));
+    }
+
+    #[test]
+    fn test_forward_yield_sizes_payments() {
+        let options = IncomeProjectionOptions {
+            use_forward_yield: true,
+            ..Default::default()
+        };
+        let projection = project_income(
+            &[position("KO", dec!(10))],
+            &quarterly("KO", dec!(0.5)),
+            &HashMap::new(),
+            date(2024, 12, 1),
+            &options,
+            "USD",
+        );
+
+        let symbol = &projection.by_symbol[0];
+        assert_eq!(symbol.basis, ProjectionBasis::ForwardYield);
+        // 100 x 4% a year, paid quarterly.
+        assert_eq!(symbol.amount_per_share, dec!(1));
+        assert_eq!(symbol.projected_income, dec!(40));
+    }
+
+    #[test]
+    fn test_skips_suspended_dividends() {
+        let projection = project_income(
+            &[position("KO", dec!(10))],
+            &quarterly("KO", dec!(0.5)),
+            &HashMap::new(),
+            date(2025, 9, 1),
+            &IncomeProjectionOptions::default(),
+            "USD",
+        );
+
+        assert!(projection.by_symbol.is_empty());
+        assert_eq!(projection.warnings.len(), 1);
+        assert_eq!(projection.total_income, dec!(0));
+    }
+}
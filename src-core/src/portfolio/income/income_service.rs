@@ -1,41 +1,142 @@
 use crate::{
     activities::{activities_errors::ActivityError, activities_model::IncomeData, activities_traits::ActivityRepositoryTrait}, Error, Result
 };
-use chrono::{Datelike, NaiveDate, Utc};
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use crate::activities::activities_constants::{ACTIVITY_TYPE_DIVIDEND, ACTIVITY_TYPE_DIVIDEND_REINVESTMENT};
+use crate::assets::AssetRepositoryTrait;
 use crate::constants::DISPLAY_DECIMAL_PRECISION;
+use crate::market_data::MarketDataServiceTrait;
+use crate::portfolio::snapshot::{AccountStateSnapshot, SnapshotRepositoryTrait};
+use crate::profiles::{profile_in_scope, scoped_total_account_id};
 
-use log::{debug, error};
+use log::{debug, error, warn};
 use num_traits::Zero;
 use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
 use crate::fx::fx_traits::FxServiceTrait;
-use super::IncomeSummary;
+use super::income_projection::{
+    project_income, DividendPayment, IncomeProjection, IncomeProjectionOptions,
+    ProjectionPosition, DIVIDEND_HISTORY_MONTHS,
+};
+use super::{ForeignTaxReport, IncomeSummary};
 // Define the trait for the income service
 pub trait IncomeServiceTrait: Send + Sync {
     fn get_income_summary(
         &self,
     ) -> Result<Vec<IncomeSummary>>;
+    /// Expected dividends from the current positions, month by month in base currency.
+    fn get_income_projection(&self, options: IncomeProjectionOptions) -> Result<IncomeProjection>;
+    /// Tax withheld at source in `year`, per account and country, for foreign tax credit claims.
+    fn get_foreign_tax_report(&self, year: i32) -> Result<ForeignTaxReport>;
 }
 
 pub struct IncomeService {
     fx_service: Arc<dyn FxServiceTrait>,
     activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    snapshot_repository: Arc<dyn SnapshotRepositoryTrait>,
+    asset_repository: Arc<dyn AssetRepositoryTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
     base_currency: Arc<RwLock<String>>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
 }
 
 impl IncomeService {
     pub fn new(
         fx_service: Arc<dyn FxServiceTrait>,
         activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        snapshot_repository: Arc<dyn SnapshotRepositoryTrait>,
+        asset_repository: Arc<dyn AssetRepositoryTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
         base_currency: Arc<RwLock<String>>,
+        active_profile_id: Arc<RwLock<Option<String>>>,
     ) -> Self {
         IncomeService {
             fx_service,
             activity_repository,
+            snapshot_repository,
+            asset_repository,
+            market_data_service,
             base_currency,
+            active_profile_id,
         }
     }
 
+    /// Dividends paid on each asset since `since`, per share held the day before payment.
+    /// Payments from several accounts on the same day are combined, since the total
+    /// portfolio snapshot holds the combined quantity.
+    fn dividend_history(
+        &self,
+        asset_ids: &[&String],
+        since: NaiveDate,
+    ) -> Result<HashMap<String, Vec<DividendPayment>>> {
+        let mut totals: BTreeMap<(String, NaiveDate, String), Decimal> = BTreeMap::new();
+        for activity in self.activity_repository.get_income_activities()? {
+            let is_dividend = activity.activity_type == ACTIVITY_TYPE_DIVIDEND
+                || activity.activity_type == ACTIVITY_TYPE_DIVIDEND_REINVESTMENT;
+            let pay_date = activity.activity_date.naive_utc().date();
+            if !is_dividend
+                || activity.is_draft
+                || pay_date < since
+                || !asset_ids.contains(&&activity.asset_id)
+            {
+                continue;
+            }
+            let amount = activity
+                .amount
+                .unwrap_or(activity.quantity * activity.unit_price);
+            *totals
+                .entry((activity.asset_id, pay_date, activity.currency))
+                .or_default() += amount;
+        }
+        if totals.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // The snapshot in force on `since` plus every later one.
+        let total_id = scoped_total_account_id(&self.active_profile_id);
+        let mut snapshots: Vec<AccountStateSnapshot> = self
+            .snapshot_repository
+            .get_latest_snapshot_before_date(&total_id, since)?
+            .into_iter()
+            .chain(
+                self.snapshot_repository
+                    .get_snapshots_by_account(&total_id, Some(since), None)?,
+            )
+            .collect();
+        snapshots.sort_by_key(|s| s.snapshot_date);
+        let quantity_before = |asset_id: &str, date: NaiveDate| -> Decimal {
+            let held = |s: &&AccountStateSnapshot| {
+                s.positions
+                    .get(asset_id)
+                    .map(|p| p.quantity)
+                    .filter(|q| *q > Decimal::ZERO)
+            };
+            snapshots
+                .iter()
+                .rev()
+                .filter(|s| s.snapshot_date < date)
+                .find_map(|s| held(&s))
+                .or_else(|| snapshots.iter().filter(|s| s.snapshot_date == date).find_map(|s| held(&s)))
+                .unwrap_or_default()
+        };
+
+        let mut history: HashMap<String, Vec<DividendPayment>> = HashMap::new();
+        for ((asset_id, pay_date, currency), amount) in totals {
+            let quantity = quantity_before(&asset_id, pay_date);
+            if quantity <= Decimal::ZERO {
+                warn!("No holding of {} before its dividend on {}; skipped", asset_id, pay_date);
+                continue;
+            }
+            history.entry(asset_id).or_default().push(DividendPayment {
+                pay_date,
+                amount_per_share: amount / quantity,
+                currency,
+            });
+        }
+        Ok(history)
+    }
+
     fn calculate_yoy_growth(current: Decimal, previous: Decimal) -> Decimal {
         if previous > Decimal::zero() {
             (current - previous) / previous
@@ -53,7 +154,10 @@ impl IncomeServiceTrait for IncomeService {
         debug!("Getting income summary...");
 
         let activities = match self.activity_repository.get_income_activities_data() {
-            Ok(activity) => activity,
+            Ok(activity) => activity
+                .into_iter()
+                .filter(|a| profile_in_scope(&self.active_profile_id, &a.profile_id))
+                .collect::<Vec<_>>(),
             Err(e) => {
                 error!("Error getting aggregated income data: {:?}", e);
                 return Err(Error::Activity(ActivityError::InvalidData(e.to_string())));
@@ -123,6 +227,16 @@ impl IncomeServiceTrait for IncomeService {
                     activity.amount.clone()
                 }
             };
+            let converted_withholding = if activity.withholding_tax.is_zero() {
+                Decimal::ZERO
+            } else {
+                self.fx_service
+                    .convert_currency(activity.withholding_tax, &activity.currency, &base_currency)
+                    .unwrap_or_else(|e| {
+                        error!("Error converting withholding tax: {:?}", e);
+                        activity.withholding_tax
+                    })
+            };
 
             // Create a copy of the activity with cloned fields to avoid ownership issues
             let activity_copy = IncomeData {
@@ -132,16 +246,19 @@ impl IncomeServiceTrait for IncomeService {
                 symbol_name: activity.symbol_name.clone(),
                 currency: activity.currency.clone(),
                 amount: activity.amount.clone(), // Keep original amount in activity_copy if needed elsewhere
+                withholding_tax: activity.withholding_tax,
+                withholding_country: activity.withholding_country.clone(),
+                profile_id: activity.profile_id.clone(),
             };
 
-            total_summary.add_income(&activity_copy, converted_amount.clone());
+            total_summary.add_income(&activity_copy, converted_amount, converted_withholding);
 
             if date.year() == current_year {
-                ytd_summary.add_income(&activity_copy, converted_amount.clone());
+                ytd_summary.add_income(&activity_copy, converted_amount, converted_withholding);
             } else if date.year() == last_year {
-                last_year_summary.add_income(&activity_copy, converted_amount.clone());
+                last_year_summary.add_income(&activity_copy, converted_amount, converted_withholding);
             } else if date.year() == two_years_ago {
-                two_years_ago_summary.add_income(&activity_copy, converted_amount.clone());
+                two_years_ago_summary.add_income(&activity_copy, converted_amount, converted_withholding);
             }
         }
 
@@ -177,6 +294,9 @@ impl IncomeServiceTrait for IncomeService {
             .map(|mut summary| {
                 summary.total_income = summary.total_income.round_dp(DISPLAY_DECIMAL_PRECISION);
                 summary.monthly_average = summary.monthly_average.round_dp(DISPLAY_DECIMAL_PRECISION);
+                summary.gross_income = summary.gross_income.round_dp(DISPLAY_DECIMAL_PRECISION);
+                summary.total_withholding =
+                    summary.total_withholding.round_dp(DISPLAY_DECIMAL_PRECISION);
                 if let Some(growth) = summary.yoy_growth {
                     summary.yoy_growth = Some(growth.round_dp(DISPLAY_DECIMAL_PRECISION));
                 }
@@ -193,6 +313,9 @@ impl IncomeServiceTrait for IncomeService {
                 for val in summary.by_currency.values_mut() {
                     *val = val.round_dp(DISPLAY_DECIMAL_PRECISION);
                 }
+                for val in summary.withholding_by_country.values_mut() {
+                    *val = val.round_dp(DISPLAY_DECIMAL_PRECISION);
+                }
                 summary
             })
             .collect();
@@ -200,4 +323,124 @@ impl IncomeServiceTrait for IncomeService {
         debug!("Income summary calculation and rounding completed successfully");
         Ok(rounded_summaries)
     }
+
+    fn get_income_projection(&self, options: IncomeProjectionOptions) -> Result<IncomeProjection> {
+        debug!("Projecting income...");
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let today = Utc::now().naive_utc().date();
+
+        let positions = self
+            .snapshot_repository
+            .get_latest_snapshot_before_date(&scoped_total_account_id(&self.active_profile_id), today)?
+            .map(|snapshot| snapshot.positions)
+            .unwrap_or_default();
+        let asset_ids: Vec<&String> = positions
+            .values()
+            .filter(|p| p.quantity > Decimal::ZERO)
+            .map(|p| &p.asset_id)
+            .collect();
+
+        let since = today
+            .checked_sub_months(Months::new(DIVIDEND_HISTORY_MONTHS))
+            .unwrap_or(today);
+        let history = self.dividend_history(&asset_ids, since)?;
+
+        let mut projection_positions = Vec::new();
+        for asset_id in asset_ids {
+            let position = &positions[asset_id];
+            let asset = match self.asset_repository.get_by_id(asset_id) {
+                Ok(asset) => asset,
+                Err(e) => {
+                    warn!("Asset {} not found for income projection: {}", asset_id, e);
+                    continue;
+                }
+            };
+            let forward_yield = asset
+                .dividend_yield
+                .and_then(|y| Decimal::try_from(y).ok());
+            let quote = if options.use_forward_yield && forward_yield.is_some() {
+                self.market_data_service
+                    .get_latest_quote_for_symbol(&asset.symbol)
+                    .ok()
+            } else {
+                None
+            };
+            projection_positions.push(ProjectionPosition {
+                asset_id: asset_id.clone(),
+                symbol: asset.symbol.clone(),
+                name: asset.name.clone(),
+                quantity: position.quantity,
+                currency: quote
+                    .as_ref()
+                    .map(|q| q.currency.clone())
+                    .unwrap_or_else(|| asset.currency.clone()),
+                price: quote.map(|q| q.close),
+                forward_yield,
+            });
+        }
+
+        let mut fx_rates = HashMap::new();
+        let currencies = history
+            .values()
+            .flatten()
+            .map(|p| &p.currency)
+            .chain(projection_positions.iter().map(|p| &p.currency));
+        for currency in currencies {
+            if fx_rates.contains_key(currency) {
+                continue;
+            }
+            match self.fx_service.get_latest_exchange_rate(currency, &base_currency) {
+                Ok(rate) => {
+                    fx_rates.insert(currency.clone(), rate);
+                }
+                Err(e) => warn!("No exchange rate from {} to {}: {}", currency, base_currency, e),
+            }
+        }
+
+        Ok(project_income(
+            &projection_positions,
+            &history,
+            &fx_rates,
+            today,
+            &options,
+            &base_currency,
+        ))
+    }
+
+    fn get_foreign_tax_report(&self, year: i32) -> Result<ForeignTaxReport> {
+        debug!("Building foreign tax report for {}...", year);
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let mut report = ForeignTaxReport::new(year, base_currency.clone());
+
+        for payment in self
+            .activity_repository
+            .get_foreign_tax_data(year)?
+            .into_iter()
+            .filter(|p| profile_in_scope(&self.active_profile_id, &p.profile_id))
+        {
+            // Tax authorities expect the rate of the payment date, not today's.
+            let convert = |amount: Decimal| {
+                self.fx_service
+                    .convert_currency_for_date(
+                        amount,
+                        &payment.currency,
+                        &base_currency,
+                        payment.activity_date,
+                    )
+                    .unwrap_or_else(|e| {
+                        warn!(
+                            "No {}/{} rate for {}: {}; amount left unconverted",
+                            payment.currency, base_currency, payment.activity_date, e
+                        );
+                        amount
+                    })
+            };
+            let converted_amount = convert(payment.amount);
+            let converted_withholding = convert(payment.withholding_tax);
+            report.add_payment(&payment, converted_amount, converted_withholding);
+        }
+
+        report.finalize(DISPLAY_DECIMAL_PRECISION);
+        Ok(report)
+    }
 }
@@ -1,7 +1,9 @@
 pub mod income_service;
 pub mod income_model;
+pub mod income_projection;
 
 pub use income_service::{IncomeServiceTrait, IncomeService};
 pub use income_model::*;
+pub use income_projection::*;
 
 
@@ -1,3 +1,4 @@
+pub mod fees;
 pub mod income;
 pub mod performance;
 pub mod snapshot;
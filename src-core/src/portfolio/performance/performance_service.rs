@@ -1,4 +1,5 @@
-use crate::constants::{DECIMAL_PRECISION, PORTFOLIO_TOTAL_ACCOUNT_ID};
+use crate::constants::DECIMAL_PRECISION;
+use crate::profiles::scoped_total_account_id;
 use crate::errors::{self, Result, ValidationError};
 use crate::market_data::MarketDataServiceTrait;
 use crate::performance::ReturnData;
@@ -7,7 +8,7 @@ use crate::valuation::ValuationServiceTrait;
 use async_trait::async_trait;
 use chrono::{Duration, NaiveDate};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use log::{debug, warn};
 use rust_decimal::Decimal;
@@ -47,6 +48,7 @@ pub trait PerformanceServiceTrait: Send + Sync {
 pub struct PerformanceService {
     valuation_service: Arc<dyn ValuationServiceTrait + Send + Sync>,
     market_data_service: Arc<dyn MarketDataServiceTrait + Send + Sync>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
 }
 
 const TRADING_DAYS_PER_YEAR: u32 = 252;
@@ -58,10 +60,12 @@ impl PerformanceService {
     pub fn new(
         valuation_service: Arc<dyn ValuationServiceTrait + Send + Sync>,
         market_data_service: Arc<dyn MarketDataServiceTrait + Send + Sync>,
+        active_profile_id: Arc<RwLock<Option<String>>>,
     ) -> Self {
         Self {
             valuation_service,
             market_data_service,
+            active_profile_id,
         }
     }
 
@@ -712,10 +716,11 @@ impl PerformanceServiceTrait for PerformanceService {
             return Ok(Vec::new());
         }
 
-        // Include "TOTAL" to get the total portfolio value reference date
+        // Include "TOTAL" (or the active profile's aggregate) to get the total portfolio value
+        let total_id = scoped_total_account_id(&self.active_profile_id);
         let mut ids_to_fetch = account_ids.to_vec();
-        if !account_ids.contains(&PORTFOLIO_TOTAL_ACCOUNT_ID.to_string()) {
-            ids_to_fetch.push(PORTFOLIO_TOTAL_ACCOUNT_ID.to_string());
+        if !account_ids.contains(&total_id) {
+            ids_to_fetch.push(total_id.clone());
         }
 
         // 1. Fetch the *absolute* latest record for each account
@@ -765,7 +770,7 @@ impl PerformanceServiceTrait for PerformanceService {
 
         // 4. Calculate total portfolio value using the absolute latest "TOTAL" valuation
         let total_portfolio_value_base = latest_daily_map
-            .get(PORTFOLIO_TOTAL_ACCOUNT_ID)
+            .get(&total_id)
             .map(|p| p.total_value * p.fx_rate_to_base);
 
         // 5. Construct results using the absolute latest and previous-to-latest records
@@ -0,0 +1,98 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::activities::{Activity, ActivityImport, ACTIVITY_TYPE_SPLIT};
+
+/// Tracks which accounts have stale holdings snapshots and from which date.
+///
+/// Each entry maps an account id to the earliest date whose snapshot must be
+/// rebuilt. `None` means the account has to be replayed from its first activity,
+/// which is required when a split changes because split factors are applied
+/// retroactively to every earlier activity.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DirtyRanges {
+    ranges: HashMap<String, Option<NaiveDate>>,
+}
+
+impl DirtyRanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks `account_id` dirty from `from_date` onward, keeping the earliest date seen.
+    pub fn mark(&mut self, account_id: &str, from_date: NaiveDate) {
+        self.ranges
+            .entry(account_id.to_string())
+            .and_modify(|existing| {
+                if let Some(date) = existing {
+                    if from_date < *date {
+                        *date = from_date;
+                    }
+                }
+            })
+            .or_insert(Some(from_date));
+    }
+
+    /// Marks the whole history of `account_id` dirty.
+    pub fn mark_all(&mut self, account_id: &str) {
+        self.ranges.insert(account_id.to_string(), None);
+    }
+
+    /// Marks the range affected by adding, removing or editing `activity`.
+    pub fn mark_activity(&mut self, activity: &Activity) {
+        if activity.activity_type == ACTIVITY_TYPE_SPLIT {
+            self.mark_all(&activity.account_id);
+        } else {
+            self.mark(&activity.account_id, activity.activity_date.naive_utc().date());
+        }
+    }
+
+    /// Marks the range affected by importing `activity` into `account_id`. Rows whose
+    /// date cannot be read mark the whole account dirty.
+    pub fn mark_import(&mut self, account_id: &str, activity: &ActivityImport) {
+        match activity.parsed_date() {
+            Some(date) if activity.activity_type != ACTIVITY_TYPE_SPLIT => self.mark(account_id, date),
+            _ => self.mark_all(account_id),
+        }
+    }
+
+    pub fn merge(&mut self, other: &DirtyRanges) {
+        for (account_id, from_date) in &other.ranges {
+            match from_date {
+                Some(date) => self.mark(account_id, *date),
+                None => self.mark_all(account_id),
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn account_ids(&self) -> Vec<String> {
+        self.ranges.keys().cloned().collect()
+    }
+
+    /// Returns the dirty start date for `account_id`: `Some(None)` when the whole
+    /// history is dirty, `None` when the account is not dirty at all.
+    pub fn get(&self, account_id: &str) -> Option<Option<NaiveDate>> {
+        self.ranges.get(account_id).copied()
+    }
+
+    /// The earliest dirty date across all accounts, or `None` if any account
+    /// needs a full replay.
+    pub fn earliest_date(&self) -> Option<NaiveDate> {
+        let mut earliest: Option<NaiveDate> = None;
+        for from_date in self.ranges.values() {
+            let date = (*from_date)?;
+            earliest = Some(earliest.map_or(date, |e| e.min(date)));
+        }
+        earliest
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Option<NaiveDate>)> {
+        self.ranges.iter()
+    }
+}
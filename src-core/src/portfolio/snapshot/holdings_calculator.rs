@@ -1,10 +1,11 @@
-use crate::activities::{Activity, ActivityType};
+use crate::activities::{corporate_action_cost_ratio, Activity, ActivityType};
 use crate::assets::AssetRepositoryTrait;
 use crate::constants::CASH_ASSET_PREFIX;
 use crate::errors::{CalculatorError, Error, Result};
 use crate::fx::fx_traits::FxServiceTrait;
 use crate::portfolio::snapshot::AccountStateSnapshot;
 use crate::portfolio::snapshot::Position;
+use crate::portfolio::snapshot::positions_model::is_quantity_significant;
 
 use chrono::{DateTime, NaiveDate, Utc};
 use log::{debug, error, warn};
@@ -178,12 +179,14 @@ pub struct HoldingsCalculator {
             ActivityType::Deposit => self.handle_deposit(activity, state, account_currency, amount_acct, fee_acct),
             ActivityType::Withdrawal => self.handle_withdrawal(activity, state, account_currency, amount_acct, fee_acct),
             ActivityType::Dividend | ActivityType::Interest => self.handle_income(state, account_currency, amount_acct, fee_acct),
+            ActivityType::DividendReinvestment => self.handle_dividend_reinvestment(activity, state, account_currency, fee_acct),
             ActivityType::Fee | ActivityType::Tax => self.handle_charge(activity, state, account_currency, &activity_type),
             ActivityType::AddHolding => self.handle_add_holding(activity, state, account_currency, fee_acct),
             ActivityType::RemoveHolding => self.handle_remove_holding(activity, state, account_currency, fee_acct),
             ActivityType::TransferIn => self.handle_transfer_in(activity, state, account_currency, amount_acct, fee_acct),
             ActivityType::TransferOut => self.handle_transfer_out(activity, state, account_currency, amount_acct, fee_acct),
             ActivityType::Split => Ok(()), 
+            ActivityType::TickerChange | ActivityType::Merger | ActivityType::SpinOff => self.handle_corporate_action(activity, state, account_currency, fee_acct, &activity_type),
          }
     }
 
@@ -390,6 +393,38 @@ pub struct HoldingsCalculator {
         Ok(())
     }
 
+    /// Books the dividend as income and buys the reinvested shares with it. Cash only
+    /// changes by the fee and any part of the dividend not reinvested.
+    fn handle_dividend_reinvestment(
+        &self,
+        activity: &Activity,
+        state: &mut AccountStateSnapshot,
+        account_currency: &str,
+        fee_acct: Decimal, // Already converted using activity date
+    ) -> Result<()> {
+        let activity_date = activity.activity_date.naive_utc().date();
+        let dividend = activity
+            .amount
+            .unwrap_or(activity.quantity * activity.unit_price);
+        let dividend_acct = match self.fx_service.convert_currency_for_date(
+            dividend,
+            &activity.currency,
+            account_currency,
+            activity_date,
+        ) {
+            Ok(converted) => converted,
+            Err(e) => {
+                warn!(
+                    "Holdings Calc (Reinvested Dividend {}): Failed conversion {} {}->{} on {}: {}. Using original amount.",
+                    activity.id, dividend, activity.currency, account_currency, activity_date, e
+                );
+                dividend
+            }
+        };
+        self.handle_income(state, account_currency, dividend_acct, Decimal::ZERO)?;
+        self.handle_buy(activity, state, account_currency, fee_acct)
+    }
+
     fn handle_charge(
         &self,
         activity: &Activity,
@@ -847,6 +882,103 @@ pub struct HoldingsCalculator {
         Ok(())
     }
 
+    /// Moves lots from `source_asset_id` into the activity's asset. `quantity` is the number
+    /// of new shares received (defaulting to the old quantity) and `amount` the share of cost
+    /// basis that moves. A merger's cash per old share (`unit_price`) is paid into the account.
+    fn handle_corporate_action(
+        &self,
+        activity: &Activity,
+        state: &mut AccountStateSnapshot,
+        account_currency: &str,
+        fee_acct: Decimal, // Already converted using activity date
+        activity_type: &ActivityType,
+    ) -> Result<()> {
+        let source_asset_id = activity
+            .source_asset_id
+            .as_deref()
+            .filter(|id| !id.is_empty() && *id != activity.asset_id)
+            .ok_or_else(|| {
+                CalculatorError::InvalidActivity(format!(
+                    "{} activity {} needs a source asset different from {}",
+                    activity_type.as_str(),
+                    activity.id,
+                    activity.asset_id
+                ))
+            })?;
+        let cost_ratio = corporate_action_cost_ratio(activity_type, activity.amount)
+            .map_err(|e| CalculatorError::InvalidActivity(format!("Activity {}: {}", activity.id, e)))?;
+        let activity_date = activity.activity_date.naive_utc().date();
+
+        *state
+            .cash_balances
+            .entry(account_currency.to_string())
+            .or_insert(Decimal::ZERO) -= fee_acct;
+
+        let Some(source) = state
+            .positions
+            .get_mut(source_asset_id)
+            .filter(|position| is_quantity_significant(&position.quantity))
+        else {
+            warn!("{} activity {} found no {} holding to move. Applying fee only.",
+                activity_type.as_str(), activity.id, source_asset_id);
+            return Ok(());
+        };
+        let source_quantity = source.quantity;
+        let source_currency = source.currency.clone();
+        let moved_lots = source.carve_out_lots(cost_ratio, *activity_type == ActivityType::SpinOff);
+
+        if *activity_type == ActivityType::Merger && !activity.unit_price.is_zero() {
+            let cash_received = source_quantity * activity.unit_price;
+            let cash_received_acct = match self.fx_service.convert_currency_for_date(
+                cash_received,
+                &activity.currency,
+                account_currency,
+                activity_date,
+            ) {
+                Ok(converted) => converted,
+                Err(e) => {
+                    warn!(
+                        "Holdings Calc (Merger Cash {}): Failed conversion {} {}->{} on {}: {}. Using original amount.",
+                        activity.id, cash_received, activity.currency, account_currency, activity_date, e
+                    );
+                    cash_received
+                }
+            };
+            *state
+                .cash_balances
+                .entry(account_currency.to_string())
+                .or_insert(Decimal::ZERO) += cash_received_acct;
+        }
+
+        let new_quantity = if activity.quantity.is_sign_positive() && !activity.quantity.is_zero() {
+            activity.quantity
+        } else {
+            source_quantity
+        };
+        let target = self.get_or_create_position_mut(
+            state,
+            &activity.asset_id,
+            &source_currency,
+            activity.activity_date,
+        )?;
+        let fx_rate = if source_currency.is_empty() || target.currency.is_empty() || target.currency == source_currency {
+            Decimal::ONE
+        } else {
+            self.fx_service
+                .convert_currency_for_date(Decimal::ONE, &source_currency, &target.currency, activity_date)
+                .map_err(|e| {
+                    CalculatorError::CurrencyConversion(format!(
+                        "Failed to convert {} cost basis of activity {} from {} to {}: {}",
+                        activity_type.as_str(), activity.id, source_currency, target.currency, e
+                    ))
+                })?
+        };
+        if target.currency.is_empty() {
+            target.currency = source_currency;
+        }
+        target.receive_lots(moved_lots, new_quantity / source_quantity, fx_rate);
+        Ok(())
+    }
 
     /// Gets amount from activity, handling missing values. Returns ZERO if missing.
     fn get_activity_amount(&self, activity: &Activity) -> Decimal {
@@ -4,6 +4,7 @@ mod tests {
     use crate::activities::{Activity, ActivityType};
     use crate::assets::{Asset, AssetRepositoryTrait, NewAsset, UpdateAssetProfile};
     use crate::fx::FxError;
+    use crate::fx::fx_model::{FxConversionExplanation, FxConversionPolicy};
     use crate::fx::fx_traits::FxServiceTrait;
     use crate::portfolio::snapshot::holdings_calculator::HoldingsCalculator;
     use crate::portfolio::snapshot::{AccountStateSnapshot, Position, Lot};
@@ -38,6 +39,12 @@ mod tests {
             mock.add_asset("TSLA", "USD");  // Tesla listed in USD
             mock.add_asset("XYZ", "USD");   // Test stock in USD
             mock.add_asset("ADS.DE", "EUR"); // Adidas listed in EUR
+            mock.add_asset("FB", "USD");    // Renamed to META
+            mock.add_asset("META", "USD");
+            mock.add_asset("GE", "USD");    // Spun off GEHC
+            mock.add_asset("GEHC", "USD");
+            mock.add_asset("VMW", "USD");   // Acquired by AVGO for cash and stock
+            mock.add_asset("AVGO", "USD");
             
             mock
         }
@@ -72,6 +79,14 @@ mod tests {
         async fn update_data_source(&self, _asset_id: &str, _data_source: String) -> Result<Asset> {
             unimplemented!("Not needed for tests")
         }
+
+        async fn update_isin(&self, _asset_id: &str, _isin: String) -> Result<Asset> {
+            unimplemented!("Not needed for tests")
+        }
+
+        async fn update_expense_ratio(&self, _asset_id: &str, _expense_ratio: Option<f64>) -> Result<Asset> {
+            unimplemented!("Not needed for tests")
+        }
         
         fn get_by_id(&self, asset_id: &str) -> Result<Asset> {
             self.assets.get(asset_id)
@@ -196,6 +211,24 @@ mod tests {
          async fn register_currency_pair_manual(&self, _from_currency: &str, _to_currency: &str) -> Result<()> {
             Err(crate::errors::Error::Unexpected("MockFxService::register_currency_pair_manual not implemented".to_string()))
         }
+        async fn import_reference_rates(&self, _provider_id: &str, _content: &str, _currencies: Option<Vec<String>>) -> Result<usize> {
+            Err(crate::errors::Error::Unexpected("MockFxService::import_reference_rates not implemented".to_string()))
+        }
+        async fn sync_reference_rates(&self, _provider_id: &str, _start_date: NaiveDate, _end_date: NaiveDate, _currencies: Option<Vec<String>>) -> Result<usize> {
+            Err(crate::errors::Error::Unexpected("MockFxService::sync_reference_rates not implemented".to_string()))
+        }
+
+        fn get_conversion_policy(&self) -> Result<FxConversionPolicy> {
+            Ok(FxConversionPolicy::default())
+        }
+
+        fn set_conversion_policy(&self, _policy: FxConversionPolicy) -> Result<()> {
+            Ok(())
+        }
+
+        fn explain_conversion_for_date(&self, _amount: Decimal, _from_currency: &str, _to_currency: &str, _date: NaiveDate) -> Result<FxConversionExplanation> {
+            Err(crate::errors::Error::Unexpected("MockFxService::explain_conversion_for_date not implemented".to_string()))
+        }
     }
 
     // --- Helper Functions ---
@@ -230,6 +263,10 @@ mod tests {
             comment: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
         }
     }
     
@@ -261,6 +298,10 @@ mod tests {
             comment: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
         }
     }
 
@@ -1592,4 +1633,136 @@ mod tests {
             "Cash should be deducted in account currency (EUR)"
         );
     }
+
+    #[test]
+    fn test_dividend_reinvestment_adds_lot_without_net_cash() {
+        let calculator = create_calculator(Arc::new(MockFxService::new()), Arc::new(RwLock::new("USD".to_string())));
+        let mut previous = create_initial_snapshot("acc_1", "USD", "2023-03-01");
+        previous.cash_balances.insert("USD".to_string(), dec!(100));
+        let date = NaiveDate::from_str("2023-03-02").unwrap();
+
+        // 0.5 shares at 150 bought with exactly the dividend
+        let drip = create_default_activity("drip_1", ActivityType::DividendReinvestment, "MSFT", dec!(0.5), dec!(150), dec!(0), "USD", "2023-03-02");
+        let next = calculator.calculate_next_holdings(&previous, &[drip], date).unwrap();
+        let position = next.positions.get("MSFT").unwrap();
+        assert_eq!(position.quantity, dec!(0.5));
+        assert_eq!(position.total_cost_basis, dec!(75));
+        assert_eq!(position.lots[0].acquisition_date.date_naive(), date);
+        assert_eq!(next.cash_balances.get("USD"), Some(&dec!(100)));
+        assert_eq!(next.net_contribution, previous.net_contribution);
+
+        // A dividend of 80 buys 0.5 shares; the residual 5 stays in cash and the fee is paid from it
+        let mut drip = create_default_activity("drip_2", ActivityType::DividendReinvestment, "MSFT", dec!(0.5), dec!(150), dec!(1), "USD", "2023-03-02");
+        drip.amount = Some(dec!(80));
+        let next = calculator.calculate_next_holdings(&previous, &[drip], date).unwrap();
+        assert_eq!(next.positions.get("MSFT").unwrap().total_cost_basis, dec!(76));
+        assert_eq!(next.cash_balances.get("USD"), Some(&dec!(104)));
+    }
+
+    // --- Corporate actions ---
+    fn create_corporate_action(
+        id: &str,
+        activity_type: ActivityType,
+        source_asset_id: &str,
+        asset_id: &str,
+        new_quantity: Decimal,
+        cost_ratio: Option<Decimal>,
+        date_str: &str,
+    ) -> Activity {
+        let mut activity = create_default_activity(
+            id, activity_type, asset_id, new_quantity, dec!(0), dec!(0), "USD", date_str,
+        );
+        activity.source_asset_id = Some(source_asset_id.to_string());
+        activity.amount = cost_ratio;
+        activity
+    }
+
+    /// Buys 10 shares at 100 on 2023-01-02 and 10 at 120 on 2023-01-03.
+    fn snapshot_with_two_lots(calculator: &HoldingsCalculator, asset_id: &str) -> AccountStateSnapshot {
+        let mut snapshot = create_initial_snapshot("acc_1", "USD", "2023-01-01");
+        for (id, price, date_str) in [("buy_1", dec!(100), "2023-01-02"), ("buy_2", dec!(120), "2023-01-03")] {
+            let buy = create_default_activity(id, ActivityType::Buy, asset_id, dec!(10), price, dec!(0), "USD", date_str);
+            snapshot = calculator
+                .calculate_next_holdings(&snapshot, &[buy], NaiveDate::from_str(date_str).unwrap())
+                .unwrap();
+        }
+        snapshot
+    }
+
+    #[test]
+    fn test_ticker_change_moves_lots_with_acquisition_dates() {
+        let calculator = create_calculator(Arc::new(MockFxService::new()), Arc::new(RwLock::new("USD".to_string())));
+        let previous = snapshot_with_two_lots(&calculator, "FB");
+        let date = NaiveDate::from_str("2023-02-01").unwrap();
+        let rename = create_corporate_action("ca_1", ActivityType::TickerChange, "FB", "META", dec!(0), None, "2023-02-01");
+
+        let next = calculator.calculate_next_holdings(&previous, &[rename], date).unwrap();
+
+        let old = next.positions.get("FB").unwrap();
+        assert!(old.lots.is_empty());
+        assert_eq!(old.quantity, dec!(0));
+        let new = next.positions.get("META").unwrap();
+        assert_eq!(new.quantity, dec!(20));
+        assert_eq!(new.total_cost_basis, dec!(2200));
+        let lot_dates: Vec<_> = new.lots.iter().map(|lot| lot.acquisition_date.date_naive()).collect();
+        assert_eq!(lot_dates, vec![NaiveDate::from_ymd_opt(2023, 1, 2).unwrap(), NaiveDate::from_ymd_opt(2023, 1, 3).unwrap()]);
+        assert_eq!(new.inception_date.date_naive(), NaiveDate::from_ymd_opt(2023, 1, 2).unwrap());
+        assert_eq!(next.cost_basis, previous.cost_basis);
+        assert_eq!(next.cash_balances, previous.cash_balances);
+    }
+
+    #[test]
+    fn test_merger_allocates_cost_and_pays_cash() {
+        let calculator = create_calculator(Arc::new(MockFxService::new()), Arc::new(RwLock::new("USD".to_string())));
+        let previous = snapshot_with_two_lots(&calculator, "VMW");
+        let date = NaiveDate::from_str("2023-11-22").unwrap();
+        // 20 VMW become 5 AVGO plus 40 USD per VMW share; 80% of cost follows the stock.
+        let mut merger = create_corporate_action("ca_2", ActivityType::Merger, "VMW", "AVGO", dec!(5), Some(dec!(0.8)), "2023-11-22");
+        merger.unit_price = dec!(40);
+        merger.fee = dec!(2);
+
+        let next = calculator.calculate_next_holdings(&previous, &[merger], date).unwrap();
+
+        assert!(next.positions.get("VMW").unwrap().lots.is_empty());
+        let avgo = next.positions.get("AVGO").unwrap();
+        assert_eq!(avgo.quantity, dec!(5));
+        assert_eq!(avgo.total_cost_basis, dec!(1760));
+        assert_eq!(avgo.lots[0].quantity, dec!(2.5));
+        assert_eq!(avgo.lots[0].cost_basis, dec!(800));
+        assert_eq!(avgo.lots[1].acquisition_date.date_naive(), NaiveDate::from_ymd_opt(2023, 1, 3).unwrap());
+        let cash_before = previous.cash_balances.get("USD").copied().unwrap_or_default();
+        assert_eq!(next.cash_balances.get("USD"), Some(&(cash_before + dec!(800) - dec!(2))));
+        assert_eq!(next.net_contribution, previous.net_contribution);
+    }
+
+    #[test]
+    fn test_spin_off_splits_cost_and_keeps_parent_lots() {
+        let calculator = create_calculator(Arc::new(MockFxService::new()), Arc::new(RwLock::new("USD".to_string())));
+        let previous = snapshot_with_two_lots(&calculator, "GE");
+        let date = NaiveDate::from_str("2023-01-04").unwrap();
+        // One GEHC for every three GE, carrying 10% of the cost basis.
+        let spin_off = create_corporate_action("ca_3", ActivityType::SpinOff, "GE", "GEHC", dec!(6), Some(dec!(0.1)), "2023-01-04");
+
+        let next = calculator.calculate_next_holdings(&previous, &[spin_off], date).unwrap();
+
+        let parent = next.positions.get("GE").unwrap();
+        assert_eq!(parent.quantity, dec!(20));
+        assert_eq!(parent.total_cost_basis, dec!(1980));
+        assert_eq!(parent.lots.len(), 2);
+        let child = next.positions.get("GEHC").unwrap();
+        assert_eq!(child.quantity, dec!(6));
+        assert_eq!(child.total_cost_basis, dec!(220));
+        assert_eq!(child.lots[0].acquisition_date, parent.lots[0].acquisition_date);
+        assert_eq!(next.cost_basis, dec!(2200));
+
+        // Without a source holding only the fee applies.
+        let mut orphan = create_corporate_action("ca_4", ActivityType::SpinOff, "MSFT", "GEHC", dec!(1), Some(dec!(0.1)), "2023-01-04");
+        orphan.fee = dec!(1);
+        let next = calculator.calculate_next_holdings(&previous, &[orphan], date).unwrap();
+        assert!(!next.positions.contains_key("GEHC"));
+        assert_eq!(
+            next.cash_balances.get("USD").copied().unwrap_or_default(),
+            previous.cash_balances.get("USD").copied().unwrap_or_default() - dec!(1)
+        );
+    }
 } 
\ No newline at end of file
@@ -1,11 +1,13 @@
 // src-core/src/portfolio/snapshot/mod.rs
 
+mod dirty_ranges;
 mod snapshot_repository;
 pub mod snapshot_service;
 pub mod holdings_calculator;
 mod positions_model;
 mod snapshot_model;
 
+pub use dirty_ranges::*;
 pub use snapshot_repository::*;
 pub use snapshot_service::*;
 pub use holdings_calculator::*;
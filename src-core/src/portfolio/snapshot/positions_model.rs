@@ -324,6 +324,61 @@ impl Position {
         ))
     }
 
+    /// Takes `cost_ratio` of every lot's cost basis out of the position for a corporate
+    /// action and returns it as lots with their original ids and acquisition dates.
+    /// With `keep_lots` the lots stay here carrying the remaining cost (a spin-off),
+    /// otherwise they are removed (a ticker change or merger).
+    pub fn carve_out_lots(&mut self, cost_ratio: Decimal, keep_lots: bool) -> Vec<Lot> {
+        let carved: Vec<Lot> = self
+            .lots
+            .iter()
+            .map(|lot| Lot {
+                cost_basis: lot.cost_basis * cost_ratio,
+                acquisition_price: lot.acquisition_price * cost_ratio,
+                acquisition_fees: lot.acquisition_fees * cost_ratio,
+                ..lot.clone()
+            })
+            .collect();
+        if keep_lots {
+            for (lot, moved) in self.lots.iter_mut().zip(carved.iter()) {
+                lot.cost_basis -= moved.cost_basis;
+                lot.acquisition_price -= moved.acquisition_price;
+                lot.acquisition_fees -= moved.acquisition_fees;
+            }
+        } else {
+            self.lots.clear();
+        }
+        self.recalculate_aggregates();
+        carved
+    }
+
+    /// Adds lots carved out of another position. Quantities are scaled by
+    /// `quantity_factor` (new shares per old share) and costs by `fx_rate` into this
+    /// position's currency; acquisition dates are kept for holding periods.
+    pub fn receive_lots(&mut self, lots: Vec<Lot>, quantity_factor: Decimal, fx_rate: Decimal) {
+        if !quantity_factor.is_sign_positive() || quantity_factor.is_zero() {
+            warn!(
+                "Ignoring {} lots for position {} with non-positive quantity factor {}",
+                lots.len(),
+                self.id,
+                quantity_factor
+            );
+            return;
+        }
+        for mut lot in lots {
+            lot.position_id = self.id.clone();
+            lot.quantity *= quantity_factor;
+            lot.cost_basis *= fx_rate;
+            lot.acquisition_fees *= fx_rate;
+            lot.acquisition_price = lot.acquisition_price * fx_rate / quantity_factor;
+            self.lots.push_back(lot);
+        }
+        let mut vec_lots: Vec<_> = self.lots.drain(..).collect();
+        vec_lots.sort_by_key(|lot| lot.acquisition_date);
+        self.lots = vec_lots.into();
+        self.recalculate_aggregates();
+    }
+
     /// Applies stock split.
     pub fn apply_split(&mut self, split_ratio: Decimal, activity_id: &str) -> Result<()> {
         if !split_ratio.is_sign_positive() {
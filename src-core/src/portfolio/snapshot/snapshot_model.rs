@@ -152,3 +152,13 @@ impl From<AccountStateSnapshot> for AccountStateSnapshotDB {
         }
     }
 }
+
+/// Replacement of one account's stored keyframes, applied as part of a batch by
+/// `SnapshotRepositoryTrait::overwrite_snapshot_batch`.
+#[derive(Debug, Clone)]
+pub struct AccountSnapshotWrite {
+    pub account_id: String,
+    /// Inclusive date range to clear before saving. `None` clears every keyframe of the account.
+    pub range: Option<(NaiveDate, NaiveDate)>,
+    pub snapshots: Vec<AccountStateSnapshot>,
+}
@@ -2,6 +2,7 @@ use crate::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
 use crate::errors::{Error, Result};
 use crate::portfolio::snapshot::AccountStateSnapshot;
 use crate::portfolio::snapshot::AccountStateSnapshotDB;
+use crate::portfolio::snapshot::AccountSnapshotWrite;
 use chrono::NaiveDate;
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
@@ -100,6 +101,9 @@ pub trait SnapshotRepositoryTrait: Send + Sync {
         account_id: &str,
         snapshots_to_save: &[AccountStateSnapshot],
     ) -> Result<()>;
+
+    /// Applies several account overwrites in a single writer transaction.
+    async fn overwrite_snapshot_batch(&self, writes: Vec<AccountSnapshotWrite>) -> Result<()>;
 }
 
 pub struct SnapshotRepository {
@@ -565,6 +569,48 @@ impl SnapshotRepository {
             })
             .await
     }
+
+    pub async fn overwrite_snapshot_batch(&self, writes: Vec<AccountSnapshotWrite>) -> Result<()> {
+        use crate::schema::holdings_snapshots::dsl::*;
+        if writes.is_empty() {
+            return Ok(());
+        }
+
+        self.writer
+            .exec(move |conn| {
+                for write in writes {
+                    match write.range {
+                        Some((start, end)) => {
+                            diesel::delete(
+                                holdings_snapshots
+                                    .filter(account_id.eq(&write.account_id))
+                                    .filter(snapshot_date.ge(start.format("%Y-%m-%d").to_string()))
+                                    .filter(snapshot_date.le(end.format("%Y-%m-%d").to_string())),
+                            )
+                            .execute(conn)?;
+                        }
+                        None => {
+                            diesel::delete(holdings_snapshots.filter(account_id.eq(&write.account_id)))
+                                .execute(conn)?;
+                        }
+                    }
+
+                    let db_models: Vec<AccountStateSnapshotDB> = write
+                        .snapshots
+                        .into_iter()
+                        .filter(|s| s.account_id == write.account_id)
+                        .map(AccountStateSnapshotDB::from)
+                        .collect();
+                    for chunk in db_models.chunks(1000) {
+                        diesel::replace_into(holdings_snapshots)
+                            .values(chunk)
+                            .execute(conn)?;
+                    }
+                }
+                Ok(())
+            })
+            .await
+    }
 }
 
 // Implement the trait methods for SnapshotRepository
@@ -678,4 +724,8 @@ impl SnapshotRepositoryTrait for SnapshotRepository {
         self.overwrite_all_snapshots_for_account(account_id, snapshots_to_save)
             .await
     }
+
+    async fn overwrite_snapshot_batch(&self, writes: Vec<AccountSnapshotWrite>) -> Result<()> {
+        self.overwrite_snapshot_batch(writes).await
+    }
 }
@@ -1,13 +1,15 @@
 use super::holdings_calculator::HoldingsCalculator;
 use super::snapshot_repository::SnapshotRepositoryTrait;
-use crate::accounts::{Account, AccountRepositoryTrait};
+use crate::accounts::{Account, AccountGroupMembership, AccountRepositoryTrait};
+use crate::profiles::ProfileMembership;
 use crate::activities::{Activity, ActivityRepositoryTrait};
 use crate::assets::AssetRepositoryTrait;
-use crate::constants::{DECIMAL_PRECISION, PORTFOLIO_TOTAL_ACCOUNT_ID};
+use crate::constants::{ACCOUNT_WRITE_BATCH_SIZE, DECIMAL_PRECISION, PORTFOLIO_TOTAL_ACCOUNT_ID};
 use crate::errors::{CalculatorError, Error, Result};
 use crate::fx::fx_traits::FxServiceTrait;
-use crate::portfolio::snapshot::{AccountStateSnapshot, Position};
+use crate::portfolio::snapshot::{AccountSnapshotWrite, AccountStateSnapshot, DirtyRanges, Position};
 use crate::utils::time_utils::get_days_between;
+use crate::utils::worker_pool;
 
 use async_trait::async_trait;
 use chrono::{Local, NaiveDate, Utc};
@@ -64,6 +66,37 @@ pub trait SnapshotServiceTrait: Send + Sync {
     /// It iterates through each day from the earliest activity to the present, generating a TOTAL snapshot
     /// by aggregating individual account snapshots for that day.
     async fn calculate_total_portfolio_snapshots(&self) -> Result<usize>;
+
+    /// Recalculates **holdings** snapshots only for the accounts in `dirty_ranges`.
+    /// Each account resumes from its nearest stored keyframe before the dirty date, and only
+    /// keyframes from that date onward are rewritten. Accounts marked for a full replay, or
+    /// without an earlier keyframe, are recalculated from their first activity.
+    async fn recalculate_dirty_holdings_snapshots(&self, dirty_ranges: &DirtyRanges) -> Result<usize>;
+
+    /// Like `calculate_total_portfolio_snapshots`, but only rebuilds TOTAL snapshots dated on or
+    /// after `start_date`. Passing `None` rebuilds the whole TOTAL history.
+    async fn calculate_total_portfolio_snapshots_from(
+        &self,
+        start_date: Option<NaiveDate>,
+    ) -> Result<usize>;
+
+    /// Calculates and stores aggregated snapshots for each account group, keyed by the group id,
+    /// the same way TOTAL is built but from the group's member accounts only. Snapshots dated
+    /// before `start_date` are kept; `None` rebuilds each group's whole history.
+    async fn calculate_group_snapshots_from(
+        &self,
+        memberships: &[AccountGroupMembership],
+        start_date: Option<NaiveDate>,
+    ) -> Result<usize>;
+
+    /// Calculates and stores each profile's aggregated snapshots, keyed by the profile id, from
+    /// the profile's active accounts and in the profile's own base currency. Follows the same
+    /// `start_date` rules as the group variant.
+    async fn calculate_profile_snapshots_from(
+        &self,
+        memberships: &[ProfileMembership],
+        start_date: Option<NaiveDate>,
+    ) -> Result<usize>;
 }
 
 // --- Service Implementation ---
@@ -106,10 +139,15 @@ impl SnapshotService {
 
     // Create a virtual account object for TOTAL
     fn create_total_virtual_account(&self) -> Account {
+        self.create_aggregate_virtual_account(PORTFOLIO_TOTAL_ACCOUNT_ID, "Total Portfolio")
+    }
+
+    // Virtual account for an aggregate (TOTAL, an account group or a profile), held in base currency
+    fn create_aggregate_virtual_account(&self, aggregate_id: &str, name: &str) -> Account {
         let now = Utc::now().naive_utc();
         Account {
-            id: PORTFOLIO_TOTAL_ACCOUNT_ID.to_string(),
-            name: "Total Portfolio".to_string(),
+            id: aggregate_id.to_string(),
+            name: name.to_string(),
             currency: self.base_currency.read().unwrap().clone(),
             is_active: true,                       // Correct field name
             account_type: "AGGREGATE".to_string(), // Indicate it's a special type
@@ -118,6 +156,8 @@ impl SnapshotService {
             created_at: now,
             updated_at: now,
             platform_id: None,
+            group_id: None,
+            profile_id: String::new(),
         }
     }
 
@@ -184,42 +224,205 @@ impl SnapshotService {
             return Ok(0);
         }
 
-        let (_final_holdings_states, keyframes_to_save) = self.calculate_daily_holdings_snapshots(
+        debug!(
+            "Calculating {} accounts from {} to {}.",
+            accounts_needing_calculation.len(),
+            calculation_min_date,
+            calculation_end_date
+        );
+        let keyframes_by_account = self.calculate_accounts_in_parallel(
             &accounts_needing_calculation,
             &activities_by_account_date,
             &start_keyframes,
             &effective_start_dates,
-            calculation_min_date,
             calculation_end_date,
         )?;
 
-        // Step 8: Persist keyframe snapshots using the new clear method
-        for acc_id in accounts_needing_calculation.keys() {
-            let frames: Vec<_> = keyframes_to_save
-                .iter()
-                .filter(|kf| kf.account_id == *acc_id)
-                .cloned()
-                .collect();
+        // Step 8: Persist keyframe snapshots in batches through the writer
+        let writes: Vec<AccountSnapshotWrite> = keyframes_by_account
+            .into_iter()
+            .map(|(acc_id, frames)| {
+                let range = if force_full_calculation {
+                    None // wipe whole account then insert
+                } else {
+                    Some((effective_start_dates[&acc_id], calculation_end_date))
+                };
+                AccountSnapshotWrite {
+                    account_id: acc_id,
+                    range,
+                    snapshots: frames,
+                }
+            })
+            .collect();
 
-            if force_full_calculation {
-                // wipe whole account then insert
-                self.snapshot_repository
-                    .overwrite_all_snapshots_for_account(acc_id, &frames)
-                    .await?;
-            } else {
-                let start = *effective_start_dates.get(acc_id).unwrap();
-                self.snapshot_repository
-                    .overwrite_snapshots_for_account_in_range(
-                        acc_id,
-                        start,
-                        calculation_end_date,
-                        &frames,
-                    )
-                    .await?;
+        self.persist_snapshot_writes(writes).await
+    }
+
+    // Runs the day-by-day calculation of each account on a bounded pool of worker
+    // threads. Accounts, including the virtual TOTAL, share no state at this stage,
+    // so the keyframes match those of a single combined pass.
+    fn calculate_accounts_in_parallel(
+        &self,
+        accounts_needing_calculation: &AccountsMap,
+        activities_by_account_date: &ActivitiesByAccount,
+        start_keyframes: &StartSnapshotsMap,
+        effective_start_dates: &StartDatesMap,
+        calculation_end_date: NaiveDate,
+    ) -> Result<Vec<(String, Vec<AccountStateSnapshot>)>> {
+        let jobs: Vec<(&String, &Account)> = accounts_needing_calculation.iter().collect();
+        let workers = worker_pool::worker_count(jobs.len());
+
+        worker_pool::run_bounded(&jobs, workers, |(acc_id, account)| {
+            let single_account: AccountsMap =
+                HashMap::from([((*acc_id).clone(), (*account).clone())]);
+            let start_keyframe: StartSnapshotsMap = start_keyframes
+                .get(*acc_id)
+                .map(|keyframe| HashMap::from([((*acc_id).clone(), keyframe.clone())]))
+                .unwrap_or_default();
+            let start_date = effective_start_dates[*acc_id];
+
+            self.calculate_daily_holdings_snapshots(
+                &single_account,
+                activities_by_account_date,
+                &start_keyframe,
+                effective_start_dates,
+                start_date,
+                calculation_end_date,
+            )
+            .map(|(_final_state, keyframes)| ((*acc_id).clone(), keyframes))
+        })
+        .into_iter()
+        .collect()
+    }
+
+    // Saves per-account keyframe overwrites, several accounts per writer transaction.
+    async fn persist_snapshot_writes(&self, writes: Vec<AccountSnapshotWrite>) -> Result<usize> {
+        let saved = writes.iter().map(|write| write.snapshots.len()).sum();
+        for batch in writes.chunks(ACCOUNT_WRITE_BATCH_SIZE) {
+            self.snapshot_repository
+                .overwrite_snapshot_batch(batch.to_vec())
+                .await?;
+        }
+        Ok(saved)
+    }
+
+    // --- Dirty-range recalculation (Internal Helper) ---
+    // Replays only the part of each dirty account's history that can have changed.
+    async fn recalculate_dirty_ranges_internal(&self, dirty_ranges: &DirtyRanges) -> Result<usize> {
+        let account_ids: Vec<String> = dirty_ranges
+            .account_ids()
+            .into_iter()
+            .filter(|id| id != PORTFOLIO_TOTAL_ACCOUNT_ID)
+            .collect();
+        if account_ids.is_empty() {
+            return Ok(0);
+        }
+        debug!("Recalculating dirty snapshot ranges: {:?}", dirty_ranges);
+
+        let (accounts_to_process, all_activities, min_activity_date, calculation_end_date) =
+            self.fetch_required_data(Some(&account_ids))?;
+        if accounts_to_process.is_empty() {
+            warn!("No active accounts found among dirty accounts {:?}.", account_ids);
+            return Ok(0);
+        }
+
+        let (activities_by_account_date, _) = self.preprocess_data(
+            &accounts_to_process,
+            &all_activities,
+            min_activity_date,
+            calculation_end_date,
+        )?;
+
+        let mut start_keyframes: StartSnapshotsMap = HashMap::new();
+        let mut effective_start_dates: StartDatesMap = HashMap::new();
+        let mut full_replay_accounts: HashSet<String> = HashSet::new();
+        let mut cleared_accounts: Vec<AccountSnapshotWrite> = Vec::new();
+
+        for (acc_id, account) in &accounts_to_process {
+            let first_activity_date = activities_by_account_date
+                .get(acc_id)
+                .and_then(|dates_map| dates_map.keys().next().cloned());
+
+            // Resume from the last keyframe strictly before the dirty date, if there is one.
+            let resume_point = match dirty_ranges.get(acc_id).flatten() {
+                Some(dirty_from) => match dirty_from.pred_opt() {
+                    Some(day_before) => self
+                        .snapshot_repository
+                        .get_latest_snapshot_before_date(acc_id, day_before)?
+                        .map(|keyframe| (dirty_from, keyframe)),
+                    None => None,
+                },
+                None => None,
+            };
+
+            match (resume_point, first_activity_date) {
+                (Some((dirty_from, keyframe)), _) => {
+                    if dirty_from > calculation_end_date {
+                        debug!(
+                            "Dirty date {} for account {} is after {}. Nothing to recalculate.",
+                            dirty_from, acc_id, calculation_end_date
+                        );
+                        continue;
+                    }
+                    debug!(
+                        "Resuming account {} from keyframe {} to recalculate from {}.",
+                        acc_id, keyframe.snapshot_date, dirty_from
+                    );
+                    start_keyframes.insert(acc_id.clone(), keyframe);
+                    effective_start_dates.insert(acc_id.clone(), dirty_from);
+                }
+                (None, Some(first_date)) => {
+                    debug!(
+                        "No keyframe before the dirty date for account {}. Replaying from {}.",
+                        acc_id, first_date
+                    );
+                    let day_before = first_date.pred_opt().unwrap_or(first_date);
+                    start_keyframes.insert(
+                        acc_id.clone(),
+                        Self::create_initial_snapshot(account, day_before),
+                    );
+                    effective_start_dates.insert(acc_id.clone(), first_date);
+                    full_replay_accounts.insert(acc_id.clone());
+                }
+                (None, None) => {
+                    debug!("Account {} has no activities left. Clearing its snapshots.", acc_id);
+                    cleared_accounts.push(AccountSnapshotWrite {
+                        account_id: acc_id.clone(),
+                        range: None,
+                        snapshots: Vec::new(),
+                    });
+                }
             }
         }
 
-        Ok(keyframes_to_save.len())
+        let accounts_needing_calculation: AccountsMap = accounts_to_process
+            .into_iter()
+            .filter(|(id, _)| effective_start_dates.contains_key(id))
+            .collect();
+
+        let keyframes_by_account = self.calculate_accounts_in_parallel(
+            &accounts_needing_calculation,
+            &activities_by_account_date,
+            &start_keyframes,
+            &effective_start_dates,
+            calculation_end_date,
+        )?;
+
+        let mut writes = cleared_accounts;
+        writes.extend(keyframes_by_account.into_iter().map(|(acc_id, frames)| {
+            let range = if full_replay_accounts.contains(&acc_id) {
+                None
+            } else {
+                Some((effective_start_dates[&acc_id], calculation_end_date))
+            };
+            AccountSnapshotWrite {
+                account_id: acc_id,
+                range,
+                snapshots: frames,
+            }
+        }));
+
+        self.persist_snapshot_writes(writes).await
     }
 
     // --- Step 1-3: Fetch required data ---
@@ -614,17 +817,20 @@ impl SnapshotService {
         Ok((current_holdings_snapshots, keyframes_to_save))
     }
 
-    // Renamed and refined from the previous aggregate_total_portfolio_snapshot
-    fn generate_total_portfolio_snapshot_for_date(
+    // Renamed and refined from the previous aggregate_total_portfolio_snapshot.
+    // Builds the snapshot of `aggregate_id` (TOTAL, an account group or a profile) from its
+    // members, in `base_portfolio_currency`.
+    fn generate_aggregate_snapshot_for_date(
         &self,
+        aggregate_id: &str,
         target_date: NaiveDate,
         // Map of Account ID -> AccountStateSnapshot for all *individual* accounts as of target_date
         individual_snapshots_on_date: &HashMap<String, AccountStateSnapshot>,
         base_portfolio_currency: &str,
     ) -> Result<AccountStateSnapshot> {
         debug!(
-            "Generating aggregated TOTAL portfolio snapshot for date: {}",
-            target_date
+            "Generating aggregated {} snapshot for date: {}",
+            aggregate_id, target_date
         );
 
         let mut aggregated_cash_balances: HashMap<String, Decimal> = HashMap::new();
@@ -648,15 +854,19 @@ impl SnapshotService {
             }
 
             // 2. Aggregate Net Contribution (already frozen FX)
-            overall_net_contribution_base_ccy += individual_snapshot.net_contribution_base;
+            overall_net_contribution_base_ccy += self.net_contribution_in(
+                individual_snapshot,
+                base_portfolio_currency,
+                target_date,
+            );
 
             // 3. Aggregate Positions & Calculate Overall Cost Basis for TOTAL (in base_portfolio_currency)
             for (_pos_asset_id, pos) in &individual_snapshot.positions {
                 let agg_pos = aggregated_positions
                     .entry(pos.asset_id.clone())
                     .or_insert_with(|| Position {
-                        id: format!("{}_{}", pos.asset_id, PORTFOLIO_TOTAL_ACCOUNT_ID),
-                        account_id: PORTFOLIO_TOTAL_ACCOUNT_ID.to_string(),
+                        id: format!("{}_{}", pos.asset_id, aggregate_id),
+                        account_id: aggregate_id.to_string(),
                         asset_id: pos.asset_id.clone(),
                         quantity: Decimal::ZERO,
                         average_cost: Decimal::ZERO,
@@ -687,8 +897,8 @@ impl SnapshotService {
                     }
                     Err(e) => {
                         warn!(
-                            "Failed to convert position cost basis for asset {} ({} {} to {}) for TOTAL on {}: {}. Adding unconverted.",
-                            pos.asset_id, pos.total_cost_basis, pos.currency, base_portfolio_currency, target_date, e
+                            "Failed to convert position cost basis for asset {} ({} {} to {}) for {} on {}: {}. Adding unconverted.",
+                            pos.asset_id, pos.total_cost_basis, pos.currency, base_portfolio_currency, aggregate_id, target_date, e
                         );
                         if pos.currency != base_portfolio_currency {
                             overall_cost_basis_base_ccy += pos.total_cost_basis;
@@ -714,12 +924,8 @@ impl SnapshotService {
         }
 
         Ok(AccountStateSnapshot {
-            id: format!(
-                "{}_{}",
-                PORTFOLIO_TOTAL_ACCOUNT_ID,
-                target_date.format("%Y-%m-%d")
-            ),
-            account_id: PORTFOLIO_TOTAL_ACCOUNT_ID.to_string(),
+            id: format!("{}_{}", aggregate_id, target_date.format("%Y-%m-%d")),
+            account_id: aggregate_id.to_string(),
             snapshot_date: target_date,
             currency: base_portfolio_currency.to_string(), // TOTAL snapshot is denominated in base currency
             cash_balances: aggregated_cash_balances, // Itemized by account currency holding the cash
@@ -733,6 +939,34 @@ impl SnapshotService {
 
     // --- Helpers ---
 
+    // Net contribution of a member snapshot in the aggregate's currency. The frozen amount is
+    // kept in the household base currency, so an aggregate in another currency takes the
+    // account's own figure when the currencies match and converts on `date` otherwise.
+    fn net_contribution_in(
+        &self,
+        snapshot: &AccountStateSnapshot,
+        currency: &str,
+        date: NaiveDate,
+    ) -> Decimal {
+        let household_currency = self.base_currency.read().unwrap().clone();
+        if currency == household_currency {
+            return snapshot.net_contribution_base;
+        }
+        if snapshot.currency == currency {
+            return snapshot.net_contribution;
+        }
+        self.holdings_calculator
+            .fx_service
+            .convert_currency_for_date(snapshot.net_contribution_base, &household_currency, currency, date)
+            .unwrap_or_else(|e| {
+                warn!(
+                    "Failed to convert net contribution of {} ({} to {}) on {}: {}. Adding unconverted.",
+                    snapshot.account_id, household_currency, currency, date, e
+                );
+                snapshot.net_contribution_base
+            })
+    }
+
     // create_initial_snapshot creates a snapshot with default values
     fn create_initial_snapshot(account: &Account, date: NaiveDate) -> AccountStateSnapshot {
         AccountStateSnapshot {
@@ -850,8 +1084,14 @@ impl SnapshotService {
     }
 
     // --- New method to calculate and store TOTAL portfolio snapshots ---
-    async fn calculate_total_portfolio_snapshots_impl(&self) -> Result<usize> {
-        debug!("Starting calculation of TOTAL portfolio snapshots (based on stored individual keyframes).");
+    async fn calculate_total_portfolio_snapshots_impl(
+        &self,
+        start_date: Option<NaiveDate>,
+    ) -> Result<usize> {
+        debug!(
+            "Starting calculation of TOTAL portfolio snapshots (based on stored individual keyframes) from {:?}.",
+            start_date
+        );
 
         let active_accounts = self.account_repository.list(Some(true), None)?;
         if active_accounts.is_empty() {
@@ -862,93 +1102,178 @@ impl SnapshotService {
             return Ok(0);
         }
 
-        let all_individual_keyframes = self
-            .snapshot_repository
-            .get_all_active_account_snapshots(None, None)?;
+        let keyframes_by_account = self.load_individual_keyframes()?;
+        let base_currency = self.base_currency.read().unwrap().clone();
+        self.save_aggregate_snapshots(
+            PORTFOLIO_TOTAL_ACCOUNT_ID,
+            &base_currency,
+            &keyframes_by_account,
+            None,
+            start_date,
+        )
+        .await
+    }
 
-        if all_individual_keyframes.is_empty() {
-            warn!("No keyframes found for any active individual accounts. Cannot generate TOTAL snapshots.");
-            self.snapshot_repository
-                .overwrite_all_snapshots_for_account(PORTFOLIO_TOTAL_ACCOUNT_ID, &[])
-                .await?;
-            info!("Cleaned any existing TOTAL snapshots as no new ones were generated.");
+    // Saves snapshots for each `(aggregate_id, member account ids, currency)`, as for TOTAL.
+    async fn calculate_aggregate_snapshots_impl(
+        &self,
+        aggregates: &[(&str, &[String], &str)],
+        start_date: Option<NaiveDate>,
+    ) -> Result<usize> {
+        if aggregates.is_empty() {
             return Ok(0);
         }
+        debug!(
+            "Starting calculation of {} aggregate snapshots from {:?}.",
+            aggregates.len(),
+            start_date
+        );
+
+        let keyframes_by_account = self.load_individual_keyframes()?;
+        let mut saved = 0;
+        for (aggregate_id, account_ids, currency) in aggregates {
+            let member_ids: HashSet<String> = account_ids.iter().cloned().collect();
+            saved += self
+                .save_aggregate_snapshots(
+                    aggregate_id,
+                    currency,
+                    &keyframes_by_account,
+                    Some(&member_ids),
+                    start_date,
+                )
+                .await?;
+        }
+        Ok(saved)
+    }
 
+    // Stored keyframes of every active individual account, by account and date.
+    fn load_individual_keyframes(
+        &self,
+    ) -> Result<HashMap<String, BTreeMap<NaiveDate, AccountStateSnapshot>>> {
         let mut keyframes_by_account: HashMap<String, BTreeMap<NaiveDate, AccountStateSnapshot>> =
             HashMap::new();
-        let mut all_snapshot_dates: HashSet<NaiveDate> = HashSet::new();
-
-        for keyframe in all_individual_keyframes {
+        for keyframe in self
+            .snapshot_repository
+            .get_all_active_account_snapshots(None, None)?
+        {
             if keyframe.account_id == PORTFOLIO_TOTAL_ACCOUNT_ID {
                 continue;
             }
-            all_snapshot_dates.insert(keyframe.snapshot_date);
             keyframes_by_account
                 .entry(keyframe.account_id.clone())
                 .or_default()
                 .insert(keyframe.snapshot_date, keyframe);
         }
+        Ok(keyframes_by_account)
+    }
+
+    // Aggregates the keyframes of the member accounts (all of them when `member_ids` is None)
+    // into snapshots of `aggregate_id` held in `currency`, one per date on which any member
+    // has a keyframe.
+    async fn save_aggregate_snapshots(
+        &self,
+        aggregate_id: &str,
+        currency: &str,
+        keyframes_by_account: &HashMap<String, BTreeMap<NaiveDate, AccountStateSnapshot>>,
+        member_ids: Option<&HashSet<String>>,
+        start_date: Option<NaiveDate>,
+    ) -> Result<usize> {
+        let member_keyframes: Vec<(&String, &BTreeMap<NaiveDate, AccountStateSnapshot>)> =
+            keyframes_by_account
+                .iter()
+                .filter(|(account_id, _)| member_ids.is_none_or(|ids| ids.contains(*account_id)))
+                .collect();
+        let all_snapshot_dates: HashSet<NaiveDate> = member_keyframes
+            .iter()
+            .flat_map(|(_, keyframes)| keyframes.keys().copied())
+            .collect();
 
         if all_snapshot_dates.is_empty() {
-            info!("No individual account keyframes found after processing. Cannot generate TOTAL snapshots.");
+            warn!(
+                "No keyframes found for the accounts of {}. Cannot generate its snapshots.",
+                aggregate_id
+            );
+            self.snapshot_repository
+                .overwrite_all_snapshots_for_account(aggregate_id, &[])
+                .await?;
+            info!("Cleaned any existing {} snapshots as no new ones were generated.", aggregate_id);
             return Ok(0);
         }
 
-        let base_portfolio_currency = self.base_currency.read().unwrap().clone();
-        let mut total_portfolio_snapshots_to_save: Vec<AccountStateSnapshot> = Vec::new();
+        let mut aggregate_snapshots_to_save: Vec<AccountStateSnapshot> = Vec::new();
 
-        let mut sorted_snapshot_dates: Vec<NaiveDate> = all_snapshot_dates.into_iter().collect();
+        // Earlier aggregate snapshots only depend on keyframes that did not change.
+        let mut sorted_snapshot_dates: Vec<NaiveDate> = all_snapshot_dates
+            .into_iter()
+            .filter(|date| start_date.is_none_or(|start| *date >= start))
+            .collect();
         sorted_snapshot_dates.sort();
 
         for target_date in sorted_snapshot_dates {
             let mut individual_snapshots_on_or_before_date: HashMap<String, AccountStateSnapshot> =
                 HashMap::new();
 
-            for (account_id, account_keyframes) in &keyframes_by_account {
+            for (account_id, account_keyframes) in &member_keyframes {
                 if let Some((_, latest_snapshot)) = account_keyframes.range(..=target_date).last() {
                     individual_snapshots_on_or_before_date
-                        .insert(account_id.clone(), latest_snapshot.clone());
+                        .insert((*account_id).clone(), latest_snapshot.clone());
                 }
             }
 
             if !individual_snapshots_on_or_before_date.is_empty() {
-                match self.generate_total_portfolio_snapshot_for_date(
+                match self.generate_aggregate_snapshot_for_date(
+                    aggregate_id,
                     target_date,
                     &individual_snapshots_on_or_before_date,
-                    &base_portfolio_currency,
+                    currency,
                 ) {
-                    Ok(total_snapshot) => {
-                        total_portfolio_snapshots_to_save.push(total_snapshot);
+                    Ok(aggregate_snapshot) => {
+                        aggregate_snapshots_to_save.push(aggregate_snapshot);
                     }
                     Err(e) => {
                         error!(
-                            "Failed to generate TOTAL portfolio snapshot for target_date {}: {}",
-                            target_date, e
+                            "Failed to generate {} snapshot for target_date {}: {}",
+                            aggregate_id, target_date, e
                         );
                     }
                 }
             }
         }
 
-        if !total_portfolio_snapshots_to_save.is_empty() {
+        if let Some(start) = start_date {
+            let end = Utc::now().naive_utc().date().max(start);
             info!(
-                "Saving {} new TOTAL portfolio snapshots.",
-                total_portfolio_snapshots_to_save.len()
+                "Saving {} {} snapshots for {} to {}.",
+                aggregate_snapshots_to_save.len(),
+                aggregate_id,
+                start,
+                end
             );
             self.snapshot_repository
-                .overwrite_all_snapshots_for_account(
-                    PORTFOLIO_TOTAL_ACCOUNT_ID,
-                    &total_portfolio_snapshots_to_save,
+                .overwrite_snapshots_for_account_in_range(
+                    aggregate_id,
+                    start,
+                    end,
+                    &aggregate_snapshots_to_save,
                 )
                 .await?;
-            Ok(total_portfolio_snapshots_to_save.len())
+            Ok(aggregate_snapshots_to_save.len())
+        } else if !aggregate_snapshots_to_save.is_empty() {
+            info!(
+                "Saving {} new {} snapshots.",
+                aggregate_snapshots_to_save.len(),
+                aggregate_id
+            );
+            self.snapshot_repository
+                .overwrite_all_snapshots_for_account(aggregate_id, &aggregate_snapshots_to_save)
+                .await?;
+            Ok(aggregate_snapshots_to_save.len())
         } else {
-            warn!("No TOTAL portfolio snapshots were generated to save. Deleting existing TOTAL snapshots anyway if any existed.");
+            warn!("No {} snapshots were generated to save. Deleting existing ones anyway if any existed.", aggregate_id);
             self.snapshot_repository
-                .overwrite_all_snapshots_for_account(PORTFOLIO_TOTAL_ACCOUNT_ID, &[])
+                .overwrite_all_snapshots_for_account(aggregate_id, &[])
                 .await?;
-            info!("Cleaned any existing TOTAL snapshots as no new ones were generated.");
+            info!("Cleaned any existing {} snapshots as no new ones were generated.", aggregate_id);
             Ok(0)
         }
     }
@@ -1049,19 +1374,21 @@ impl SnapshotServiceTrait for SnapshotService {
 
                 // Otherwise, history starts within our date range. We create a default "empty" state
                 // for the day before the loop, and the loop will then pick up the first keyframe correctly.
-                let account_details =
-                    self.account_repository.get_by_id(account_id).or_else(|_| {
-                        if account_id == PORTFOLIO_TOTAL_ACCOUNT_ID {
-                            Ok(self.create_total_virtual_account())
-                        } else {
-                            Err(Error::Repository(format!(
-                                "Account not found while reconstructing daily snapshots: {}",
-                                account_id
-                            )))
-                        }
-                    })?;
+                // Ids that are not accounts hold aggregates: TOTAL or an account group.
+                let account_details = match self.account_repository.get_by_id(account_id) {
+                    Ok(account) => account,
+                    Err(_) if account_id == PORTFOLIO_TOTAL_ACCOUNT_ID => {
+                        self.create_total_virtual_account()
+                    }
+                    Err(_) => self.create_aggregate_virtual_account(account_id, account_id),
+                };
                 let day_before_start = start_date.pred_opt().unwrap_or(start_date);
-                Self::create_initial_snapshot(&account_details, day_before_start)
+                let mut initial = Self::create_initial_snapshot(&account_details, day_before_start);
+                // Profile aggregates are held in the profile's currency, as their keyframes show.
+                if let Some(first_keyframe) = keyframes_map.values().next() {
+                    initial.currency = first_keyframe.currency.clone();
+                }
+                initial
             }
         };
 
@@ -1119,6 +1446,50 @@ impl SnapshotServiceTrait for SnapshotService {
     }
 
     async fn calculate_total_portfolio_snapshots(&self) -> Result<usize> {
-        self.calculate_total_portfolio_snapshots_impl().await
+        self.calculate_total_portfolio_snapshots_impl(None).await
+    }
+
+    async fn recalculate_dirty_holdings_snapshots(&self, dirty_ranges: &DirtyRanges) -> Result<usize> {
+        self.recalculate_dirty_ranges_internal(dirty_ranges).await
+    }
+
+    async fn calculate_total_portfolio_snapshots_from(
+        &self,
+        start_date: Option<NaiveDate>,
+    ) -> Result<usize> {
+        self.calculate_total_portfolio_snapshots_impl(start_date).await
+    }
+
+    async fn calculate_group_snapshots_from(
+        &self,
+        memberships: &[AccountGroupMembership],
+        start_date: Option<NaiveDate>,
+    ) -> Result<usize> {
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let aggregates: Vec<(&str, &[String], &str)> = memberships
+            .iter()
+            .map(|m| (m.group.id.as_str(), m.account_ids.as_slice(), base_currency.as_str()))
+            .collect();
+        self.calculate_aggregate_snapshots_impl(&aggregates, start_date)
+            .await
+    }
+
+    async fn calculate_profile_snapshots_from(
+        &self,
+        memberships: &[ProfileMembership],
+        start_date: Option<NaiveDate>,
+    ) -> Result<usize> {
+        let aggregates: Vec<(&str, &[String], &str)> = memberships
+            .iter()
+            .map(|m| {
+                (
+                    m.profile.id.as_str(),
+                    m.account_ids.as_slice(),
+                    m.profile.base_currency.as_str(),
+                )
+            })
+            .collect();
+        self.calculate_aggregate_snapshots_impl(&aggregates, start_date)
+            .await
     }
 }
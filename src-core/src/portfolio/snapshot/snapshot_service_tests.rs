@@ -7,21 +7,28 @@ mod tests {
     use std::collections::HashMap;
     use std::sync::{Arc, RwLock};
 
-    use crate::accounts::{Account, AccountRepositoryTrait, AccountUpdate, NewAccount};
+    use crate::accounts::{
+        Account, AccountGroup, AccountGroupMembership, AccountRepositoryTrait, AccountUpdate,
+        NewAccount,
+    };
     use crate::activities::{
-        activities_model::IncomeData as ActivityIncomeData, Activity, ActivityRepositoryTrait,
-        ActivitySearchResponse, ActivityUpdate, ImportMapping as ActivityImportMapping,
-        NewActivity, Sort as ActivitySort,
+        activities_model::{ActivitySavedViewDB, ForeignTaxData, IncomeData as ActivityIncomeData},
+        Activity, ActivityBulkRequest, ActivityBulkResult, ActivityRepositoryTrait, ActivitySavedView,
+        ActivitySearchFilters, ActivitySearchResponse, ActivityUpdate,
+        ImportMapping as ActivityImportMapping, ImportSession, NewActivity, Sort as ActivitySort,
     };
     use crate::assets::{Asset, AssetRepositoryTrait, NewAsset, UpdateAssetProfile};
     use crate::constants::{DECIMAL_PRECISION, PORTFOLIO_TOTAL_ACCOUNT_ID};
     use crate::errors::{Error, Result as AppResult};
-    use crate::fx::fx_model::{ExchangeRate, NewExchangeRate};
+    use crate::fx::fx_model::{
+        ExchangeRate, FxConversionExplanation, FxConversionPolicy, NewExchangeRate,
+    };
     use crate::fx::fx_traits::FxServiceTrait;
     use crate::portfolio::snapshot::{
-        snapshot_repository::SnapshotRepositoryTrait, AccountStateSnapshot, Position,
-        SnapshotService, SnapshotServiceTrait,
+        snapshot_repository::SnapshotRepositoryTrait, AccountSnapshotWrite, AccountStateSnapshot,
+        DirtyRanges, Position, SnapshotService, SnapshotServiceTrait,
     };
+    use crate::profiles::{Profile, ProfileMembership};
 
     #[derive(Clone, Debug)]
     struct MockFxService {
@@ -139,6 +146,43 @@ mod tests {
         ) -> AppResult<()> {
             Ok(())
         }
+
+        async fn import_reference_rates(
+            &self,
+            _provider_id: &str,
+            _content: &str,
+            _currencies: Option<Vec<String>>,
+        ) -> AppResult<usize> {
+            Ok(0)
+        }
+
+        async fn sync_reference_rates(
+            &self,
+            _provider_id: &str,
+            _start_date: NaiveDate,
+            _end_date: NaiveDate,
+            _currencies: Option<Vec<String>>,
+        ) -> AppResult<usize> {
+            Ok(0)
+        }
+
+        fn get_conversion_policy(&self) -> AppResult<FxConversionPolicy> {
+            Ok(FxConversionPolicy::default())
+        }
+
+        fn set_conversion_policy(&self, _policy: FxConversionPolicy) -> AppResult<()> {
+            Ok(())
+        }
+
+        fn explain_conversion_for_date(
+            &self,
+            _amount: Decimal,
+            _from_currency: &str,
+            _to_currency: &str,
+            _date: NaiveDate,
+        ) -> AppResult<FxConversionExplanation> {
+            unimplemented!()
+        }
     }
 
     #[derive(Clone, Debug)]
@@ -169,6 +213,8 @@ mod tests {
                 data_source: "MANUAL".to_string(),
                 sectors: Some("Technology".to_string()),
                 url: None,
+                dividend_yield: None,
+                expense_ratio: None,
                 created_at: chrono::Utc::now().naive_utc(),
                 updated_at: chrono::Utc::now().naive_utc(),
             });
@@ -191,6 +237,8 @@ mod tests {
                 data_source: "MANUAL".to_string(),
                 sectors: Some("Technology".to_string()),
                 url: None,
+                dividend_yield: None,
+                expense_ratio: None,
                 created_at: chrono::Utc::now().naive_utc(),
                 updated_at: chrono::Utc::now().naive_utc(),
             });
@@ -213,6 +261,18 @@ mod tests {
             unimplemented!("update_data_source not implemented for MockAssetRepository")
         }
 
+        async fn update_isin(&self, _asset_id: &str, _isin: String) -> AppResult<Asset> {
+            unimplemented!("update_isin not implemented for MockAssetRepository")
+        }
+
+        async fn update_expense_ratio(
+            &self,
+            _asset_id: &str,
+            _expense_ratio: Option<f64>,
+        ) -> AppResult<Asset> {
+            unimplemented!("update_expense_ratio not implemented for MockAssetRepository")
+        }
+
         fn get_by_id(&self, asset_id: &str) -> AppResult<Asset> {
             self.assets
                 .get(asset_id)
@@ -341,10 +401,8 @@ mod tests {
             &self,
             _page: i64,
             _page_size: i64,
-            _account_id_filter: Option<Vec<String>>,
-            _activity_type_filter: Option<Vec<String>>,
-            _asset_id_keyword: Option<String>,
-            _sort: Option<ActivitySort>,
+            _filters: ActivitySearchFilters,
+            _sort: Vec<ActivitySort>,
         ) -> AppResult<ActivitySearchResponse> {
             unimplemented!()
         }
@@ -360,6 +418,30 @@ mod tests {
         async fn create_activities(&self, _activities: Vec<NewActivity>) -> AppResult<usize> {
             unimplemented!()
         }
+        async fn bulk_edit_activities(
+            &self,
+            _request: ActivityBulkRequest,
+            _dry_run: bool,
+        ) -> AppResult<ActivityBulkResult> {
+            unimplemented!()
+        }
+        async fn create_import_session(
+            &self,
+            _session: ImportSession,
+            _activities: Vec<NewActivity>,
+            _merges: Vec<ActivityUpdate>,
+        ) -> AppResult<ImportSession> {
+            unimplemented!()
+        }
+        fn get_import_sessions(&self, _account_id: Option<&str>) -> AppResult<Vec<ImportSession>> {
+            unimplemented!()
+        }
+        fn get_activities_by_import_session(&self, _session_id: &str) -> AppResult<Vec<Activity>> {
+            unimplemented!()
+        }
+        async fn rollback_import_session(&self, _session_id: String) -> AppResult<Vec<Activity>> {
+            unimplemented!()
+        }
         fn get_first_activity_date(
             &self,
             _account_ids: Option<&[String]>,
@@ -375,12 +457,24 @@ mod tests {
         async fn save_import_mapping(&self, _mapping: &ActivityImportMapping) -> AppResult<()> {
             unimplemented!()
         }
+        fn get_saved_views(&self) -> AppResult<Vec<ActivitySavedView>> {
+            unimplemented!()
+        }
+        async fn save_view(&self, _view: ActivitySavedViewDB) -> AppResult<ActivitySavedView> {
+            unimplemented!()
+        }
+        async fn delete_saved_view(&self, _view_id: String) -> AppResult<()> {
+            unimplemented!()
+        }
         fn calculate_average_cost(&self, _account_id: &str, _asset_id: &str) -> AppResult<Decimal> {
             unimplemented!()
         }
         fn get_income_activities_data(&self) -> AppResult<Vec<ActivityIncomeData>> {
             unimplemented!()
         }
+        fn get_foreign_tax_data(&self, _year: i32) -> AppResult<Vec<ForeignTaxData>> {
+            unimplemented!()
+        }
         fn get_first_activity_date_overall(&self) -> AppResult<DateTime<Utc>> {
             unimplemented!()
         }
@@ -444,10 +538,8 @@ mod tests {
             &self,
             _page: i64,
             _size: i64,
-            _acc: Option<Vec<String>>,
-            _typ: Option<Vec<String>>,
-            _kw: Option<String>,
-            _sort: Option<ActivitySort>,
+            _filters: ActivitySearchFilters,
+            _sort: Vec<ActivitySort>,
         ) -> AppResult<ActivitySearchResponse> {
             unimplemented!()
         }
@@ -463,6 +555,30 @@ mod tests {
         async fn create_activities(&self, _a: Vec<NewActivity>) -> AppResult<usize> {
             unimplemented!()
         }
+        async fn bulk_edit_activities(
+            &self,
+            _request: ActivityBulkRequest,
+            _dry_run: bool,
+        ) -> AppResult<ActivityBulkResult> {
+            unimplemented!()
+        }
+        async fn create_import_session(
+            &self,
+            _session: ImportSession,
+            _activities: Vec<NewActivity>,
+            _merges: Vec<ActivityUpdate>,
+        ) -> AppResult<ImportSession> {
+            unimplemented!()
+        }
+        fn get_import_sessions(&self, _account_id: Option<&str>) -> AppResult<Vec<ImportSession>> {
+            unimplemented!()
+        }
+        fn get_activities_by_import_session(&self, _session_id: &str) -> AppResult<Vec<Activity>> {
+            unimplemented!()
+        }
+        async fn rollback_import_session(&self, _session_id: String) -> AppResult<Vec<Activity>> {
+            unimplemented!()
+        }
         fn get_first_activity_date(
             &self,
             _ids: Option<&[String]>,
@@ -475,12 +591,24 @@ mod tests {
         async fn save_import_mapping(&self, _m: &ActivityImportMapping) -> AppResult<()> {
             Ok(())
         }
+        fn get_saved_views(&self) -> AppResult<Vec<ActivitySavedView>> {
+            unimplemented!()
+        }
+        async fn save_view(&self, _view: ActivitySavedViewDB) -> AppResult<ActivitySavedView> {
+            unimplemented!()
+        }
+        async fn delete_saved_view(&self, _view_id: String) -> AppResult<()> {
+            unimplemented!()
+        }
         fn calculate_average_cost(&self, _acc: &str, _asset: &str) -> AppResult<Decimal> {
             unimplemented!()
         }
         fn get_income_activities_data(&self) -> AppResult<Vec<ActivityIncomeData>> {
             unimplemented!()
         }
+        fn get_foreign_tax_data(&self, _year: i32) -> AppResult<Vec<ForeignTaxData>> {
+            unimplemented!()
+        }
         fn get_first_activity_date_overall(&self) -> AppResult<DateTime<Utc>> {
             unimplemented!()
         }
@@ -711,6 +839,24 @@ mod tests {
             saved_store.extend(snapshots_to_save.iter().cloned());
             Ok(())
         }
+
+        async fn overwrite_snapshot_batch(&self, writes: Vec<AccountSnapshotWrite>) -> AppResult<()> {
+            let mut store = self.snapshots.write().unwrap();
+            let mut saved_store = self.saved_snapshots.write().unwrap();
+            saved_store.clear();
+            for write in writes {
+                let account_snaps = store.entry(write.account_id.clone()).or_default();
+                match write.range {
+                    Some((start, end)) => account_snaps
+                        .retain(|s| s.snapshot_date < start || s.snapshot_date > end),
+                    None => account_snaps.clear(),
+                }
+                account_snaps.extend(write.snapshots.iter().cloned());
+                account_snaps.sort_by_key(|s| s.snapshot_date);
+                saved_store.extend(write.snapshots);
+            }
+            Ok(())
+        }
     }
 
     fn create_test_account(id: &str, currency: &str, name: &str) -> Account {
@@ -725,6 +871,8 @@ mod tests {
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
             platform_id: None,
+            group_id: None,
+            profile_id: "default".to_string(),
         }
     }
 
@@ -940,6 +1088,144 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_calculate_group_snapshots_aggregates_member_accounts() {
+        let date1_str = "2023-01-01";
+        let date2_str = "2023-01-05";
+        let target_date2 = NaiveDate::parse_from_str(date2_str, "%Y-%m-%d").unwrap();
+
+        let mut mock_account_repo_instance = MockAccountRepository::new();
+        let acc1 = create_test_account("acc1", "CAD", "Spouse RRSP");
+        let acc2 = create_test_account("acc2", "CAD", "Joint Taxable");
+        let acc3 = create_test_account("acc3", "CAD", "Spouse TFSA");
+        for account in [&acc1, &acc2, &acc3] {
+            mock_account_repo_instance.add_account(account.clone());
+        }
+
+        let mock_snapshot_repo = MockSnapshotRepository::new();
+        let mut snapshots = Vec::new();
+        for (account, date, cash) in [
+            (&acc1, date1_str, dec!(1000)),
+            (&acc2, date1_str, dec!(5000)),
+            (&acc3, date2_str, dec!(200)),
+        ] {
+            let mut snapshot = create_blank_snapshot(&account.id, &account.currency, date);
+            snapshot.cash_balances.insert("CAD".to_string(), cash);
+            snapshot.net_contribution = cash;
+            snapshot.net_contribution_base = cash;
+            snapshots.push(snapshot);
+        }
+        mock_snapshot_repo.add_snapshots(snapshots);
+        let mock_snapshot_repo_arc = Arc::new(mock_snapshot_repo);
+
+        let snapshot_service = SnapshotService::new(
+            Arc::new(RwLock::new("CAD".to_string())),
+            Arc::new(mock_account_repo_instance),
+            Arc::new(MockActivityRepository::new()),
+            mock_snapshot_repo_arc.clone(),
+            Arc::new(MockAssetRepository::new()),
+            Arc::new(MockFxService::new()),
+        );
+
+        let membership = AccountGroupMembership {
+            group: AccountGroup {
+                id: "group-spouse".to_string(),
+                name: "Spouse".to_string(),
+                parent_id: Some("group-retirement".to_string()),
+                created_at: Utc::now().naive_utc(),
+                updated_at: Utc::now().naive_utc(),
+            },
+            path: "Retirement > Spouse".to_string(),
+            depth: 1,
+            account_ids: vec![acc1.id.clone(), acc3.id.clone()],
+        };
+        let saved = snapshot_service
+            .calculate_group_snapshots_from(&[membership], None)
+            .await
+            .unwrap();
+        assert_eq!(saved, 2);
+
+        let group_snapshots = snapshot_service
+            .get_holdings_keyframes("group-spouse", None, None)
+            .unwrap();
+        assert_eq!(group_snapshots.len(), 2);
+        let latest = group_snapshots
+            .iter()
+            .find(|s| s.snapshot_date == target_date2)
+            .unwrap();
+        assert_eq!(latest.account_id, "group-spouse");
+        assert_eq!(latest.id, "group-spouse_2023-01-05");
+        // The joint account is outside the group.
+        assert_eq!(latest.cash_balances.get("CAD"), Some(&dec!(1200)));
+        assert_eq!(latest.net_contribution, dec!(1200));
+    }
+
+    #[tokio::test]
+    async fn test_calculate_profile_snapshots_use_the_profile_currency() {
+        let date_str = "2023-01-05";
+        let target_date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d").unwrap();
+
+        let mut mock_account_repo_instance = MockAccountRepository::new();
+        let usd_account = create_test_account("acc-usd", "USD", "Brokerage");
+        let cad_account = create_test_account("acc-cad", "CAD", "Savings");
+        for account in [&usd_account, &cad_account] {
+            mock_account_repo_instance.add_account(account.clone());
+        }
+
+        let mock_snapshot_repo = MockSnapshotRepository::new();
+        let mut usd_snapshot = create_blank_snapshot(&usd_account.id, "USD", date_str);
+        usd_snapshot.cash_balances.insert("USD".to_string(), dec!(100));
+        usd_snapshot.net_contribution = dec!(100);
+        usd_snapshot.net_contribution_base = dec!(120); // Frozen at the deposit's CAD rate
+        let mut cad_snapshot = create_blank_snapshot(&cad_account.id, "CAD", date_str);
+        cad_snapshot.cash_balances.insert("CAD".to_string(), dec!(650));
+        cad_snapshot.net_contribution = dec!(650);
+        cad_snapshot.net_contribution_base = dec!(650);
+        mock_snapshot_repo.add_snapshots(vec![usd_snapshot, cad_snapshot]);
+        let mock_snapshot_repo_arc = Arc::new(mock_snapshot_repo);
+
+        let mut mock_fx_service_instance = MockFxService::new();
+        mock_fx_service_instance.add_bidirectional_rate("USD", "CAD", target_date, dec!(1.30));
+
+        // The household reports in CAD, the profile in USD.
+        let snapshot_service = SnapshotService::new(
+            Arc::new(RwLock::new("CAD".to_string())),
+            Arc::new(mock_account_repo_instance),
+            Arc::new(MockActivityRepository::new()),
+            mock_snapshot_repo_arc.clone(),
+            Arc::new(MockAssetRepository::new()),
+            Arc::new(mock_fx_service_instance),
+        );
+
+        let membership = ProfileMembership {
+            profile: Profile {
+                id: "alice".to_string(),
+                name: "Alice".to_string(),
+                base_currency: "USD".to_string(),
+                created_at: Utc::now().naive_utc(),
+                updated_at: Utc::now().naive_utc(),
+            },
+            account_ids: vec![usd_account.id.clone(), cad_account.id.clone()],
+        };
+        let saved = snapshot_service
+            .calculate_profile_snapshots_from(&[membership], None)
+            .await
+            .unwrap();
+        assert_eq!(saved, 1);
+
+        let profile_snapshots = snapshot_service
+            .get_holdings_keyframes("alice", None, None)
+            .unwrap();
+        assert_eq!(profile_snapshots.len(), 1);
+        let snapshot = &profile_snapshots[0];
+        assert_eq!(snapshot.currency, "USD");
+        // The USD account contributes its own figure; the CAD one is converted.
+        assert_eq!(
+            snapshot.net_contribution.round_dp(DECIMAL_PRECISION),
+            (dec!(100) + dec!(650) / dec!(1.30)).round_dp(DECIMAL_PRECISION)
+        );
+    }
+
     #[tokio::test]
     async fn test_calculate_holdings_snapshots_persists() {
         let base_currency_arc = Arc::new(RwLock::new("CAD".to_string()));
@@ -971,6 +1257,10 @@ mod tests {
             comment: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
         };
         let act2 = Activity {
             id: "act2".into(),
@@ -1031,6 +1321,10 @@ mod tests {
             comment: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
         };
         let mut dividend = deposit("div1".into(), d2, dec!(100000));
         dividend.activity_type = "DIVIDEND".into();
@@ -1075,4 +1369,158 @@ mod tests {
         assert_eq!(second_frame.net_contribution, dec!(15000), "Second keyframe should reflect both deposits, ignoring the dividend for net contribution calculation.");
         assert_eq!(second_frame.snapshot_date, d2);
     }
+
+    #[tokio::test]
+    async fn test_recalculate_dirty_holdings_snapshots_resumes_from_keyframe() {
+        let base = Arc::new(RwLock::new("CAD".to_string()));
+
+        let mut account_repo = MockAccountRepository::new();
+        let acc = create_test_account("acc1", "CAD", "Cash-Only");
+        account_repo.add_account(acc.clone());
+        let account_repo = Arc::new(account_repo);
+
+        let d1 = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        let d2 = NaiveDate::from_ymd_opt(2025, 2, 10).unwrap();
+        let d3 = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let deposit = |id: &str, date: NaiveDate, amt: Decimal| Activity {
+            id: id.to_string(),
+            account_id: acc.id.clone(),
+            asset_id: "$CASH-CAD".into(),
+            activity_type: "DEPOSIT".into(),
+            activity_date: DateTime::from_naive_utc_and_offset(
+                date.and_hms_opt(0, 0, 0).unwrap(),
+                Utc,
+            ),
+            quantity: Decimal::ZERO,
+            unit_price: Decimal::ZERO,
+            currency: "CAD".into(),
+            fee: Decimal::ZERO,
+            amount: Some(amt),
+            is_draft: false,
+            comment: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
+        };
+
+        let snaps = Arc::new(MockSnapshotRepository::new());
+        let fx = Arc::new(MockFxService::new());
+        let asset_repo = Arc::new(MockAssetRepository::new());
+
+        let original = SnapshotService::new(
+            base.clone(),
+            account_repo.clone(),
+            Arc::new(MockActivityRepositoryWithData::new(vec![
+                deposit("dep1", d1, dec!(1000)),
+                deposit("dep2", d2, dec!(2000)),
+                deposit("dep3", d3, dec!(3000)),
+            ])),
+            snaps.clone(),
+            asset_repo.clone(),
+            fx.clone(),
+        );
+        original.calculate_holdings_snapshots(None).await.unwrap();
+
+        // The third deposit is edited; only its date onward should be replayed.
+        let edited = deposit("dep3", d3, dec!(4000));
+        let updated = SnapshotService::new(
+            base,
+            account_repo,
+            Arc::new(MockActivityRepositoryWithData::new(vec![
+                deposit("dep1", d1, dec!(1000)),
+                deposit("dep2", d2, dec!(2000)),
+                edited.clone(),
+            ])),
+            snaps.clone(),
+            asset_repo,
+            fx,
+        );
+
+        let mut dirty = DirtyRanges::new();
+        dirty.mark_activity(&edited);
+        let mut older_edit = DirtyRanges::new();
+        older_edit.mark(&acc.id, NaiveDate::from_ymd_opt(2025, 4, 1).unwrap());
+        dirty.merge(&older_edit);
+        assert_eq!(dirty.earliest_date(), Some(d3));
+
+        let saved = updated
+            .recalculate_dirty_holdings_snapshots(&dirty)
+            .await
+            .unwrap();
+        assert_eq!(saved, 1, "Only the keyframe on the dirty date should be recalculated.");
+
+        let frames = snaps.get_saved_snapshots();
+        assert!(frames.iter().all(|kf| kf.snapshot_date >= d3));
+
+        let keyframes = updated.get_holdings_keyframes(&acc.id, None, None).unwrap();
+        let by_date: HashMap<NaiveDate, &AccountStateSnapshot> =
+            keyframes.iter().map(|kf| (kf.snapshot_date, kf)).collect();
+        assert_eq!(by_date[&d2].net_contribution, dec!(3000));
+        assert_eq!(by_date[&d3].net_contribution, dec!(7000));
+    }
+
+    #[tokio::test]
+    async fn test_calculate_holdings_snapshots_many_accounts_in_parallel() {
+        let base = Arc::new(RwLock::new("CAD".to_string()));
+        let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+
+        let mut account_repo = MockAccountRepository::new();
+        let mut activities = Vec::new();
+        for i in 1..=12 {
+            let acc = create_test_account(&format!("acc{}", i), "CAD", &format!("Account {}", i));
+            activities.push(Activity {
+                id: format!("dep{}", i),
+                account_id: acc.id.clone(),
+                asset_id: "$CASH-CAD".into(),
+                activity_type: "DEPOSIT".into(),
+                activity_date: DateTime::from_naive_utc_and_offset(
+                    date.and_hms_opt(0, 0, 0).unwrap(),
+                    Utc,
+                ),
+                quantity: Decimal::ZERO,
+                unit_price: Decimal::ZERO,
+                currency: "CAD".into(),
+                fee: Decimal::ZERO,
+                amount: Some(Decimal::from(i * 100)),
+                is_draft: false,
+                comment: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                import_session_id: None,
+                source_asset_id: None,
+                withholding_tax: None,
+                withholding_country: None,
+            });
+            account_repo.add_account(acc);
+        }
+
+        let snaps = Arc::new(MockSnapshotRepository::new());
+        let svc = SnapshotService::new(
+            base,
+            Arc::new(account_repo),
+            Arc::new(MockActivityRepositoryWithData::new(activities)),
+            snaps.clone(),
+            Arc::new(MockAssetRepository::new()),
+            Arc::new(MockFxService::new()),
+        );
+
+        let saved = svc.force_recalculate_holdings_snapshots(None).await.unwrap();
+        assert_eq!(saved, 12, "Each account should get exactly one keyframe.");
+
+        for i in 1..=12 {
+            let keyframes = svc
+                .get_holdings_keyframes(&format!("acc{}", i), None, None)
+                .unwrap();
+            assert_eq!(keyframes.len(), 1);
+            assert_eq!(keyframes[0].net_contribution, Decimal::from(i * 100));
+        }
+        svc.calculate_total_portfolio_snapshots().await.unwrap();
+        let total = svc
+            .get_holdings_keyframes(PORTFOLIO_TOTAL_ACCOUNT_ID, None, None)
+            .unwrap();
+        assert_eq!(total[0].net_contribution, dec!(7800));
+    }
 }
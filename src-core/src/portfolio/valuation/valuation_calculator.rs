@@ -101,6 +101,7 @@ pub fn calculate_valuation(
         cost_basis: cost_basis_acct_ccy,
         net_contribution: net_contribution_acct_ccy,
         calculated_at: Utc::now(),
+        accrued_interest: Decimal::ZERO,
     };
 
     Ok(metrics)
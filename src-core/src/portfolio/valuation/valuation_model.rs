@@ -20,6 +20,9 @@ pub struct DailyAccountValuation {
     pub cost_basis: Decimal,
     pub net_contribution: Decimal,
     pub calculated_at: DateTime<Utc>,
+    /// Interest earned on cash since the last payout, in the account currency.
+    /// Not part of `total_value` until the payout is booked as an INTEREST activity.
+    pub accrued_interest: Decimal,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Queryable, QueryableByName, Insertable)]
@@ -38,6 +41,7 @@ pub struct DailyAccountValuationDb {
     pub cost_basis: String,
     pub net_contribution: String,
     pub calculated_at: String,
+    pub accrued_interest: String,
 }
 
 impl From<DailyAccountValuation> for DailyAccountValuationDb {
@@ -61,6 +65,10 @@ impl From<DailyAccountValuation> for DailyAccountValuationDb {
                 .round_dp(DECIMAL_PRECISION)
                 .to_string(),
             calculated_at: value.calculated_at.to_rfc3339(),
+            accrued_interest: value
+                .accrued_interest
+                .round_dp(DECIMAL_PRECISION)
+                .to_string(),
         }
     }
 }
@@ -83,6 +91,17 @@ impl From<DailyAccountValuationDb> for DailyAccountValuation {
             calculated_at: DateTime::parse_from_rfc3339(&value.calculated_at)
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
+            accrued_interest: Decimal::from_str(&value.accrued_interest).unwrap_or_default(),
         }
     }
 }
+
+/// Replacement of one account's stored valuations, applied as part of a batch by
+/// `ValuationRepositoryTrait::write_valuation_batch`.
+#[derive(Debug, Clone)]
+pub struct AccountValuationWrite {
+    pub account_id: String,
+    /// Rows dated on or after this date are replaced. `None` replaces every row of the account.
+    pub replace_from: Option<NaiveDate>,
+    pub valuations: Vec<DailyAccountValuation>,
+}
@@ -11,7 +11,9 @@ use async_trait::async_trait;
 
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::Result;
-use crate::portfolio::valuation::valuation_model::{DailyAccountValuation, DailyAccountValuationDb};
+use crate::portfolio::valuation::valuation_model::{
+    AccountValuationWrite, DailyAccountValuation, DailyAccountValuationDb,
+};
 use crate::schema::daily_account_valuation::dsl::*;
 use crate::schema::daily_account_valuation;
 
@@ -27,6 +29,16 @@ pub trait ValuationRepositoryTrait: Send + Sync {
     ) -> Result<Vec<DailyAccountValuation>>;
     fn load_latest_valuation_date(&self, account_id: &str) -> Result<Option<NaiveDate>>;
     async fn delete_valuations_for_account(&self, account_id: &str) -> Result<()>;
+    /// Replaces all valuations of an account dated on or after `start_date` with
+    /// `valuation_records`, in a single transaction. Earlier rows are kept.
+    async fn overwrite_valuations_from(
+        &self,
+        account_id: &str,
+        start_date: NaiveDate,
+        valuation_records: &[DailyAccountValuation],
+    ) -> Result<()>;
+    /// Applies several account valuation replacements in a single writer transaction.
+    async fn write_valuation_batch(&self, writes: Vec<AccountValuationWrite>) -> Result<()>;
     fn get_latest_valuations(
         &self,
         input_account_ids: &[String],
@@ -137,6 +149,77 @@ impl ValuationRepositoryTrait for ValuationRepository {
         }).await
     }
 
+    async fn overwrite_valuations_from(
+        &self,
+        input_account_id: &str,
+        start_date: NaiveDate,
+        valuation_records: &[DailyAccountValuation],
+    ) -> Result<()> {
+        let account_id_owned = input_account_id.to_string();
+        let records_to_save: Vec<DailyAccountValuationDb> = valuation_records
+            .iter()
+            .filter(|record| record.account_id == input_account_id && record.valuation_date >= start_date)
+            .cloned()
+            .map(DailyAccountValuationDb::from)
+            .collect();
+
+        self.writer.exec(move |conn| {
+            diesel::delete(
+                daily_account_valuation::table
+                    .filter(account_id.eq(&account_id_owned))
+                    .filter(valuation_date.ge(start_date)),
+            )
+            .execute(conn)?;
+
+            for chunk in records_to_save.chunks(1000) {
+                diesel::replace_into(daily_account_valuation::table)
+                    .values(chunk)
+                    .execute(conn)?;
+            }
+            Ok(())
+        }).await
+    }
+
+    async fn write_valuation_batch(&self, writes: Vec<AccountValuationWrite>) -> Result<()> {
+        if writes.is_empty() {
+            return Ok(());
+        }
+
+        self.writer.exec(move |conn| {
+            for write in writes {
+                match write.replace_from {
+                    Some(start_date) => {
+                        diesel::delete(
+                            daily_account_valuation::table
+                                .filter(account_id.eq(&write.account_id))
+                                .filter(valuation_date.ge(start_date)),
+                        )
+                        .execute(conn)?;
+                    }
+                    None => {
+                        diesel::delete(
+                            daily_account_valuation::table.filter(account_id.eq(&write.account_id)),
+                        )
+                        .execute(conn)?;
+                    }
+                }
+
+                let records_to_save: Vec<DailyAccountValuationDb> = write
+                    .valuations
+                    .into_iter()
+                    .filter(|record| record.account_id == write.account_id)
+                    .map(DailyAccountValuationDb::from)
+                    .collect();
+                for chunk in records_to_save.chunks(1000) {
+                    diesel::replace_into(daily_account_valuation::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+            }
+            Ok(())
+        }).await
+    }
+
     fn get_latest_valuations(
         &self,
         input_account_ids: &[String],
@@ -159,7 +242,7 @@ impl ValuationRepositoryTrait for ValuationRepository {
                 SELECT \
                     id, account_id, valuation_date, account_currency, base_currency, \
                     fx_rate_to_base, cash_balance, investment_market_value, total_value, \
-                    cost_basis, net_contribution, calculated_at, \
+                    cost_basis, net_contribution, calculated_at, accrued_interest, \
                     ROW_NUMBER() OVER (PARTITION BY account_id ORDER BY valuation_date DESC) as rn \
                 FROM {} \
                 WHERE account_id IN ({}) \
@@ -167,7 +250,7 @@ impl ValuationRepositoryTrait for ValuationRepository {
             SELECT \
                 id, account_id, valuation_date, account_currency, base_currency, \
                 fx_rate_to_base, cash_balance, investment_market_value, total_value, \
-                cost_basis, net_contribution, calculated_at \
+                cost_basis, net_contribution, calculated_at, accrued_interest \
             FROM RankedValuations \
             WHERE rn = 1",
             "daily_account_valuation", // Use direct table name string
@@ -1,17 +1,22 @@
+use crate::constants::ACCOUNT_WRITE_BATCH_SIZE;
 use crate::errors::{CalculatorError, Error as CoreError, Result as CoreResult};
 use crate::fx::fx_traits::FxServiceTrait;
+use crate::interest::CashInterestServiceTrait;
 use crate::market_data::MarketDataServiceTrait;
 use crate::portfolio::snapshot::SnapshotServiceTrait;
 use crate::portfolio::valuation::valuation_calculator::calculate_valuation;
-use crate::portfolio::valuation::valuation_model::DailyAccountValuation;
+use crate::portfolio::valuation::valuation_model::{AccountValuationWrite, DailyAccountValuation};
 use crate::portfolio::valuation::ValuationRepositoryTrait;
-use crate::utils::time_utils;
+use crate::utils::{time_utils, worker_pool};
 use async_trait::async_trait;
 use chrono::NaiveDate;
 use log::{debug, error, warn};
-use std::collections::{HashMap, HashSet};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
 use super::DailyFxRateMap;
 
@@ -69,6 +74,44 @@ pub trait ValuationServiceTrait: Send + Sync {
         account_ids: &[String],
         date: NaiveDate,
     ) -> CoreResult<Vec<DailyAccountValuation>>;
+
+    /// Re-expresses the base-currency side of stored valuations in `currency`, using the
+    /// rate from each account currency on the valuation date. Stored valuations use the
+    /// household base currency, so a profile in view converts them for display.
+    fn convert_valuations(
+        &self,
+        valuations: Vec<DailyAccountValuation>,
+        currency: &str,
+    ) -> CoreResult<Vec<DailyAccountValuation>>;
+
+    /// Recalculates the valuation history of the account from `start_date` onward,
+    /// rewriting only the stored rows in that range.
+    ///
+    /// Args:
+    ///     account_id: The ID of the account ("TOTAL" for portfolio aggregate).
+    ///     start_date: First date (inclusive) whose valuation is recalculated.
+    async fn recalculate_valuation_history_from(
+        &self,
+        account_id: &str,
+        start_date: NaiveDate,
+    ) -> CoreResult<()>;
+
+    /// Calculates the valuation history of several accounts concurrently on a bounded
+    /// worker pool and writes the results in batched transactions.
+    ///
+    /// Args:
+    ///     requests: Account IDs paired with an optional start date. Accounts with a
+    ///         start date are recalculated from that date onward; the others follow
+    ///         `calculate_valuation_history`.
+    ///     recalculate_all: Whether accounts without a start date are fully recalculated.
+    ///
+    /// Returns:
+    ///     The accounts whose valuation could not be calculated, with the error for each.
+    async fn calculate_valuation_histories(
+        &self,
+        requests: &[(String, Option<NaiveDate>)],
+        recalculate_all: bool,
+    ) -> CoreResult<Vec<(String, CoreError)>>;
 }
 
 #[derive(Clone)]
@@ -78,6 +121,7 @@ pub struct ValuationService {
     snapshot_service: Arc<dyn SnapshotServiceTrait>,
     market_data_service: Arc<dyn MarketDataServiceTrait>,
     fx_service: Arc<dyn FxServiceTrait>,
+    interest_service: Arc<dyn CashInterestServiceTrait>,
 }
 
 impl ValuationService {
@@ -87,6 +131,7 @@ impl ValuationService {
         snapshot_service: Arc<dyn SnapshotServiceTrait>,
         market_data_service: Arc<dyn MarketDataServiceTrait>,
         fx_service: Arc<dyn FxServiceTrait>,
+        interest_service: Arc<dyn CashInterestServiceTrait>,
     ) -> Self {
         Self {
             base_currency,
@@ -94,6 +139,7 @@ impl ValuationService {
             market_data_service,
             fx_service,
             valuation_repository,
+            interest_service,
         }
     }
 
@@ -135,37 +181,15 @@ impl ValuationService {
 
         Ok(fx_rates_by_date)
     }
-}
 
-#[async_trait]
-impl ValuationServiceTrait for ValuationService {
-    async fn calculate_valuation_history(
+    /// Values the daily holdings snapshots of an account from `calculation_start_date`
+    /// (or from its first snapshot) up to the latest snapshot, skipping days with
+    /// missing quotes or FX rates.
+    async fn compute_valuations(
         &self,
         account_id: &str,
-        recalculate_all: bool,
-    ) -> CoreResult<()> {
-        let total_start_time = Instant::now();
-        debug!(
-            "Starting valuation data update/recalculation for account '{}', recalculate_all: {}",
-            account_id, recalculate_all
-        );
-
-        let mut calculation_start_date: Option<NaiveDate> = None;
-
-        if recalculate_all {
-            self.valuation_repository
-                .delete_valuations_for_account(account_id)
-                .await?;
-        } else {
-            let last_saved_date_opt = self
-                .valuation_repository
-                .load_latest_valuation_date(account_id)?;
-
-            if let Some(last_saved) = last_saved_date_opt {
-                calculation_start_date = Some(last_saved);
-            }
-        }
-
+        calculation_start_date: Option<NaiveDate>,
+    ) -> CoreResult<Vec<DailyAccountValuation>> {
         let snapshots_to_process = self
             .snapshot_service
             .get_daily_holdings_snapshots(account_id, calculation_start_date, None)
@@ -177,7 +201,7 @@ impl ValuationServiceTrait for ValuationService {
             })?;
 
         if snapshots_to_process.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         let actual_calculation_start_date = snapshots_to_process.first().unwrap().snapshot_date;
@@ -231,7 +255,7 @@ impl ValuationServiceTrait for ValuationService {
             map
         };
 
-        let newly_calculated_valuations: Vec<DailyAccountValuation> = snapshots_to_process
+        let mut valuations: Vec<DailyAccountValuation> = snapshots_to_process
             .into_iter()
             .filter_map(|holdings_snapshot| {
                 let current_date = holdings_snapshot.snapshot_date;
@@ -301,12 +325,76 @@ impl ValuationServiceTrait for ValuationService {
             })
             .collect();
 
-        if !newly_calculated_valuations.is_empty() {
-            self.valuation_repository
-                .save_valuations(&newly_calculated_valuations)
-                .await?;
+        if let Some(account_curr) = valuations.first().map(|v| v.account_currency.clone()) {
+            let accrued_by_date = self
+                .interest_service
+                .get_accrued_interest(
+                    account_id,
+                    &account_curr,
+                    actual_calculation_start_date,
+                    calculation_end_date,
+                )
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "Failed to accrue cash interest for account {}: {}. Valuations show none.",
+                        account_id, e
+                    );
+                    BTreeMap::new()
+                });
+            for valuation in &mut valuations {
+                if let Some(accrued) = accrued_by_date.get(&valuation.valuation_date) {
+                    valuation.accrued_interest = *accrued;
+                }
+            }
         }
 
+        Ok(valuations)
+    }
+
+    /// Computes the valuations of an account and describes how they replace the stored rows.
+    async fn prepare_valuation_write(
+        &self,
+        account_id: &str,
+        start_date: Option<NaiveDate>,
+        recalculate_all: bool,
+    ) -> CoreResult<AccountValuationWrite> {
+        let replace_from = match start_date {
+            Some(date) => Some(date),
+            None if recalculate_all => None,
+            None => self
+                .valuation_repository
+                .load_latest_valuation_date(account_id)?,
+        };
+        let valuations = self.compute_valuations(account_id, replace_from).await?;
+
+        Ok(AccountValuationWrite {
+            account_id: account_id.to_string(),
+            replace_from,
+            valuations,
+        })
+    }
+}
+
+#[async_trait]
+impl ValuationServiceTrait for ValuationService {
+    async fn calculate_valuation_history(
+        &self,
+        account_id: &str,
+        recalculate_all: bool,
+    ) -> CoreResult<()> {
+        let total_start_time = Instant::now();
+        debug!(
+            "Starting valuation data update/recalculation for account '{}', recalculate_all: {}",
+            account_id, recalculate_all
+        );
+
+        let write = self
+            .prepare_valuation_write(account_id, None, recalculate_all)
+            .await?;
+        self.valuation_repository
+            .write_valuation_batch(vec![write])
+            .await?;
+
         let total_duration = total_start_time.elapsed();
         debug!(
             "Successfully updated/recalculated valuation data for account '{}' in {:?}",
@@ -353,4 +441,110 @@ impl ValuationServiceTrait for ValuationService {
         self.valuation_repository
             .get_valuations_on_date(account_ids, date)
     }
-}
\ No newline at end of file
+
+    fn convert_valuations(
+        &self,
+        valuations: Vec<DailyAccountValuation>,
+        currency: &str,
+    ) -> CoreResult<Vec<DailyAccountValuation>> {
+        valuations
+            .into_iter()
+            .map(|mut valuation| {
+                if valuation.base_currency != currency {
+                    valuation.fx_rate_to_base = if valuation.account_currency == currency {
+                        Decimal::ONE
+                    } else {
+                        self.fx_service.get_exchange_rate_for_date(
+                            &valuation.account_currency,
+                            currency,
+                            valuation.valuation_date,
+                        )?
+                    };
+                    valuation.base_currency = currency.to_string();
+                }
+                Ok(valuation)
+            })
+            .collect()
+    }
+
+    async fn recalculate_valuation_history_from(
+        &self,
+        account_id: &str,
+        start_date: NaiveDate,
+    ) -> CoreResult<()> {
+        let start_time = Instant::now();
+        debug!(
+            "Recalculating valuation data for account '{}' from {}",
+            account_id, start_date
+        );
+
+        let valuations = self
+            .compute_valuations(account_id, Some(start_date))
+            .await?;
+        self.valuation_repository
+            .overwrite_valuations_from(account_id, start_date, &valuations)
+            .await?;
+
+        debug!(
+            "Recalculated {} valuation records for account '{}' in {:?}",
+            valuations.len(),
+            account_id,
+            start_time.elapsed()
+        );
+        Ok(())
+    }
+
+    async fn calculate_valuation_histories(
+        &self,
+        requests: &[(String, Option<NaiveDate>)],
+        recalculate_all: bool,
+    ) -> CoreResult<Vec<(String, CoreError)>> {
+        let start_time = Instant::now();
+        let permits = Arc::new(Semaphore::new(worker_pool::worker_count(requests.len())));
+        let mut tasks = JoinSet::new();
+
+        for (account_id, start_date) in requests.iter().cloned() {
+            let service = self.clone();
+            let permits = permits.clone();
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let result = service
+                    .prepare_valuation_write(&account_id, start_date, recalculate_all)
+                    .await;
+                (account_id, result)
+            });
+        }
+
+        let mut writes = Vec::with_capacity(requests.len());
+        let mut failures = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((_, Ok(write))) => writes.push(write),
+                Ok((account_id, Err(e))) => {
+                    error!("Failed to calculate valuation history for account '{}': {}", account_id, e);
+                    failures.push((account_id, e));
+                }
+                Err(e) => {
+                    return Err(CoreError::Unexpected(format!(
+                        "Valuation worker failed: {}",
+                        e
+                    )));
+                }
+            }
+        }
+
+        for batch in writes.chunks(ACCOUNT_WRITE_BATCH_SIZE) {
+            self.valuation_repository
+                .write_valuation_batch(batch.to_vec())
+                .await?;
+        }
+
+        debug!(
+            "Calculated valuation history for {} accounts ({} failed) in {:?}",
+            writes.len(),
+            failures.len(),
+            start_time.elapsed()
+        );
+        Ok(failures)
+    }
+}
@@ -0,0 +1,14 @@
+// Module declarations
+pub(crate) mod profiles_model;
+pub(crate) mod profiles_repository;
+pub(crate) mod profiles_service;
+pub(crate) mod profiles_traits;
+
+// Re-export the public interface
+pub use profiles_model::{
+    profile_in_scope, profile_memberships, scoped_total_account_id, NewProfile, Profile, ProfileDB,
+    ProfileMembership, ProfileUpdate, ACTIVE_PROFILE_SETTING_KEY,
+};
+pub use profiles_repository::ProfileRepository;
+pub use profiles_service::ProfileService;
+pub use profiles_traits::{ProfileRepositoryTrait, ProfileServiceTrait};
@@ -0,0 +1,209 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use crate::accounts::Account;
+use crate::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
+use crate::{errors::ValidationError, Error, Result};
+
+/// App setting holding the id of the active profile; empty for the household view.
+pub const ACTIVE_PROFILE_SETTING_KEY: &str = "active_profile_id";
+
+/// A separate portfolio within the database, e.g. one per household member.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    /// Currency the profile's holdings, valuations and reports are shown in.
+    pub base_currency: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Input model for creating a new profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    pub base_currency: String,
+}
+
+impl NewProfile {
+    pub fn validate(&self) -> Result<()> {
+        validate_profile(&self.name, &self.base_currency)
+    }
+}
+
+/// Input model for renaming a profile or changing its base currency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileUpdate {
+    pub id: String,
+    pub name: String,
+    pub base_currency: String,
+}
+
+impl ProfileUpdate {
+    pub fn validate(&self) -> Result<()> {
+        validate_profile(&self.name, &self.base_currency)
+    }
+}
+
+fn validate_profile(name: &str, base_currency: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(Error::Validation(ValidationError::InvalidInput(
+            "Profile name cannot be empty".to_string(),
+        )));
+    }
+    if base_currency.trim().len() != 3 {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Base currency must be a 3-letter code, got '{}'",
+            base_currency
+        ))));
+    }
+    Ok(())
+}
+
+/// Database model for profiles
+#[derive(
+    Queryable,
+    Identifiable,
+    Insertable,
+    AsChangeset,
+    Selectable,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+)]
+#[diesel(table_name = crate::schema::profiles)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ProfileDB {
+    pub id: String,
+    pub name: String,
+    pub base_currency: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<ProfileDB> for Profile {
+    fn from(db: ProfileDB) -> Self {
+        Self {
+            id: db.id,
+            name: db.name,
+            base_currency: db.base_currency,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        }
+    }
+}
+
+/// A profile with the active accounts whose snapshots make up its aggregate,
+/// stored under the profile id the way `TOTAL` covers every account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileMembership {
+    pub profile: Profile,
+    pub account_ids: Vec<String>,
+}
+
+/// Every profile with its active accounts.
+pub fn profile_memberships(profiles: &[Profile], accounts: &[Account]) -> Vec<ProfileMembership> {
+    profiles
+        .iter()
+        .map(|profile| ProfileMembership {
+            profile: profile.clone(),
+            account_ids: accounts
+                .iter()
+                .filter(|a| a.is_active && a.profile_id == profile.id)
+                .map(|a| a.id.clone())
+                .collect(),
+        })
+        .collect()
+}
+
+/// Whether data belonging to `profile_id` is visible. Everything is visible in
+/// the household view, when no profile is active.
+pub fn profile_in_scope(active_profile_id: &RwLock<Option<String>>, profile_id: &str) -> bool {
+    active_profile_id
+        .read()
+        .unwrap()
+        .as_deref()
+        .is_none_or(|active| active == profile_id)
+}
+
+/// Id whose snapshots and valuations stand for the whole portfolio in view: the
+/// active profile's aggregate, or `TOTAL` across all profiles.
+pub fn scoped_total_account_id(active_profile_id: &RwLock<Option<String>>) -> String {
+    active_profile_id
+        .read()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(|| PORTFOLIO_TOTAL_ACCOUNT_ID.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: &str) -> Profile {
+        Profile {
+            id: id.to_string(),
+            name: id.to_string(),
+            base_currency: "CAD".to_string(),
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    fn account(id: &str, profile_id: &str, is_active: bool) -> Account {
+        Account {
+            id: id.to_string(),
+            profile_id: profile_id.to_string(),
+            is_active,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_memberships_list_active_accounts_of_each_profile() {
+        let accounts = vec![
+            account("a1", "alice", true),
+            account("a2", "bob", true),
+            account("a3", "alice", false),
+            account("a4", "alice", true),
+        ];
+        let memberships = profile_memberships(&[profile("alice"), profile("bob")], &accounts);
+        assert_eq!(memberships[0].account_ids, vec!["a1", "a4"]);
+        assert_eq!(memberships[1].account_ids, vec!["a2"]);
+    }
+
+    #[test]
+    fn test_household_view_sees_every_profile() {
+        let active = RwLock::new(None);
+        assert!(profile_in_scope(&active, "alice"));
+        assert_eq!(scoped_total_account_id(&active), PORTFOLIO_TOTAL_ACCOUNT_ID);
+
+        *active.write().unwrap() = Some("alice".to_string());
+        assert!(profile_in_scope(&active, "alice"));
+        assert!(!profile_in_scope(&active, "bob"));
+        assert_eq!(scoped_total_account_id(&active), "alice");
+    }
+
+    #[test]
+    fn test_profiles_need_a_name_and_currency_code() {
+        let new_profile = |name: &str, currency: &str| NewProfile {
+            id: None,
+            name: name.to_string(),
+            base_currency: currency.to_string(),
+        };
+        assert!(new_profile("Alice", "CAD").validate().is_ok());
+        assert!(new_profile(" ", "CAD").validate().is_err());
+        assert!(new_profile("Alice", "").validate().is_err());
+    }
+}
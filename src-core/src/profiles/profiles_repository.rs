@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{self, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::constants::DEFAULT_PROFILE_ID;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::{Result, ValidationError};
+use crate::schema::{
+    accounts, contribution_limits, daily_account_valuation, goals, holdings_snapshots, profiles,
+};
+use crate::Error;
+
+use super::profiles_model::{NewProfile, Profile, ProfileDB, ProfileUpdate};
+use super::profiles_traits::ProfileRepositoryTrait;
+
+/// Repository for managing profiles in the database
+pub struct ProfileRepository {
+    pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl ProfileRepository {
+    pub fn new(
+        pool: Arc<Pool<r2d2::ConnectionManager<SqliteConnection>>>,
+        writer: WriteHandle,
+    ) -> Self {
+        Self { pool, writer }
+    }
+}
+
+/// Fails unless a profile with `profile_id` exists.
+pub(crate) fn ensure_profile_exists(conn: &mut SqliteConnection, profile_id: &str) -> Result<()> {
+    let found = profiles::table
+        .find(profile_id)
+        .select(profiles::id)
+        .first::<String>(conn)
+        .optional()?;
+    match found {
+        Some(_) => Ok(()),
+        None => Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Profile {} does not exist",
+            profile_id
+        )))),
+    }
+}
+
+#[async_trait]
+impl ProfileRepositoryTrait for ProfileRepository {
+    fn list(&self) -> Result<Vec<Profile>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(profiles::table
+            .select(ProfileDB::as_select())
+            .order((profiles::created_at.asc(), profiles::name.asc()))
+            .load::<ProfileDB>(&mut conn)?
+            .into_iter()
+            .map(Profile::from)
+            .collect())
+    }
+
+    fn get_by_id(&self, profile_id: &str) -> Result<Profile> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(profiles::table
+            .find(profile_id)
+            .select(ProfileDB::as_select())
+            .first::<ProfileDB>(&mut conn)?
+            .into())
+    }
+
+    async fn create(&self, new_profile: NewProfile) -> Result<Profile> {
+        new_profile.validate()?;
+        self.writer
+            .exec(move |conn| {
+                let now = chrono::Utc::now().naive_utc();
+                let profile_db = ProfileDB {
+                    id: new_profile
+                        .id
+                        .filter(|id| !id.trim().is_empty())
+                        .unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    name: new_profile.name.trim().to_string(),
+                    base_currency: new_profile.base_currency.trim().to_uppercase(),
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(profiles::table)
+                    .values(&profile_db)
+                    .execute(conn)?;
+                Ok(profile_db.into())
+            })
+            .await
+    }
+
+    async fn update(&self, profile_update: ProfileUpdate) -> Result<Profile> {
+        profile_update.validate()?;
+        self.writer
+            .exec(move |conn| {
+                let existing = profiles::table
+                    .find(&profile_update.id)
+                    .select(ProfileDB::as_select())
+                    .first::<ProfileDB>(conn)?;
+                let profile_db = ProfileDB {
+                    id: existing.id,
+                    name: profile_update.name.trim().to_string(),
+                    base_currency: profile_update.base_currency.trim().to_uppercase(),
+                    created_at: existing.created_at,
+                    updated_at: chrono::Utc::now().naive_utc(),
+                };
+                diesel::update(profiles::table.find(&profile_db.id))
+                    .set(&profile_db)
+                    .execute(conn)?;
+                Ok(profile_db.into())
+            })
+            .await
+    }
+
+    async fn delete(&self, profile_id: &str) -> Result<usize> {
+        if profile_id == DEFAULT_PROFILE_ID {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The default profile cannot be deleted".to_string(),
+            )));
+        }
+        let profile_id = profile_id.to_string();
+        self.writer
+            .exec(move |conn| {
+                diesel::update(accounts::table.filter(accounts::profile_id.eq(&profile_id)))
+                    .set(accounts::profile_id.eq(DEFAULT_PROFILE_ID))
+                    .execute(conn)?;
+                diesel::update(goals::table.filter(goals::profile_id.eq(&profile_id)))
+                    .set(goals::profile_id.eq(DEFAULT_PROFILE_ID))
+                    .execute(conn)?;
+                diesel::update(
+                    contribution_limits::table
+                        .filter(contribution_limits::profile_id.eq(&profile_id)),
+                )
+                .set(contribution_limits::profile_id.eq(DEFAULT_PROFILE_ID))
+                .execute(conn)?;
+                diesel::delete(
+                    holdings_snapshots::table
+                        .filter(holdings_snapshots::account_id.eq(&profile_id)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    daily_account_valuation::table
+                        .filter(daily_account_valuation::account_id.eq(&profile_id)),
+                )
+                .execute(conn)?;
+                Ok(diesel::delete(profiles::table.find(&profile_id)).execute(conn)?)
+            })
+            .await
+    }
+}
@@ -0,0 +1,160 @@
+use log::{debug, error, warn};
+use std::sync::{Arc, RwLock};
+
+use super::profiles_model::{
+    profile_memberships, NewProfile, Profile, ProfileMembership, ProfileUpdate,
+    ACTIVE_PROFILE_SETTING_KEY,
+};
+use super::profiles_traits::{ProfileRepositoryTrait, ProfileServiceTrait};
+use crate::accounts::AccountRepositoryTrait;
+use crate::errors::{DatabaseError, Error, Result};
+use crate::fx::fx_traits::FxServiceTrait;
+use crate::settings::SettingsRepositoryTrait;
+
+/// Service for managing profiles and which one is in view
+pub struct ProfileService {
+    repository: Arc<dyn ProfileRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    settings_repository: Arc<dyn SettingsRepositoryTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    active_profile_id: Arc<RwLock<Option<String>>>,
+}
+
+impl ProfileService {
+    pub fn new(
+        repository: Arc<dyn ProfileRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        settings_repository: Arc<dyn SettingsRepositoryTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        active_profile_id: Arc<RwLock<Option<String>>>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            settings_repository,
+            fx_service,
+            active_profile_id,
+        }
+    }
+
+    /// Restores the profile that was in view when the app last ran. A profile
+    /// deleted since then falls back to the household view.
+    pub fn initialize(&self) -> Result<()> {
+        let stored = match self
+            .settings_repository
+            .get_setting(ACTIVE_PROFILE_SETTING_KEY)
+        {
+            Ok(value) => Some(value).filter(|id| !id.is_empty()),
+            Err(Error::Database(DatabaseError::QueryFailed(diesel::result::Error::NotFound))) => {
+                None
+            }
+            Err(e) => return Err(e),
+        };
+        let active = match stored {
+            Some(profile_id) if self.repository.get_by_id(&profile_id).is_err() => {
+                warn!(
+                    "Active profile {} no longer exists, showing the household view",
+                    profile_id
+                );
+                None
+            }
+            stored => stored,
+        };
+        *self.active_profile_id.write().unwrap() = active;
+        Ok(())
+    }
+
+    // Every account currency needs a rate into the currency the profile reports in, and
+    // the profile's valuations need one into the household base currency.
+    async fn register_currency_pairs(&self, base_currency: &str) -> Result<()> {
+        let mut currencies = self
+            .settings_repository
+            .get_distinct_currencies_excluding_base(base_currency)?;
+        let household_currency = self.settings_repository.get_settings()?.base_currency;
+        if household_currency != base_currency && !currencies.contains(&household_currency) {
+            currencies.push(household_currency);
+        }
+        for currency in currencies {
+            if let Err(e) = self
+                .fx_service
+                .register_currency_pair(&currency, base_currency)
+                .await
+            {
+                error!(
+                    "Failed to register currency pair {}{}: {}. Skipping.",
+                    base_currency, currency, e
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ProfileServiceTrait for ProfileService {
+    fn get_profiles(&self) -> Result<Vec<Profile>> {
+        self.repository.list()
+    }
+
+    async fn create_profile(&self, new_profile: NewProfile) -> Result<Profile> {
+        debug!("Creating profile {}", new_profile.name);
+        let profile = self.repository.create(new_profile).await?;
+        self.register_currency_pairs(&profile.base_currency).await?;
+        Ok(profile)
+    }
+
+    async fn update_profile(&self, profile_update: ProfileUpdate) -> Result<Profile> {
+        debug!("Updating profile {}", profile_update.id);
+        let profile = self.repository.update(profile_update).await?;
+        self.register_currency_pairs(&profile.base_currency).await?;
+        Ok(profile)
+    }
+
+    async fn delete_profile(&self, profile_id: &str) -> Result<()> {
+        debug!("Deleting profile {}", profile_id);
+        self.repository.delete(profile_id).await?;
+        let was_active = self.active_profile_id.read().unwrap().as_deref() == Some(profile_id);
+        if was_active {
+            self.set_active_profile(None).await?;
+        }
+        Ok(())
+    }
+
+    fn get_active_profile(&self) -> Result<Option<Profile>> {
+        let active = self.active_profile_id.read().unwrap().clone();
+        active
+            .map(|profile_id| self.repository.get_by_id(&profile_id))
+            .transpose()
+    }
+
+    async fn set_active_profile(&self, profile_id: Option<String>) -> Result<Option<Profile>> {
+        let profile = profile_id
+            .as_deref()
+            .map(|id| self.repository.get_by_id(id))
+            .transpose()?;
+        if let Some(profile) = &profile {
+            self.register_currency_pairs(&profile.base_currency).await?;
+        }
+        self.settings_repository
+            .update_setting(
+                ACTIVE_PROFILE_SETTING_KEY,
+                profile_id.as_deref().unwrap_or_default(),
+            )
+            .await?;
+        *self.active_profile_id.write().unwrap() = profile_id;
+        Ok(profile)
+    }
+
+    fn get_base_currency(&self) -> Result<String> {
+        match self.get_active_profile()? {
+            Some(profile) => Ok(profile.base_currency),
+            None => Ok(self.settings_repository.get_settings()?.base_currency),
+        }
+    }
+
+    fn get_profile_memberships(&self) -> Result<Vec<ProfileMembership>> {
+        let profiles = self.repository.list()?;
+        let accounts = self.account_repository.list(Some(true), None)?;
+        Ok(profile_memberships(&profiles, &accounts))
+    }
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+
+use super::profiles_model::{NewProfile, Profile, ProfileMembership, ProfileUpdate};
+use crate::errors::Result;
+
+/// Trait defining the contract for Profile repository operations.
+#[async_trait]
+pub trait ProfileRepositoryTrait: Send + Sync {
+    fn list(&self) -> Result<Vec<Profile>>;
+    fn get_by_id(&self, profile_id: &str) -> Result<Profile>;
+    async fn create(&self, new_profile: NewProfile) -> Result<Profile>;
+    async fn update(&self, profile_update: ProfileUpdate) -> Result<Profile>;
+    /// Deletes the profile, moving its accounts, goals and contribution limits to
+    /// the default profile.
+    async fn delete(&self, profile_id: &str) -> Result<usize>;
+}
+
+/// Trait defining the contract for Profile service operations.
+#[async_trait]
+pub trait ProfileServiceTrait: Send + Sync {
+    fn get_profiles(&self) -> Result<Vec<Profile>>;
+    async fn create_profile(&self, new_profile: NewProfile) -> Result<Profile>;
+    async fn update_profile(&self, profile_update: ProfileUpdate) -> Result<Profile>;
+    async fn delete_profile(&self, profile_id: &str) -> Result<()>;
+    /// The profile in view, or `None` for the household view across all profiles.
+    fn get_active_profile(&self) -> Result<Option<Profile>>;
+    /// Switches the view to `profile_id`, or to the household view for `None`.
+    async fn set_active_profile(&self, profile_id: Option<String>) -> Result<Option<Profile>>;
+    /// Base currency of the active profile, or the household base currency from settings.
+    /// Amounts are shown in it; calculations always run in the household base currency.
+    fn get_base_currency(&self) -> Result<String>;
+    /// Every profile with the active accounts it aggregates.
+    fn get_profile_memberships(&self) -> Result<Vec<ProfileMembership>>;
+}
@@ -0,0 +1,13 @@
+mod reports_model;
+mod reports_render;
+mod reports_service;
+mod reports_traits;
+
+pub use reports_model::{
+    build_statement_line, statement_periods, total_statement_line, PeriodFlows, PortfolioStatement,
+    RenderedStatement, StatementFormat, StatementLine, StatementPeriod, StatementPeriodicity,
+    StatementRequest,
+};
+pub use reports_render::{render_csv, render_html, render_statement};
+pub use reports_service::ReportService;
+pub use reports_traits::ReportServiceTrait;
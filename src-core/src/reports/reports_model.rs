@@ -0,0 +1,490 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::activities::activities_constants::*;
+use crate::activities::Activity;
+use crate::constants::{DISPLAY_DECIMAL_PRECISION, PORTFOLIO_TOTAL_ACCOUNT_ID};
+use crate::errors::{Error, Result, ValidationError};
+use crate::portfolio::valuation::DailyAccountValuation;
+
+/// Length of each period a statement is broken into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StatementPeriodicity {
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+/// Output format of an exported statement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StatementFormat {
+    Csv,
+    Html,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementRequest {
+    pub periodicity: StatementPeriodicity,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Accounts to report on; all active accounts in view when omitted.
+    #[serde(default)]
+    pub account_ids: Option<Vec<String>>,
+}
+
+impl StatementRequest {
+    pub fn validate(&self) -> Result<()> {
+        if self.start_date > self.end_date {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Statement start date must not be after its end date".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// One calendar period of a statement, clipped to the requested range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementPeriod {
+    /// e.g. "2024-03", "2024-Q1" or "2024"
+    pub label: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+/// Movement of one account (or `TOTAL`) over one period, in base currency.
+///
+/// The line always reconciles: opening value plus every column from deposits to
+/// FX effect equals the closing value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementLine {
+    pub period: StatementPeriod,
+    pub account_id: String,
+    pub account_name: String,
+    pub opening_value: Decimal,
+    pub deposits: Decimal,
+    /// Negative: money and holdings taken out of the account.
+    pub withdrawals: Decimal,
+    /// Dividends and interest before tax withheld at source.
+    pub income: Decimal,
+    /// Negative: fees charged separately or on trades.
+    pub fees: Decimal,
+    /// Negative: tax activities and tax withheld on income.
+    pub taxes: Decimal,
+    /// Gains locked in by sales, in the account's currency converted at the closing rate.
+    pub realized_change: Decimal,
+    /// Change in market value over cost of the holdings still open.
+    pub unrealized_change: Decimal,
+    /// Effect of the account currency's rate to base moving on the opening value.
+    pub fx_effect: Decimal,
+    pub closing_value: Decimal,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioStatement {
+    pub periodicity: StatementPeriodicity,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub base_currency: String,
+    pub periods: Vec<StatementPeriod>,
+    /// Per period: one line per account followed by the `TOTAL` line.
+    pub lines: Vec<StatementLine>,
+    pub generated_at: NaiveDateTime,
+}
+
+/// A statement rendered to a file the user can save or print.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedStatement {
+    pub format: StatementFormat,
+    pub file_name: String,
+    pub mime_type: String,
+    pub content: String,
+}
+
+/// Splits `start..=end` into calendar periods; the first and last are clipped to the range.
+pub fn statement_periods(
+    periodicity: StatementPeriodicity,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<StatementPeriod> {
+    let mut periods = Vec::new();
+    let mut cursor = start;
+    while cursor <= end {
+        let (period_start, next_start, label) = match periodicity {
+            StatementPeriodicity::Monthly => {
+                let first = NaiveDate::from_ymd_opt(cursor.year(), cursor.month(), 1).unwrap();
+                (
+                    first,
+                    first + chrono::Months::new(1),
+                    first.format("%Y-%m").to_string(),
+                )
+            }
+            StatementPeriodicity::Quarterly => {
+                let quarter = (cursor.month() - 1) / 3;
+                let first = NaiveDate::from_ymd_opt(cursor.year(), quarter * 3 + 1, 1).unwrap();
+                (
+                    first,
+                    first + chrono::Months::new(3),
+                    format!("{}-Q{}", cursor.year(), quarter + 1),
+                )
+            }
+            StatementPeriodicity::Yearly => {
+                let first = NaiveDate::from_ymd_opt(cursor.year(), 1, 1).unwrap();
+                (
+                    first,
+                    first + chrono::Months::new(12),
+                    cursor.year().to_string(),
+                )
+            }
+        };
+        let period_end = next_start.pred_opt().unwrap().min(end);
+        periods.push(StatementPeriod {
+            label,
+            start_date: period_start.max(start),
+            end_date: period_end,
+        });
+        cursor = next_start;
+    }
+    periods
+}
+
+/// Cash flows of one account over a period, in the account's currency.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PeriodFlows {
+    pub deposits: Decimal,
+    pub withdrawals: Decimal,
+    pub income: Decimal,
+    pub fees: Decimal,
+    pub taxes: Decimal,
+}
+
+impl PeriodFlows {
+    /// Adds `activity`, with `to_account_currency` converting its amounts. Amounts are
+    /// taken the same way the holdings calculator books them, so the flows match the
+    /// cash and contributions behind the valuations.
+    pub fn record(
+        &mut self,
+        activity: &Activity,
+        to_account_currency: impl Fn(Decimal) -> Decimal,
+    ) {
+        let amount = activity.amount.unwrap_or(Decimal::ZERO);
+        let trade_value = activity.quantity * activity.unit_price;
+        let fee = to_account_currency(activity.fee);
+        match activity.activity_type.as_str() {
+            ACTIVITY_TYPE_DEPOSIT => self.deposits += to_account_currency(amount),
+            ACTIVITY_TYPE_WITHDRAWAL => self.withdrawals += to_account_currency(amount),
+            ACTIVITY_TYPE_TRANSFER_IN | ACTIVITY_TYPE_ADD_HOLDING => {
+                self.deposits += to_account_currency(activity.amount.unwrap_or(trade_value))
+            }
+            ACTIVITY_TYPE_TRANSFER_OUT | ACTIVITY_TYPE_REMOVE_HOLDING => {
+                self.withdrawals += to_account_currency(activity.amount.unwrap_or(trade_value))
+            }
+            ACTIVITY_TYPE_DIVIDEND
+            | ACTIVITY_TYPE_INTEREST
+            | ACTIVITY_TYPE_DIVIDEND_REINVESTMENT => {
+                let received = if activity.activity_type == ACTIVITY_TYPE_DIVIDEND_REINVESTMENT {
+                    activity.amount.unwrap_or(trade_value)
+                } else {
+                    amount
+                };
+                let withheld = activity.withholding_tax.unwrap_or(Decimal::ZERO);
+                self.income += to_account_currency(received + withheld);
+                self.taxes += to_account_currency(withheld);
+            }
+            ACTIVITY_TYPE_FEE | ACTIVITY_TYPE_TAX => {
+                // Charges carry their value in `fee` or, failing that, in `amount`.
+                let charge = if activity.fee != Decimal::ZERO {
+                    activity.fee
+                } else {
+                    amount
+                };
+                let charge = to_account_currency(charge).abs();
+                if activity.activity_type == ACTIVITY_TYPE_FEE {
+                    self.fees += charge;
+                } else {
+                    self.taxes += charge;
+                }
+                return;
+            }
+            _ => {}
+        }
+        self.fees += fee;
+    }
+}
+
+/// Builds an account's line for `period` from the last valuation before the period
+/// (`opening`) and the last one within it (`closing`). Returns `None` when the account
+/// had neither, i.e. did not exist yet.
+///
+/// Values are in account currency until converted at the closing rate to base; what
+/// the opening value gains or loses from the rate moving is the FX effect. The change
+/// not explained by flows is the market change, split into the change in unrealized
+/// gain and the realized remainder.
+pub fn build_statement_line(
+    period: &StatementPeriod,
+    account_id: &str,
+    account_name: &str,
+    opening: Option<&DailyAccountValuation>,
+    closing: Option<&DailyAccountValuation>,
+    flows: &PeriodFlows,
+) -> Option<StatementLine> {
+    let closing = closing.or(opening)?;
+    let close_rate = closing.fx_rate_to_base;
+    let open_rate = opening.map_or(close_rate, |v| v.fx_rate_to_base);
+    let open_value = opening.map_or(Decimal::ZERO, |v| v.total_value);
+    let open_unrealized =
+        opening.map_or(Decimal::ZERO, |v| v.investment_market_value - v.cost_basis);
+
+    let market_change = closing.total_value
+        - open_value
+        - (flows.deposits - flows.withdrawals)
+        - (flows.income - flows.fees - flows.taxes);
+    let unrealized_change = closing.investment_market_value - closing.cost_basis - open_unrealized;
+
+    Some(StatementLine {
+        period: period.clone(),
+        account_id: account_id.to_string(),
+        account_name: account_name.to_string(),
+        opening_value: open_value * open_rate,
+        deposits: flows.deposits * close_rate,
+        withdrawals: -flows.withdrawals * close_rate,
+        income: flows.income * close_rate,
+        fees: -flows.fees * close_rate,
+        taxes: -flows.taxes * close_rate,
+        realized_change: (market_change - unrealized_change) * close_rate,
+        unrealized_change: unrealized_change * close_rate,
+        fx_effect: open_value * (close_rate - open_rate),
+        closing_value: closing.total_value * close_rate,
+    })
+}
+
+/// Sums account lines of one period into its `TOTAL` line.
+pub fn total_statement_line(period: &StatementPeriod, lines: &[StatementLine]) -> StatementLine {
+    let sum = |f: fn(&StatementLine) -> Decimal| lines.iter().map(f).sum::<Decimal>();
+    StatementLine {
+        period: period.clone(),
+        account_id: PORTFOLIO_TOTAL_ACCOUNT_ID.to_string(),
+        account_name: "Total".to_string(),
+        opening_value: sum(|l| l.opening_value),
+        deposits: sum(|l| l.deposits),
+        withdrawals: sum(|l| l.withdrawals),
+        income: sum(|l| l.income),
+        fees: sum(|l| l.fees),
+        taxes: sum(|l| l.taxes),
+        realized_change: sum(|l| l.realized_change),
+        unrealized_change: sum(|l| l.unrealized_change),
+        fx_effect: sum(|l| l.fx_effect),
+        closing_value: sum(|l| l.closing_value),
+    }
+}
+
+impl StatementLine {
+    /// Rounds every amount for display. Done once totals are summed so rounding
+    /// differences don't accumulate.
+    pub(crate) fn rounded(mut self) -> Self {
+        for value in [
+            &mut self.opening_value,
+            &mut self.deposits,
+            &mut self.withdrawals,
+            &mut self.income,
+            &mut self.fees,
+            &mut self.taxes,
+            &mut self.realized_change,
+            &mut self.unrealized_change,
+            &mut self.fx_effect,
+            &mut self.closing_value,
+        ] {
+            *value = value.round_dp(DISPLAY_DECIMAL_PRECISION);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn valuation(
+        total: Decimal,
+        market: Decimal,
+        cost: Decimal,
+        rate: Decimal,
+    ) -> DailyAccountValuation {
+        DailyAccountValuation {
+            id: "v".to_string(),
+            account_id: "acc".to_string(),
+            valuation_date: date(2024, 1, 1),
+            account_currency: "EUR".to_string(),
+            base_currency: "USD".to_string(),
+            fx_rate_to_base: rate,
+            cash_balance: total - market,
+            investment_market_value: market,
+            total_value: total,
+            cost_basis: cost,
+            net_contribution: Decimal::ZERO,
+            calculated_at: Utc::now(),
+            accrued_interest: Decimal::ZERO,
+        }
+    }
+
+    fn activity(activity_type: &str, amount: Option<Decimal>, fee: Decimal) -> Activity {
+        Activity {
+            id: "a".to_string(),
+            account_id: "acc".to_string(),
+            asset_id: "$CASH-EUR".to_string(),
+            activity_type: activity_type.to_string(),
+            activity_date: Utc::now(),
+            quantity: Decimal::ZERO,
+            unit_price: Decimal::ZERO,
+            currency: "EUR".to_string(),
+            fee,
+            amount,
+            is_draft: false,
+            comment: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            import_session_id: None,
+            source_asset_id: None,
+            withholding_tax: None,
+            withholding_country: None,
+        }
+    }
+
+    #[test]
+    fn test_periods_are_calendar_aligned_and_clipped() {
+        let quarters = statement_periods(
+            StatementPeriodicity::Quarterly,
+            date(2024, 2, 10),
+            date(2024, 7, 31),
+        );
+        let summary: Vec<_> = quarters
+            .iter()
+            .map(|p| (p.label.as_str(), p.start_date, p.end_date))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2024-Q1", date(2024, 2, 10), date(2024, 3, 31)),
+                ("2024-Q2", date(2024, 4, 1), date(2024, 6, 30)),
+                ("2024-Q3", date(2024, 7, 1), date(2024, 7, 31)),
+            ]
+        );
+
+        let months = statement_periods(
+            StatementPeriodicity::Monthly,
+            date(2023, 12, 5),
+            date(2024, 1, 1),
+        );
+        assert_eq!(
+            months.iter().map(|p| p.label.as_str()).collect::<Vec<_>>(),
+            vec!["2023-12", "2024-01"]
+        );
+        assert_eq!(
+            statement_periods(
+                StatementPeriodicity::Yearly,
+                date(2024, 3, 1),
+                date(2024, 3, 1)
+            )
+            .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_flows_follow_how_activities_are_booked() {
+        let mut flows = PeriodFlows::default();
+        let double = |v: Decimal| v * dec!(2);
+        flows.record(
+            &activity(ACTIVITY_TYPE_DEPOSIT, Some(dec!(100)), dec!(1)),
+            double,
+        );
+        flows.record(
+            &activity(ACTIVITY_TYPE_FEE, Some(dec!(5)), Decimal::ZERO),
+            double,
+        );
+        flows.record(
+            &activity(ACTIVITY_TYPE_TAX, Some(dec!(3)), Decimal::ZERO),
+            double,
+        );
+        let mut dividend = activity(ACTIVITY_TYPE_DIVIDEND, Some(dec!(85)), Decimal::ZERO);
+        dividend.withholding_tax = Some(dec!(15));
+        flows.record(&dividend, double);
+
+        assert_eq!(
+            flows,
+            PeriodFlows {
+                deposits: dec!(200),
+                withdrawals: Decimal::ZERO,
+                income: dec!(200),
+                fees: dec!(12),
+                taxes: dec!(36),
+            }
+        );
+    }
+
+    #[test]
+    fn test_line_reconciles_opening_to_closing() {
+        let period = StatementPeriod {
+            label: "2024-01".to_string(),
+            start_date: date(2024, 1, 1),
+            end_date: date(2024, 1, 31),
+        };
+        // EUR account: 1000 (800 invested at cost 700), rate 1.10 -> 1.20.
+        let opening = valuation(dec!(1000), dec!(800), dec!(700), dec!(1.10));
+        let closing = valuation(dec!(1300), dec!(950), dec!(750), dec!(1.20));
+        let flows = PeriodFlows {
+            deposits: dec!(200),
+            withdrawals: dec!(50),
+            income: dec!(30),
+            fees: dec!(5),
+            taxes: dec!(5),
+        };
+
+        let line = build_statement_line(
+            &period,
+            "acc",
+            "Broker",
+            Some(&opening),
+            Some(&closing),
+            &flows,
+        )
+        .unwrap();
+
+        assert_eq!(line.opening_value, dec!(1100));
+        assert_eq!(line.closing_value, dec!(1560));
+        assert_eq!(line.fx_effect, dec!(100));
+        // Unrealized gain went from 100 to 200.
+        assert_eq!(line.unrealized_change, dec!(120));
+        // 300 change - 150 net deposits - 20 net income - 100 unrealized.
+        assert_eq!(line.realized_change, dec!(36));
+        let reconciled = line.opening_value
+            + line.deposits
+            + line.withdrawals
+            + line.income
+            + line.fees
+            + line.taxes
+            + line.realized_change
+            + line.unrealized_change
+            + line.fx_effect;
+        assert_eq!(reconciled, line.closing_value);
+
+        let total = total_statement_line(&period, &[line.clone(), line.clone()]);
+        assert_eq!(total.account_id, PORTFOLIO_TOTAL_ACCOUNT_ID);
+        assert_eq!(total.closing_value, dec!(3120));
+        assert!(build_statement_line(&period, "acc", "Broker", None, None, &flows).is_none());
+    }
+}
@@ -0,0 +1,253 @@
+use rust_decimal::Decimal;
+
+use super::reports_model::{PortfolioStatement, RenderedStatement, StatementFormat, StatementLine};
+use crate::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
+use crate::errors::{Error, Result};
+
+const COLUMNS: [&str; 10] = [
+    "Opening value",
+    "Deposits",
+    "Withdrawals",
+    "Income",
+    "Fees",
+    "Taxes",
+    "Realized change",
+    "Unrealized change",
+    "FX effect",
+    "Closing value",
+];
+
+fn amounts(line: &StatementLine) -> [Decimal; 10] {
+    [
+        line.opening_value,
+        line.deposits,
+        line.withdrawals,
+        line.income,
+        line.fees,
+        line.taxes,
+        line.realized_change,
+        line.unrealized_change,
+        line.fx_effect,
+        line.closing_value,
+    ]
+}
+
+fn file_stem(statement: &PortfolioStatement) -> String {
+    format!(
+        "statement_{}_{}",
+        statement.start_date.format("%Y%m%d"),
+        statement.end_date.format("%Y%m%d")
+    )
+}
+
+pub fn render_statement(
+    statement: &PortfolioStatement,
+    format: StatementFormat,
+) -> Result<RenderedStatement> {
+    let (content, extension, mime_type) = match format {
+        StatementFormat::Csv => (render_csv(statement)?, "csv", "text/csv"),
+        StatementFormat::Html => (render_html(statement), "html", "text/html"),
+    };
+    Ok(RenderedStatement {
+        format,
+        file_name: format!("{}.{}", file_stem(statement), extension),
+        mime_type: mime_type.to_string(),
+        content,
+    })
+}
+
+/// One row per account and period, amounts in the statement's base currency.
+pub fn render_csv(statement: &PortfolioStatement) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let mut header = vec![
+        "Period",
+        "Start",
+        "End",
+        "Account ID",
+        "Account",
+        "Currency",
+    ];
+    header.extend(COLUMNS);
+    writer
+        .write_record(&header)
+        .map_err(|e| Error::Unexpected(e.to_string()))?;
+
+    for line in &statement.lines {
+        let mut record = vec![
+            line.period.label.clone(),
+            line.period.start_date.to_string(),
+            line.period.end_date.to_string(),
+            line.account_id.clone(),
+            line.account_name.clone(),
+            statement.base_currency.clone(),
+        ];
+        record.extend(amounts(line).iter().map(Decimal::to_string));
+        writer
+            .write_record(&record)
+            .map_err(|e| Error::Unexpected(e.to_string()))?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| Error::Unexpected(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| Error::Unexpected(e.to_string()))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_amount(value: Decimal) -> String {
+    let rounded = format!("{:.2}", value.abs());
+    let (int_part, frac_part) = rounded.split_once('.').unwrap_or((&rounded, "00"));
+    let mut grouped = String::new();
+    for (i, c) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let sign = if value.is_sign_negative() && !value.is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    format!("{}{}.{}", sign, grouped, frac_part)
+}
+
+/// A self-contained page with one table per period, laid out to print (or save as
+/// PDF) with a page break between periods.
+pub fn render_html(statement: &PortfolioStatement) -> String {
+    let title = format!(
+        "Portfolio statement {} to {}",
+        statement.start_date, statement.end_date
+    );
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape_html(&title)));
+    html.push_str(
+        "<style>\n\
+         body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; font-size: 12px; color: #111; margin: 24px; }\n\
+         h1 { font-size: 20px; margin-bottom: 4px; }\n\
+         h2 { font-size: 15px; margin: 24px 0 8px; }\n\
+         .meta { color: #555; margin-bottom: 16px; }\n\
+         table { width: 100%; border-collapse: collapse; }\n\
+         th, td { padding: 4px 6px; border-bottom: 1px solid #ddd; white-space: nowrap; }\n\
+         th { text-align: right; font-weight: 600; background: #f4f4f4; }\n\
+         th:first-child, td:first-child { text-align: left; }\n\
+         td { text-align: right; font-variant-numeric: tabular-nums; }\n\
+         tr.total td { font-weight: 600; border-top: 2px solid #111; }\n\
+         section { page-break-inside: avoid; }\n\
+         @media print { body { margin: 0; } section + section { page-break-before: always; } }\n\
+         </style>\n</head>\n<body>\n",
+    );
+    html.push_str(&format!("<h1>{}</h1>\n", escape_html(&title)));
+    html.push_str(&format!(
+        "<div class=\"meta\">Amounts in {} &middot; generated {}</div>\n",
+        escape_html(&statement.base_currency),
+        statement.generated_at.format("%Y-%m-%d %H:%M")
+    ));
+
+    for period in &statement.periods {
+        html.push_str("<section>\n");
+        html.push_str(&format!(
+            "<h2>{} <span class=\"meta\">({} &ndash; {})</span></h2>\n",
+            escape_html(&period.label),
+            period.start_date,
+            period.end_date
+        ));
+        html.push_str("<table>\n<thead><tr><th>Account</th>");
+        for column in COLUMNS {
+            html.push_str(&format!("<th>{}</th>", column));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        for line in statement.lines.iter().filter(|l| &l.period == period) {
+            let class = if line.account_id == PORTFOLIO_TOTAL_ACCOUNT_ID {
+                " class=\"total\""
+            } else {
+                ""
+            };
+            html.push_str(&format!(
+                "<tr{}><td>{}</td>",
+                class,
+                escape_html(&line.account_name)
+            ));
+            for value in amounts(line) {
+                html.push_str(&format!("<td>{}</td>", format_amount(value)));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::reports_model::{StatementPeriod, StatementPeriodicity};
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    fn statement() -> PortfolioStatement {
+        let period = StatementPeriod {
+            label: "2024".to_string(),
+            start_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+        };
+        let line = StatementLine {
+            period: period.clone(),
+            account_id: "acc".to_string(),
+            account_name: "Joint <Broker>, main".to_string(),
+            opening_value: dec!(1234567.5),
+            deposits: dec!(100),
+            withdrawals: dec!(-50),
+            income: Decimal::ZERO,
+            fees: dec!(-0.25),
+            taxes: Decimal::ZERO,
+            realized_change: Decimal::ZERO,
+            unrealized_change: Decimal::ZERO,
+            fx_effect: Decimal::ZERO,
+            closing_value: dec!(1234617.25),
+        };
+        PortfolioStatement {
+            periodicity: StatementPeriodicity::Yearly,
+            start_date: period.start_date,
+            end_date: period.end_date,
+            base_currency: "USD".to_string(),
+            periods: vec![period],
+            lines: vec![line],
+            generated_at: NaiveDate::from_ymd_opt(2025, 1, 2)
+                .unwrap()
+                .and_hms_opt(9, 30, 0)
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_csv_has_one_row_per_line_with_quoted_names() {
+        let rendered = render_statement(&statement(), StatementFormat::Csv).unwrap();
+        assert_eq!(rendered.file_name, "statement_20240101_20241231.csv");
+        let rows: Vec<&str> = rendered.content.lines().collect();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].starts_with("Period,Start,End,Account ID,Account,Currency,Opening value"));
+        assert_eq!(
+            rows[1],
+            "2024,2024-01-01,2024-12-31,acc,\"Joint <Broker>, main\",USD,1234567.5,100,-50,0,-0.25,0,0,0,0,1234617.25"
+        );
+    }
+
+    #[test]
+    fn test_html_escapes_names_and_formats_amounts() {
+        let html = render_html(&statement());
+        assert!(html.contains("<td>Joint &lt;Broker&gt;, main</td>"));
+        assert!(html.contains("<td>1,234,567.50</td>"));
+        assert!(html.contains("<td>-0.25</td>"));
+        assert!(html.contains("Amounts in USD"));
+    }
+}
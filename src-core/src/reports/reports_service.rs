@@ -0,0 +1,172 @@
+use chrono::Utc;
+use log::{debug, warn};
+use rust_decimal::Decimal;
+use std::sync::{Arc, RwLock};
+
+use super::reports_model::{
+    build_statement_line, statement_periods, total_statement_line, PeriodFlows, PortfolioStatement,
+    RenderedStatement, StatementFormat, StatementLine, StatementRequest,
+};
+use super::reports_render::render_statement;
+use super::reports_traits::ReportServiceTrait;
+use crate::accounts::AccountServiceTrait;
+use crate::activities::{Activity, ActivityRepositoryTrait};
+use crate::errors::Result;
+use crate::fx::fx_traits::FxServiceTrait;
+use crate::portfolio::valuation::ValuationServiceTrait;
+
+pub struct ReportService {
+    account_service: Arc<dyn AccountServiceTrait>,
+    activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    valuation_service: Arc<dyn ValuationServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl ReportService {
+    pub fn new(
+        account_service: Arc<dyn AccountServiceTrait>,
+        activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        valuation_service: Arc<dyn ValuationServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        ReportService {
+            account_service,
+            activity_repository,
+            valuation_service,
+            fx_service,
+            base_currency,
+        }
+    }
+
+    // Converts an activity amount on its date, keeping the original amount when no
+    // rate is available, as the holdings calculator does.
+    fn to_account_currency(
+        &self,
+        activity: &Activity,
+        account_currency: &str,
+        amount: Decimal,
+    ) -> Decimal {
+        if amount.is_zero() || activity.currency == account_currency {
+            return amount;
+        }
+        let date = activity.activity_date.naive_utc().date();
+        self.fx_service
+            .convert_currency_for_date(amount, &activity.currency, account_currency, date)
+            .unwrap_or_else(|e| {
+                warn!(
+                    "Statement: failed to convert {} {}->{} on {} for activity {}: {}. Using original amount.",
+                    amount, activity.currency, account_currency, date, activity.id, e
+                );
+                amount
+            })
+    }
+}
+
+impl ReportServiceTrait for ReportService {
+    fn get_portfolio_statement(&self, request: &StatementRequest) -> Result<PortfolioStatement> {
+        request.validate()?;
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let periods = statement_periods(request.periodicity, request.start_date, request.end_date);
+
+        let accounts: Vec<_> = self
+            .account_service
+            .get_active_accounts()?
+            .into_iter()
+            .filter(|a| {
+                request
+                    .account_ids
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&a.id))
+            })
+            .collect();
+        let account_ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+        debug!(
+            "Building {:?} statement for {} accounts over {} periods",
+            request.periodicity,
+            accounts.len(),
+            periods.len()
+        );
+
+        let activities: Vec<Activity> = self
+            .activity_repository
+            .get_activities_by_account_ids(&account_ids)?
+            .into_iter()
+            .filter(|a| {
+                let date = a.activity_date.naive_utc().date();
+                !a.is_draft && date >= request.start_date && date <= request.end_date
+            })
+            .collect();
+
+        let mut lines_by_period: Vec<Vec<StatementLine>> = vec![Vec::new(); periods.len()];
+        for account in &accounts {
+            let mut valuations = self.valuation_service.get_historical_valuations(
+                &account.id,
+                None,
+                Some(request.end_date),
+            )?;
+            valuations.sort_by_key(|v| v.valuation_date);
+
+            let mut flows = vec![PeriodFlows::default(); periods.len()];
+            for activity in activities.iter().filter(|a| a.account_id == account.id) {
+                let date = activity.activity_date.naive_utc().date();
+                if let Some(index) = periods
+                    .iter()
+                    .position(|p| p.start_date <= date && date <= p.end_date)
+                {
+                    flows[index].record(activity, |amount| {
+                        self.to_account_currency(activity, &account.currency, amount)
+                    });
+                }
+            }
+
+            for (index, period) in periods.iter().enumerate() {
+                let opening = valuations
+                    .iter()
+                    .rev()
+                    .find(|v| v.valuation_date < period.start_date);
+                let closing = valuations
+                    .iter()
+                    .rev()
+                    .find(|v| v.valuation_date <= period.end_date);
+                if let Some(line) = build_statement_line(
+                    period,
+                    &account.id,
+                    &account.name,
+                    opening,
+                    closing,
+                    &flows[index],
+                ) {
+                    lines_by_period[index].push(line);
+                }
+            }
+        }
+
+        let mut lines = Vec::new();
+        for (period, period_lines) in periods.iter().zip(lines_by_period) {
+            let total = total_statement_line(period, &period_lines);
+            lines.extend(period_lines.into_iter().map(StatementLine::rounded));
+            lines.push(total.rounded());
+        }
+
+        Ok(PortfolioStatement {
+            periodicity: request.periodicity,
+            start_date: request.start_date,
+            end_date: request.end_date,
+            base_currency,
+            periods,
+            lines,
+            generated_at: Utc::now().naive_utc(),
+        })
+    }
+
+    fn export_portfolio_statement(
+        &self,
+        request: &StatementRequest,
+        format: StatementFormat,
+    ) -> Result<RenderedStatement> {
+        let statement = self.get_portfolio_statement(request)?;
+        render_statement(&statement, format)
+    }
+}
@@ -0,0 +1,18 @@
+use super::reports_model::{
+    PortfolioStatement, RenderedStatement, StatementFormat, StatementRequest,
+};
+use crate::errors::Result;
+
+/// Trait defining the contract for periodic portfolio reports.
+pub trait ReportServiceTrait: Send + Sync {
+    /// Cash flow and net worth statement of the requested accounts, per period,
+    /// with a `TOTAL` line for each period.
+    fn get_portfolio_statement(&self, request: &StatementRequest) -> Result<PortfolioStatement>;
+
+    /// Builds the statement and renders it as a file in `format`.
+    fn export_portfolio_statement(
+        &self,
+        request: &StatementRequest,
+        format: StatementFormat,
+    ) -> Result<RenderedStatement>;
+}
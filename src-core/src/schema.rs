@@ -1,5 +1,15 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    account_groups (id) {
+        id -> Text,
+        name -> Text,
+        parent_id -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     accounts (id) {
         id -> Text,
@@ -12,6 +22,8 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         platform_id -> Nullable<Text>,
+        group_id -> Nullable<Text>,
+        profile_id -> Text,
     }
 }
 
@@ -31,6 +43,10 @@ diesel::table! {
         comment -> Nullable<Text>,
         created_at -> Text,
         updated_at -> Text,
+        import_session_id -> Nullable<Text>,
+        source_asset_id -> Nullable<Text>,
+        withholding_tax -> Nullable<Text>,
+        withholding_country -> Nullable<Text>,
     }
 }
 
@@ -46,6 +62,30 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    activity_saved_views (id) {
+        id -> Text,
+        name -> Text,
+        filters -> Text,
+        sort -> Text,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    allocation_models (id) {
+        id -> Text,
+        name -> Text,
+        target_kind -> Text,
+        scope_type -> Text,
+        scope_id -> Nullable<Text>,
+        targets -> Text,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
 diesel::table! {
     app_settings (setting_key) {
         setting_key -> Text,
@@ -74,6 +114,34 @@ diesel::table! {
         data_source -> Text,
         sectors -> Nullable<Text>,
         url -> Nullable<Text>,
+        dividend_yield -> Nullable<Double>,
+        expense_ratio -> Nullable<Double>,
+    }
+}
+
+diesel::table! {
+    cash_interest_rates (id) {
+        id -> Text,
+        account_id -> Text,
+        currency -> Text,
+        annual_rate -> Text,
+        effective_date -> Date,
+        compounding -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    change_log (id) {
+        id -> Text,
+        entity_type -> Text,
+        entity_id -> Text,
+        operation -> Text,
+        actor -> Text,
+        before_json -> Nullable<Text>,
+        after_json -> Nullable<Text>,
+        created_at -> Text,
     }
 }
 
@@ -88,6 +156,11 @@ diesel::table! {
         updated_at -> Timestamp,
         start_date -> Nullable<Timestamp>,
         end_date -> Nullable<Timestamp>,
+        preset -> Nullable<Text>,
+        birth_year -> Nullable<Integer>,
+        room_start_year -> Nullable<Integer>,
+        rules -> Nullable<Text>,
+        profile_id -> Text,
     }
 }
 
@@ -105,6 +178,7 @@ diesel::table! {
         cost_basis -> Text,
         net_contribution -> Text,
         calculated_at -> Text,
+        accrued_interest -> Text,
     }
 }
 
@@ -115,6 +189,11 @@ diesel::table! {
         description -> Nullable<Text>,
         target_amount -> Double,
         is_achieved -> Bool,
+        target_date -> Nullable<Date>,
+        starting_amount -> Double,
+        contribution_amount -> Double,
+        contribution_frequency -> Text,
+        profile_id -> Text,
     }
 }
 
@@ -142,6 +221,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    import_session_merges (session_id, activity_id) {
+        session_id -> Text,
+        activity_id -> Text,
+        before_json -> Text,
+    }
+}
+
+diesel::table! {
+    import_sessions (id) {
+        id -> Text,
+        account_id -> Text,
+        source -> Text,
+        file_name -> Nullable<Text>,
+        file_hash -> Nullable<Text>,
+        row_count -> Integer,
+        status -> Text,
+        created_at -> Text,
+        rolled_back_at -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     market_data_providers (id) {
         id -> Text,
@@ -165,6 +266,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    profiles (id) {
+        id -> Text,
+        name -> Text,
+        base_currency -> Text,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     quotes (id) {
         id -> Text,
@@ -182,23 +293,49 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    symbol_identifiers (identifier) {
+        identifier -> Text,
+        identifier_type -> Text,
+        symbol -> Text,
+        isin -> Nullable<Text>,
+        name -> Nullable<Text>,
+        exchange -> Nullable<Text>,
+        source -> Text,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
 diesel::joinable!(accounts -> platforms (platform_id));
+diesel::joinable!(cash_interest_rates -> accounts (account_id));
 diesel::joinable!(goals_allocation -> accounts (account_id));
 diesel::joinable!(goals_allocation -> goals (goal_id));
+diesel::joinable!(import_session_merges -> import_sessions (session_id));
+diesel::joinable!(import_sessions -> accounts (account_id));
 diesel::joinable!(quotes -> assets (symbol));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    account_groups,
     accounts,
     activities,
     activity_import_profiles,
+    activity_saved_views,
+    allocation_models,
     app_settings,
     assets,
+    cash_interest_rates,
+    change_log,
     contribution_limits,
     daily_account_valuation,
     goals,
     goals_allocation,
     holdings_snapshots,
+    import_session_merges,
+    import_sessions,
     market_data_providers,
     platforms,
+    profiles,
     quotes,
+    symbol_identifiers,
 );
@@ -1,5 +1,6 @@
 use diesel::prelude::*;
 use diesel::Queryable;
+use crate::fx::fx_model::FxConversionPolicy;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -11,6 +12,8 @@ pub struct Settings {
     pub instance_id: String,
     pub onboarding_completed: bool,
     pub auto_update_check_enabled: bool,
+    #[serde(default)]
+    pub fx_conversion_policy: FxConversionPolicy,
 }
 
 impl Default for Settings {
@@ -22,6 +25,7 @@ impl Default for Settings {
             instance_id: "".to_string(),
             onboarding_completed: false,
             auto_update_check_enabled: true,
+            fx_conversion_policy: FxConversionPolicy::default(),
         }
     }
 }
@@ -34,6 +38,7 @@ pub struct SettingsUpdate {
     pub base_currency: Option<String>,
     pub onboarding_completed: Option<bool>,
     pub auto_update_check_enabled: Option<bool>,
+    pub fx_conversion_policy: Option<FxConversionPolicy>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
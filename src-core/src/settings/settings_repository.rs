@@ -54,6 +54,13 @@ impl SettingsRepositoryTrait for SettingsRepository {
                     // Parse the string value into a boolean
                     settings.auto_update_check_enabled = value.parse().unwrap_or(true);
                 }
+                "fx_conversion_policy" => {
+                    // Stored as JSON; fall back to the default policy if it cannot be read
+                    settings.fx_conversion_policy = serde_json::from_str(&value).unwrap_or_else(|e| {
+                        log::warn!("Invalid fx_conversion_policy setting, using default: {}", e);
+                        Default::default()
+                    });
+                }
                 _ => {} // Ignore unknown settings
             }
         }
@@ -67,6 +74,12 @@ impl SettingsRepositoryTrait for SettingsRepository {
 
     async fn update_settings(&self, new_settings: &SettingsUpdate) -> Result<()> {
         let settings = new_settings.clone();
+        let fx_conversion_policy = settings
+            .fx_conversion_policy
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| Error::InvalidConfigValue(format!("fx_conversion_policy: {}", e)))?;
         self.writer
             .exec(move |conn| {
                 if let Some(ref theme) = settings.theme {
@@ -114,6 +127,15 @@ impl SettingsRepositoryTrait for SettingsRepository {
                         .execute(conn)?;
                 }
 
+                if let Some(ref fx_conversion_policy) = fx_conversion_policy {
+                    diesel::replace_into(app_settings)
+                        .values(&AppSetting {
+                            setting_key: "fx_conversion_policy".to_string(),
+                            setting_value: fx_conversion_policy.clone(),
+                        })
+                        .execute(conn)?;
+                }
+
                 Ok(())
             })
             .await
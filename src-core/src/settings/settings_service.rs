@@ -42,6 +42,10 @@ impl SettingsServiceTrait for SettingsService {
         }
         
         self.settings_repository.update_settings(new_settings).await?;
+
+        if let Some(ref policy) = new_settings.fx_conversion_policy {
+            self.fx_service.set_conversion_policy(policy.clone())?;
+        }
         Ok(())
     }
 
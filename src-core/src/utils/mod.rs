@@ -1,2 +1,3 @@
 // This file declares utility modules
-pub mod time_utils; 
\ No newline at end of file
+pub mod time_utils; 
+pub mod worker_pool;
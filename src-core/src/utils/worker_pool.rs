@@ -0,0 +1,50 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::constants::MAX_CALCULATION_WORKERS;
+
+/// Number of workers to use for `job_count` independent jobs, bounded by the
+/// available parallelism and `MAX_CALCULATION_WORKERS`.
+pub fn worker_count(job_count: usize) -> usize {
+    let available = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    job_count.min(available).clamp(1, MAX_CALCULATION_WORKERS)
+}
+
+/// Runs `job` for every item on at most `workers` threads and returns the results
+/// in the same order as `items`. Runs inline when a single worker is enough.
+pub fn run_bounded<T, R, F>(items: &[T], workers: usize, job: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if workers <= 1 || items.len() <= 1 {
+        return items.iter().map(job).collect();
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..items.len()).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..workers.min(items.len()) {
+            scope.spawn(|| loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = job(item);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every job is run exactly once"))
+        .collect()
+}
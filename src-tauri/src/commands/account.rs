@@ -7,7 +7,10 @@ use crate::{
 use log::{debug, error, warn};
 use tauri::{AppHandle, State};
 
-use wealthfolio_core::accounts::{Account, AccountUpdate, NewAccount};
+use wealthfolio_core::accounts::{
+    Account, AccountGroup, AccountGroupMembership, AccountGroupUpdate, AccountUpdate, NewAccount,
+    NewAccountGroup,
+};
 
 #[tauri::command]
 pub async fn get_accounts(state: State<'_, Arc<ServiceContext>>) -> Result<Vec<Account>, String> {
@@ -155,3 +158,81 @@ pub async fn delete_account(
 
     Ok(())
 }
+
+#[tauri::command]
+pub async fn get_account_groups(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<AccountGroup>, String> {
+    debug!("Fetching account groups...");
+    state
+        .account_group_service()
+        .get_account_groups()
+        .map_err(|e| format!("Failed to load account groups: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_account_group_memberships(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<AccountGroupMembership>, String> {
+    debug!("Fetching account group memberships...");
+    state
+        .account_group_service()
+        .get_group_memberships()
+        .map_err(|e| format!("Failed to load account groups: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_account_group(
+    group: NewAccountGroup,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AccountGroup, String> {
+    debug!("Adding account group {}...", group.name);
+    // An empty group has nothing to aggregate until accounts are moved into it.
+    state
+        .account_group_service()
+        .create_account_group(group)
+        .await
+        .map_err(|e| format!("Failed to create account group: {}", e))
+}
+
+#[tauri::command]
+pub async fn update_account_group(
+    group_update: AccountGroupUpdate,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<AccountGroup, String> {
+    debug!("Updating account group {}...", group_update.id);
+    let group = state
+        .account_group_service()
+        .update_account_group(group_update)
+        .await
+        .map_err(|e| format!("Failed to update account group: {}", e))?;
+
+    // Moving a group changes what its former and new ancestors aggregate.
+    let payload = PortfolioRequestPayload::builder().account_ids(None).build();
+    emit_portfolio_trigger_recalculate(&handle, payload);
+
+    Ok(group)
+}
+
+#[tauri::command]
+pub async fn delete_account_group(
+    group_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<(), String> {
+    debug!("Deleting account group {}...", group_id);
+    state
+        .account_group_service()
+        .delete_account_group(&group_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete account group {}: {}", group_id, e);
+            e.to_string()
+        })?;
+
+    let payload = PortfolioRequestPayload::builder().account_ids(None).build();
+    emit_portfolio_trigger_recalculate(&handle, payload);
+
+    Ok(())
+}
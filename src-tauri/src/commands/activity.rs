@@ -6,9 +6,13 @@ use crate::events::{emit_portfolio_trigger_recalculate, PortfolioRequestPayload}
 use log::debug;
 use tauri::{AppHandle, State};
 use wealthfolio_core::activities::{
-    Activity, ActivityDetails, ActivityImport, ActivitySearchResponse, ActivityUpdate, ImportMappingData,
-    NewActivity, Sort,
+    Activity, ActivityBulkRequest, ActivityBulkResult, ActivityDetails, ActivityImport,
+    ActivitySavedView, ActivitySearchFilters, ActivitySearchResponse, ActivityUpdate,
+    DuplicateStrategy, ImportMappingData, ImportSession, NewActivity, NewActivitySavedView,
+    NewImportSession, Sort, StatementParserInfo,
 };
+use wealthfolio_core::activities::statement_parsers;
+use wealthfolio_core::portfolio::snapshot::DirtyRanges;
 
 use csv::WriterBuilder;
 
@@ -142,18 +146,28 @@ pub async fn search_activities(
     activity_type_filter: Option<Vec<String>>, // Optional activity_type filter
     asset_id_keyword: Option<String>,          // Optional asset_id keyword for search
     sort: Option<Sort>,
+    filters: Option<ActivitySearchFilters>, // Additional filters; the arguments above take precedence
+    sorts: Option<Vec<Sort>>,               // Multi-column sort; replaces `sort` when given
     state: State<'_, Arc<ServiceContext>>,
 ) -> Result<ActivitySearchResponse, String> {
-    debug!("Search activities params: page={}, page_size={}, account_filter={:?}, type_filter={:?}, keyword={:?}, sort={:?}",
-           page, page_size, account_id_filter, activity_type_filter, asset_id_keyword, sort);
-    let result = state.activity_service().search_activities(
-        page,
-        page_size,
-        account_id_filter,
-        activity_type_filter,
-        asset_id_keyword,
-        sort,
+    let mut filters = filters.unwrap_or_default();
+    if account_id_filter.is_some() {
+        filters.account_ids = account_id_filter;
+    }
+    if activity_type_filter.is_some() {
+        filters.activity_types = activity_type_filter;
+    }
+    if asset_id_keyword.is_some() {
+        filters.asset_id_keyword = asset_id_keyword;
+    }
+    let sort = sorts.unwrap_or_else(|| sort.into_iter().collect());
+    debug!(
+        "Search activities params: page={}, page_size={}, filters={:?}, sort={:?}",
+        page, page_size, filters, sort
     );
+    let result = state
+        .activity_service()
+        .search_activities(page, page_size, filters, sort);
     match &result {
         Ok(r) => debug!("Search activities result: total={}, data_len={}", r.meta.total_row_count, r.data.len()),
         Err(e) => debug!("Search activities error: {}", e),
@@ -161,6 +175,43 @@ pub async fn search_activities(
     Ok(result?)
 }
 
+#[tauri::command]
+pub async fn get_activity_views(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ActivitySavedView>, String> {
+    debug!("Fetching saved activity views...");
+    state
+        .activity_service()
+        .get_saved_views()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn save_activity_view(
+    view: NewActivitySavedView,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<ActivitySavedView, String> {
+    debug!("Saving activity view: {}", view.name);
+    state
+        .activity_service()
+        .save_view(view)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_activity_view(
+    view_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    debug!("Deleting activity view: {}", view_id);
+    state
+        .activity_service()
+        .delete_saved_view(view_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn create_activity(
     activity: NewActivity,
@@ -178,10 +229,14 @@ pub async fn create_activity(
         &result.asset_id,
     )?;
 
+    let mut dirty_ranges = DirtyRanges::new();
+    dirty_ranges.mark_activity(&result);
+
     let payload = PortfolioRequestPayload::builder()
         .account_ids(Some(vec![result.account_id.clone()]))
         .refetch_all_market_data(true)
         .symbols(Some(symbols_for_payload))
+        .dirty_ranges(Some(dirty_ranges))
         .build();
     emit_portfolio_trigger_recalculate(&handle, payload);
 
@@ -211,6 +266,11 @@ pub async fn update_activity(
         &result.asset_id,
     )?;
 
+    // Both the old and the new position of the activity invalidate snapshots.
+    let mut dirty_ranges = DirtyRanges::new();
+    dirty_ranges.mark_activity(&original_activity);
+    dirty_ranges.mark_activity(&result);
+
     let mut account_ids_for_payload = vec![result.account_id.clone()];
     if original_activity.account_id != result.account_id {
         account_ids_for_payload.push(original_activity.account_id);
@@ -220,6 +280,7 @@ pub async fn update_activity(
         .account_ids(Some(account_ids_for_payload))
         .refetch_all_market_data(true)
         .symbols(Some(symbols_for_payload))
+        .dirty_ranges(Some(dirty_ranges))
         .build();
     emit_portfolio_trigger_recalculate(&handle, payload);
 
@@ -237,11 +298,52 @@ pub async fn delete_activity(
     let handle = handle.clone();
     let account_id_clone = result.account_id.clone();
     let symbols = vec![result.asset_id.clone()];
+    let mut dirty_ranges = DirtyRanges::new();
+    dirty_ranges.mark_activity(&result);
 
     let payload = PortfolioRequestPayload::builder()
         .account_ids(Some(vec![account_id_clone]))
         .refetch_all_market_data(true)
         .symbols(Some(symbols))
+        .dirty_ranges(Some(dirty_ranges))
+        .build();
+    emit_portfolio_trigger_recalculate(&handle, payload);
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn bulk_edit_activities(
+    request: ActivityBulkRequest,
+    dry_run: bool,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<ActivityBulkResult, String> {
+    debug!("Bulk editing activities (dry run: {})...", dry_run);
+    let result = state
+        .activity_service()
+        .bulk_edit_activities(request, dry_run)
+        .await
+        .map_err(|e| e.to_string())?;
+    if result.dry_run || result.before.is_empty() {
+        return Ok(result);
+    }
+
+    // Both sides matter: moved activities leave one account and land in another
+    let mut dirty_ranges = DirtyRanges::new();
+    let mut account_ids: HashSet<String> = HashSet::new();
+    let mut symbols: HashSet<String> = HashSet::new();
+    for activity in result.before.iter().chain(&result.after) {
+        dirty_ranges.mark_activity(activity);
+        account_ids.insert(activity.account_id.clone());
+        symbols.insert(activity.asset_id.clone());
+    }
+
+    let payload = PortfolioRequestPayload::builder()
+        .account_ids(Some(account_ids.into_iter().collect()))
+        .refetch_all_market_data(true)
+        .symbols(Some(symbols.into_iter().collect()))
+        .dirty_ranges(Some(dirty_ranges))
         .build();
     emit_portfolio_trigger_recalculate(&handle, payload);
 
@@ -280,10 +382,39 @@ pub async fn check_activities_import(
     Ok(result)
 }
 
+#[tauri::command]
+pub async fn get_statement_parsers() -> Result<Vec<StatementParserInfo>, String> {
+    debug!("Fetching statement parsers...");
+    Ok(statement_parsers::get_statement_parsers()
+        .iter()
+        .map(|parser| StatementParserInfo {
+            id: parser.id().to_string(),
+            name: parser.name().to_string(),
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn parse_statement(
+    account_id: String,
+    parser_id: Option<String>,
+    content: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ActivityImport>, String> {
+    debug!("Parsing broker statement for account: {}", account_id);
+    let result = state
+        .activity_service()
+        .parse_statement(account_id, parser_id, content)
+        .await?;
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn import_activities(
     account_id: String,
     activities: Vec<ActivityImport>,
+    duplicate_strategy: Option<DuplicateStrategy>,
+    source: Option<NewImportSession>,
     state: State<'_, Arc<ServiceContext>>,
     handle: AppHandle,
 ) -> Result<Vec<ActivityImport>, String> {
@@ -294,20 +425,91 @@ pub async fn import_activities(
 
     let result = state
         .activity_service()
-        .import_activities(account_id.clone(), activities) // activities is moved here
+        .import_activities(
+            account_id.clone(),
+            activities, // activities is moved here
+            duplicate_strategy.unwrap_or_default(),
+            source.unwrap_or_default(),
+        )
         .await?;
     let handle = handle.clone();
 
+    let mut dirty_ranges = DirtyRanges::new();
+    for activity in &result {
+        dirty_ranges.mark_import(&account_id, activity);
+    }
+
     let payload = PortfolioRequestPayload::builder()
         .account_ids(Some(vec![account_id])) // account_id is still available
         .refetch_all_market_data(true)
         .symbols(Some(symbols_for_payload))
+        .dirty_ranges(Some(dirty_ranges))
         .build();
     emit_portfolio_trigger_recalculate(&handle, payload);
 
     Ok(result)
 }
 
+#[tauri::command]
+pub async fn get_import_sessions(
+    account_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ImportSession>, String> {
+    debug!("Fetching import sessions...");
+    state
+        .activity_service()
+        .get_import_sessions(account_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_import_session_activities(
+    session_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<Activity>, String> {
+    debug!("Fetching activities for import session: {}", session_id);
+    state
+        .activity_service()
+        .get_activities_by_import_session(&session_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn rollback_import_session(
+    session_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Vec<Activity>, String> {
+    debug!("Rolling back import session: {}", session_id);
+    let removed = state
+        .activity_service()
+        .rollback_import_session(session_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if removed.is_empty() {
+        return Ok(removed);
+    }
+
+    let mut dirty_ranges = DirtyRanges::new();
+    let mut account_ids: HashSet<String> = HashSet::new();
+    let mut symbols: HashSet<String> = HashSet::new();
+    for activity in &removed {
+        dirty_ranges.mark_activity(activity);
+        account_ids.insert(activity.account_id.clone());
+        symbols.insert(activity.asset_id.clone());
+    }
+
+    let payload = PortfolioRequestPayload::builder()
+        .account_ids(Some(account_ids.into_iter().collect()))
+        .refetch_all_market_data(true)
+        .symbols(Some(symbols.into_iter().collect()))
+        .dirty_ranges(Some(dirty_ranges))
+        .build();
+    emit_portfolio_trigger_recalculate(&handle, payload);
+
+    Ok(removed)
+}
+
 #[tauri::command]
 pub async fn export_activities(
     account_id: String,
@@ -316,7 +518,11 @@ pub async fn export_activities(
 ) -> Result<String, String> {
     debug!("Exporting activities for account: {}", account_id);
 
-    let search_result = state.activity_service().search_activities(1, 100000, Some(vec![account_id.clone()]), None, None, None)
+    let filters = ActivitySearchFilters {
+        account_ids: Some(vec![account_id.clone()]),
+        ..Default::default()
+    };
+    let search_result = state.activity_service().search_activities(1, 100000, filters, Vec::new())
         .map_err(|e| format!("Failed to get activities: {}", e))?;
     let mut activities: Vec<ActivityDetails> = search_result.data;
 
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use log::debug;
+use tauri::State;
+use wealthfolio_core::allocations::{
+    AllocationModel, NewAllocationModel, RebalanceOptions, RebalancePlan,
+};
+
+#[tauri::command]
+pub async fn get_allocation_models(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<AllocationModel>, String> {
+    debug!("Fetching allocation models...");
+    state
+        .allocation_service()
+        .get_allocation_models()
+        .map_err(|e| format!("Failed to load allocation models: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_allocation_model(
+    new_model: NewAllocationModel,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AllocationModel, String> {
+    debug!("Creating new allocation model...");
+    state
+        .allocation_service()
+        .create_allocation_model(new_model)
+        .await
+        .map_err(|e| format!("Failed to create allocation model: {}", e))
+}
+
+#[tauri::command]
+pub async fn update_allocation_model(
+    id: String,
+    updated_model: NewAllocationModel,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AllocationModel, String> {
+    debug!("Updating allocation model...");
+    state
+        .allocation_service()
+        .update_allocation_model(&id, updated_model)
+        .await
+        .map_err(|e| format!("Failed to update allocation model: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_allocation_model(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    debug!("Deleting allocation model...");
+    state
+        .allocation_service()
+        .delete_allocation_model(&id)
+        .await
+        .map_err(|e| format!("Failed to delete allocation model: {}", e))
+}
+
+#[tauri::command]
+pub async fn calculate_rebalance(
+    model_id: String,
+    options: Option<RebalanceOptions>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<RebalancePlan, String> {
+    debug!("Calculating rebalance for allocation model {}...", model_id);
+    let base_currency = state.get_display_currency();
+    state
+        .allocation_service()
+        .calculate_rebalance(&model_id, options.unwrap_or_default(), &base_currency)
+        .await
+        .map_err(|e| format!("Failed to calculate rebalance: {}", e))
+}
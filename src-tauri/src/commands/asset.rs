@@ -5,7 +5,7 @@ use crate::{
     events::{emit_portfolio_trigger_recalculate, PortfolioRequestPayload},
 };
 use tauri::{AppHandle, State};
-use wealthfolio_core::assets::{Asset, UpdateAssetProfile};
+use wealthfolio_core::assets::{Asset, SymbolIdentifier, UpdateAssetProfile};
 
 #[tauri::command]
 pub async fn get_asset_profile(
@@ -31,6 +31,19 @@ pub async fn update_asset_profile(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn update_asset_expense_ratio(
+    id: String,
+    expense_ratio: Option<f64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Asset, String> {
+    state
+        .asset_service()
+        .update_asset_expense_ratio(&id, expense_ratio)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn update_asset_data_source(
     id: String,
@@ -57,3 +70,50 @@ pub async fn update_asset_data_source(
 
     Ok(asset)
 }
+
+#[tauri::command]
+pub async fn resolve_symbol_identifier(
+    identifier: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<SymbolIdentifier>, String> {
+    state
+        .identifier_service()
+        .resolve(&identifier)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_symbol_identifiers(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<SymbolIdentifier>, String> {
+    state
+        .identifier_service()
+        .get_identifier_mappings()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn save_symbol_identifier(
+    identifier: String,
+    symbol: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SymbolIdentifier, String> {
+    state
+        .identifier_service()
+        .save_identifier_mapping(&identifier, &symbol)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_symbol_identifier(
+    identifier: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    state
+        .identifier_service()
+        .delete_identifier_mapping(&identifier)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use crate::events::{emit_portfolio_trigger_recalculate, PortfolioRequestPayload};
+use log::debug;
+use tauri::{AppHandle, State};
+use wealthfolio_core::audit::{
+    ChangeLogEntry, ENTITY_TYPE_ACCOUNT, ENTITY_TYPE_ACTIVITY, ENTITY_TYPE_ASSET,
+};
+use wealthfolio_core::portfolio::snapshot::DirtyRanges;
+
+/// Recalculates the history touched by restored entities: the dates of restored
+/// activities, whole restored accounts, and every account holding a restored asset.
+fn recalculate_after_restore(handle: &AppHandle, restored: &[ChangeLogEntry]) {
+    let mut dirty_ranges = DirtyRanges::new();
+    let mut account_ids: HashSet<String> = HashSet::new();
+    let mut symbols: HashSet<String> = HashSet::new();
+    let mut all_accounts = false;
+    for entry in restored {
+        match entry.entity_type.as_str() {
+            ENTITY_TYPE_ACTIVITY => {
+                for activity in entry.activity_snapshots() {
+                    dirty_ranges.mark_activity(&activity);
+                    account_ids.insert(activity.account_id.clone());
+                    symbols.insert(activity.asset_id.clone());
+                }
+            }
+            ENTITY_TYPE_ACCOUNT => {
+                dirty_ranges.mark_all(&entry.entity_id);
+                account_ids.insert(entry.entity_id.clone());
+            }
+            ENTITY_TYPE_ASSET => {
+                all_accounts = true;
+                symbols.insert(entry.entity_id.clone());
+            }
+            _ => {}
+        }
+    }
+
+    let payload = PortfolioRequestPayload::builder()
+        .account_ids((!all_accounts).then(|| account_ids.into_iter().collect()))
+        .refetch_all_market_data(true)
+        .symbols(Some(symbols.into_iter().collect()))
+        .dirty_ranges((!all_accounts).then_some(dirty_ranges))
+        .build();
+    emit_portfolio_trigger_recalculate(handle, payload);
+}
+
+#[tauri::command]
+pub async fn get_entity_history(
+    entity_type: String,
+    entity_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ChangeLogEntry>, String> {
+    debug!("Fetching change history for {} {}", entity_type, entity_id);
+    state
+        .audit_service()
+        .get_entity_history(&entity_type, &entity_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_recent_changes(
+    entity_type: Option<String>,
+    operation: Option<String>,
+    limit: Option<i64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ChangeLogEntry>, String> {
+    debug!("Fetching recent changes...");
+    state
+        .audit_service()
+        .get_changes(entity_type, operation, limit)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn restore_entity_version(
+    change_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<ChangeLogEntry, String> {
+    debug!("Restoring entity version from change {}", change_id);
+    let restored = state
+        .audit_service()
+        .restore_version(change_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    recalculate_after_restore(&handle, std::slice::from_ref(&restored));
+    Ok(restored)
+}
+
+#[tauri::command]
+pub async fn undelete_entity(
+    entity_type: String,
+    entity_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Vec<ChangeLogEntry>, String> {
+    debug!("Undeleting {} {}", entity_type, entity_id);
+    let restored = state
+        .audit_service()
+        .undelete(entity_type, entity_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    recalculate_after_restore(&handle, &restored);
+    Ok(restored)
+}
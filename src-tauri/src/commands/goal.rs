@@ -3,7 +3,7 @@ use std::sync::Arc;
 use crate::context::ServiceContext;
 use log::debug;
 use tauri::State;
-use wealthfolio_core::goals::goals_model::{Goal, GoalsAllocation, NewGoal};
+use wealthfolio_core::goals::goals_model::{Goal, GoalProgressReport, GoalsAllocation, NewGoal};
 
 #[tauri::command]
 pub async fn get_goals(state: State<'_, Arc<ServiceContext>>) -> Result<Vec<Goal>, String> {
@@ -73,3 +73,35 @@ pub async fn load_goals_allocations(
         .load_goals_allocations()
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn get_goals_progress(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<GoalProgressReport>, String> {
+    debug!("Calculating goals progress...");
+    state
+        .goal_progress_service()
+        .get_goals_progress()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_goal_progress(
+    goal_id: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<GoalProgressReport, String> {
+    debug!("Calculating progress for goal {}...", goal_id);
+    let parse_date = |date_str: String| {
+        chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date {}: {}", date_str, e))
+    };
+    let start_date = start_date.map(parse_date).transpose()?;
+    let end_date = end_date.map(parse_date).transpose()?;
+    state
+        .goal_progress_service()
+        .get_goal_progress(&goal_id, start_date, end_date)
+        .map_err(|e| e.to_string())
+}
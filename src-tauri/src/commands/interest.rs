@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use crate::{
+    context::ServiceContext,
+    events::{emit_portfolio_trigger_recalculate, PortfolioRequestPayload},
+};
+use chrono::{NaiveDate, Utc};
+use log::debug;
+use tauri::{AppHandle, State};
+use wealthfolio_core::{
+    activities::Activity,
+    interest::{CashInterestRate, CashInterestRateUpdate, NewCashInterestRate},
+};
+
+// Accrued interest is part of every stored valuation of the account, so a
+// schedule change recalculates its history.
+fn recalculate_account(handle: &AppHandle, account_id: &str) {
+    let payload = PortfolioRequestPayload::builder()
+        .account_ids(Some(vec![account_id.to_string()]))
+        .refetch_all_market_data(false)
+        .symbols(None)
+        .build();
+    emit_portfolio_trigger_recalculate(handle, payload);
+}
+
+#[tauri::command]
+pub async fn get_interest_rates(
+    account_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<CashInterestRate>, String> {
+    debug!("Fetching cash interest rates...");
+    state
+        .interest_service()
+        .get_interest_rates(account_id.as_deref())
+        .map_err(|e| format!("Failed to load interest rates: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_interest_rate(
+    rate: NewCashInterestRate,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<CashInterestRate, String> {
+    debug!("Adding interest rate for account {}...", rate.account_id);
+    let result = state
+        .interest_service()
+        .create_interest_rate(rate)
+        .await
+        .map_err(|e| format!("Failed to add interest rate: {}", e))?;
+    recalculate_account(&handle, &result.account_id);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn update_interest_rate(
+    rate_update: CashInterestRateUpdate,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<CashInterestRate, String> {
+    debug!("Updating interest rate {}...", rate_update.id);
+    let result = state
+        .interest_service()
+        .update_interest_rate(rate_update)
+        .await
+        .map_err(|e| format!("Failed to update interest rate: {}", e))?;
+    recalculate_account(&handle, &result.account_id);
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn delete_interest_rate(
+    rate_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<(), String> {
+    debug!("Deleting interest rate {}...", rate_id);
+    let account_id = state
+        .interest_service()
+        .get_interest_rates(None)
+        .map_err(|e| format!("Failed to load interest rates: {}", e))?
+        .into_iter()
+        .find(|r| r.id == rate_id)
+        .map(|r| r.account_id);
+    state
+        .interest_service()
+        .delete_interest_rate(&rate_id)
+        .await
+        .map_err(|e| format!("Failed to delete interest rate: {}", e))?;
+    if let Some(account_id) = account_id {
+        recalculate_account(&handle, &account_id);
+    }
+    Ok(())
+}
+
+/// Drafts don't move cash until they are approved, so nothing is recalculated here.
+#[tauri::command]
+pub async fn generate_interest_activities(
+    account_id: Option<String>,
+    end_date: Option<NaiveDate>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<Activity>, String> {
+    debug!("Generating draft interest activities...");
+    let end_date = end_date.unwrap_or_else(|| Utc::now().date_naive());
+    state
+        .interest_service()
+        .generate_interest_activities(account_id.as_deref(), end_date)
+        .await
+        .map_err(|e| format!("Failed to generate interest activities: {}", e))
+}
@@ -3,7 +3,10 @@ use std::sync::Arc;
 use crate::context::ServiceContext;
 use log::debug;
 use tauri::State;
-use wealthfolio_core::limits::{ContributionLimit, DepositsCalculation, NewContributionLimit};
+use wealthfolio_core::limits::{
+    ContributionLimit, ContributionLimitPreset, ContributionRoom, DepositsCalculation,
+    NewContributionLimit,
+};
 
 #[tauri::command]
 pub async fn get_contribution_limits(
@@ -62,9 +65,30 @@ pub async fn calculate_deposits_for_contribution_limit(
     state: State<'_, Arc<ServiceContext>>,
 ) -> Result<DepositsCalculation, String> {
     debug!("Calculating deposits for contribution limit...");
-    let base_currency = state.base_currency.read().unwrap();
+    let base_currency = state.get_display_currency();
     state
         .limits_service()
         .calculate_deposits_for_contribution_limit(&limit_id, &base_currency)
         .map_err(|e| format!("Failed to calculate deposits for contribution limit: {}", e))
 }
+
+#[tauri::command]
+pub async fn calculate_contribution_room(
+    limit_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<ContributionRoom, String> {
+    debug!("Calculating contribution room for limit {}...", limit_id);
+    let base_currency = state.get_display_currency();
+    state
+        .limits_service()
+        .calculate_contribution_room(&limit_id, &base_currency)
+        .map_err(|e| format!("Failed to calculate contribution room: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_contribution_limit_presets(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ContributionLimitPreset>, String> {
+    debug!("Fetching contribution limit presets...");
+    Ok(state.limits_service().get_contribution_limit_presets())
+}
@@ -1,11 +1,16 @@
 pub mod account;
 pub mod activity;
 pub mod addon;
+pub mod allocation;
 pub mod asset;
+pub mod audit;
 pub mod goal;
+pub mod interest;
 pub mod limits;
 pub mod market_data;
 pub mod portfolio;
+pub mod profile;
+pub mod report;
 pub mod settings;
 pub mod utilities;
 pub mod secrets;
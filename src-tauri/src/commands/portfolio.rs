@@ -10,12 +10,25 @@ use crate::{
 use log::debug;
 use tauri::{AppHandle, State};
 use wealthfolio_core::{
+    fees::{FeeAnalysis, FeeAnalysisOptions},
     holdings::Holding,
-    income::IncomeSummary,
+    income::{ForeignTaxReport, IncomeProjection, IncomeProjectionOptions, IncomeSummary},
     performance::{PerformanceMetrics, SimplePerformanceMetrics},
     valuation::DailyAccountValuation,
 };
 
+// Valuations are stored against the household base currency; a profile in view reports
+// them in its own.
+fn in_display_currency(
+    state: &ServiceContext,
+    valuations: Vec<DailyAccountValuation>,
+) -> Result<Vec<DailyAccountValuation>, String> {
+    state
+        .valuation_service()
+        .convert_valuations(valuations, &state.get_display_currency())
+        .map_err(|e| format!("Failed to convert valuations: {}", e))
+}
+
 #[tauri::command]
 pub async fn recalculate_portfolio(handle: AppHandle) -> Result<(), String> {
     debug!("Emitting PORTFOLIO_TRIGGER_RECALCULATE event...");
@@ -46,10 +59,10 @@ pub async fn get_holdings(
     account_id: String,
 ) -> Result<Vec<Holding>, String> {
     debug!("Get holdings...");
-    let base_currency = state.get_base_currency();
+    let base_currency = state.get_display_currency();
     state
         .holdings_service()
-        .get_holdings(&account_id, &base_currency)
+        .get_holdings(&state.scoped_account_id(&account_id), &base_currency)
         .await
         .map_err(|e| e.to_string())
 }
@@ -64,10 +77,31 @@ pub async fn get_holding(
         "Get specific holding for asset {} in account {}",
         asset_id, account_id
     );
-    let base_currency = state.get_base_currency();
+    let base_currency = state.get_display_currency();
+    state
+        .holdings_service()
+        .get_holding(&state.scoped_account_id(&account_id), &asset_id, &base_currency)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_holdings_as_of(
+    state: State<'_, Arc<ServiceContext>>,
+    account_id: String,
+    date: String,
+) -> Result<Vec<Holding>, String> {
+    debug!("Get holdings for account {} as of {}", account_id, date);
+    let as_of_date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date: {}", e))?;
+    let base_currency = state.get_display_currency();
     state
         .holdings_service()
-        .get_holding(&account_id, &asset_id, &base_currency)
+        .get_holdings_as_of(
+            &state.scoped_account_id(&account_id),
+            &base_currency,
+            as_of_date,
+        )
         .await
         .map_err(|e| e.to_string())
 }
@@ -95,10 +129,15 @@ pub async fn get_historical_valuations(
         })
         .transpose()?;
 
-    state
+    let valuations = state
         .valuation_service()
-        .get_historical_valuations(&account_id, from_date_opt, to_date_opt)
-        .map_err(|e| e.to_string())
+        .get_historical_valuations(
+            &state.scoped_account_id(&account_id),
+            from_date_opt,
+            to_date_opt,
+        )
+        .map_err(|e| e.to_string())?;
+    in_display_currency(&state, valuations)
 }
 
 #[tauri::command]
@@ -119,16 +158,20 @@ pub async fn get_latest_valuations(
             .collect()
     } else {
         account_ids
+            .iter()
+            .map(|id| state.scoped_account_id(id))
+            .collect()
     };
 
     if ids_to_process.is_empty() {
         return Ok(Vec::new());
     }
 
-    state
+    let valuations = state
         .valuation_service()
         .get_latest_valuations(&ids_to_process)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    in_display_currency(&state, valuations)
 }
 
 #[tauri::command]
@@ -142,6 +185,42 @@ pub async fn get_income_summary(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn get_income_projection(
+    state: State<'_, Arc<ServiceContext>>,
+    options: Option<IncomeProjectionOptions>,
+) -> Result<IncomeProjection, String> {
+    debug!("Projecting income...");
+    state
+        .income_service()
+        .get_income_projection(options.unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_foreign_tax_report(
+    state: State<'_, Arc<ServiceContext>>,
+    year: i32,
+) -> Result<ForeignTaxReport, String> {
+    debug!("Building foreign tax report for {}...", year);
+    state
+        .income_service()
+        .get_foreign_tax_report(year)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_fee_analysis(
+    state: State<'_, Arc<ServiceContext>>,
+    options: Option<FeeAnalysisOptions>,
+) -> Result<FeeAnalysis, String> {
+    debug!("Analysing fees...");
+    state
+        .fee_service()
+        .get_fee_analysis(options.unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn calculate_accounts_simple_performance(
     state: State<'_, Arc<ServiceContext>>,
@@ -163,6 +242,9 @@ pub async fn calculate_accounts_simple_performance(
             .collect()
     } else {
         account_ids
+            .iter()
+            .map(|id| state.scoped_account_id(id))
+            .collect()
     };
 
     if ids_to_process.is_empty() {
@@ -205,6 +287,12 @@ pub async fn calculate_performance_history(
         })
         .transpose()?;
 
+    let item_id = if item_type == "account" {
+        state.scoped_account_id(&item_id)
+    } else {
+        item_id
+    };
+
     state
         .performance_service()
         .calculate_performance_history(&item_type, &item_id, start_date_opt, end_date_opt)
@@ -242,6 +330,12 @@ pub async fn calculate_performance_summary(
         })
         .transpose()?;
 
+    let item_id = if item_type == "account" {
+        state.scoped_account_id(&item_id)
+    } else {
+        item_id
+    };
+
     state
         .performance_service()
         .calculate_performance_summary(&item_type, &item_id, start_date_opt, end_date_opt)
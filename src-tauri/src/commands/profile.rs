@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use crate::{
+    context::ServiceContext,
+    events::{
+        emit_portfolio_trigger_recalculate, emit_portfolio_trigger_update, PortfolioRequestPayload,
+    },
+};
+use log::debug;
+use tauri::{AppHandle, State};
+use wealthfolio_core::{
+    constants::PORTFOLIO_TOTAL_ACCOUNT_ID,
+    profiles::{NewProfile, Profile, ProfileUpdate},
+    valuation::DailyAccountValuation,
+};
+
+#[tauri::command]
+pub async fn get_profiles(state: State<'_, Arc<ServiceContext>>) -> Result<Vec<Profile>, String> {
+    debug!("Fetching profiles...");
+    state
+        .profile_service()
+        .get_profiles()
+        .map_err(|e| format!("Failed to load profiles: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_active_profile(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<Profile>, String> {
+    debug!("Fetching active profile...");
+    state
+        .profile_service()
+        .get_active_profile()
+        .map_err(|e| format!("Failed to load active profile: {}", e))
+}
+
+#[tauri::command]
+pub async fn set_active_profile(
+    profile_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<Profile>, String> {
+    debug!("Switching to profile {:?}...", profile_id);
+    state
+        .profile_service()
+        .set_active_profile(profile_id)
+        .await
+        .map_err(|e| format!("Failed to switch profile: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_profile(
+    profile: NewProfile,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Profile, String> {
+    debug!("Adding profile {}...", profile.name);
+    state
+        .profile_service()
+        .create_profile(profile)
+        .await
+        .map_err(|e| format!("Failed to create profile: {}", e))
+}
+
+#[tauri::command]
+pub async fn update_profile(
+    profile_update: ProfileUpdate,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Profile, String> {
+    debug!("Updating profile {}...", profile_update.id);
+    let previous_currency = state
+        .profile_service()
+        .get_profiles()
+        .map_err(|e| format!("Failed to load profiles: {}", e))?
+        .into_iter()
+        .find(|p| p.id == profile_update.id)
+        .map(|p| p.base_currency);
+    let profile = state
+        .profile_service()
+        .update_profile(profile_update)
+        .await
+        .map_err(|e| format!("Failed to update profile: {}", e))?;
+
+    // The profile's aggregate is held in its base currency, so a new one rebuilds it.
+    if previous_currency.as_deref() != Some(profile.base_currency.as_str()) {
+        let payload = PortfolioRequestPayload::builder().account_ids(None).build();
+        emit_portfolio_trigger_update(&handle, payload);
+    }
+    Ok(profile)
+}
+
+#[tauri::command]
+pub async fn delete_profile(
+    profile_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<(), String> {
+    debug!("Deleting profile {}...", profile_id);
+    state
+        .profile_service()
+        .delete_profile(&profile_id)
+        .await
+        .map_err(|e| format!("Failed to delete profile: {}", e))?;
+
+    // The default profile takes over the deleted profile's accounts.
+    let payload = PortfolioRequestPayload::builder().account_ids(None).build();
+    emit_portfolio_trigger_recalculate(&handle, payload);
+    Ok(())
+}
+
+/// Latest valuation of the whole household (`TOTAL`) and of each profile, for the
+/// consolidated view. Each profile's values are in its own base currency, with
+/// `fx_rate_to_base` into the household one.
+#[tauri::command]
+pub async fn get_household_valuations(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<DailyAccountValuation>, String> {
+    debug!("Fetching household valuations...");
+    let mut ids = vec![PORTFOLIO_TOTAL_ACCOUNT_ID.to_string()];
+    ids.extend(
+        state
+            .profile_service()
+            .get_profiles()
+            .map_err(|e| format!("Failed to load profiles: {}", e))?
+            .into_iter()
+            .map(|p| p.id),
+    );
+    state
+        .valuation_service()
+        .get_latest_valuations(&ids)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use log::debug;
+use tauri::State;
+use wealthfolio_core::reports::{
+    PortfolioStatement, RenderedStatement, StatementFormat, StatementRequest,
+};
+
+#[tauri::command]
+pub async fn get_portfolio_statement(
+    request: StatementRequest,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<PortfolioStatement, String> {
+    debug!(
+        "Building {:?} statement from {} to {}...",
+        request.periodicity, request.start_date, request.end_date
+    );
+    state
+        .report_service()
+        .get_portfolio_statement(&request)
+        .map_err(|e| format!("Failed to build statement: {}", e))
+}
+
+#[tauri::command]
+pub async fn export_portfolio_statement(
+    request: StatementRequest,
+    format: StatementFormat,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<RenderedStatement, String> {
+    debug!(
+        "Exporting {:?} statement as {:?}...",
+        request.periodicity, format
+    );
+    state
+        .report_service()
+        .export_portfolio_statement(&request, format)
+        .map_err(|e| format!("Failed to export statement: {}", e))
+}
@@ -2,9 +2,11 @@ use std::sync::Arc;
 
 use crate::context::ServiceContext;
 use crate::events::{emit_portfolio_trigger_recalculate, PortfolioRequestPayload};
+use chrono::NaiveDate;
 use log::debug;
 use tauri::{AppHandle, State};
-use wealthfolio_core::fx::fx_model::{ExchangeRate, NewExchangeRate};
+use rust_decimal::Decimal;
+use wealthfolio_core::fx::fx_model::{ExchangeRate, FxConversionExplanation, NewExchangeRate};
 use wealthfolio_core::settings::{Settings, SettingsUpdate};
 
 #[tauri::command]
@@ -47,6 +49,15 @@ pub async fn update_settings(
         }
     }
 
+    let fx_policy_changed = match settings_update.fx_conversion_policy {
+        Some(ref policy) => state
+            .fx_service()
+            .get_conversion_policy()
+            .map(|current| &current != policy)
+            .unwrap_or(true),
+        None => false,
+    };
+
     // Update settings in the database (this applies all changes in settings_update)
     service
         .update_settings(&settings_update)
@@ -77,6 +88,18 @@ pub async fn update_settings(
                 emit_portfolio_trigger_recalculate(&handle, payload);
             });
         }
+    } else if fx_policy_changed {
+        debug!("FX conversion policy changed, recalculating portfolio.");
+        let handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            // Converted values depend on the policy, so rebuild all snapshots
+            let payload = PortfolioRequestPayload::builder()
+                .account_ids(None)
+                .refetch_all_market_data(false)
+                .symbols(None)
+                .build();
+            emit_portfolio_trigger_recalculate(&handle, payload);
+        });
     }
 
     // Return the latest settings from the database
@@ -158,3 +181,73 @@ pub async fn delete_exchange_rate(
     });
     Ok(())
 }
+
+#[tauri::command]
+pub async fn import_reference_rates(
+    provider_id: String,
+    content: String,
+    currencies: Option<Vec<String>>,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Importing reference rates from {} file...", provider_id);
+    let imported = state
+        .fx_service()
+        .import_reference_rates(&provider_id, &content, currencies)
+        .await
+        .map_err(|e| format!("Failed to import reference rates: {}", e))?;
+
+    let handle = handle.clone();
+    tauri::async_runtime::spawn(async move {
+        // Emit event to trigger portfolio update
+        emit_portfolio_trigger_recalculate(&handle, PortfolioRequestPayload::builder().build());
+    });
+    Ok(imported)
+}
+
+#[tauri::command]
+pub async fn sync_reference_rates(
+    provider_id: String,
+    start_date: String,
+    end_date: String,
+    currencies: Option<Vec<String>>,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Syncing reference rates from {}...", provider_id);
+    let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start date: {}", e))?;
+    let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid end date: {}", e))?;
+
+    let synced = state
+        .fx_service()
+        .sync_reference_rates(&provider_id, start, end, currencies)
+        .await
+        .map_err(|e| format!("Failed to sync reference rates: {}", e))?;
+
+    let handle = handle.clone();
+    tauri::async_runtime::spawn(async move {
+        // Emit event to trigger portfolio update
+        emit_portfolio_trigger_recalculate(&handle, PortfolioRequestPayload::builder().build());
+    });
+    Ok(synced)
+}
+
+#[tauri::command]
+pub async fn explain_currency_conversion(
+    amount: Decimal,
+    from_currency: String,
+    to_currency: String,
+    date: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<FxConversionExplanation, String> {
+    debug!("Explaining conversion from {} to {} on {}...", from_currency, to_currency, date);
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date: {}", e))?;
+
+    state
+        .fx_service()
+        .explain_conversion_for_date(amount, &from_currency, &to_currency, date)
+        .map_err(|e| format!("Failed to explain currency conversion: {}", e))
+}
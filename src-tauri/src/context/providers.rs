@@ -1,22 +1,28 @@
 use super::registry::ServiceContext;
 use std::sync::{Arc, RwLock};
 use wealthfolio_core::{
-    accounts::{AccountRepository, AccountService},
+    accounts::{AccountGroupRepository, AccountGroupService, AccountRepository, AccountService},
     activities::{ActivityRepository, ActivityService},
+    allocations::{AllocationRepository, AllocationService},
+    audit::{AuditRepository, AuditService},
     db::{self, write_actor},
     fx::{FxRepository, FxService, FxServiceTrait},
-    goals::{GoalRepository, GoalService},
+    goals::{GoalProgressService, GoalRepository, GoalService},
+    interest::{CashInterestRepository, CashInterestService},
     limits::{ContributionLimitRepository, ContributionLimitService},
     market_data::{MarketDataRepository, MarketDataService, MarketDataServiceTrait},
     portfolio::{
         holdings::{HoldingsService, HoldingsValuationService},
+        fees::FeeService,
         income::IncomeService,
         performance::PerformanceService,
     },
+    profiles::{ProfileRepository, ProfileService, ProfileServiceTrait},
+    reports::ReportService,
     settings::{settings_repository::SettingsRepository, SettingsService, SettingsServiceTrait},
     snapshot::{SnapshotRepository, SnapshotService},
     valuation::{ValuationRepository, ValuationService},
-    AssetRepository, AssetService,
+    AssetRepository, AssetService, IdentifierRepository, IdentifierService,
 };
 
 // Other imports
@@ -34,14 +40,21 @@ pub async fn initialize_context(
     // Instantiate Repositories
     let settings_repository = Arc::new(SettingsRepository::new(pool.clone(), writer.clone()));
     let account_repository = Arc::new(AccountRepository::new(pool.clone(), writer.clone()));
+    let profile_repository = Arc::new(ProfileRepository::new(pool.clone(), writer.clone()));
+    let account_group_repository =
+        Arc::new(AccountGroupRepository::new(pool.clone(), writer.clone()));
     let activity_repository = Arc::new(ActivityRepository::new(pool.clone(), writer.clone()));
     let asset_repository = Arc::new(AssetRepository::new(pool.clone(), writer.clone()));
     let goal_repo = Arc::new(GoalRepository::new(pool.clone(), writer.clone()));
     let market_data_repo = Arc::new(MarketDataRepository::new(pool.clone(), writer.clone()));
     let limit_repository = Arc::new(ContributionLimitRepository::new(pool.clone(), writer.clone()));
+    let allocation_repository = Arc::new(AllocationRepository::new(pool.clone(), writer.clone()));
     let fx_repository = Arc::new(FxRepository::new(pool.clone(), writer.clone()));
     let snapshot_repository = Arc::new(SnapshotRepository::new(pool.clone(), writer.clone()));
     let valuation_repository = Arc::new(ValuationRepository::new(pool.clone(), writer.clone()));
+    let identifier_repository = Arc::new(IdentifierRepository::new(pool.clone(), writer.clone()));
+    let audit_repository = Arc::new(AuditRepository::new(pool.clone(), writer.clone()));
+    let interest_repository = Arc::new(CashInterestRepository::new(pool.clone(), writer.clone()));
     // Instantiate Transaction Executor using the Arc<DbPool> directly
     let transaction_executor = pool.clone();
 
@@ -53,8 +66,19 @@ pub async fn initialize_context(
         fx_service.clone(),
     ));
     let settings = settings_service.get_settings()?;
-    let base_currency_string = settings.base_currency.clone();
-    let base_currency = Arc::new(RwLock::new(base_currency_string.clone()));
+    fx_service.set_conversion_policy(settings.fx_conversion_policy.clone())?;
+
+    // A profile in view reports in its own base currency; calculations stay in the household one.
+    let active_profile_id = Arc::new(RwLock::new(None));
+    let profile_service = Arc::new(ProfileService::new(
+        profile_repository.clone(),
+        account_repository.clone(),
+        settings_repository.clone(),
+        fx_service.clone(),
+        active_profile_id.clone(),
+    ));
+    profile_service.initialize()?;
+    let base_currency = Arc::new(RwLock::new(settings.base_currency.clone()));
     let instance_id = Arc::new(settings.instance_id.clone());
 
     let market_data_service: Arc<dyn MarketDataServiceTrait> =
@@ -65,29 +89,54 @@ pub async fn initialize_context(
         market_data_service.clone(),
     )?);
 
+    let identifier_service = Arc::new(IdentifierService::new(
+        identifier_repository.clone(),
+        market_data_service.clone(),
+    ));
+
     let account_service = Arc::new(AccountService::new(
         account_repository.clone(),
         fx_service.clone(),
         transaction_executor.clone(),
         base_currency.clone(),
+        active_profile_id.clone(),
+    ));
+    let account_group_service = Arc::new(AccountGroupService::new(
+        account_group_repository.clone(),
+        account_repository.clone(),
     ));
     let activity_service = Arc::new(ActivityService::new(
         activity_repository.clone(),
         account_service.clone(),
         asset_service.clone(),
         fx_service.clone(),
+        identifier_service.clone(),
+        active_profile_id.clone(),
+    ));
+    let goal_service = Arc::new(GoalService::new(
+        goal_repo.clone(),
+        active_profile_id.clone(),
+    ));
+    let audit_service = Arc::new(AuditService::new(
+        audit_repository.clone(),
+        account_repository.clone(),
+        active_profile_id.clone(),
     ));
-    let goal_service = Arc::new(GoalService::new(goal_repo.clone()));
     let limits_service = Arc::new(ContributionLimitService::new(
         fx_service.clone(),
         limit_repository.clone(),
         activity_repository.clone(),
+        active_profile_id.clone(),
     ));
 
     let income_service = Arc::new(IncomeService::new(
         fx_service.clone(),
         activity_repository.clone(),
+        snapshot_repository.clone(),
+        asset_repository.clone(),
+        market_data_service.clone(),
         base_currency.clone(),
+        active_profile_id.clone(),
     ));
 
     let snapshot_service = Arc::new(SnapshotService::new(
@@ -104,17 +153,29 @@ pub async fn initialize_context(
         market_data_service.clone(),
     ));
 
+    let interest_service = Arc::new(CashInterestService::new(
+        interest_repository.clone(),
+        account_repository.clone(),
+        account_group_repository.clone(),
+        activity_service.clone(),
+        snapshot_service.clone(),
+        fx_service.clone(),
+        active_profile_id.clone(),
+    ));
+
     let valuation_service = Arc::new(ValuationService::new(
         base_currency.clone(),
         valuation_repository.clone(),
         snapshot_service.clone(),
         market_data_service.clone(),
         fx_service.clone(),
+        interest_service.clone(),
     ));
 
     let performance_service = Arc::new(PerformanceService::new(
         valuation_service.clone(),
         market_data_service.clone(),
+        active_profile_id.clone(),
     ));
 
     let holdings_service = Arc::new(HoldingsService::new(
@@ -123,19 +184,62 @@ pub async fn initialize_context(
         holdings_valuation_service.clone(),
     ));
 
+    let goal_progress_service = Arc::new(GoalProgressService::new(
+        goal_repo.clone(),
+        valuation_service.clone(),
+        active_profile_id.clone(),
+    ));
+
+    let allocation_service = Arc::new(AllocationService::new(
+        allocation_repository.clone(),
+        account_service.clone(),
+        holdings_service.clone(),
+        market_data_service.clone(),
+        fx_service.clone(),
+    ));
+
+    let fee_service = Arc::new(FeeService::new(
+        account_service.clone(),
+        activity_repository.clone(),
+        asset_repository.clone(),
+        snapshot_service.clone(),
+        valuation_service.clone(),
+        market_data_service.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+
+    let report_service = Arc::new(ReportService::new(
+        account_service.clone(),
+        activity_repository.clone(),
+        valuation_service.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+
     Ok(ServiceContext {
         base_currency,
+        active_profile_id,
         instance_id,
         settings_service,
+        profile_service,
         account_service,
+        account_group_service,
         activity_service,
         asset_service,
+        identifier_service,
+        audit_service,
         goal_service,
+        goal_progress_service,
         market_data_service,
         limits_service,
+        allocation_service,
         fx_service,
         performance_service,
         income_service,
+        fee_service,
+        report_service,
+        interest_service,
         snapshot_service,
         holdings_service,
         valuation_service,
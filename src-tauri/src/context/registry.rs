@@ -1,22 +1,34 @@
 use std::sync::{Arc, RwLock};
 use wealthfolio_core::{
-    self, accounts, activities, assets, fx, goals, limits, market_data, portfolio, settings,
+    self, accounts, activities, allocations, assets, audit, constants::PORTFOLIO_TOTAL_ACCOUNT_ID,
+    fx, goals, interest, limits, market_data, portfolio, profiles, reports, settings,
 };
 pub struct ServiceContext {
     pub base_currency: Arc<RwLock<String>>,
+    /// Profile in view; `None` is the household view across all profiles.
+    pub active_profile_id: Arc<RwLock<Option<String>>>,
     pub instance_id: Arc<String>,
 
     // Services
     pub settings_service: Arc<dyn settings::SettingsServiceTrait>,
+    pub profile_service: Arc<dyn profiles::ProfileServiceTrait>,
     pub activity_service: Arc<dyn activities::ActivityServiceTrait>,
     pub account_service: Arc<dyn accounts::AccountServiceTrait>,
+    pub account_group_service: Arc<dyn accounts::AccountGroupServiceTrait>,
     pub goal_service: Arc<dyn goals::GoalServiceTrait>,
+    pub goal_progress_service: Arc<dyn goals::GoalProgressServiceTrait>,
     pub asset_service: Arc<dyn assets::AssetServiceTrait>,
+    pub identifier_service: Arc<dyn assets::IdentifierServiceTrait>,
+    pub audit_service: Arc<dyn audit::AuditServiceTrait>,
     pub market_data_service: Arc<dyn market_data::MarketDataServiceTrait>,
     pub limits_service: Arc<dyn limits::ContributionLimitServiceTrait>,
+    pub allocation_service: Arc<dyn allocations::AllocationServiceTrait>,
     pub fx_service: Arc<dyn fx::FxServiceTrait>,
     pub performance_service: Arc<dyn portfolio::performance::PerformanceServiceTrait>,
     pub income_service: Arc<dyn portfolio::income::IncomeServiceTrait>,
+    pub fee_service: Arc<dyn portfolio::fees::FeeServiceTrait>,
+    pub report_service: Arc<dyn reports::ReportServiceTrait>,
+    pub interest_service: Arc<dyn interest::CashInterestServiceTrait>,
     pub snapshot_service: Arc<dyn portfolio::snapshot::SnapshotServiceTrait>,
     pub holdings_service: Arc<dyn portfolio::holdings::HoldingsServiceTrait>,
     pub valuation_service: Arc<dyn portfolio::valuation::ValuationServiceTrait>,
@@ -31,14 +43,44 @@ impl ServiceContext {
         *self.base_currency.write().unwrap() = new_currency;
     }
 
+    /// Currency amounts are shown in: the active profile's base currency, or the
+    /// household one. Snapshots and valuations are always calculated in the latter.
+    pub fn get_display_currency(&self) -> String {
+        self.profile_service
+            .get_base_currency()
+            .unwrap_or_else(|_| self.get_base_currency())
+    }
+
+    pub fn get_active_profile_id(&self) -> Option<String> {
+        self.active_profile_id.read().unwrap().clone()
+    }
+
+    /// Maps `TOTAL` to the active profile's aggregate so portfolio-wide views
+    /// follow the profile in view. Other ids pass through.
+    pub fn scoped_account_id(&self, account_id: &str) -> String {
+        if account_id == PORTFOLIO_TOTAL_ACCOUNT_ID {
+            profiles::scoped_total_account_id(&self.active_profile_id)
+        } else {
+            account_id.to_string()
+        }
+    }
+
     pub fn settings_service(&self) -> Arc<dyn settings::SettingsServiceTrait> {
         Arc::clone(&self.settings_service)
     }
 
+    pub fn profile_service(&self) -> Arc<dyn profiles::ProfileServiceTrait> {
+        Arc::clone(&self.profile_service)
+    }
+
     pub fn account_service(&self) -> Arc<dyn accounts::AccountServiceTrait> {
         Arc::clone(&self.account_service)
     }
 
+    pub fn account_group_service(&self) -> Arc<dyn accounts::AccountGroupServiceTrait> {
+        Arc::clone(&self.account_group_service)
+    }
+
     pub fn activity_service(&self) -> Arc<dyn activities::ActivityServiceTrait> {
         Arc::clone(&self.activity_service)
     }
@@ -47,10 +89,22 @@ impl ServiceContext {
         Arc::clone(&self.asset_service)
     }
 
+    pub fn identifier_service(&self) -> Arc<dyn assets::IdentifierServiceTrait> {
+        Arc::clone(&self.identifier_service)
+    }
+
+    pub fn audit_service(&self) -> Arc<dyn audit::AuditServiceTrait> {
+        Arc::clone(&self.audit_service)
+    }
+
     pub fn goal_service(&self) -> Arc<dyn goals::GoalServiceTrait> {
         Arc::clone(&self.goal_service)
     }
 
+    pub fn goal_progress_service(&self) -> Arc<dyn goals::GoalProgressServiceTrait> {
+        Arc::clone(&self.goal_progress_service)
+    }
+
     pub fn market_data_service(&self) -> Arc<dyn market_data::MarketDataServiceTrait> {
         Arc::clone(&self.market_data_service)
     }
@@ -59,6 +113,10 @@ impl ServiceContext {
         Arc::clone(&self.limits_service)
     }
 
+    pub fn allocation_service(&self) -> Arc<dyn allocations::AllocationServiceTrait> {
+        Arc::clone(&self.allocation_service)
+    }
+
     pub fn fx_service(&self) -> Arc<dyn fx::FxServiceTrait> {
         Arc::clone(&self.fx_service)
     }
@@ -71,6 +129,18 @@ impl ServiceContext {
         Arc::clone(&self.income_service)
     }
 
+    pub fn fee_service(&self) -> Arc<dyn portfolio::fees::FeeServiceTrait> {
+        Arc::clone(&self.fee_service)
+    }
+
+    pub fn report_service(&self) -> Arc<dyn reports::ReportServiceTrait> {
+        Arc::clone(&self.report_service)
+    }
+
+    pub fn interest_service(&self) -> Arc<dyn interest::CashInterestServiceTrait> {
+        Arc::clone(&self.interest_service)
+    }
+
     pub fn snapshot_service(&self) -> Arc<dyn portfolio::snapshot::SnapshotServiceTrait> {
         Arc::clone(&self.snapshot_service)
     }
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use tauri::Emitter;
+use wealthfolio_core::portfolio::snapshot::DirtyRanges;
 
 pub const PORTFOLIO_TOTAL_ACCOUNT_ID: &str = "TOTAL";
 
@@ -36,6 +37,10 @@ pub struct PortfolioRequestPayload {
     /// If syncing, specifies whether to refetch all symbols.
     #[serde(default)]
     pub refetch_all_market_data: bool,
+    /// Accounts and dates invalidated by an edit. When set, only these ranges are
+    /// recalculated instead of the full history of `account_ids`.
+    #[serde(default)]
+    pub dirty_ranges: Option<DirtyRanges>,
 }
 
 impl PortfolioRequestPayload {
@@ -51,6 +56,7 @@ pub struct PortfolioRequestPayloadBuilder {
     account_ids: Option<Vec<String>>,
     symbols: Option<Vec<String>>,
     refetch_all_market_data: Option<bool>,
+    dirty_ranges: Option<DirtyRanges>,
 }
 
 impl PortfolioRequestPayloadBuilder {
@@ -80,12 +86,19 @@ impl PortfolioRequestPayloadBuilder {
         self
     }
 
+    /// Limits recalculation to the given dirty ranges.
+    pub fn dirty_ranges(mut self, dirty_ranges: Option<DirtyRanges>) -> Self {
+        self.dirty_ranges = dirty_ranges;
+        self
+    }
+
     /// Builds the PortfolioRequestPayload.
     pub fn build(self) -> PortfolioRequestPayload {
         PortfolioRequestPayload {
             account_ids: self.account_ids,
             symbols: self.symbols,
             refetch_all_market_data: self.refetch_all_market_data.unwrap_or(false),
+            dirty_ranges: self.dirty_ranges,
         }
     }
 }
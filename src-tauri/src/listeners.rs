@@ -1,10 +1,11 @@
-use futures::future::join_all;
+use chrono::NaiveDate;
 use log::{debug, error, info};
 use serde::Serialize;
 use std::sync::Arc;
 use std::time::Instant;
 use tauri::{async_runtime::spawn, AppHandle, Emitter, Listener, Manager};
 use wealthfolio_core::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
+use wealthfolio_core::portfolio::snapshot::DirtyRanges;
 
 use crate::context::ServiceContext;
 use crate::events::{
@@ -50,6 +51,7 @@ fn handle_portfolio_request(handle: AppHandle, payload_str: &str, force_recalc:
                 let symbols_to_sync = payload.symbols.clone(); // None means sync all relevant symbols
                 let accounts_to_recalc = payload.account_ids.clone();
                 let refetch_all = payload.refetch_all_market_data;
+                let dirty_ranges = payload.dirty_ranges.clone();
                 let context_result = handle_clone.try_state::<Arc<ServiceContext>>();
 
                 if let Some(context) = context_result {
@@ -92,6 +94,7 @@ fn handle_portfolio_request(handle: AppHandle, payload_str: &str, force_recalc:
                                 handle_clone.clone(), // Clone again for this call
                                 accounts_to_recalc,
                                 force_recalc,
+                                dirty_ranges,
                             );
                         }
                         Err(e) => {
@@ -136,6 +139,7 @@ fn handle_portfolio_calculation(
     app_handle: AppHandle,
     account_ids_input: Option<Vec<String>>,
     force_full_recalculation: bool,
+    dirty_ranges: Option<DirtyRanges>,
 ) {
     if let Err(e) = app_handle.emit(PORTFOLIO_UPDATE_START, ()) {
         error!("Failed to emit {} event: {}", PORTFOLIO_UPDATE_START, e);
@@ -162,11 +166,15 @@ fn handle_portfolio_calculation(
         let snapshot_service = context.snapshot_service();
         let valuation_service = context.valuation_service();
 
+        // Requests that carry dirty ranges know exactly what changed, so they are
+        // honoured even on the recalculate channel instead of rebuilding everything.
+        let dirty_ranges = dirty_ranges.filter(|d| !d.is_empty());
+
         // Step 0: Resolve initially targeted active accounts for individual calculations.
         // This list might be empty if account_ids_input is None and no accounts are active,
         // or if account_ids_input specified accounts that are now all inactive.
         let initially_targeted_active_accounts: Vec<String> =
-            match account_service.list_accounts(Some(true), account_ids_input.as_deref()) {
+            match account_service.list_household_accounts(Some(true), account_ids_input.as_deref()) {
                 Ok(accounts) => accounts.iter().map(|a| a.id.clone()).collect(),
                 Err(e) => {
                     let err_msg = format!("Failed to list active accounts: {}", e);
@@ -183,7 +191,11 @@ fn handle_portfolio_calculation(
 
         // --- Step 1: Calculate Account-Specific Snapshots (only if there are specific active accounts to process) ---
         if !initially_targeted_active_accounts.is_empty() {
-            let account_snapshot_result = if force_full_recalculation {
+            let account_snapshot_result = if let Some(dirty) = dirty_ranges.as_ref() {
+                snapshot_service
+                    .recalculate_dirty_holdings_snapshots(dirty)
+                    .await
+            } else if force_full_recalculation {
                 snapshot_service
                     .force_recalculate_holdings_snapshots(Some(
                         initially_targeted_active_accounts.as_slice(),
@@ -213,7 +225,15 @@ fn handle_portfolio_calculation(
         }
 
         // --- Step 2: Calculate TOTAL portfolio snapshot ---
-        if let Err(e) = snapshot_service.calculate_total_portfolio_snapshots().await {
+        let total_snapshot_result = match dirty_ranges.as_ref() {
+            Some(dirty) => {
+                snapshot_service
+                    .calculate_total_portfolio_snapshots_from(dirty.earliest_date())
+                    .await
+            }
+            None => snapshot_service.calculate_total_portfolio_snapshots().await,
+        };
+        if let Err(e) = total_snapshot_result {
             let err_msg = format!("Failed to calculate TOTAL portfolio snapshot: {}", e);
             error!("{}", err_msg);
             if let Err(e_emit) = app_handle.emit(PORTFOLIO_UPDATE_ERROR, &err_msg) {
@@ -225,42 +245,132 @@ fn handle_portfolio_calculation(
             return;
         }
 
+        // --- Step 2b: Calculate account group snapshots ---
+        // Group membership can change without any activity changing, so groups are
+        // always recalculated; a failure here still lets the valuations below run.
+        let group_ids: Vec<String> = match context.account_group_service().get_group_memberships()
+        {
+            Ok(memberships) => {
+                let start_date = dirty_ranges.as_ref().and_then(|d| d.earliest_date());
+                match snapshot_service
+                    .calculate_group_snapshots_from(&memberships, start_date)
+                    .await
+                {
+                    Ok(_) => memberships
+                        .into_iter()
+                        .filter(|m| !m.account_ids.is_empty())
+                        .map(|m| m.group.id)
+                        .collect(),
+                    Err(e) => {
+                        let err_msg = format!("Failed to calculate account group snapshots: {}", e);
+                        error!("{}", err_msg);
+                        if let Err(e_emit) = app_handle.emit(PORTFOLIO_UPDATE_ERROR, &err_msg) {
+                            error!(
+                                "Failed to emit {} event: {}",
+                                PORTFOLIO_UPDATE_ERROR, e_emit
+                            );
+                        }
+                        Vec::new()
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Failed to load account groups: {}", e);
+                Vec::new()
+            }
+        };
+
+        // --- Step 2c: Calculate profile snapshots ---
+        // Profiles are aggregated like groups so each person's portfolio has its own
+        // holdings and valuation history under the profile id.
+        let profile_ids: Vec<String> = match context.profile_service().get_profile_memberships() {
+            Ok(memberships) => {
+                let start_date = dirty_ranges.as_ref().and_then(|d| d.earliest_date());
+                match snapshot_service
+                    .calculate_profile_snapshots_from(&memberships, start_date)
+                    .await
+                {
+                    Ok(_) => memberships
+                        .into_iter()
+                        .filter(|m| !m.account_ids.is_empty())
+                        .map(|m| m.profile.id)
+                        .collect(),
+                    Err(e) => {
+                        let err_msg = format!("Failed to calculate profile snapshots: {}", e);
+                        error!("{}", err_msg);
+                        if let Err(e_emit) = app_handle.emit(PORTFOLIO_UPDATE_ERROR, &err_msg) {
+                            error!(
+                                "Failed to emit {} event: {}",
+                                PORTFOLIO_UPDATE_ERROR, e_emit
+                            );
+                        }
+                        Vec::new()
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Failed to load profiles: {}", e);
+                Vec::new()
+            }
+        };
+
         // --- Step 3: Calculate Valuation History ---
         let mut accounts_for_valuation = initially_targeted_active_accounts;
         if !accounts_for_valuation.contains(&PORTFOLIO_TOTAL_ACCOUNT_ID.to_string()) {
             accounts_for_valuation.push(PORTFOLIO_TOTAL_ACCOUNT_ID.to_string());
         }
+        for aggregate_id in group_ids.iter().chain(profile_ids.iter()) {
+            if !accounts_for_valuation.contains(aggregate_id) {
+                accounts_for_valuation.push(aggregate_id.clone());
+            }
+        }
 
         if !accounts_for_valuation.is_empty() {
-            let history_futures = accounts_for_valuation.iter().map(|account_id| {
-                let valuation_service_clone = valuation_service.clone();
-                let account_id_clone = account_id.clone();
-                async move {
-                    let result = valuation_service_clone
-                        .calculate_valuation_history(&account_id_clone, force_full_recalculation)
-                        .await;
-                    (account_id_clone, result)
-                }
-            });
-
-            let history_results = join_all(history_futures).await;
+            // Resume valuations from the dirty date when one is known for the account.
+            let valuation_requests: Vec<(String, Option<NaiveDate>)> = accounts_for_valuation
+                .into_iter()
+                .map(|account_id| {
+                    let dirty_from = dirty_ranges.as_ref().and_then(|dirty| {
+                        if account_id == PORTFOLIO_TOTAL_ACCOUNT_ID
+                            || group_ids.contains(&account_id)
+                            || profile_ids.contains(&account_id)
+                        {
+                            dirty.earliest_date()
+                        } else {
+                            dirty.get(&account_id).flatten()
+                        }
+                    });
+                    (account_id, dirty_from)
+                })
+                .collect();
 
-            let mut history_errors: Vec<String> = Vec::new();
-            for (account_id, result) in history_results {
-                if let Err(e) = result {
-                    let err_detail = format!("Account '{}': {}", account_id, e);
-                    error!("Failed to calculate valuation history: {}", err_detail);
-                    history_errors.push(err_detail);
+            match valuation_service
+                .calculate_valuation_histories(&valuation_requests, force_full_recalculation)
+                .await
+            {
+                Ok(failures) if !failures.is_empty() => {
+                    let history_errors: Vec<String> = failures
+                        .iter()
+                        .map(|(account_id, e)| format!("Account '{}': {}", account_id, e))
+                        .collect();
+                    error!(
+                        "Valuation history calculation completed with errors: {}",
+                        history_errors.join("; ")
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    let err_msg = format!("Failed to save valuation history: {}", e);
+                    error!("{}", err_msg);
+                    if let Err(e_emit) = app_handle.emit(PORTFOLIO_UPDATE_ERROR, &err_msg) {
+                        error!(
+                            "Failed to emit {} event: {}",
+                            PORTFOLIO_UPDATE_ERROR, e_emit
+                        );
+                    }
                 }
             }
-
-            if !history_errors.is_empty() {
-                error!(
-                    "Valuation history calculation completed with errors: {}",
-                    history_errors.join("; ")
-                );
-            }
-        } 
+        }
 
         if let Err(e) = app_handle.emit(PORTFOLIO_UPDATE_COMPLETE, ()) {
             error!("Failed to emit {} event: {}", PORTFOLIO_UPDATE_COMPLETE, e);
@@ -95,13 +95,34 @@ pub fn main() {
             commands::account::create_account,
             commands::account::update_account,
             commands::account::delete_account,
+            commands::account::get_account_groups,
+            commands::account::get_account_group_memberships,
+            commands::account::create_account_group,
+            commands::account::update_account_group,
+            commands::account::delete_account_group,
+            commands::profile::get_profiles,
+            commands::profile::get_active_profile,
+            commands::profile::set_active_profile,
+            commands::profile::create_profile,
+            commands::profile::update_profile,
+            commands::profile::delete_profile,
+            commands::profile::get_household_valuations,
             commands::activity::search_activities,
+            commands::activity::get_activity_views,
+            commands::activity::save_activity_view,
+            commands::activity::delete_activity_view,
             commands::activity::get_activities,
             commands::activity::create_activity,
             commands::activity::update_activity,
             commands::activity::delete_activity,
+            commands::activity::bulk_edit_activities,
             commands::activity::check_activities_import,
             commands::activity::import_activities,
+            commands::activity::get_statement_parsers,
+            commands::activity::parse_statement,
+            commands::activity::get_import_sessions,
+            commands::activity::get_import_session_activities,
+            commands::activity::rollback_import_session,
             commands::activity::get_account_import_mapping,
             commands::activity::save_account_import_mapping,
             commands::activity::export_activities,
@@ -112,15 +133,31 @@ pub fn main() {
             commands::settings::update_exchange_rate,
             commands::settings::add_exchange_rate,
             commands::settings::delete_exchange_rate,
+            commands::settings::import_reference_rates,
+            commands::settings::sync_reference_rates,
+            commands::settings::explain_currency_conversion,
             commands::goal::create_goal,
             commands::goal::update_goal,
             commands::goal::delete_goal,
             commands::goal::get_goals,
             commands::goal::update_goal_allocations,
             commands::goal::load_goals_allocations,
+            commands::goal::get_goals_progress,
+            commands::goal::get_goal_progress,
             commands::portfolio::get_holdings,
             commands::portfolio::get_holding,
+            commands::portfolio::get_holdings_as_of,
             commands::portfolio::get_income_summary,
+            commands::portfolio::get_income_projection,
+            commands::portfolio::get_foreign_tax_report,
+            commands::portfolio::get_fee_analysis,
+            commands::report::get_portfolio_statement,
+            commands::report::export_portfolio_statement,
+            commands::interest::get_interest_rates,
+            commands::interest::create_interest_rate,
+            commands::interest::update_interest_rate,
+            commands::interest::delete_interest_rate,
+            commands::interest::generate_interest_activities,
             commands::portfolio::get_historical_valuations,
             commands::portfolio::get_latest_valuations,
             commands::portfolio::calculate_accounts_simple_performance,
@@ -133,12 +170,28 @@ pub fn main() {
             commands::limits::update_contribution_limit,
             commands::limits::delete_contribution_limit,
             commands::limits::calculate_deposits_for_contribution_limit,
+            commands::limits::calculate_contribution_room,
+            commands::limits::get_contribution_limit_presets,
+            commands::allocation::get_allocation_models,
+            commands::allocation::create_allocation_model,
+            commands::allocation::update_allocation_model,
+            commands::allocation::delete_allocation_model,
+            commands::allocation::calculate_rebalance,
             commands::utilities::backup_database,
             commands::utilities::backup_database_to_path,
             commands::utilities::restore_database,
             commands::asset::get_asset_profile,
             commands::asset::update_asset_profile,
+            commands::asset::update_asset_expense_ratio,
             commands::asset::update_asset_data_source,
+            commands::asset::resolve_symbol_identifier,
+            commands::asset::get_symbol_identifiers,
+            commands::asset::save_symbol_identifier,
+            commands::asset::delete_symbol_identifier,
+            commands::audit::get_entity_history,
+            commands::audit::get_recent_changes,
+            commands::audit::restore_entity_version,
+            commands::audit::undelete_entity,
             commands::market_data::search_symbol,
             commands::market_data::sync_market_data,
             commands::market_data::update_quote,
@@ -1,5 +1,5 @@
 import z from 'zod';
-import { Account } from '@/lib/types';
+import { Account, AccountGroup, AccountGroupMembership } from '@/lib/types';
 import { newAccountSchema } from '@/lib/schemas';
 import { getRunEnv, RUN_ENV, invokeTauri } from '@/adapters';
 import { logger } from '@/adapters';
@@ -66,3 +66,79 @@ export const deleteAccount = async (accountId: string): Promise<void> => {
     throw error;
   }
 };
+
+// getAccountGroups
+export const getAccountGroups = async (): Promise<AccountGroup[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_account_groups');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching account groups.');
+    throw error;
+  }
+};
+
+// getAccountGroupMemberships
+export const getAccountGroupMemberships = async (): Promise<AccountGroupMembership[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_account_group_memberships');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching account group memberships.');
+    throw error;
+  }
+};
+
+// createAccountGroup
+export const createAccountGroup = async (group: { name: string; parentId?: string | null }): Promise<AccountGroup> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('create_account_group', { group });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error creating account group.');
+    throw error;
+  }
+};
+
+// updateAccountGroup
+export const updateAccountGroup = async (groupUpdate: { id: string; name: string; parentId?: string | null }): Promise<AccountGroup> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('update_account_group', { groupUpdate });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error updating account group.');
+    throw error;
+  }
+};
+
+// deleteAccountGroup
+export const deleteAccountGroup = async (groupId: string): Promise<void> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        await invokeTauri('delete_account_group', { groupId });
+        return;
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error deleting account group.');
+    throw error;
+  }
+};
@@ -1,12 +1,24 @@
-import { ActivityImport, ImportMappingData } from '@/lib/types';
+import {
+  Activity,
+  ActivityImport,
+  DuplicateStrategy,
+  ImportMappingData,
+  ImportSession,
+  ImportSource,
+  StatementParserInfo,
+} from '@/lib/types';
 import { getRunEnv, RUN_ENV, invokeTauri } from '@/adapters';
 import { logger } from '@/adapters';
 
 
 export const importActivities = async ({
   activities,
+  duplicateStrategy,
+  source,
 }: {
   activities: ActivityImport[];
+  duplicateStrategy?: DuplicateStrategy;
+  source?: ImportSource;
 }): Promise<ActivityImport[]> => {
   try {
     switch (getRunEnv()) {
@@ -14,6 +26,8 @@ export const importActivities = async ({
         return invokeTauri('import_activities', {
           accountId: activities[0].accountId,
           activities: activities,
+          duplicateStrategy,
+          source,
         });
       default:
         throw new Error(`Unsupported`);
@@ -47,6 +61,84 @@ export const checkActivitiesImport = async ({
   }
 };
 
+export const getStatementParsers = async (): Promise<StatementParserInfo[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_statement_parsers');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching statement parsers.');
+    throw error;
+  }
+};
+
+export const parseStatement = async ({
+  accountId,
+  parserId,
+  content,
+}: {
+  accountId: string;
+  parserId?: string;
+  content: string;
+}): Promise<ActivityImport[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('parse_statement', { accountId, parserId, content });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error parsing broker statement.');
+    throw error;
+  }
+};
+
+export const getImportSessions = async (accountId?: string): Promise<ImportSession[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_import_sessions', { accountId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching import sessions.');
+    throw error;
+  }
+};
+
+export const getImportSessionActivities = async (sessionId: string): Promise<Activity[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_import_session_activities', { sessionId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching import session activities.');
+    throw error;
+  }
+};
+
+export const rollbackImportSession = async (sessionId: string): Promise<Activity[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('rollback_import_session', { sessionId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error rolling back import session.');
+    throw error;
+  }
+};
+
 export const getAccountImportMapping = async (accountId: string): Promise<ImportMappingData> => {
   try {
     switch (getRunEnv()) {
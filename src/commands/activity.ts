@@ -1,15 +1,28 @@
-import { Activity, ActivityCreate, ActivityDetails, ActivitySearchResponse, ActivityUpdate } from '@/lib/types';
+import {
+  Activity,
+  ActivityBulkRequest,
+  ActivityBulkResult,
+  ActivityCreate,
+  ActivityDetails,
+  ActivitySavedView,
+  ActivitySearchResponse,
+  ActivitySort,
+  ActivityUpdate,
+  NewActivitySavedView,
+} from '@/lib/types';
 import { getRunEnv, RUN_ENV, invokeTauri, logger } from '@/adapters';
 
 interface Filters {
   accountId?: string[];
   activityType?: string;
   symbol?: string;
-}
-
-interface Sort {
-  id: string;
-  desc: boolean;
+  startDate?: string;
+  endDate?: string;
+  minAmount?: number;
+  maxAmount?: number;
+  currencies?: string[];
+  isDraft?: boolean;
+  commentQuery?: string;
 }
 
 
@@ -31,7 +44,7 @@ export const searchActivities = async (
   pageSize: number,
   filters: Filters,
   searchKeyword: string,
-  sort: Sort,
+  sort: ActivitySort | ActivitySort[],
 ): Promise<ActivitySearchResponse> => {
   try {
     switch (getRunEnv()) {
@@ -43,7 +56,17 @@ export const searchActivities = async (
           accountIdFilter: filters?.accountId,
           activityTypeFilter: filters?.activityType ? [filters.activityType] : null,
           assetIdKeyword: keywordForBackend,
-          sort,
+          filters: {
+            startDate: filters?.startDate,
+            endDate: filters?.endDate,
+            minAmount: filters?.minAmount,
+            maxAmount: filters?.maxAmount,
+            currencies: filters?.currencies,
+            isDraft: filters?.isDraft,
+            commentQuery: filters?.commentQuery?.trim() || undefined,
+          },
+          sort: Array.isArray(sort) ? null : sort,
+          sorts: Array.isArray(sort) ? sort : null,
         }) as ActivitySearchResponse;
         return response;
       default:
@@ -55,6 +78,48 @@ export const searchActivities = async (
   }
 };
 
+export const getActivityViews = async (): Promise<ActivitySavedView[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_activity_views');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching saved activity views.');
+    throw error;
+  }
+};
+
+export const saveActivityView = async (view: NewActivitySavedView): Promise<ActivitySavedView> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('save_activity_view', { view });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error saving activity view.');
+    throw error;
+  }
+};
+
+export const deleteActivityView = async (viewId: string): Promise<void> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('delete_activity_view', { viewId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error deleting activity view.');
+    throw error;
+  }
+};
+
 export const createActivity = async (activity: ActivityCreate): Promise<Activity> => {
   try {
     switch (getRunEnv()) {
@@ -111,6 +176,23 @@ export const deleteActivity = async (activityId: string): Promise<Activity> => {
   }
 };
 
+export const bulkEditActivities = async (
+  request: ActivityBulkRequest,
+  dryRun: boolean,
+): Promise<ActivityBulkResult> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('bulk_edit_activities', { request, dryRun });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error bulk editing activities.');
+    throw error;
+  }
+};
+
 export const getActivityCountByAccount = async (accountId: string): Promise<number> => {
   try {
     const response = await searchActivities(1, 1, { accountId: [accountId] }, '', { id: 'date', desc: true });
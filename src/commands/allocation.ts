@@ -0,0 +1,78 @@
+import { AllocationModel, NewAllocationModel, RebalanceOptions, RebalancePlan } from '@/lib/types';
+import { getRunEnv, RUN_ENV, invokeTauri, logger } from '@/adapters';
+
+export const getAllocationModels = async (): Promise<AllocationModel[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_allocation_models');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching allocation models.');
+    throw error;
+  }
+};
+
+export const createAllocationModel = async (newModel: NewAllocationModel): Promise<AllocationModel> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('create_allocation_model', { newModel });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error creating allocation model.');
+    throw error;
+  }
+};
+
+export const updateAllocationModel = async (
+  id: string,
+  updatedModel: NewAllocationModel,
+): Promise<AllocationModel> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('update_allocation_model', { id, updatedModel });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error updating allocation model.');
+    throw error;
+  }
+};
+
+export const deleteAllocationModel = async (id: string): Promise<void> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('delete_allocation_model', { id });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error deleting allocation model.');
+    throw error;
+  }
+};
+
+export const calculateRebalance = async (
+  modelId: string,
+  options?: RebalanceOptions,
+): Promise<RebalancePlan> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('calculate_rebalance', { modelId, options });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error calculating rebalance.');
+    throw error;
+  }
+};
@@ -0,0 +1,69 @@
+import { ChangeEntityType, ChangeLogEntry, ChangeOperation } from '@/lib/types';
+import { getRunEnv, RUN_ENV, invokeTauri } from '@/adapters';
+import { logger } from '@/adapters';
+
+export const getEntityHistory = async (
+  entityType: ChangeEntityType,
+  entityId: string,
+): Promise<ChangeLogEntry[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_entity_history', { entityType, entityId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching entity history.');
+    throw error;
+  }
+};
+
+export const getRecentChanges = async (
+  entityType?: ChangeEntityType,
+  operation?: ChangeOperation,
+  limit?: number,
+): Promise<ChangeLogEntry[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_recent_changes', { entityType, operation, limit });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching recent changes.');
+    throw error;
+  }
+};
+
+export const restoreEntityVersion = async (changeId: string): Promise<ChangeLogEntry> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('restore_entity_version', { changeId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error restoring entity version.');
+    throw error;
+  }
+};
+
+export const undeleteEntity = async (
+  entityType: ChangeEntityType,
+  entityId: string,
+): Promise<ChangeLogEntry[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('undelete_entity', { entityType, entityId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error undeleting entity.');
+    throw error;
+  }
+};
@@ -1,4 +1,10 @@
-import { ContributionLimit, NewContributionLimit, DepositsCalculation } from '@/lib/types';
+import {
+  ContributionLimit,
+  NewContributionLimit,
+  DepositsCalculation,
+  ContributionRoom,
+  ContributionLimitPreset,
+} from '@/lib/types';
 import { getRunEnv, RUN_ENV, invokeTauri, logger } from '@/adapters';
 
 export const getContributionLimit = async (): Promise<ContributionLimit[]> => {
@@ -78,3 +84,31 @@ export const calculateDepositsForLimit = async (
   }
 };
 
+
+export const calculateContributionRoom = async (limitId: string): Promise<ContributionRoom> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('calculate_contribution_room', { limitId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error calculating contribution room.');
+    throw error;
+  }
+};
+
+export const getContributionLimitPresets = async (): Promise<ContributionLimitPreset[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_contribution_limit_presets');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching contribution limit presets.');
+    throw error;
+  }
+};
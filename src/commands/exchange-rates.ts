@@ -1,4 +1,4 @@
-import type { ExchangeRate } from '@/lib/types';
+import type { ExchangeRate, FxConversionExplanation } from '@/lib/types';
 import { getRunEnv, RUN_ENV, invokeTauri, logger } from '@/adapters';
 
 export const getExchangeRates = async (): Promise<ExchangeRate[]> => {
@@ -56,3 +56,59 @@ export const deleteExchangeRate = async (rateId: string): Promise<void> => {
     throw error;
   }
 };
+
+export const importReferenceRates = async (
+  providerId: string,
+  content: string,
+  currencies?: string[],
+): Promise<number> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('import_reference_rates', { providerId, content, currencies });
+      default:
+        throw new Error('Unsupported environment');
+    }
+  } catch (error) {
+    logger.error('Error importing reference rates.');
+    throw error;
+  }
+};
+
+export const syncReferenceRates = async (
+  providerId: string,
+  startDate: string,
+  endDate: string,
+  currencies?: string[],
+): Promise<number> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('sync_reference_rates', { providerId, startDate, endDate, currencies });
+      default:
+        throw new Error('Unsupported environment');
+    }
+  } catch (error) {
+    logger.error('Error syncing reference rates.');
+    throw error;
+  }
+};
+
+export const explainCurrencyConversion = async (
+  amount: number,
+  fromCurrency: string,
+  toCurrency: string,
+  date: string,
+): Promise<FxConversionExplanation> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('explain_currency_conversion', { amount, fromCurrency, toCurrency, date });
+      default:
+        throw new Error('Unsupported environment');
+    }
+  } catch (error) {
+    logger.error('Error explaining currency conversion.');
+    throw error;
+  }
+};
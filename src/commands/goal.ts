@@ -1,10 +1,10 @@
 import z from 'zod';
-import { Goal, GoalAllocation } from '@/lib/types';
+import { Goal, GoalAllocation, GoalProgressReport } from '@/lib/types';
 import { newGoalSchema } from '@/lib/schemas';
 import { getRunEnv, RUN_ENV, invokeTauri } from '@/adapters';
 import { logger } from '@/adapters';
 
-type NewGoal = z.infer<typeof newGoalSchema>;
+type NewGoal = Omit<z.infer<typeof newGoalSchema>, 'targetDate'> & { targetDate?: string | null };
 
 export const getGoals = async (): Promise<Goal[]> => {
   try {
@@ -25,7 +25,6 @@ export const createGoal = async (goal: NewGoal): Promise<Goal> => {
     ...goal,
     yearlyContribution: 0,
     goalType: 'NEEDS',
-    isAchieved: false,
   };
   try {
     switch (getRunEnv()) {
@@ -97,3 +96,35 @@ export const getGoalsAllocation = async (): Promise<GoalAllocation[]> => {
     throw error;
   }
 };
+
+export const getGoalsProgress = async (): Promise<GoalProgressReport[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_goals_progress');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching goals progress.');
+    throw error;
+  }
+};
+
+export const getGoalProgress = async (
+  goalId: string,
+  startDate?: string,
+  endDate?: string,
+): Promise<GoalProgressReport> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_goal_progress', { goalId, startDate, endDate });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching goal progress.');
+    throw error;
+  }
+};
@@ -0,0 +1,84 @@
+import {
+  Activity,
+  CashInterestRate,
+  CashInterestRateUpdate,
+  NewCashInterestRate,
+} from '@/lib/types';
+import { getRunEnv, RUN_ENV, invokeTauri } from '@/adapters';
+import { logger } from '@/adapters';
+
+export const getInterestRates = async (accountId?: string): Promise<CashInterestRate[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_interest_rates', { accountId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching interest rates.');
+    throw error;
+  }
+};
+
+export const createInterestRate = async (rate: NewCashInterestRate): Promise<CashInterestRate> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('create_interest_rate', { rate });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error adding interest rate.');
+    throw error;
+  }
+};
+
+export const updateInterestRate = async (
+  rateUpdate: CashInterestRateUpdate,
+): Promise<CashInterestRate> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('update_interest_rate', { rateUpdate });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error updating interest rate.');
+    throw error;
+  }
+};
+
+export const deleteInterestRate = async (rateId: string): Promise<void> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('delete_interest_rate', { rateId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error deleting interest rate.');
+    throw error;
+  }
+};
+
+// Creates draft INTEREST activities for payouts up to endDate (default today)
+export const generateInterestActivities = async (
+  accountId?: string,
+  endDate?: string,
+): Promise<Activity[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('generate_interest_activities', { accountId, endDate });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error generating interest activities.');
+    throw error;
+  }
+};
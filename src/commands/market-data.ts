@@ -4,6 +4,7 @@ import {
   Quote,
   UpdateAssetProfile,
   MarketDataProviderInfo,
+  SymbolIdentifier,
 } from '@/lib/types';
 import { getRunEnv, RUN_ENV, invokeTauri, logger } from '@/adapters';
 
@@ -92,6 +93,84 @@ export const updateAssetDataSource = async (symbol: string, dataSource: string):
   }
 };
 
+export const updateAssetExpenseRatio = async (
+  symbol: string,
+  expenseRatio: number | null,
+): Promise<Asset> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('update_asset_expense_ratio', { id: symbol, expenseRatio });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error updating asset expense ratio.');
+    throw error;
+  }
+};
+
+export const resolveSymbolIdentifier = async (
+  identifier: string,
+): Promise<SymbolIdentifier | null> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('resolve_symbol_identifier', { identifier });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error resolving symbol identifier.');
+    throw error;
+  }
+};
+
+export const getSymbolIdentifiers = async (): Promise<SymbolIdentifier[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_symbol_identifiers');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error loading symbol identifiers.');
+    throw error;
+  }
+};
+
+export const saveSymbolIdentifier = async (
+  identifier: string,
+  symbol: string,
+): Promise<SymbolIdentifier> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('save_symbol_identifier', { identifier, symbol });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error saving symbol identifier.');
+    throw error;
+  }
+};
+
+export const deleteSymbolIdentifier = async (identifier: string): Promise<void> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('delete_symbol_identifier', { identifier });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error deleting symbol identifier.');
+    throw error;
+  }
+};
+
 export const updateQuote = async (symbol: string, quote: Quote): Promise<void> => {
   try {
     const runEnv = await getRunEnv();
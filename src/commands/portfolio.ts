@@ -2,6 +2,11 @@ import { getRunEnv, RUN_ENV, invokeTauri, logger } from '@/adapters';
 import {
   Holding,
   IncomeSummary,
+  IncomeProjection,
+  IncomeProjectionOptions,
+  FeeAnalysis,
+  FeeAnalysisOptions,
+  ForeignTaxReport,
   AccountValuation,
   PerformanceMetrics,
   SimplePerformanceMetrics,
@@ -50,6 +55,20 @@ export const getHoldings = async (accountId: string): Promise<Holding[]> => {
   }
 };
 
+export const getHoldingsAsOf = async (accountId: string, date: string): Promise<Holding[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_holdings_as_of', { accountId, date });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching holdings as of date.');
+    throw error;
+  }
+};
+
 export const getIncomeSummary = async (): Promise<IncomeSummary[]> => {
   try {
     switch (getRunEnv()) {
@@ -64,6 +83,50 @@ export const getIncomeSummary = async (): Promise<IncomeSummary[]> => {
   }
 };
 
+export const getIncomeProjection = async (
+  options?: IncomeProjectionOptions,
+): Promise<IncomeProjection> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_income_projection', { options });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching income projection.');
+    throw error;
+  }
+};
+
+export const getForeignTaxReport = async (year: number): Promise<ForeignTaxReport> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_foreign_tax_report', { year });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching foreign tax report.');
+    throw error;
+  }
+};
+
+export const getFeeAnalysis = async (options?: FeeAnalysisOptions): Promise<FeeAnalysis> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_fee_analysis', { options });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching fee analysis.');
+    throw error;
+  }
+};
+
 export const getHistoricalValuations = async (
   accountId?: string,
   startDate?: string,
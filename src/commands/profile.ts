@@ -0,0 +1,120 @@
+import { AccountValuation, Profile } from '@/lib/types';
+import { getRunEnv, RUN_ENV, invokeTauri } from '@/adapters';
+import { logger } from '@/adapters';
+
+export interface NewProfile {
+  id?: string;
+  name: string;
+  baseCurrency: string;
+}
+
+export interface ProfileUpdate {
+  id: string;
+  name: string;
+  baseCurrency: string;
+}
+
+// getProfiles
+export const getProfiles = async (): Promise<Profile[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_profiles');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching profiles.');
+    throw error;
+  }
+};
+
+// getActiveProfile
+export const getActiveProfile = async (): Promise<Profile | null> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_active_profile');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching active profile.');
+    throw error;
+  }
+};
+
+// setActiveProfile; null switches to the household view
+export const setActiveProfile = async (profileId: string | null): Promise<Profile | null> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('set_active_profile', { profileId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error switching profile.');
+    throw error;
+  }
+};
+
+// createProfile
+export const createProfile = async (profile: NewProfile): Promise<Profile> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('create_profile', { profile });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error creating profile.');
+    throw error;
+  }
+};
+
+// updateProfile
+export const updateProfile = async (profileUpdate: ProfileUpdate): Promise<Profile> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('update_profile', { profileUpdate });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error updating profile.');
+    throw error;
+  }
+};
+
+// deleteProfile
+export const deleteProfile = async (profileId: string): Promise<void> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('delete_profile', { profileId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error deleting profile.');
+    throw error;
+  }
+};
+
+// getHouseholdValuations
+export const getHouseholdValuations = async (): Promise<AccountValuation[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_household_valuations');
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching household valuations.');
+    throw error;
+  }
+};
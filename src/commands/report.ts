@@ -0,0 +1,43 @@
+import {
+  PortfolioStatement,
+  RenderedStatement,
+  StatementFormat,
+  StatementRequest,
+} from '@/lib/types';
+import { getRunEnv, RUN_ENV, invokeTauri } from '@/adapters';
+import { logger } from '@/adapters';
+
+// getPortfolioStatement
+export const getPortfolioStatement = async (
+  request: StatementRequest,
+): Promise<PortfolioStatement> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('get_portfolio_statement', { request });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error fetching portfolio statement.');
+    throw error;
+  }
+};
+
+// exportPortfolioStatement
+export const exportPortfolioStatement = async (
+  request: StatementRequest,
+  format: StatementFormat,
+): Promise<RenderedStatement> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri('export_portfolio_statement', { request, format });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error('Error exporting portfolio statement.');
+    throw error;
+  }
+};
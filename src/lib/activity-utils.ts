@@ -1,4 +1,4 @@
-import { ActivityType, CASH_ACTIVITY_TYPES, INCOME_ACTIVITY_TYPES } from './constants';
+import { ActivityType, CASH_ACTIVITY_TYPES, CORPORATE_ACTION_ACTIVITY_TYPES, INCOME_ACTIVITY_TYPES } from './constants';
 import { ActivityDetails } from './types';
 
 /**
@@ -52,6 +52,11 @@ export const isSplitActivity = (activityType: string): boolean => {
   return activityType === ActivityType.SPLIT;
 };
 
+// Helper to check if activity is a ticker change, merger or spin-off
+export const isCorporateAction = (activityType: string): boolean => {
+  return (CORPORATE_ACTION_ACTIVITY_TYPES as readonly string[]).includes(activityType);
+};
+
 /**
  * Gets the fee amount from an activity
  * @param activity The activity to get the fee from
@@ -97,8 +102,8 @@ export const calculateActivityValue = (activity: ActivityDetails): number => {
   const { activityType, assetSymbol } = activity;
   
   // Handle special cases first
-  if (activityType === ActivityType.SPLIT) {
-    return 0; // Split activities don't have a monetary value
+  if (activityType === ActivityType.SPLIT || isCorporateAction(activityType)) {
+    return 0; // Splits and corporate actions move quantity and cost basis, not value
   }
   
   if (activityType === ActivityType.FEE || activityType === ActivityType.TAX) {
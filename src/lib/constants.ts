@@ -111,6 +111,7 @@ export const ActivityType = {
   BUY: 'BUY',
   SELL: 'SELL',
   DIVIDEND: 'DIVIDEND',
+  DIVIDEND_REINVESTMENT: 'DIVIDEND_REINVESTMENT',
   INTEREST: 'INTEREST',
   DEPOSIT: 'DEPOSIT',
   WITHDRAWAL: 'WITHDRAWAL',
@@ -121,6 +122,9 @@ export const ActivityType = {
   FEE: 'FEE',
   TAX: 'TAX',
   SPLIT: 'SPLIT',
+  TICKER_CHANGE: 'TICKER_CHANGE',
+  MERGER: 'MERGER',
+  SPIN_OFF: 'SPIN_OFF',
 } as const;
 
 export type ActivityType = (typeof ActivityType)[keyof typeof ActivityType];
@@ -133,6 +137,13 @@ export const TRADING_ACTIVITY_TYPES = [
   ActivityType.REMOVE_HOLDING,
 ] as const;
 
+// Move lots from `sourceAssetId` into the activity's asset
+export const CORPORATE_ACTION_ACTIVITY_TYPES = [
+  ActivityType.TICKER_CHANGE,
+  ActivityType.MERGER,
+  ActivityType.SPIN_OFF,
+] as const;
+
 export const CASH_ACTIVITY_TYPES = [
   ActivityType.DEPOSIT,
   ActivityType.WITHDRAWAL,
@@ -145,6 +156,7 @@ export const CASH_ACTIVITY_TYPES = [
 
 export const INCOME_ACTIVITY_TYPES = [
   ActivityType.DIVIDEND,
+  ActivityType.DIVIDEND_REINVESTMENT,
   ActivityType.INTEREST,
 ] as const;
 
@@ -153,6 +165,7 @@ export const activityTypeSchema = z.enum([
   ActivityType.BUY,
   ActivityType.SELL,
   ActivityType.DIVIDEND,
+  ActivityType.DIVIDEND_REINVESTMENT,
   ActivityType.INTEREST,
   ActivityType.DEPOSIT,
   ActivityType.WITHDRAWAL,
@@ -163,12 +176,16 @@ export const activityTypeSchema = z.enum([
   ActivityType.FEE,
   ActivityType.TAX,
   ActivityType.SPLIT,
+  ActivityType.TICKER_CHANGE,
+  ActivityType.MERGER,
+  ActivityType.SPIN_OFF,
 ]);
 
 export const ActivityTypeNames: Record<ActivityType, string> = {
   [ActivityType.BUY]: 'Buy',
   [ActivityType.SELL]: 'Sell',
   [ActivityType.DIVIDEND]: 'Dividend',
+  [ActivityType.DIVIDEND_REINVESTMENT]: 'Dividend Reinvestment',
   [ActivityType.INTEREST]: 'Interest',
   [ActivityType.DEPOSIT]: 'Deposit',
   [ActivityType.WITHDRAWAL]: 'Withdrawal',
@@ -179,4 +196,7 @@ export const ActivityTypeNames: Record<ActivityType, string> = {
   [ActivityType.FEE]: 'Fee',
   [ActivityType.TAX]: 'Tax',
   [ActivityType.SPLIT]: 'Split',
+  [ActivityType.TICKER_CHANGE]: 'Ticker Change',
+  [ActivityType.MERGER]: 'Merger',
+  [ActivityType.SPIN_OFF]: 'Spin-off',
 }; 
\ No newline at end of file
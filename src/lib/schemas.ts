@@ -49,9 +49,16 @@ export const newGoalSchema = z.object({
       invalid_type_error: 'Target amount must be a positive number.',
     })
     .min(0, { message: 'Target amount must be a positive number.' }),
-  yearlyContribution: z.number().optional(),
-  deadline: z.date().optional(),
-  isAchieved: z.boolean().optional(),
+  targetDate: z.date().optional(),
+  startingAmount: z.coerce
+    .number()
+    .min(0, { message: 'Starting amount must be a positive number.' })
+    .optional(),
+  contributionAmount: z.coerce
+    .number()
+    .min(0, { message: 'Contribution must be a positive number.' })
+    .optional(),
+  contributionFrequency: z.enum(['WEEKLY', 'MONTHLY', 'QUARTERLY', 'YEARLY']).optional(),
 });
 
 export const importActivitySchema = z.object({
@@ -67,7 +74,7 @@ export const importActivitySchema = z.object({
   ]).optional(),
   symbol: z.string().min(1, { message: 'Symbol is required' })
     .refine(
-      (val) => /^(\$CASH-[A-Z]{3}|[A-Z0-9]{1,10}([\.-][A-Z0-9]+){0,2})$/.test(val.trim()), 
+      (val) => /^(\$CASH-[A-Z]{3}|[A-Z0-9]{1,12}([\.-][A-Z0-9]+){0,2})$/.test(val.trim()), 
       { message: 'Invalid symbol format' }
     ),
   amount: z.coerce
@@ -102,6 +109,15 @@ export const importActivitySchema = z.object({
   errors: z.record(z.string(), z.array(z.string())).optional(),
   isValid: z.boolean().default(false),
   lineNumber: z.number().optional(),
+  duplicateOf: z.string().optional(),
+  isin: z.string().optional(),
+  importSessionId: z.string().optional(),
+  sourceAssetId: z.string().optional(),
+  // Negative for a refund of tax withheld earlier
+  withholdingTax: z.coerce
+    .number({ invalid_type_error: 'Withholding tax must be a number.' })
+    .optional(),
+  withholdingCountry: z.string().optional(),
   isDraft: z.boolean(),
   comment: z.string().optional(),
 }).refine(
@@ -191,4 +207,8 @@ export const newContributionLimitSchema = z.object({
   accountIds: z.string().nullable().optional(),
   startDate: z.union([z.date(), z.string().datetime(), z.null()]).optional(),
   endDate: z.union([z.date(), z.string().datetime(), z.null()]).optional(),
+  preset: z.enum(['TFSA', 'RRSP', 'ISA', 'IRA', '401K']).nullable().optional(),
+  birthYear: z.coerce.number().int().min(1900, 'Invalid year').nullable().optional(),
+  roomStartYear: z.coerce.number().int().min(1900, 'Invalid year').nullable().optional(),
+  rules: z.string().nullable().optional(),
 });
@@ -23,7 +23,8 @@ export type Account = {
   id: string;
   name: string;
   accountType: AccountType;
-  group?: string; // Optional
+  group?: string; // Path of the account group, e.g. "Retirement > Spouse"
+  groupId?: string;
   balance: number;
   currency: string;
   isDefault: boolean;
@@ -31,8 +32,37 @@ export type Account = {
   createdAt: Date;
   updatedAt: Date;
   platformId?: string; // Optional
+  profileId?: string;
 };
 
+export interface Profile {
+  id: string;
+  name: string;
+  baseCurrency: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface ProfileMembership {
+  profile: Profile;
+  accountIds: string[];
+}
+
+export interface AccountGroup {
+  id: string;
+  name: string;
+  parentId?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface AccountGroupMembership {
+  group: AccountGroup;
+  path: string;
+  depth: number;
+  accountIds: string[];
+}
+
 export type Activity = {
   id: string;
   type: ActivityType;
@@ -69,6 +99,9 @@ export interface ActivityDetails {
   assetSymbol: string;
   assetName?: string;
   assetDataSource?: DataSource;
+  sourceAssetId?: string | null;
+  withholdingTax?: number | null;
+  withholdingCountry?: string | null;
   subRows?: ActivityDetails[];
 }
 
@@ -79,6 +112,39 @@ export type ActivitySearchResponse = {
   };
 };
 
+export interface ActivitySort {
+  id: string;
+  desc: boolean;
+}
+
+// Activity search filters; dates are inclusive 'YYYY-MM-DD', amounts compare the
+// activity amount or quantity x unit price
+export interface ActivitySearchFilters {
+  accountIds?: string[];
+  activityTypes?: string[];
+  assetIdKeyword?: string;
+  startDate?: string;
+  endDate?: string;
+  minAmount?: number;
+  maxAmount?: number;
+  currencies?: string[];
+  isDraft?: boolean;
+  commentQuery?: string;
+}
+
+export interface ActivitySavedView {
+  id: string;
+  name: string;
+  filters: ActivitySearchFilters;
+  sort: ActivitySort[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type NewActivitySavedView = Pick<ActivitySavedView, 'name' | 'filters' | 'sort'> & {
+  id?: string;
+};
+
 export type ActivityCreate = {
   accountId: string;
   activityType: string;
@@ -91,12 +157,109 @@ export type ActivityCreate = {
   fee?: number;
   isDraft: boolean;
   comment?: string | null;
+  // Corporate actions only: the asset whose lots move into assetId
+  sourceAssetId?: string | null;
+  // Income only: tax withheld at source; amount stays the net cash received
+  withholdingTax?: number | null;
+  withholdingCountry?: string | null;
 }
 
 export type ActivityUpdate = ActivityCreate & { id: string };
+
+// Selects the activities a bulk edit applies to; dates are inclusive 'YYYY-MM-DD'
+export interface ActivityBulkFilter {
+  accountIds?: string[];
+  activityTypes?: string[];
+  assetIdKeyword?: string;
+  assetIds?: string[];
+  startDate?: string;
+  endDate?: string;
+}
+
+export type ActivityBulkAction =
+  | {
+      action: 'UPDATE';
+      accountId?: string;
+      assetId?: string;
+      currency?: string;
+      isDraft?: boolean;
+    }
+  | { action: 'DELETE' };
+
+export interface ActivityBulkRequest {
+  filter: ActivityBulkFilter;
+  action: ActivityBulkAction;
+}
+
+export interface ActivityBulkResult {
+  dryRun: boolean;
+  before: Activity[];
+  after: Activity[];
+}
 export type ActivityImport = z.infer<typeof importActivitySchema>;
 export type ImportMappingData = z.infer<typeof importMappingSchema>;
 
+// How rows flagged as duplicates of existing activities are handled on import
+export type DuplicateStrategy = 'SKIP' | 'MERGE';
+
+export interface StatementParserInfo {
+  id: string;
+  name: string;
+}
+
+// Provenance sent with an import; source is a statement parser id or 'CSV'
+export interface ImportSource {
+  source?: string;
+  fileName?: string;
+  fileHash?: string;
+}
+
+export type IdentifierType = 'ISIN' | 'CUSIP' | 'SEDOL' | 'FIGI';
+
+// Cached resolution of a security identifier to a provider symbol
+export interface SymbolIdentifier {
+  identifier: string;
+  identifierType: IdentifierType;
+  symbol: string;
+  isin?: string | null;
+  name?: string | null;
+  exchange?: string | null;
+  source: 'ASSET' | 'SEARCH' | 'MANUAL';
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type ImportSessionStatus = 'COMPLETED' | 'ROLLED_BACK';
+
+export interface ImportSession {
+  id: string;
+  accountId: string;
+  source: string;
+  fileName?: string | null;
+  fileHash?: string | null;
+  rowCount: number;
+  status: ImportSessionStatus;
+  createdAt: string;
+  rolledBackAt?: string | null;
+}
+
+export type ChangeEntityType = 'ACTIVITY' | 'ACCOUNT' | 'ASSET';
+export type ChangeOperation = 'CREATE' | 'UPDATE' | 'DELETE' | 'RESTORE';
+export type ChangeActor = 'USER' | 'IMPORT' | 'SYSTEM';
+
+export interface ChangeLogEntry {
+  id: string;
+  entityType: ChangeEntityType;
+  entityId: string;
+  operation: ChangeOperation;
+  actor: ChangeActor;
+  /** JSON of the entity's row before the change; absent for creates */
+  beforeJson?: string | null;
+  /** JSON of the entity's row after the change; absent for deletes */
+  afterJson?: string | null;
+  createdAt: string;
+}
+
 // Define a generic type for the parsed row data
 export type CsvRowData = Record<string, string> & { lineNumber: string };
 export interface CsvRowError {
@@ -291,6 +454,9 @@ export interface Asset {
   dataSource: string;
   sectors?: string | null;
   url?: string | null;
+  dividendYield?: number | null;
+  /** Fund total expense ratio as a yearly fraction, e.g. 0.002 for 0.20%. */
+  expenseRatio?: number | null;
 }
 
 export interface Quote {
@@ -325,6 +491,7 @@ export interface Settings {
   baseCurrency: string;
   onboardingCompleted: boolean;
   autoUpdateCheckEnabled: boolean;
+  fxConversionPolicy?: FxConversionPolicy;
 }
 
 export interface SettingsContextType {
@@ -342,7 +509,37 @@ export interface Goal {
   description?: string;
   targetAmount: number;
   isAchieved?: boolean;
+  targetDate?: string | null;
+  startingAmount?: number;
+  contributionAmount?: number;
+  contributionFrequency?: ContributionFrequency;
   allocations?: GoalAllocation[];
+  profileId?: string;
+}
+
+export type ContributionFrequency = 'WEEKLY' | 'MONTHLY' | 'QUARTERLY' | 'YEARLY';
+
+export type GoalStatus = 'ACHIEVED' | 'ON_TRACK' | 'BEHIND' | 'UNSCHEDULED';
+
+export interface GoalFundingPoint {
+  date: string;
+  value: number;
+}
+
+export interface GoalProgressReport {
+  goalId: string;
+  title: string;
+  targetAmount: number;
+  fundedValue: number;
+  progress: number;
+  targetDate?: string | null;
+  monthsRemaining?: number | null;
+  plannedMonthlyContribution: number;
+  requiredMonthlyContribution?: number | null;
+  projectedValue?: number | null;
+  status: GoalStatus;
+  isAchieved: boolean;
+  history: GoalFundingPoint[];
 }
 
 export interface GoalAllocation {
@@ -367,11 +564,222 @@ export interface IncomeSummary {
   bySymbol: Record<string, number>;
   byCurrency: Record<string, number>;
   totalIncome: number;
+  grossIncome: number;
+  totalWithholding: number;
+  withholdingByCountry: Record<string, number>;
   currency: string;
   monthlyAverage: number;
   yoyGrowth: number | null; // Changed from optional to nullable
 }
 
+export interface ForeignTaxRow {
+  accountId: string;
+  accountName: string;
+  country: string;
+  currency: string;
+  grossIncome: number;
+  withholdingTax: number;
+  netIncome: number;
+  grossIncomeBase: number;
+  withholdingTaxBase: number;
+  netIncomeBase: number;
+  paymentCount: number;
+}
+
+export interface ForeignTaxReport {
+  year: number;
+  currency: string;
+  rows: ForeignTaxRow[];
+  totalGrossIncome: number;
+  totalWithholdingTax: number;
+}
+
+export interface FeeAnalysisOptions {
+  startYear?: number;
+  endYear?: number;
+  projectionYears?: number;
+  expectedReturn?: number;
+}
+
+export interface AccountFeeYear {
+  accountId: string;
+  accountName: string;
+  year: number;
+  tradingFees: number;
+  accountFees: number;
+  fundFees: number;
+  totalFees: number;
+  averageValue: number;
+  costRatio?: number | null;
+}
+
+export interface FundFeeHolding {
+  assetId: string;
+  symbol: string;
+  name?: string | null;
+  expenseRatio: number;
+  marketValue: number;
+  annualCost: number;
+}
+
+export interface CostDragYear {
+  year: number;
+  valueWithoutCosts: number;
+  valueWithCosts: number;
+  cumulativeDrag: number;
+}
+
+export interface CostDragProjection {
+  startingValue: number;
+  expectedReturn: number;
+  fundCostRate: number;
+  explicitCostRate: number;
+  annualCostRate: number;
+  years: CostDragYear[];
+  totalDrag: number;
+}
+
+export interface FeeAnalysis {
+  currency: string;
+  asOf: string;
+  byAccountYear: AccountFeeYear[];
+  fundHoldings: FundFeeHolding[];
+  missingExpenseRatios: string[];
+  projection: CostDragProjection;
+}
+
+export type InterestCompounding = 'DAILY' | 'MONTHLY';
+
+/** Annual rate on an account's cash in one currency, in effect until the next rate starts */
+export interface CashInterestRate {
+  id: string;
+  accountId: string;
+  currency: string;
+  /** Fraction, e.g. 0.045 for 4.5% */
+  annualRate: number;
+  effectiveDate: string;
+  compounding: InterestCompounding;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewCashInterestRate {
+  id?: string;
+  accountId: string;
+  currency: string;
+  annualRate: number;
+  effectiveDate: string;
+  compounding: InterestCompounding;
+}
+
+export interface CashInterestRateUpdate {
+  id: string;
+  currency: string;
+  annualRate: number;
+  effectiveDate: string;
+  compounding: InterestCompounding;
+}
+
+export type StatementPeriodicity = 'MONTHLY' | 'QUARTERLY' | 'YEARLY';
+export type StatementFormat = 'CSV' | 'HTML';
+
+export interface StatementRequest {
+  periodicity: StatementPeriodicity;
+  startDate: string;
+  endDate: string;
+  accountIds?: string[];
+}
+
+export interface StatementPeriod {
+  label: string;
+  startDate: string;
+  endDate: string;
+}
+
+/** One account's (or TOTAL's) movement over a period, in base currency. */
+export interface StatementLine {
+  period: StatementPeriod;
+  accountId: string;
+  accountName: string;
+  openingValue: number;
+  deposits: number;
+  withdrawals: number;
+  income: number;
+  fees: number;
+  taxes: number;
+  realizedChange: number;
+  unrealizedChange: number;
+  fxEffect: number;
+  closingValue: number;
+}
+
+export interface PortfolioStatement {
+  periodicity: StatementPeriodicity;
+  startDate: string;
+  endDate: string;
+  baseCurrency: string;
+  periods: StatementPeriod[];
+  lines: StatementLine[];
+  generatedAt: string;
+}
+
+export interface RenderedStatement {
+  format: StatementFormat;
+  fileName: string;
+  mimeType: string;
+  content: string;
+}
+
+export type PaymentFrequency = 'MONTHLY' | 'QUARTERLY' | 'SEMI_ANNUAL' | 'ANNUAL';
+export type ProjectionBasis = 'TRAILING_DIVIDENDS' | 'FORWARD_YIELD';
+
+export interface IncomeProjectionOptions {
+  months?: number;
+  useForwardYield?: boolean;
+}
+
+export interface ProjectedPayment {
+  assetId: string;
+  symbol: string;
+  payDate: string;
+  amountPerShare: number;
+  quantity: number;
+  amount: number;
+  currency: string;
+  convertedAmount: number;
+}
+
+export interface ProjectedIncomeMonth {
+  month: string;
+  total: number;
+  payments: ProjectedPayment[];
+}
+
+export interface SymbolIncomeProjection {
+  assetId: string;
+  symbol: string;
+  name?: string | null;
+  quantity: number;
+  frequency: PaymentFrequency;
+  basis: ProjectionBasis;
+  amountPerShare: number;
+  currency: string;
+  lastPayDate?: string | null;
+  nextPayDate?: string | null;
+  expectedPayDates: string[];
+  projectedIncome: number;
+}
+
+export interface IncomeProjection {
+  currency: string;
+  asOf: string;
+  totalIncome: number;
+  monthlyAverage: number;
+  byMonth: ProjectedIncomeMonth[];
+  bySymbol: SymbolIncomeProjection[];
+  warnings: string[];
+}
+
 // Define custom DateRange type matching react-day-picker's
 export type DateRange = {
   from: Date | undefined;
@@ -394,6 +802,8 @@ export interface AccountValuation {
   costBasis: number;
   netContribution: number;
   calculatedAt: string;
+  /** Interest earned on cash since the last payout; not included in totalValue */
+  accruedInterest: number;
 }
 
 export interface AccountSummaryView {
@@ -442,6 +852,36 @@ export interface ExchangeRate {
   timestamp: string;
 }
 
+export interface FxConversionPolicy {
+  pivotCurrency: string | null;
+  maxStalenessDays: number | null;
+  allowInverse: boolean;
+  allowTransitive: boolean;
+}
+
+export type FxConversionPath = 'IDENTITY' | 'DIRECT' | 'INVERSE' | 'TRANSITIVE' | 'LATEST_FALLBACK';
+
+export interface FxConversionLeg {
+  fromCurrency: string;
+  toCurrency: string;
+  rate: number;
+  sourceSymbol: string;
+  sourceDate: string;
+  source: string;
+  inverted: boolean;
+}
+
+export interface FxConversionExplanation {
+  fromCurrency: string;
+  toCurrency: string;
+  requestedDate: string;
+  amount: number;
+  convertedAmount: number;
+  rate: number;
+  path: FxConversionPath;
+  legs: FxConversionLeg[];
+}
+
 export interface ContributionLimit {
   id: string;
   groupName: string;
@@ -450,10 +890,68 @@ export interface ContributionLimit {
   accountIds?: string | null;
   startDate?: string | null;
   endDate?: string | null;
+  preset?: ContributionLimitPresetId | null;
+  birthYear?: number | null;
+  roomStartYear?: number | null;
+  /** JSON-encoded ContributionRules overriding the preset. */
+  rules?: string | null;
+  profileId?: string;
   createdAt?: string;
   updatedAt?: string;
 }
 
+export type ContributionLimitPresetId = 'TFSA' | 'RRSP' | 'ISA' | 'IRA' | '401K';
+
+export interface CatchUpRule {
+  minAge: number;
+  amounts: Record<string, number>;
+}
+
+export interface ContributionRules {
+  currency?: string | null;
+  annualLimits: Record<string, number>;
+  carryForward: boolean;
+  restoreWithdrawalsNextYear: boolean;
+  catchUp?: CatchUpRule | null;
+  minAge?: number | null;
+  taxYearStartMonth: number;
+  taxYearStartDay: number;
+  incomePercent?: number | null;
+  earnedIncome: Record<string, number>;
+  overContributionAllowance: number;
+}
+
+export interface ContributionLimitPreset {
+  id: ContributionLimitPresetId;
+  name: string;
+  rules: ContributionRules;
+}
+
+export interface ContributionRoomYear {
+  year: number;
+  annualLimit: number;
+  catchUp: number;
+  carriedForward: number;
+  restoredWithdrawals: number;
+  totalRoom: number;
+  contributions: number;
+  withdrawals: number;
+  remainingRoom: number;
+}
+
+export interface ContributionRoom {
+  limitId: string;
+  preset?: string | null;
+  year: number;
+  currency: string;
+  totalRoom: number;
+  contributions: number;
+  remainingRoom: number;
+  overContribution: number;
+  warnings: string[];
+  years: ContributionRoomYear[];
+}
+
 export type NewContributionLimit = Omit<ContributionLimit, 'id' | 'createdAt' | 'updatedAt'>;
 
 export interface AccountDeposit {
@@ -468,6 +966,76 @@ export interface DepositsCalculation {
   byAccount: Record<string, AccountDeposit>;
 }
 
+export type AllocationTargetKind = 'ASSET' | 'ASSET_CLASS' | 'SECTOR' | 'TAG';
+export type AllocationScope = 'ACCOUNT' | 'GROUP' | 'TOTAL';
+
+export interface AllocationTarget {
+  key: string;
+  targetWeight: number;
+  tolerance: number;
+  assetIds: string[];
+}
+
+export interface AllocationModel {
+  id: string;
+  name: string;
+  targetKind: AllocationTargetKind;
+  scopeType: AllocationScope;
+  scopeId?: string | null;
+  targets: AllocationTarget[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type NewAllocationModel = Omit<AllocationModel, 'id' | 'createdAt' | 'updatedAt'> & {
+  id?: string;
+};
+
+export interface RebalanceOptions {
+  cashOnly?: boolean;
+  contribution?: number;
+  minTradeSize?: number;
+  wholeShares?: boolean;
+  avoidSellingGains?: boolean;
+}
+
+export interface AllocationDrift {
+  key: string;
+  targetWeight: number;
+  currentWeight: number;
+  drift: number;
+  tolerance: number;
+  currentValue: number;
+  targetValue: number;
+  isOutOfBand: boolean;
+}
+
+export interface RebalanceTrade {
+  accountId: string;
+  assetId: string;
+  symbol: string;
+  targetKey: string;
+  side: 'BUY' | 'SELL';
+  quantity: number;
+  price: number;
+  currency: string;
+  value: number;
+  valueBase: number;
+}
+
+export interface RebalancePlan {
+  modelId: string;
+  baseCurrency: string;
+  totalValue: number;
+  cashValue: number;
+  cashTargetValue: number;
+  needsRebalance: boolean;
+  drift: AllocationDrift[];
+  trades: RebalanceTrade[];
+  cashAfter: number;
+  warnings: string[];
+}
+
 export const ACTIVITY_TYPE_PREFIX_LENGTH = 12;
 
 // Renamed from CumulativeReturn to match Rust struct ReturnData
@@ -153,6 +153,7 @@ const ActivityImportPage = () => {
             headers={headers}
             accounts={accounts}
             activities={activities}
+            file={selectedFile}
             onNext={handlePreviewComplete}
             onBack={goToPreviousStep}
           />
@@ -16,6 +16,7 @@ interface DataPreviewStepProps {
   headers: string[];
   activities?: ActivityImport[];
   accounts: Account[];
+  file?: File | null;
   onNext: (processedActivities: ActivityImport[]) => void;
   onBack: () => void;
   onError?: () => void;
@@ -26,6 +27,7 @@ export const DataPreviewStep = ({
   data,
   accounts,
   activities = [],
+  file,
   onNext,
   onBack,
   onError,
@@ -146,7 +148,15 @@ export const DataPreviewStep = ({
     setConfirmationState('confirm');
   };
 
-  const handleConfirmClick = () => {
+  // SHA-256 of the source file, recorded on the import session
+  const hashFile = async (source: File): Promise<string> => {
+    const digest = await crypto.subtle.digest('SHA-256', await source.arrayBuffer());
+    return Array.from(new Uint8Array(digest))
+      .map((byte) => byte.toString(16).padStart(2, '0'))
+      .join('');
+  };
+
+  const handleConfirmClick = async () => {
     // Filter only valid activities for import
     const validActivities = activities.filter((activity) => activity.isValid);
 
@@ -157,9 +167,12 @@ export const DataPreviewStep = ({
 
     setConfirmationState('processing');
 
+    const fileHash = file ? await hashFile(file).catch(() => undefined) : undefined;
+
     // Ensure we have a single transaction for data integrity
     confirmImportMutation.mutate({
       activities: validActivities,
+      source: { fileName: file?.name, fileHash },
     });
   };
 
@@ -183,6 +183,15 @@ const activityLogicMap: Partial<Record<ActivityType, ActivityLogicConfig>> = {
       activity.amount ? Math.abs(activity.amount) : Math.abs(calculateCashActivityAmount(activity.quantity, activity.unitPrice)),
     calculateFee: (activity) => activity.fee ? Math.abs(activity.fee) : 0,
   },
+  [ActivityType.DIVIDEND_REINVESTMENT]: {
+    calculateSymbol: (activity) => activity.symbol, // The asset the dividend is reinvested in
+    calculateAmount: (activity) =>
+      // The dividend paid; defaults to the value of the shares bought
+      activity.amount
+        ? Math.abs(activity.amount)
+        : Math.abs(activity.quantity ?? 0) * Math.abs(activity.unitPrice ?? 0),
+    calculateFee: (activity) => activity.fee ? Math.abs(activity.fee) : 0,
+  },
   [ActivityType.FEE]: {
     calculateSymbol: (activity, accountCurrency) =>
       `$CASH-${(activity.currency || accountCurrency).toUpperCase()}`,
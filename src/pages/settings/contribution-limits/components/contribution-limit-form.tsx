@@ -22,6 +22,11 @@ import {
   FormDescription,
   DatePickerInput,
   Input,
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
 } from '@wealthfolio/ui';
 
 import { newContributionLimitSchema } from '@/lib/schemas';
@@ -30,6 +35,17 @@ import { MoneyInput } from '@wealthfolio/ui';
 
 type NewContributionLimit = z.infer<typeof newContributionLimitSchema>;
 
+const NO_PRESET = 'NONE';
+
+const limitPresets = [
+  { label: 'None (flat yearly limit)', value: NO_PRESET },
+  { label: 'TFSA (Canada)', value: 'TFSA' },
+  { label: 'RRSP (Canada)', value: 'RRSP' },
+  { label: 'ISA (UK)', value: 'ISA' },
+  { label: 'IRA (US)', value: 'IRA' },
+  { label: '401(k) (US)', value: '401K' },
+] as const;
+
  type ContributionLimitFormValues = Omit<NewContributionLimit, 'limitAmount'> & { 
   limitAmount?: number 
 };
@@ -159,6 +175,84 @@ export function ContributionLimitForm({
                   )}
                 />
 
+                <div className="grid grid-cols-1 md:grid-cols-3 gap-5">
+                  <FormField
+                    control={form.control}
+                    name="preset"
+                    render={({ field }) => (
+                      <FormItem>
+                        <FormLabel className="text-base font-medium">Account Rules</FormLabel>
+                        <Select
+                          onValueChange={(value) =>
+                            field.onChange(value === NO_PRESET ? null : value)
+                          }
+                          defaultValue={field.value ?? NO_PRESET}
+                        >
+                          <FormControl>
+                            <SelectTrigger>
+                              <SelectValue placeholder="Select account rules" />
+                            </SelectTrigger>
+                          </FormControl>
+                          <SelectContent>
+                            {limitPresets.map((preset) => (
+                              <SelectItem value={preset.value} key={preset.value}>
+                                {preset.label}
+                              </SelectItem>
+                            ))}
+                          </SelectContent>
+                        </Select>
+                        <FormMessage />
+                      </FormItem>
+                    )}
+                  />
+
+                  <FormField
+                    control={form.control}
+                    name="birthYear"
+                    render={({ field }) => (
+                      <FormItem>
+                        <FormLabel className="text-base font-medium">Birth Year</FormLabel>
+                        <FormControl>
+                          <Input
+                            type="number"
+                            placeholder="e.g., 1985"
+                            value={field.value ?? ''}
+                            onChange={(e) =>
+                              field.onChange(e.target.value === '' ? null : Number(e.target.value))
+                            }
+                          />
+                        </FormControl>
+                        <FormMessage />
+                      </FormItem>
+                    )}
+                  />
+
+                  <FormField
+                    control={form.control}
+                    name="roomStartYear"
+                    render={({ field }) => (
+                      <FormItem>
+                        <FormLabel className="text-base font-medium">Room Since</FormLabel>
+                        <FormControl>
+                          <Input
+                            type="number"
+                            placeholder="e.g., 2009"
+                            value={field.value ?? ''}
+                            onChange={(e) =>
+                              field.onChange(e.target.value === '' ? null : Number(e.target.value))
+                            }
+                          />
+                        </FormControl>
+                        <FormMessage />
+                      </FormItem>
+                    )}
+                  />
+                </div>
+                <p className="text-sm text-muted-foreground -mt-3 italic">
+                  Account rules apply published yearly maximums, carry-forward room and catch-up
+                  contributions. Birth year sets when room starts and catch-up eligibility.
+                </p>
+
                 <div className="space-y-2">
                   <div className="grid grid-cols-1 md:grid-cols-3 gap-5">
                     <FormField
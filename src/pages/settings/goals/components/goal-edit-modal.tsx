@@ -14,7 +14,10 @@ export function GoalEditModal({ goal, open, onClose }: GoalEditModalProps) {
     title: goal?.title || '',
     description: goal?.description || '',
     targetAmount: goal?.targetAmount || 0,
-    isAchieved: goal?.isAchieved || false,
+    targetDate: goal?.targetDate ? new Date(`${goal.targetDate}T12:00:00`) : undefined,
+    startingAmount: goal?.startingAmount || 0,
+    contributionAmount: goal?.contributionAmount || 0,
+    contributionFrequency: goal?.contributionFrequency || 'MONTHLY',
   };
 
   return (
@@ -1,9 +1,9 @@
 import { zodResolver } from '@hookform/resolvers/zod';
 import { useForm } from 'react-hook-form';
+import { format } from 'date-fns';
 import * as z from 'zod';
 
 import { Button } from '@/components/ui/button';
-import { Switch } from '@/components/ui/switch';
 
 import { Icons } from '@/components/ui/icons';
 
@@ -23,13 +23,27 @@ import {
   FormMessage,
 } from '@/components/ui/form';
 import { Input } from '@/components/ui/input';
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from '@/components/ui/select';
 
 import { newGoalSchema } from '@/lib/schemas';
 import { useGoalMutations } from '@/pages/settings/goals/use-goal-mutations';
-import { MoneyInput } from '@wealthfolio/ui';
+import { DatePickerInput, MoneyInput } from '@wealthfolio/ui';
 
 type NewGoal = z.infer<typeof newGoalSchema>;
 
+const contributionFrequencies = [
+  { label: 'Weekly', value: 'WEEKLY' },
+  { label: 'Monthly', value: 'MONTHLY' },
+  { label: 'Quarterly', value: 'QUARTERLY' },
+  { label: 'Yearly', value: 'YEARLY' },
+] as const;
+
 interface GoalFormlProps {
   defaultValues?: NewGoal;
   onSuccess?: () => void;
@@ -44,11 +58,12 @@ export function GoalForm({ defaultValues, onSuccess = () => {} }: GoalFormlProps
   });
 
   function onSubmit(data: NewGoal) {
-    const { id, ...rest } = data;
+    const { id, targetDate, ...rest } = data;
+    const goal = { ...rest, targetDate: targetDate ? format(targetDate, 'yyyy-MM-dd') : null };
     if (id) {
-      return updateGoalMutation.mutate({ id, ...rest }, { onSuccess });
+      return updateGoalMutation.mutate({ id, ...goal }, { onSuccess });
     }
-    return addGoalMutation.mutate(data, { onSuccess });
+    return addGoalMutation.mutate(goal, { onSuccess });
   }
 
   return (
@@ -104,21 +119,75 @@ export function GoalForm({ defaultValues, onSuccess = () => {} }: GoalFormlProps
               </FormItem>
             )}
           />
-          {defaultValues?.id ? (
+          <FormField
+            control={form.control}
+            name="targetDate"
+            render={({ field }) => (
+              <FormItem>
+                <FormLabel>Target date</FormLabel>
+                <FormControl>
+                  <DatePickerInput
+                    onChange={(date: Date | undefined) => field.onChange(date)}
+                    value={field.value}
+                    disabled={field.disabled}
+                  />
+                </FormControl>
+                <FormMessage />
+              </FormItem>
+            )}
+          />
+          <FormField
+            control={form.control}
+            name="startingAmount"
+            render={({ field }) => (
+              <FormItem>
+                <FormLabel>Starting amount</FormLabel>
+                <FormControl>
+                  <MoneyInput placeholder="Saved outside the allocated accounts" {...field} />
+                </FormControl>
+                <FormMessage />
+              </FormItem>
+            )}
+          />
+          <div className="grid grid-cols-2 gap-4">
             <FormField
               control={form.control}
-              name="isAchieved"
+              name="contributionAmount"
               render={({ field }) => (
-                <FormItem className="flex items-center">
+                <FormItem>
+                  <FormLabel>Planned contribution</FormLabel>
                   <FormControl>
-                    <Switch checked={field.value} onCheckedChange={field.onChange} />
+                    <MoneyInput placeholder="Contribution" {...field} />
                   </FormControl>
-                  <FormLabel className="space-y-0 pl-2"> Goal Achieved</FormLabel>
                   <FormMessage />
                 </FormItem>
               )}
             />
-          ) : null}
+            <FormField
+              control={form.control}
+              name="contributionFrequency"
+              render={({ field }) => (
+                <FormItem>
+                  <FormLabel>Every</FormLabel>
+                  <Select onValueChange={field.onChange} defaultValue={field.value ?? 'MONTHLY'}>
+                    <FormControl>
+                      <SelectTrigger>
+                        <SelectValue placeholder="Select a frequency" />
+                      </SelectTrigger>
+                    </FormControl>
+                    <SelectContent>
+                      {contributionFrequencies.map((frequency) => (
+                        <SelectItem value={frequency.value} key={frequency.value}>
+                          {frequency.label}
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                  <FormMessage />
+                </FormItem>
+              )}
+            />
+          </div>
         </div>
         <DialogFooter>
           <DialogTrigger asChild>